@@ -8,13 +8,16 @@ use stwo::{
     },
 };
 
-use crate::components::TraceColumn;
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
 
 use super::witness::N_TRACE_COLUMNS;
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct AddTraceTable {
-    pub table: Vec<AddTraceTableRow>,
+    pub table: PackedRows<AddTraceTableRow>,
 }
 
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
@@ -24,10 +27,6 @@ pub struct AddTraceTableRow {
     pub rhs_id: M31,
     pub idx: M31,
     pub is_last_idx: M31,
-    pub next_node_id: M31,
-    pub next_lhs_id: M31,
-    pub next_rhs_id: M31,
-    pub next_idx: M31,
     pub lhs: M31,
     pub rhs: M31,
     pub out: M31,
@@ -44,10 +43,6 @@ impl AddTraceTableRow {
             rhs_id: M31::zero(),
             idx: M31::zero(),
             is_last_idx: M31::one(),
-            next_node_id: M31::zero(),
-            next_lhs_id: M31::zero(),
-            next_rhs_id: M31::zero(),
-            next_idx: M31::zero(),
             lhs: M31::zero(),
             rhs: M31::zero(),
             out: M31::zero(),
@@ -58,6 +53,12 @@ impl AddTraceTableRow {
     }
 }
 
+impl TransitionRow for AddTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct PackedAddTraceTableRow {
     pub node_id: PackedM31,
@@ -65,10 +66,6 @@ pub struct PackedAddTraceTableRow {
     pub rhs_id: PackedM31,
     pub idx: PackedM31,
     pub is_last_idx: PackedM31,
-    pub next_node_id: PackedM31,
-    pub next_lhs_id: PackedM31,
-    pub next_rhs_id: PackedM31,
-    pub next_idx: PackedM31,
     pub lhs: PackedM31,
     pub rhs: PackedM31,
     pub out: PackedM31,
@@ -87,10 +84,6 @@ impl Pack for AddTraceTableRow {
             rhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs_id)),
             idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
             is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
-            next_node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_node_id)),
-            next_lhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_lhs_id)),
-            next_rhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_rhs_id)),
-            next_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_idx)),
             lhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lhs)),
             rhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs)),
             out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
@@ -111,10 +104,6 @@ impl Unpack for PackedAddTraceTableRow {
             rhs_id,
             idx,
             is_last_idx,
-            next_node_id,
-            next_lhs_id,
-            next_rhs_id,
-            next_idx,
             lhs,
             rhs,
             out,
@@ -127,10 +116,6 @@ impl Unpack for PackedAddTraceTableRow {
             self.rhs_id.to_array(),
             self.idx.to_array(),
             self.is_last_idx.to_array(),
-            self.next_node_id.to_array(),
-            self.next_lhs_id.to_array(),
-            self.next_rhs_id.to_array(),
-            self.next_idx.to_array(),
             self.lhs.to_array(),
             self.rhs.to_array(),
             self.out.to_array(),
@@ -145,10 +130,6 @@ impl Unpack for PackedAddTraceTableRow {
             rhs_id: rhs_id[i],
             idx: idx[i],
             is_last_idx: is_last_idx[i],
-            next_node_id: next_node_id[i],
-            next_lhs_id: next_lhs_id[i],
-            next_rhs_id: next_rhs_id[i],
-            next_idx: next_idx[i],
             lhs: lhs[i],
             rhs: rhs[i],
             out: out[i],
@@ -176,10 +157,6 @@ pub enum AddColumn {
     RhsId,
     Idx,
     IsLastIdx,
-    NextNodeId,
-    NextLhsId,
-    NextRhsId,
-    NextIdx,
     Lhs,
     Rhs,
     Out,
@@ -196,16 +173,12 @@ impl AddColumn {
             Self::RhsId => 2,
             Self::Idx => 3,
             Self::IsLastIdx => 4,
-            Self::NextNodeId => 5,
-            Self::NextLhsId => 6,
-            Self::NextRhsId => 7,
-            Self::NextIdx => 8,
-            Self::Lhs => 9,
-            Self::Rhs => 10,
-            Self::Out => 11,
-            Self::LhsMult => 12,
-            Self::RhsMult => 13,
-            Self::OutMult => 14,
+            Self::Lhs => 5,
+            Self::Rhs => 6,
+            Self::Out => 7,
+            Self::LhsMult => 8,
+            Self::RhsMult => 9,
+            Self::OutMult => 10,
         }
     }
 }
@@ -14,12 +14,12 @@ use crate::{
         add::table::{AddColumn, AddTraceTableRow},
         AddClaim, InteractionClaim, NodeElements,
     },
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
 use super::table::{AddTraceTable, PackedAddTraceTableRow};
 
-pub(crate) const N_TRACE_COLUMNS: usize = 15;
+pub(crate) const N_TRACE_COLUMNS: usize = 11;
 
 pub struct ClaimGenerator {
     pub inputs: AddTraceTable,
@@ -31,7 +31,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(AddClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -43,8 +43,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs.table.resize(size, AddTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+        let packed_inputs = self
+            .inputs
+            .table
+            .into_packed_coset_order(AddTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -85,10 +87,6 @@ fn write_trace_simd(
             *row[AddColumn::RhsId.index()] = input.rhs_id;
             *row[AddColumn::Idx.index()] = input.idx;
             *row[AddColumn::IsLastIdx.index()] = input.is_last_idx;
-            *row[AddColumn::NextNodeId.index()] = input.next_node_id;
-            *row[AddColumn::NextLhsId.index()] = input.next_lhs_id;
-            *row[AddColumn::NextRhsId.index()] = input.next_rhs_id;
-            *row[AddColumn::NextIdx.index()] = input.next_idx;
             *row[AddColumn::Lhs.index()] = input.lhs;
             *row[AddColumn::Rhs.index()] = input.rhs;
             *row[AddColumn::Out.index()] = input.out;
@@ -0,0 +1,195 @@
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::fields::m31::M31,
+    prover::backend::simd::{
+        conversion::{Pack, Unpack},
+        m31::{PackedM31, N_LANES},
+    },
+};
+
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
+
+/// Rows of the additions of a given constant, proven by their own component instances
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct AddConstTraceTable {
+    pub table: PackedRows<AddConstTraceTableRow>,
+    /// Fixed-point value of the constant operand
+    pub constant: M31,
+}
+
+/// Additions of a constant of a graph, with one table per constant
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct AddConstTraceTables {
+    pub tables: Vec<AddConstTraceTable>,
+}
+
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AddConstTraceTableRow {
+    pub node_id: M31,
+    pub input_id: M31,
+    pub idx: M31,
+    pub is_last_idx: M31,
+    pub input: M31,
+    pub out: M31,
+    pub input_mult: M31,
+    pub out_mult: M31,
+}
+
+impl AddConstTraceTableRow {
+    /// Padding row of the additions of `constant`, whose output is the constant itself so
+    /// that the addition constraint holds on a zero input
+    pub(crate) fn padding(constant: M31) -> Self {
+        Self {
+            node_id: M31::zero(),
+            input_id: M31::zero(),
+            idx: M31::zero(),
+            is_last_idx: M31::one(),
+            input: M31::zero(),
+            out: constant,
+            input_mult: M31::zero(),
+            out_mult: M31::zero(),
+        }
+    }
+}
+
+impl TransitionRow for AddConstTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct PackedAddConstTraceTableRow {
+    pub node_id: PackedM31,
+    pub input_id: PackedM31,
+    pub idx: PackedM31,
+    pub is_last_idx: PackedM31,
+    pub input: PackedM31,
+    pub out: PackedM31,
+    pub input_mult: PackedM31,
+    pub out_mult: PackedM31,
+}
+
+impl Pack for AddConstTraceTableRow {
+    type SimdType = PackedAddConstTraceTableRow;
+
+    fn pack(inputs: [Self; N_LANES]) -> Self::SimdType {
+        PackedAddConstTraceTableRow {
+            node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].node_id)),
+            input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_id)),
+            idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
+            is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
+            input: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input)),
+            out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
+            input_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_mult)),
+            out_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out_mult)),
+        }
+    }
+}
+
+impl Unpack for PackedAddConstTraceTableRow {
+    type CpuType = AddConstTraceTableRow;
+
+    fn unpack(self) -> [Self::CpuType; N_LANES] {
+        let (node_id, input_id, idx, is_last_idx, input, out, input_mult, out_mult) = (
+            self.node_id.to_array(),
+            self.input_id.to_array(),
+            self.idx.to_array(),
+            self.is_last_idx.to_array(),
+            self.input.to_array(),
+            self.out.to_array(),
+            self.input_mult.to_array(),
+            self.out_mult.to_array(),
+        );
+
+        std::array::from_fn(|i| AddConstTraceTableRow {
+            node_id: node_id[i],
+            input_id: input_id[i],
+            idx: idx[i],
+            is_last_idx: is_last_idx[i],
+            input: input[i],
+            out: out[i],
+            input_mult: input_mult[i],
+            out_mult: out_mult[i],
+        })
+    }
+}
+
+impl AddConstTraceTable {
+    pub fn new(constant: M31) -> Self {
+        Self {
+            table: PackedRows::new(),
+            constant,
+        }
+    }
+
+    pub fn add_row(&mut self, row: AddConstTraceTableRow) {
+        self.table.push(row);
+    }
+}
+
+impl AddConstTraceTables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the table of the additions of `constant`, adding it if there is none yet
+    pub fn table_mut(&mut self, constant: M31) -> &mut AddConstTraceTable {
+        let index = match self
+            .tables
+            .iter()
+            .position(|table| table.constant == constant)
+        {
+            Some(index) => index,
+            None => {
+                self.tables.push(AddConstTraceTable::new(constant));
+                self.tables.len() - 1
+            }
+        };
+        &mut self.tables[index]
+    }
+
+    /// Appends the rows of `other` to the table of their constant
+    pub fn append(&mut self, other: Self) {
+        for table in other.tables {
+            self.table_mut(table.constant).table.append(table.table);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AddConstColumn {
+    NodeId,
+    InputId,
+    Idx,
+    IsLastIdx,
+    Input,
+    Out,
+    InputMult,
+    OutMult,
+}
+
+impl AddConstColumn {
+    pub const fn index(self) -> usize {
+        match self {
+            Self::NodeId => 0,
+            Self::InputId => 1,
+            Self::Idx => 2,
+            Self::IsLastIdx => 3,
+            Self::Input => 4,
+            Self::Out => 5,
+            Self::InputMult => 6,
+            Self::OutMult => 7,
+        }
+    }
+}
+
+impl TraceColumn for AddConstColumn {
+    fn count() -> (usize, usize) {
+        (8, 2)
+    }
+}
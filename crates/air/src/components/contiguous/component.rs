@@ -1,7 +1,7 @@
 use crate::components::{ContiguousClaim, NodeElements};
 use num_traits::One;
 use stwo_constraint_framework::{
-    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry,
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
 };
 
 pub type ContiguousComponent = FrameworkComponent<ContiguousEval>;
@@ -35,17 +35,15 @@ impl FrameworkEval for ContiguousEval {
 
     /// Evaluates the contiguous tensor constraints and relations
     fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
-        // IDs
-        let node_id = eval.next_trace_mask(); // ID of the node in the computational graph.
-        let input_id = eval.next_trace_mask(); // ID of the input tensor.
-        let idx = eval.next_trace_mask(); // Index in the flattened tensor.
+        // IDs, along with their value on the next row for transition constraints
+        // ID of the node in the computational graph.
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the input tensor.
+        let [input_id, next_input_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Index in the flattened tensor.
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
         let is_last_idx = eval.next_trace_mask(); // Flag if this is the last index for this operation.
 
-        // Next IDs for transition constraints
-        let next_node_id = eval.next_trace_mask();
-        let next_input_id = eval.next_trace_mask();
-        let next_idx = eval.next_trace_mask();
-
         let input = eval.next_trace_mask();
         let out = eval.next_trace_mask();
 
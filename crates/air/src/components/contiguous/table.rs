@@ -8,13 +8,16 @@ use stwo::{
     },
 };
 
-use crate::components::TraceColumn;
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
 
 use super::witness::N_TRACE_COLUMNS;
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct ContiguousTraceTable {
-    pub table: Vec<ContiguousTraceTableRow>,
+    pub table: PackedRows<ContiguousTraceTableRow>,
 }
 
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
@@ -23,9 +26,6 @@ pub struct ContiguousTraceTableRow {
     pub input_id: M31,
     pub idx: M31,
     pub is_last_idx: M31,
-    pub next_node_id: M31,
-    pub next_input_id: M31,
-    pub next_idx: M31,
     pub input: M31,
     pub out: M31,
     pub input_mult: M31,
@@ -39,9 +39,6 @@ impl ContiguousTraceTableRow {
             input_id: M31::zero(),
             idx: M31::zero(),
             is_last_idx: M31::one(),
-            next_node_id: M31::zero(),
-            next_input_id: M31::zero(),
-            next_idx: M31::zero(),
             input: M31::zero(),
             out: M31::zero(),
             input_mult: M31::zero(),
@@ -50,15 +47,18 @@ impl ContiguousTraceTableRow {
     }
 }
 
+impl TransitionRow for ContiguousTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct PackedContiguousTraceTableRow {
     pub node_id: PackedM31,
     pub input_id: PackedM31,
     pub idx: PackedM31,
     pub is_last_idx: PackedM31,
-    pub next_node_id: PackedM31,
-    pub next_input_id: PackedM31,
-    pub next_idx: PackedM31,
     pub input: PackedM31,
     pub out: PackedM31,
     pub input_mult: PackedM31,
@@ -74,9 +74,6 @@ impl Pack for ContiguousTraceTableRow {
             input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_id)),
             idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
             is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
-            next_node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_node_id)),
-            next_input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_input_id)),
-            next_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_idx)),
             input: PackedM31::from_array(std::array::from_fn(|i: usize| inputs[i].input)),
             out: PackedM31::from_array(std::array::from_fn(|i: usize| inputs[i].out)),
             input_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_mult)),
@@ -89,26 +86,11 @@ impl Unpack for PackedContiguousTraceTableRow {
     type CpuType = ContiguousTraceTableRow;
 
     fn unpack(self) -> [Self::CpuType; N_LANES] {
-        let (
-            node_id,
-            input_id,
-            idx,
-            is_last_idx,
-            next_node_id,
-            next_input_id,
-            next_idx,
-            input,
-            out,
-            input_mult,
-            out_mult,
-        ) = (
+        let (node_id, input_id, idx, is_last_idx, input, out, input_mult, out_mult) = (
             self.node_id.to_array(),
             self.input_id.to_array(),
             self.idx.to_array(),
             self.is_last_idx.to_array(),
-            self.next_node_id.to_array(),
-            self.next_input_id.to_array(),
-            self.next_idx.to_array(),
             self.input.to_array(),
             self.out.to_array(),
             self.input_mult.to_array(),
@@ -120,9 +102,6 @@ impl Unpack for PackedContiguousTraceTableRow {
             input_id: input_id[i],
             idx: idx[i],
             is_last_idx: is_last_idx[i],
-            next_node_id: next_node_id[i],
-            next_input_id: next_input_id[i],
-            next_idx: next_idx[i],
             input: input[i],
             out: out[i],
             input_mult: input_mult[i],
@@ -147,9 +126,6 @@ pub enum ContiguousColumn {
     InputId,
     Idx,
     IsLastIdx,
-    NextNodeId,
-    NextInputId,
-    NextIdx,
     Input,
     Out,
     InputMult,
@@ -163,13 +139,10 @@ impl ContiguousColumn {
             Self::InputId => 1,
             Self::Idx => 2,
             Self::IsLastIdx => 3,
-            Self::NextNodeId => 4,
-            Self::NextInputId => 5,
-            Self::NextIdx => 6,
-            Self::Input => 7,
-            Self::Out => 8,
-            Self::InputMult => 9,
-            Self::OutMult => 10,
+            Self::Input => 4,
+            Self::Out => 5,
+            Self::InputMult => 6,
+            Self::OutMult => 7,
         }
     }
 }
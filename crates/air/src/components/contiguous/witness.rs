@@ -11,14 +11,14 @@ use stwo_constraint_framework::{LogupTraceGenerator, Relation};
 
 use crate::{
     components::{ContiguousClaim, InteractionClaim, NodeElements},
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
 use super::table::{
     ContiguousColumn, ContiguousTraceTable, ContiguousTraceTableRow, PackedContiguousTraceTableRow,
 };
 
-pub(crate) const N_TRACE_COLUMNS: usize = 11;
+pub(crate) const N_TRACE_COLUMNS: usize = 8;
 
 pub struct ClaimGenerator {
     pub inputs: ContiguousTraceTable,
@@ -30,7 +30,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(ContiguousClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -42,10 +42,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs
+        let packed_inputs = self
+            .inputs
             .table
-            .resize(size, ContiguousTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+            .into_packed_coset_order(ContiguousTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -85,9 +85,6 @@ fn write_trace_simd(
             *row[ContiguousColumn::InputId.index()] = input.input_id;
             *row[ContiguousColumn::Idx.index()] = input.idx;
             *row[ContiguousColumn::IsLastIdx.index()] = input.is_last_idx;
-            *row[ContiguousColumn::NextNodeId.index()] = input.next_node_id;
-            *row[ContiguousColumn::NextInputId.index()] = input.next_input_id;
-            *row[ContiguousColumn::NextIdx.index()] = input.next_idx;
             *row[ContiguousColumn::Input.index()] = input.input;
             *row[ContiguousColumn::Out.index()] = input.out;
             *row[ContiguousColumn::InputMult.index()] = input.input_mult;
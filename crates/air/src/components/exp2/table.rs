@@ -9,11 +9,14 @@ use stwo::{
 };
 
 use super::witness::N_TRACE_COLUMNS;
-use crate::components::TraceColumn;
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct Exp2TraceTable {
-    pub table: Vec<Exp2TraceTableRow>,
+    pub table: PackedRows<Exp2TraceTableRow>,
 }
 
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
@@ -22,9 +25,6 @@ pub struct Exp2TraceTableRow {
     pub input_id: M31,
     pub idx: M31,
     pub is_last_idx: M31,
-    pub next_node_id: M31,
-    pub next_input_id: M31,
-    pub next_idx: M31,
     pub input: M31,
     pub out: M31,
     pub input_mult: M31,
@@ -39,9 +39,6 @@ impl Exp2TraceTableRow {
             input_id: M31::zero(),
             idx: M31::zero(),
             is_last_idx: M31::one(),
-            next_node_id: M31::zero(),
-            next_input_id: M31::zero(),
-            next_idx: M31::zero(),
             input: M31::zero(),
             out: M31::zero(),
             input_mult: M31::zero(),
@@ -51,15 +48,18 @@ impl Exp2TraceTableRow {
     }
 }
 
+impl TransitionRow for Exp2TraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct PackedExp2TraceTableRow {
     pub node_id: PackedM31,
     pub input_id: PackedM31,
     pub idx: PackedM31,
     pub is_last_idx: PackedM31,
-    pub next_node_id: PackedM31,
-    pub next_input_id: PackedM31,
-    pub next_idx: PackedM31,
     pub input: PackedM31,
     pub out: PackedM31,
     pub input_mult: PackedM31,
@@ -76,9 +76,6 @@ impl Pack for Exp2TraceTableRow {
             input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_id)),
             idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
             is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
-            next_node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_node_id)),
-            next_input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_input_id)),
-            next_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_idx)),
             input: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input)),
             out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
             input_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_mult)),
@@ -92,27 +89,11 @@ impl Unpack for PackedExp2TraceTableRow {
     type CpuType = Exp2TraceTableRow;
 
     fn unpack(self) -> [Self::CpuType; N_LANES] {
-        let (
-            node_id,
-            input_id,
-            idx,
-            is_last_idx,
-            next_node_id,
-            next_input_id,
-            next_idx,
-            input,
-            out,
-            input_mult,
-            out_mult,
-            lookup_mult,
-        ) = (
+        let (node_id, input_id, idx, is_last_idx, input, out, input_mult, out_mult, lookup_mult) = (
             self.node_id.to_array(),
             self.input_id.to_array(),
             self.idx.to_array(),
             self.is_last_idx.to_array(),
-            self.next_node_id.to_array(),
-            self.next_input_id.to_array(),
-            self.next_idx.to_array(),
             self.input.to_array(),
             self.out.to_array(),
             self.input_mult.to_array(),
@@ -125,9 +106,6 @@ impl Unpack for PackedExp2TraceTableRow {
             input_id: input_id[i],
             idx: idx[i],
             is_last_idx: is_last_idx[i],
-            next_node_id: next_node_id[i],
-            next_input_id: next_input_id[i],
-            next_idx: next_idx[i],
             input: input[i],
             out: out[i],
             input_mult: input_mult[i],
@@ -153,9 +131,6 @@ pub enum Exp2Column {
     InputId,
     Idx,
     IsLastIdx,
-    NextNodeId,
-    NextInputId,
-    NextIdx,
     Input,
     Out,
     InputMult,
@@ -170,14 +145,11 @@ impl Exp2Column {
             Self::InputId => 1,
             Self::Idx => 2,
             Self::IsLastIdx => 3,
-            Self::NextNodeId => 4,
-            Self::NextInputId => 5,
-            Self::NextIdx => 6,
-            Self::Input => 7,
-            Self::Out => 8,
-            Self::InputMult => 9,
-            Self::OutMult => 10,
-            Self::LookupMult => 11,
+            Self::Input => 4,
+            Self::Out => 5,
+            Self::InputMult => 6,
+            Self::OutMult => 7,
+            Self::LookupMult => 8,
         }
     }
 }
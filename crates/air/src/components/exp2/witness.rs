@@ -13,10 +13,10 @@ use crate::{
     components::{
         exp2::table::{Exp2Column, Exp2TraceTable, Exp2TraceTableRow, PackedExp2TraceTableRow}, lookups::exp2::Exp2LookupElements, Exp2Claim, InteractionClaim, NodeElements
     },
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
-pub(crate) const N_TRACE_COLUMNS: usize = 12;
+pub(crate) const N_TRACE_COLUMNS: usize = 9;
 
 pub struct ClaimGenerator {
     pub inputs: Exp2TraceTable,
@@ -28,7 +28,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(Exp2Claim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -40,8 +40,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs.table.resize(size, Exp2TraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+        let packed_inputs = self
+            .inputs
+            .table
+            .into_packed_coset_order(Exp2TraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -81,9 +83,6 @@ fn write_trace_simd(
             *row[Exp2Column::InputId.index()] = input.input_id;
             *row[Exp2Column::Idx.index()] = input.idx;
             *row[Exp2Column::IsLastIdx.index()] = input.is_last_idx;
-            *row[Exp2Column::NextNodeId.index()] = input.next_node_id;
-            *row[Exp2Column::NextInputId.index()] = input.next_input_id;
-            *row[Exp2Column::NextIdx.index()] = input.next_idx;
             *row[Exp2Column::Input.index()] = input.input;
             *row[Exp2Column::Out.index()] = input.out;
             *row[Exp2Column::InputMult.index()] = input.input_mult;
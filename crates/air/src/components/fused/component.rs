@@ -0,0 +1,180 @@
+use crate::{
+    components::{FusedClaim, NodeElements},
+    DEFAULT_FP_SCALE,
+};
+use num_traits::One;
+use numerair::eval::EvalFixedPoint;
+use stwo::core::fields::{m31::M31, FieldExpOps};
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
+};
+
+use super::expr::{const_m31, FusedExpr, FusedOp, FusedOperand};
+
+pub type FusedComponent = FrameworkComponent<FusedEval>;
+
+/// Evaluation structure for fused elementwise expressions
+pub struct FusedEval {
+    log_size: u32,
+    expr: FusedExpr,
+    node_elements: NodeElements,
+}
+
+impl FusedEval {
+    /// Creates a new FusedEval with the given claim and node elements
+    pub fn new(claim: &FusedClaim, node_elements: NodeElements) -> Self {
+        Self {
+            log_size: claim.log_size,
+            expr: claim.expr.clone(),
+            node_elements,
+        }
+    }
+}
+
+impl FrameworkEval for FusedEval {
+    /// Returns the log size of the evaluation
+    fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Returns the maximum constraint log degree bound
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.log_size + 1
+    }
+
+    /// Evaluates the operations of the expression, one after the other, along with the
+    /// relations of its inputs and output
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        let n_inputs = self.expr.n_inputs;
+
+        // IDs, along with their value on the next row for transition constraints
+        // ID of the node in the computational graph.
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // IDs of the input tensors.
+        let input_ids: Vec<[E::F; 2]> = (0..n_inputs)
+            .map(|_| eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]))
+            .collect();
+        // Index in the flattened tensor.
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let is_last_idx = eval.next_trace_mask(); // Flag if this is the last index for this operation.
+        let scale = eval.next_trace_mask(); // Scale, zero on padding rows.
+
+        // Values for consistency constraints
+        // Values from the input tensors at index.
+        let input_vals: Vec<E::F> = (0..n_inputs).map(|_| eval.next_trace_mask()).collect();
+        // Result of each operation, along with its remainder if it has one.
+        let step_vals: Vec<(E::F, Option<E::F>)> = self
+            .expr
+            .steps
+            .iter()
+            .map(|step| {
+                let val = eval.next_trace_mask();
+                let rem = step.has_rem().then(|| eval.next_trace_mask());
+                (val, rem)
+            })
+            .collect();
+
+        // Multiplicities for interaction constraints
+        let input_mults: Vec<E::F> = (0..n_inputs).map(|_| eval.next_trace_mask()).collect();
+        let out_mult = eval.next_trace_mask();
+
+        // Constants are given relative to the scale column, so that they vanish along with
+        // every other value on padding rows.
+        let scale_factor = M31::from_u32_unchecked(1 << DEFAULT_FP_SCALE);
+        let inv_scale_factor = scale_factor.inverse();
+        let operand = |operand: FusedOperand| match operand {
+            FusedOperand::Input(input) => input_vals[input].clone(),
+            FusedOperand::Step(step) => step_vals[step].0.clone(),
+            FusedOperand::Const(raw) => {
+                scale.clone() * E::F::from(const_m31(raw) * inv_scale_factor)
+            }
+        };
+
+        // ┌─────────────────────────────┐
+        // │   Consistency Constraints   │
+        // └─────────────────────────────┘
+
+        // The is_last_idx flag is either 0 or 1.
+        eval.add_constraint(is_last_idx.clone() * (is_last_idx.clone() - E::F::one()));
+
+        // The scale is either the fixed-point scale factor or zero on padding rows, which
+        // take no part in the relations.
+        let is_padding = scale.clone() - E::F::from(scale_factor);
+        eval.add_constraint(scale.clone() * is_padding.clone());
+        for mult in input_mults.iter().chain([&out_mult]) {
+            eval.add_constraint(mult.clone() * is_padding.clone());
+        }
+
+        // Evaluates each fixed point operation of the expression.
+        for (step, (out_val, rem_val)) in self.expr.steps.iter().zip(&step_vals) {
+            let out_val = out_val.clone();
+            match (*step, rem_val.clone()) {
+                (FusedOp::Add(lhs, rhs), _) => {
+                    eval.eval_fixed_add(operand(lhs), operand(rhs), out_val)
+                }
+                (FusedOp::Mul(lhs, rhs), Some(rem_val)) => {
+                    eval.eval_fixed_mul(operand(lhs), operand(rhs), scale.clone(), out_val, rem_val)
+                }
+                (FusedOp::Recip(input), Some(rem_val)) => {
+                    eval.eval_fixed_recip(operand(input), scale.clone(), out_val, rem_val)
+                }
+                (FusedOp::Sqrt(input), Some(rem_val)) => {
+                    eval.eval_fixed_sqrt(operand(input), out_val, rem_val, scale.clone())
+                }
+                _ => unreachable!("operations with a remainder have a remainder column"),
+            }
+        }
+
+        // ┌────────────────────────────┐
+        // │   Transition Constraints   │
+        // └────────────────────────────┘
+
+        // If this is not the last index for this operation, then:
+        // 1. The next row should be for the same operation on the same tensors.
+        // 2. The index should increment by 1.
+        let not_last = E::F::one() - is_last_idx;
+
+        // Same node ID
+        eval.add_constraint(not_last.clone() * (next_node_id - node_id.clone()));
+
+        // Same tensor IDs
+        for [input_id, next_input_id] in &input_ids {
+            eval.add_constraint(not_last.clone() * (next_input_id.clone() - input_id.clone()));
+        }
+
+        // Index increment by 1
+        eval.add_constraint(not_last * (next_idx - idx - E::F::one()));
+
+        // ┌─────────────────────────────┐
+        // │   Interaction Constraints   │
+        // └─────────────────────────────┘
+
+        for (([input_id, _], input_val), input_mult) in input_ids
+            .into_iter()
+            .zip(input_vals.iter())
+            .zip(input_mults)
+        {
+            eval.add_to_relation(RelationEntry::new(
+                &self.node_elements,
+                input_mult.into(),
+                &[input_val.clone(), input_id],
+            ));
+        }
+
+        // The output is the result of the last operation.
+        let out_val = step_vals
+            .last()
+            .expect("fused expressions aren't empty")
+            .0
+            .clone();
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            out_mult.into(),
+            &[out_val, node_id],
+        ));
+
+        eval.finalize_logup();
+
+        eval
+    }
+}
@@ -0,0 +1,131 @@
+use numerair::Fixed;
+use serde::{Deserialize, Serialize};
+use stwo::core::{channel::Channel, fields::m31::M31};
+
+use crate::DEFAULT_FP_SCALE;
+
+/// Maximum number of input tensors of a fused expression
+pub const MAX_FUSED_INPUTS: usize = 4;
+/// Maximum number of operations of a fused expression
+pub const MAX_FUSED_STEPS: usize = 8;
+
+/// Operand of an operation of a fused expression
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FusedOperand {
+    /// Input tensor, read through the node relation
+    Input(usize),
+    /// Result of an earlier operation, held in a column of the trace
+    Step(usize),
+    /// Raw fixed-point value of a constant
+    Const(i64),
+}
+
+impl FusedOperand {
+    /// Mixes the kind of the operand, then its index or constant, into the channel
+    fn mix_into(&self, channel: &mut impl Channel) {
+        let (kind, value) = match *self {
+            Self::Input(input) => (0, input as u64),
+            Self::Step(step) => (1, step as u64),
+            Self::Const(raw) => (2, const_m31(raw).0.into()),
+        };
+        channel.mix_u64(kind);
+        channel.mix_u64(value);
+    }
+}
+
+/// Elementwise operation of a fused expression
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FusedOp {
+    Add(FusedOperand, FusedOperand),
+    Mul(FusedOperand, FusedOperand),
+    Recip(FusedOperand),
+    Sqrt(FusedOperand),
+}
+
+impl FusedOp {
+    /// Operands of the operation
+    pub fn operands(&self) -> impl Iterator<Item = FusedOperand> {
+        let (lhs, rhs) = match *self {
+            Self::Add(lhs, rhs) | Self::Mul(lhs, rhs) => (lhs, Some(rhs)),
+            Self::Recip(input) | Self::Sqrt(input) => (input, None),
+        };
+        std::iter::once(lhs).chain(rhs)
+    }
+
+    /// Whether the result of the operation comes with a remainder column
+    pub fn has_rem(&self) -> bool {
+        !matches!(self, Self::Add(..))
+    }
+
+    fn opcode(&self) -> u64 {
+        match self {
+            Self::Add(..) => 0,
+            Self::Mul(..) => 1,
+            Self::Recip(..) => 2,
+            Self::Sqrt(..) => 3,
+        }
+    }
+}
+
+/// Elementwise expression proven by a fused component, as a sequence of operations whose last
+/// one yields the output
+///
+/// Intermediate results are columns of the trace rather than tensors read and written through
+/// the node relation. The expression is public: the trace layout and the constraints of the
+/// component are derived from it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FusedExpr {
+    pub n_inputs: usize,
+    pub steps: Vec<FusedOp>,
+}
+
+impl FusedExpr {
+    /// Creates a new expression over `n_inputs` input tensors
+    pub fn new(n_inputs: usize, steps: Vec<FusedOp>) -> Self {
+        Self { n_inputs, steps }
+    }
+
+    /// Whether the expression is within the limits of the component and each operand refers
+    /// to an input or to an earlier operation
+    pub fn is_valid(&self) -> bool {
+        (1..=MAX_FUSED_INPUTS).contains(&self.n_inputs)
+            && (1..=MAX_FUSED_STEPS).contains(&self.steps.len())
+            && self.steps.iter().enumerate().all(|(i, step)| {
+                step.operands().all(|operand| match operand {
+                    FusedOperand::Input(input) => input < self.n_inputs,
+                    FusedOperand::Step(step) => step < i,
+                    FusedOperand::Const(_) => true,
+                })
+            })
+    }
+
+    /// Number of columns of the main trace
+    pub fn n_columns(&self) -> usize {
+        let n_rems = self.steps.iter().filter(|step| step.has_rem()).count();
+        // IDs and multiplicities of the inputs and output, along with the input values, the
+        // index, the is_last_idx flag and the scale
+        3 * self.n_inputs + 5 + self.steps.len() + n_rems
+    }
+
+    /// Number of LogUp columns of the interaction trace, one per input and one for the output
+    pub fn n_relations(&self) -> usize {
+        self.n_inputs + 1
+    }
+
+    /// Mixes the expression into the given channel
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.n_inputs as u64);
+        channel.mix_u64(self.steps.len() as u64);
+        for step in &self.steps {
+            channel.mix_u64(step.opcode());
+            for operand in step.operands() {
+                operand.mix_into(channel);
+            }
+        }
+    }
+}
+
+/// Field element of a raw fixed-point constant
+pub fn const_m31(raw: i64) -> M31 {
+    Fixed::<DEFAULT_FP_SCALE>(raw).to_m31()
+}
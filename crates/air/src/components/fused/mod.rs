@@ -0,0 +1,4 @@
+pub mod component;
+pub mod expr;
+pub mod table;
+pub mod witness;
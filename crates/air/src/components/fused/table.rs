@@ -0,0 +1,246 @@
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::fields::m31::M31,
+    prover::backend::simd::{
+        conversion::{Pack, Unpack},
+        m31::{PackedM31, N_LANES},
+    },
+};
+
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
+
+use super::expr::{FusedExpr, MAX_FUSED_INPUTS, MAX_FUSED_STEPS};
+
+/// Rows of the operations evaluating a given fused expression, proven by their own component
+/// instances
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FusedTraceTable {
+    pub table: PackedRows<FusedTraceTableRow>,
+    /// Expression evaluated by each row
+    pub expr: FusedExpr,
+}
+
+/// Fused elementwise operations of a graph, with one table per expression
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct FusedTraceTables {
+    pub tables: Vec<FusedTraceTable>,
+}
+
+/// Row of a fused expression, sized for the largest expression
+///
+/// Slots beyond the inputs and operations of the expression are left to zero and aren't
+/// committed.
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FusedTraceTableRow {
+    pub node_id: M31,
+    pub input_ids: [M31; MAX_FUSED_INPUTS],
+    pub idx: M31,
+    pub is_last_idx: M31,
+    pub scale: M31,
+    pub inputs: [M31; MAX_FUSED_INPUTS],
+    pub steps: [M31; MAX_FUSED_STEPS],
+    pub rems: [M31; MAX_FUSED_STEPS],
+    pub input_mults: [M31; MAX_FUSED_INPUTS],
+    pub out_mult: M31,
+}
+
+impl FusedTraceTableRow {
+    pub(crate) fn padding() -> Self {
+        Self {
+            node_id: M31::zero(),
+            input_ids: [M31::zero(); MAX_FUSED_INPUTS],
+            idx: M31::zero(),
+            is_last_idx: M31::one(),
+            scale: M31::zero(),
+            inputs: [M31::zero(); MAX_FUSED_INPUTS],
+            steps: [M31::zero(); MAX_FUSED_STEPS],
+            rems: [M31::zero(); MAX_FUSED_STEPS],
+            input_mults: [M31::zero(); MAX_FUSED_INPUTS],
+            out_mult: M31::zero(),
+        }
+    }
+}
+
+impl TransitionRow for FusedTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct PackedFusedTraceTableRow {
+    pub node_id: PackedM31,
+    pub input_ids: [PackedM31; MAX_FUSED_INPUTS],
+    pub idx: PackedM31,
+    pub is_last_idx: PackedM31,
+    pub scale: PackedM31,
+    pub inputs: [PackedM31; MAX_FUSED_INPUTS],
+    pub steps: [PackedM31; MAX_FUSED_STEPS],
+    pub rems: [PackedM31; MAX_FUSED_STEPS],
+    pub input_mults: [PackedM31; MAX_FUSED_INPUTS],
+    pub out_mult: PackedM31,
+}
+
+impl Pack for FusedTraceTableRow {
+    type SimdType = PackedFusedTraceTableRow;
+
+    fn pack(inputs: [Self; N_LANES]) -> Self::SimdType {
+        let pack = |value: &dyn Fn(&Self) -> M31| {
+            PackedM31::from_array(std::array::from_fn(|i| value(&inputs[i])))
+        };
+        PackedFusedTraceTableRow {
+            node_id: pack(&|row| row.node_id),
+            input_ids: std::array::from_fn(|j| pack(&|row| row.input_ids[j])),
+            idx: pack(&|row| row.idx),
+            is_last_idx: pack(&|row| row.is_last_idx),
+            scale: pack(&|row| row.scale),
+            inputs: std::array::from_fn(|j| pack(&|row| row.inputs[j])),
+            steps: std::array::from_fn(|j| pack(&|row| row.steps[j])),
+            rems: std::array::from_fn(|j| pack(&|row| row.rems[j])),
+            input_mults: std::array::from_fn(|j| pack(&|row| row.input_mults[j])),
+            out_mult: pack(&|row| row.out_mult),
+        }
+    }
+}
+
+impl Unpack for PackedFusedTraceTableRow {
+    type CpuType = FusedTraceTableRow;
+
+    fn unpack(self) -> [Self::CpuType; N_LANES] {
+        let (node_id, idx, is_last_idx, scale, out_mult) = (
+            self.node_id.to_array(),
+            self.idx.to_array(),
+            self.is_last_idx.to_array(),
+            self.scale.to_array(),
+            self.out_mult.to_array(),
+        );
+        let (input_ids, inputs, input_mults) = (
+            self.input_ids.map(|packed| packed.to_array()),
+            self.inputs.map(|packed| packed.to_array()),
+            self.input_mults.map(|packed| packed.to_array()),
+        );
+        let (steps, rems) = (
+            self.steps.map(|packed| packed.to_array()),
+            self.rems.map(|packed| packed.to_array()),
+        );
+
+        std::array::from_fn(|i| FusedTraceTableRow {
+            node_id: node_id[i],
+            input_ids: input_ids.map(|lanes| lanes[i]),
+            idx: idx[i],
+            is_last_idx: is_last_idx[i],
+            scale: scale[i],
+            inputs: inputs.map(|lanes| lanes[i]),
+            steps: steps.map(|lanes| lanes[i]),
+            rems: rems.map(|lanes| lanes[i]),
+            input_mults: input_mults.map(|lanes| lanes[i]),
+            out_mult: out_mult[i],
+        })
+    }
+}
+
+impl FusedTraceTable {
+    pub fn new(expr: FusedExpr) -> Self {
+        Self {
+            table: PackedRows::new(),
+            expr,
+        }
+    }
+
+    pub fn add_row(&mut self, row: FusedTraceTableRow) {
+        self.table.push(row);
+    }
+}
+
+impl FusedTraceTables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the table of the operations evaluating `expr`, adding it if there is none yet
+    pub fn table_mut(&mut self, expr: &FusedExpr) -> &mut FusedTraceTable {
+        let index = match self.tables.iter().position(|table| table.expr == *expr) {
+            Some(index) => index,
+            None => {
+                self.tables.push(FusedTraceTable::new(expr.clone()));
+                self.tables.len() - 1
+            }
+        };
+        &mut self.tables[index]
+    }
+
+    /// Appends the rows of `other` to the table of their expression
+    pub fn append(&mut self, other: Self) {
+        for table in other.tables {
+            self.table_mut(&table.expr).table.append(table.table);
+        }
+    }
+}
+
+/// Column of the main trace of a fused expression
+///
+/// The trace only holds the columns of the inputs and operations of its expression, so the
+/// index of a column depends on the expression.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum FusedColumn {
+    NodeId,
+    InputId(usize),
+    Idx,
+    IsLastIdx,
+    Scale,
+    Input(usize),
+    Step(usize),
+    Rem(usize),
+    InputMult(usize),
+    OutMult,
+}
+
+impl FusedColumn {
+    /// Columns of the main trace of `expr`, in the order they are committed and evaluated
+    pub fn layout(expr: &FusedExpr) -> Vec<Self> {
+        let inputs = 0..expr.n_inputs;
+        let mut columns = vec![Self::NodeId];
+        columns.extend(inputs.clone().map(Self::InputId));
+        columns.extend([Self::Idx, Self::IsLastIdx, Self::Scale]);
+        columns.extend(inputs.clone().map(Self::Input));
+        for (i, step) in expr.steps.iter().enumerate() {
+            columns.push(Self::Step(i));
+            if step.has_rem() {
+                columns.push(Self::Rem(i));
+            }
+        }
+        columns.extend(inputs.map(Self::InputMult));
+        columns.push(Self::OutMult);
+        columns
+    }
+
+    /// Value of the column in a packed row
+    pub fn value(self, row: &PackedFusedTraceTableRow) -> PackedM31 {
+        match self {
+            Self::NodeId => row.node_id,
+            Self::InputId(i) => row.input_ids[i],
+            Self::Idx => row.idx,
+            Self::IsLastIdx => row.is_last_idx,
+            Self::Scale => row.scale,
+            Self::Input(i) => row.inputs[i],
+            Self::Step(i) => row.steps[i],
+            Self::Rem(i) => row.rems[i],
+            Self::InputMult(i) => row.input_mults[i],
+            Self::OutMult => row.out_mult,
+        }
+    }
+}
+
+impl TraceColumn for FusedColumn {
+    /// Columns of the largest expression, see `FusedExpr::n_columns` for a given one
+    fn count() -> (usize, usize) {
+        (
+            3 * MAX_FUSED_INPUTS + 5 + 2 * MAX_FUSED_STEPS,
+            MAX_FUSED_INPUTS + 1,
+        )
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    components::{FusedClaim, InteractionClaim, NodeElements},
+    utils::TreeBuilder,
+};
+use luminair_utils::TraceError;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use stwo::{
+    core::{fields::m31::M31, poly::circle::CanonicCoset},
+    prover::{
+        backend::simd::{
+            column::BaseColumn,
+            m31::{PackedM31, LOG_N_LANES, N_LANES},
+            qm31::PackedQM31,
+            SimdBackend,
+        },
+        poly::{circle::CircleEvaluation, BitReversedOrder},
+    },
+};
+use stwo_constraint_framework::{LogupTraceGenerator, Relation};
+
+use super::{
+    expr::FusedExpr,
+    table::{FusedColumn, FusedTraceTable, FusedTraceTableRow, PackedFusedTraceTableRow},
+};
+
+pub struct ClaimGenerator {
+    pub inputs: FusedTraceTable,
+}
+
+impl ClaimGenerator {
+    pub fn new(inputs: FusedTraceTable) -> Self {
+        Self { inputs }
+    }
+
+    pub fn write_trace(
+        self,
+        tree_builder: &mut impl TreeBuilder<SimdBackend>,
+    ) -> Result<(FusedClaim, InteractionClaimGenerator), TraceError> {
+        let n_rows = self.inputs.table.len();
+
+        if n_rows == 0 {
+            return Err(TraceError::EmptyTrace);
+        }
+
+        let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
+        let log_size = size.ilog2();
+        let expr = self.inputs.expr;
+
+        let packed_inputs = self
+            .inputs
+            .table
+            .into_packed_coset_order(FusedTraceTableRow::padding());
+
+        let (trace, lookup_data) = write_trace_simd(&expr, log_size, packed_inputs);
+
+        tree_builder.extend_evals(trace);
+
+        Ok((
+            FusedClaim::new(log_size, expr),
+            InteractionClaimGenerator {
+                log_size,
+                lookup_data,
+            },
+        ))
+    }
+}
+
+/// Writes the columns of the layout of `expr`, whose number depends on the expression
+fn write_trace_simd(
+    expr: &FusedExpr,
+    log_size: u32,
+    inputs: Vec<PackedFusedTraceTableRow>,
+) -> (
+    Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    LookupData,
+) {
+    let domain = CanonicCoset::new(log_size).circle_domain();
+    let trace = FusedColumn::layout(expr)
+        .par_iter()
+        .map(|column| {
+            let values = inputs.iter().map(|input| column.value(input)).collect();
+            CircleEvaluation::new(domain, BaseColumn::from_simd(values))
+        })
+        .collect();
+
+    let last_step = expr.steps.len() - 1;
+    let lookup_data = LookupData {
+        inputs: (0..expr.n_inputs)
+            .map(|i| {
+                inputs
+                    .iter()
+                    .map(|input| ([input.inputs[i], input.input_ids[i]], input.input_mults[i]))
+                    .unzip()
+            })
+            .collect(),
+        out: inputs
+            .iter()
+            .map(|input| ([input.steps[last_step], input.node_id], input.out_mult))
+            .unzip(),
+    };
+
+    (trace, lookup_data)
+}
+
+/// Values and multiplicities of each relation entry, per packed row
+struct LookupData {
+    inputs: Vec<(Vec<[PackedM31; 2]>, Vec<PackedM31>)>,
+    out: (Vec<[PackedM31; 2]>, Vec<PackedM31>),
+}
+
+pub struct InteractionClaimGenerator {
+    log_size: u32,
+    lookup_data: LookupData,
+}
+
+impl InteractionClaimGenerator {
+    pub fn write_interaction_trace(
+        self,
+        tree_builder: &mut impl TreeBuilder<SimdBackend>,
+        node_elements: &NodeElements,
+    ) -> InteractionClaim {
+        let mut logup_gen = LogupTraceGenerator::new(self.log_size);
+
+        let relations = self
+            .lookup_data
+            .inputs
+            .iter()
+            .chain([&self.lookup_data.out]);
+        for (values, multiplicities) in relations {
+            let mut col_gen = logup_gen.new_col();
+            for row in 0..1 << (self.log_size - LOG_N_LANES) {
+                let denom: PackedQM31 = node_elements.combine(&values[row]);
+                col_gen.write_frac(row, multiplicities[row].into(), denom);
+            }
+            col_gen.finalize_col();
+        }
+
+        let (trace, claimed_sum) = logup_gen.finalize_last();
+        tree_builder.extend_evals(trace);
+
+        InteractionClaim { claimed_sum }
+    }
+}
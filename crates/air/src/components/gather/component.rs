@@ -0,0 +1,167 @@
+use crate::{
+    components::{lookups::gather::GatherLookupElements, GatherClaim, NodeElements},
+    DEFAULT_FP_SCALE_FACTOR,
+};
+use num_traits::One;
+use stwo::core::fields::m31::M31;
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
+};
+
+pub type GatherComponent = FrameworkComponent<GatherEval>;
+
+/// Evaluation structure for gather (embedding lookup) operations
+pub struct GatherEval {
+    log_size: u32,
+    node_elements: NodeElements,
+    lookup_elements: GatherLookupElements,
+}
+
+impl GatherEval {
+    /// Creates a new GatherEval with the given claim, node elements and gather lookup elements
+    pub fn new(
+        claim: &GatherClaim,
+        node_elements: NodeElements,
+        lookup_elements: GatherLookupElements,
+    ) -> Self {
+        Self {
+            log_size: claim.log_size,
+            node_elements,
+            lookup_elements,
+        }
+    }
+}
+
+impl FrameworkEval for GatherEval {
+    /// Returns the log size of the evaluation
+    fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Returns the maximum constraint log degree bound
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.log_size + 1
+    }
+
+    /// Evaluates the gather constraints and relations
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        // IDs, along with their value on the next row for transition constraints
+        // ID of the node in the computational graph.
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the table tensor.
+        let [table_id, next_table_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the indices tensor.
+        let [indices_id, next_indices_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Index in the flattened output tensor.
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let is_last_idx = eval.next_trace_mask(); // Flag if this is the last index for this operation.
+
+        // Position inside the gathered table row
+        // Number of elements in a table row.
+        let [row_size, next_row_size] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Position of this element in the gathered row.
+        let [col, next_col] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let is_last_col = eval.next_trace_mask(); // Flag if this is the last element of the row.
+        let same_row = eval.next_trace_mask(); // Flag if the next row reads the same table row.
+
+        // Values for consistency constraints
+        // Fixed-point value read from the indices tensor.
+        let index = eval.next_trace_mask();
+        // Index as a field integer.
+        let [index_int, next_index_int] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let lookup_idx = eval.next_trace_mask(); // Flattened position read in the table tensor.
+        let out_val = eval.next_trace_mask(); // Value in output tensor at index.
+
+        // Multiplicities for interaction constraints
+        let index_mult = eval.next_trace_mask();
+        let out_mult = eval.next_trace_mask();
+        let lookup_mult = eval.next_trace_mask();
+
+        let scale_factor = E::F::from(M31::from_u32_unchecked(DEFAULT_FP_SCALE_FACTOR));
+
+        // ┌─────────────────────────────┐
+        // │   Consistency Constraints   │
+        // └─────────────────────────────┘
+
+        // The is_last_idx flag is either 0 or 1.
+        eval.add_constraint(is_last_idx.clone() * (is_last_idx.clone() - E::F::one()));
+
+        // The is_last_col flag is either 0 or 1.
+        eval.add_constraint(is_last_col.clone() * (is_last_col.clone() - E::F::one()));
+
+        // The last element of a gathered row sits at position row_size - 1.
+        eval.add_constraint(
+            is_last_col.clone() * (row_size.clone() - col.clone() - E::F::one()),
+        );
+
+        // The fixed-point index is an integer, i.e. index_int scaled.
+        eval.add_constraint(index.clone() - index_int.clone() * scale_factor);
+
+        // The table is read at index * row_size + col.
+        eval.add_constraint(
+            lookup_idx.clone() - index_int.clone() * row_size.clone() - col.clone(),
+        );
+
+        // ┌────────────────────────────┐
+        // │   Transition Constraints   │
+        // └────────────────────────────┘
+
+        // If this is not the last index for this operation, then:
+        // 1. The next row should be for the same operation on the same tensors.
+        // 2. The index should increment by 1.
+        // 3. Inside a gathered row, the position increments by 1 and the index is unchanged,
+        //    otherwise the next gathered row starts at position 0.
+        let not_last = E::F::one() - is_last_idx;
+
+        // same_row = not_last * (1 - is_last_col)
+        eval.add_constraint(
+            same_row.clone() - not_last.clone() * (E::F::one() - is_last_col),
+        );
+
+        // Same node ID
+        eval.add_constraint(not_last.clone() * (next_node_id - node_id.clone()));
+
+        // Same tensor IDs
+        eval.add_constraint(not_last.clone() * (next_table_id - table_id));
+        eval.add_constraint(not_last.clone() * (next_indices_id - indices_id.clone()));
+
+        // Same row size
+        eval.add_constraint(not_last.clone() * (next_row_size - row_size));
+
+        // Index increment by 1
+        eval.add_constraint(not_last.clone() * (next_idx - idx - E::F::one()));
+
+        // Position increment by 1 inside a gathered row, reset to 0 on the next one
+        eval.add_constraint(same_row.clone() * (next_col.clone() - col - E::F::one()));
+        eval.add_constraint((not_last - same_row.clone()) * next_col);
+
+        // Same index inside a gathered row
+        eval.add_constraint(same_row * (next_index_int - index_int));
+
+        // ┌─────────────────────────────┐
+        // │   Interaction Constraints   │
+        // └─────────────────────────────┘
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            index_mult.into(),
+            &[index, indices_id],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            out_mult.into(),
+            &[out_val.clone(), node_id.clone()],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.lookup_elements,
+            lookup_mult.into(),
+            &[node_id, lookup_idx, out_val],
+        ));
+
+        eval.finalize_logup();
+
+        eval
+    }
+}
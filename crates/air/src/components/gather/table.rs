@@ -0,0 +1,234 @@
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::fields::m31::M31,
+    prover::backend::simd::{
+        conversion::{Pack, Unpack},
+        m31::{PackedM31, N_LANES},
+    },
+};
+
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct GatherTraceTable {
+    pub table: PackedRows<GatherTraceTableRow>,
+}
+
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GatherTraceTableRow {
+    pub node_id: M31,
+    pub table_id: M31,
+    pub indices_id: M31,
+    pub idx: M31,
+    pub is_last_idx: M31,
+    pub row_size: M31,
+    pub col: M31,
+    pub is_last_col: M31,
+    pub same_row: M31,
+    pub index: M31,
+    pub index_int: M31,
+    pub lookup_idx: M31,
+    pub out: M31,
+    pub index_mult: M31,
+    pub out_mult: M31,
+    pub lookup_mult: M31,
+}
+
+impl GatherTraceTableRow {
+    pub(crate) fn padding() -> Self {
+        Self {
+            node_id: M31::zero(),
+            table_id: M31::zero(),
+            indices_id: M31::zero(),
+            idx: M31::zero(),
+            is_last_idx: M31::one(),
+            row_size: M31::one(),
+            col: M31::zero(),
+            is_last_col: M31::one(),
+            same_row: M31::zero(),
+            index: M31::zero(),
+            index_int: M31::zero(),
+            lookup_idx: M31::zero(),
+            out: M31::zero(),
+            index_mult: M31::zero(),
+            out_mult: M31::zero(),
+            lookup_mult: M31::zero(),
+        }
+    }
+}
+
+impl TransitionRow for GatherTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+        self.same_row = M31::zero();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct PackedGatherTraceTableRow {
+    pub node_id: PackedM31,
+    pub table_id: PackedM31,
+    pub indices_id: PackedM31,
+    pub idx: PackedM31,
+    pub is_last_idx: PackedM31,
+    pub row_size: PackedM31,
+    pub col: PackedM31,
+    pub is_last_col: PackedM31,
+    pub same_row: PackedM31,
+    pub index: PackedM31,
+    pub index_int: PackedM31,
+    pub lookup_idx: PackedM31,
+    pub out: PackedM31,
+    pub index_mult: PackedM31,
+    pub out_mult: PackedM31,
+    pub lookup_mult: PackedM31,
+}
+
+impl Pack for GatherTraceTableRow {
+    type SimdType = PackedGatherTraceTableRow;
+
+    fn pack(inputs: [Self; N_LANES]) -> Self::SimdType {
+        PackedGatherTraceTableRow {
+            node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].node_id)),
+            table_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].table_id)),
+            indices_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].indices_id)),
+            idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
+            is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
+            row_size: PackedM31::from_array(std::array::from_fn(|i| inputs[i].row_size)),
+            col: PackedM31::from_array(std::array::from_fn(|i| inputs[i].col)),
+            is_last_col: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_col)),
+            same_row: PackedM31::from_array(std::array::from_fn(|i| inputs[i].same_row)),
+            index: PackedM31::from_array(std::array::from_fn(|i| inputs[i].index)),
+            index_int: PackedM31::from_array(std::array::from_fn(|i| inputs[i].index_int)),
+            lookup_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lookup_idx)),
+            out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
+            index_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].index_mult)),
+            out_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out_mult)),
+            lookup_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lookup_mult)),
+        }
+    }
+}
+
+impl Unpack for PackedGatherTraceTableRow {
+    type CpuType = GatherTraceTableRow;
+
+    fn unpack(self) -> [Self::CpuType; N_LANES] {
+        let (
+            node_id,
+            table_id,
+            indices_id,
+            idx,
+            is_last_idx,
+            row_size,
+            col,
+            is_last_col,
+            same_row,
+            index,
+            index_int,
+            lookup_idx,
+            out,
+            index_mult,
+            out_mult,
+            lookup_mult,
+        ) = (
+            self.node_id.to_array(),
+            self.table_id.to_array(),
+            self.indices_id.to_array(),
+            self.idx.to_array(),
+            self.is_last_idx.to_array(),
+            self.row_size.to_array(),
+            self.col.to_array(),
+            self.is_last_col.to_array(),
+            self.same_row.to_array(),
+            self.index.to_array(),
+            self.index_int.to_array(),
+            self.lookup_idx.to_array(),
+            self.out.to_array(),
+            self.index_mult.to_array(),
+            self.out_mult.to_array(),
+            self.lookup_mult.to_array(),
+        );
+
+        std::array::from_fn(|i| GatherTraceTableRow {
+            node_id: node_id[i],
+            table_id: table_id[i],
+            indices_id: indices_id[i],
+            idx: idx[i],
+            is_last_idx: is_last_idx[i],
+            row_size: row_size[i],
+            col: col[i],
+            is_last_col: is_last_col[i],
+            same_row: same_row[i],
+            index: index[i],
+            index_int: index_int[i],
+            lookup_idx: lookup_idx[i],
+            out: out[i],
+            index_mult: index_mult[i],
+            out_mult: out_mult[i],
+            lookup_mult: lookup_mult[i],
+        })
+    }
+}
+
+impl GatherTraceTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_row(&mut self, row: GatherTraceTableRow) {
+        self.table.push(row);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GatherColumn {
+    NodeId,
+    TableId,
+    IndicesId,
+    Idx,
+    IsLastIdx,
+    RowSize,
+    Col,
+    IsLastCol,
+    SameRow,
+    Index,
+    IndexInt,
+    LookupIdx,
+    Out,
+    IndexMult,
+    OutMult,
+    LookupMult,
+}
+
+impl GatherColumn {
+    pub const fn index(self) -> usize {
+        match self {
+            Self::NodeId => 0,
+            Self::TableId => 1,
+            Self::IndicesId => 2,
+            Self::Idx => 3,
+            Self::IsLastIdx => 4,
+            Self::RowSize => 5,
+            Self::Col => 6,
+            Self::IsLastCol => 7,
+            Self::SameRow => 8,
+            Self::Index => 9,
+            Self::IndexInt => 10,
+            Self::LookupIdx => 11,
+            Self::Out => 12,
+            Self::IndexMult => 13,
+            Self::OutMult => 14,
+            Self::LookupMult => 15,
+        }
+    }
+}
+
+impl TraceColumn for GatherColumn {
+    fn count() -> (usize, usize) {
+        (16, 3)
+    }
+}
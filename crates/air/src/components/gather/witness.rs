@@ -0,0 +1,170 @@
+use crate::{
+    components::{
+        lookups::gather::GatherLookupElements, GatherClaim, InteractionClaim, NodeElements,
+    },
+    utils::TreeBuilder,
+};
+use luminair_utils::TraceError;
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use stwo_air_utils::trace::component_trace::ComponentTrace;
+use stwo_air_utils_derive::{IterMut, ParIterMut, Uninitialized};
+use stwo::prover::backend::simd::{
+    m31::{PackedM31, LOG_N_LANES, N_LANES},
+    qm31::PackedQM31,
+    SimdBackend,
+};
+use stwo_constraint_framework::{LogupTraceGenerator, Relation};
+
+use super::table::{GatherColumn, GatherTraceTable, GatherTraceTableRow, PackedGatherTraceTableRow};
+
+pub(crate) const N_TRACE_COLUMNS: usize = 16;
+
+pub struct ClaimGenerator {
+    pub inputs: GatherTraceTable,
+}
+
+impl ClaimGenerator {
+    pub fn new(inputs: GatherTraceTable) -> Self {
+        Self { inputs }
+    }
+
+    pub fn write_trace(
+        self,
+        tree_builder: &mut impl TreeBuilder<SimdBackend>,
+    ) -> Result<(GatherClaim, InteractionClaimGenerator), TraceError> {
+        let n_rows = self.inputs.table.len();
+
+        if n_rows == 0 {
+            return Err(TraceError::EmptyTrace);
+        }
+
+        let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
+        let log_size = size.ilog2();
+
+        let packed_inputs = self
+            .inputs
+            .table
+            .into_packed_coset_order(GatherTraceTableRow::padding());
+
+        let (trace, lookup_data) = write_trace_simd(packed_inputs);
+
+        tree_builder.extend_evals(trace.to_evals());
+
+        Ok((
+            GatherClaim::new(log_size),
+            InteractionClaimGenerator {
+                log_size,
+                lookup_data,
+            },
+        ))
+    }
+}
+
+fn write_trace_simd(
+    inputs: Vec<PackedGatherTraceTableRow>,
+) -> (ComponentTrace<N_TRACE_COLUMNS>, LookupData) {
+    let log_n_packed_rows = inputs.len().ilog2();
+    let log_size = log_n_packed_rows + LOG_N_LANES;
+
+    let (mut trace, mut lookup_data) = unsafe {
+        (
+            ComponentTrace::<N_TRACE_COLUMNS>::uninitialized(log_size),
+            LookupData::uninitialized(log_n_packed_rows),
+        )
+    };
+
+    (
+        trace.par_iter_mut(),
+        lookup_data.par_iter_mut(),
+        inputs.into_par_iter(),
+    )
+        .into_par_iter()
+        .for_each(|(mut row, lookup_data, input)| {
+            *row[GatherColumn::NodeId.index()] = input.node_id;
+            *row[GatherColumn::TableId.index()] = input.table_id;
+            *row[GatherColumn::IndicesId.index()] = input.indices_id;
+            *row[GatherColumn::Idx.index()] = input.idx;
+            *row[GatherColumn::IsLastIdx.index()] = input.is_last_idx;
+            *row[GatherColumn::RowSize.index()] = input.row_size;
+            *row[GatherColumn::Col.index()] = input.col;
+            *row[GatherColumn::IsLastCol.index()] = input.is_last_col;
+            *row[GatherColumn::SameRow.index()] = input.same_row;
+            *row[GatherColumn::Index.index()] = input.index;
+            *row[GatherColumn::IndexInt.index()] = input.index_int;
+            *row[GatherColumn::LookupIdx.index()] = input.lookup_idx;
+            *row[GatherColumn::Out.index()] = input.out;
+            *row[GatherColumn::IndexMult.index()] = input.index_mult;
+            *row[GatherColumn::OutMult.index()] = input.out_mult;
+            *row[GatherColumn::LookupMult.index()] = input.lookup_mult;
+
+            *lookup_data.index = [input.index, input.indices_id];
+            *lookup_data.index_mult = input.index_mult;
+            *lookup_data.out = [input.out, input.node_id];
+            *lookup_data.out_mult = input.out_mult;
+            *lookup_data.lookup = [input.node_id, input.lookup_idx, input.out];
+            *lookup_data.lookup_mult = input.lookup_mult;
+        });
+
+    (trace, lookup_data)
+}
+
+#[derive(Uninitialized, IterMut, ParIterMut)]
+struct LookupData {
+    index: Vec<[PackedM31; 2]>,
+    index_mult: Vec<PackedM31>,
+    out: Vec<[PackedM31; 2]>,
+    out_mult: Vec<PackedM31>,
+    lookup: Vec<[PackedM31; 3]>,
+    lookup_mult: Vec<PackedM31>,
+}
+
+pub struct InteractionClaimGenerator {
+    log_size: u32,
+    lookup_data: LookupData,
+}
+
+impl InteractionClaimGenerator {
+    pub fn write_interaction_trace(
+        self,
+        tree_builder: &mut impl TreeBuilder<SimdBackend>,
+        node_elements: &NodeElements,
+        lookup_elements: &GatherLookupElements, // Randomness for (node_id, idx, value) table reads
+    ) -> InteractionClaim {
+        let mut logup_gen = LogupTraceGenerator::new(self.log_size);
+
+        let mut col_gen = logup_gen.new_col();
+        for row in 0..1 << (self.log_size - LOG_N_LANES) {
+            let values = &self.lookup_data.index[row];
+            let multiplicity = &self.lookup_data.index_mult[row];
+
+            let denom: PackedQM31 = node_elements.combine(values);
+            col_gen.write_frac(row, (*multiplicity).into(), denom);
+        }
+        col_gen.finalize_col();
+
+        let mut col_gen = logup_gen.new_col();
+        for row in 0..1 << (self.log_size - LOG_N_LANES) {
+            let values = &self.lookup_data.out[row];
+            let multiplicity = &self.lookup_data.out_mult[row];
+
+            let denom: PackedQM31 = node_elements.combine(values);
+            col_gen.write_frac(row, (*multiplicity).into(), denom);
+        }
+        col_gen.finalize_col();
+
+        let mut col_gen = logup_gen.new_col();
+        for row in 0..1 << (self.log_size - LOG_N_LANES) {
+            let values = &self.lookup_data.lookup[row];
+            let multiplicity = &self.lookup_data.lookup_mult[row];
+
+            let denom: PackedQM31 = lookup_elements.combine(values);
+            col_gen.write_frac(row, (*multiplicity).into(), denom);
+        }
+        col_gen.finalize_col();
+
+        let (trace, claimed_sum) = logup_gen.finalize_last();
+        tree_builder.extend_evals(trace);
+
+        InteractionClaim { claimed_sum }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::components::{lookups::gather::GatherLookupElements, Im2ColClaim, NodeElements};
+use num_traits::One;
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
+};
+
+pub type Im2ColComponent = FrameworkComponent<Im2ColEval>;
+
+/// Evaluation structure for im2col (convolution patch extraction) operations
+pub struct Im2ColEval {
+    log_size: u32,
+    node_elements: NodeElements,
+    lookup_elements: GatherLookupElements,
+}
+
+impl Im2ColEval {
+    /// Creates a new Im2ColEval with the given claim, node elements and gather lookup elements
+    pub fn new(
+        claim: &Im2ColClaim,
+        node_elements: NodeElements,
+        lookup_elements: GatherLookupElements,
+    ) -> Self {
+        Self {
+            log_size: claim.log_size,
+            node_elements,
+            lookup_elements,
+        }
+    }
+}
+
+impl FrameworkEval for Im2ColEval {
+    /// Returns the log size of the evaluation
+    fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Returns the maximum constraint log degree bound
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.log_size + 1
+    }
+
+    /// Evaluates the im2col constraints and relations
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        // IDs, along with their value on the next row for transition constraints
+        // ID of the node in the computational graph.
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the input image tensor.
+        let [input_id, next_input_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Index in the flattened patch matrix.
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let is_last_idx = eval.next_trace_mask(); // Flag if this is the last index for this operation.
+
+        // Values for consistency constraints
+        let src_idx = eval.next_trace_mask(); // Flattened position read in the input image.
+        let is_valid = eval.next_trace_mask(); // Flag if the position lies inside the image (not padding).
+        let out_val = eval.next_trace_mask(); // Value in output tensor at index.
+
+        // Multiplicities for interaction constraints
+        let out_mult = eval.next_trace_mask();
+
+        // ┌─────────────────────────────┐
+        // │   Consistency Constraints   │
+        // └─────────────────────────────┘
+
+        // The is_last_idx and is_valid flags are either 0 or 1.
+        eval.add_constraint(is_last_idx.clone() * (is_last_idx.clone() - E::F::one()));
+        eval.add_constraint(is_valid.clone() * (is_valid.clone() - E::F::one()));
+
+        // Padding positions read zero.
+        eval.add_constraint(out_val.clone() * (E::F::one() - is_valid.clone()));
+
+        // ┌────────────────────────────┐
+        // │   Transition Constraints   │
+        // └────────────────────────────┘
+
+        // If this is not the last index for this operation, then:
+        // 1. The next row should be for the same operation on the same tensors.
+        // 2. The index should increment by 1.
+        let not_last = E::F::one() - is_last_idx;
+
+        // Same node ID
+        eval.add_constraint(not_last.clone() * (next_node_id - node_id.clone()));
+
+        // Same tensor IDs
+        eval.add_constraint(not_last.clone() * (next_input_id - input_id));
+
+        // Index increment by 1
+        eval.add_constraint(not_last * (next_idx - idx - E::F::one()));
+
+        // ┌─────────────────────────────┐
+        // │   Interaction Constraints   │
+        // └─────────────────────────────┘
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            out_mult.into(),
+            &[out_val.clone(), node_id.clone()],
+        ));
+
+        // Positions inside the image are read from the gather lookup table.
+        eval.add_to_relation(RelationEntry::new(
+            &self.lookup_elements,
+            is_valid.into(),
+            &[node_id, src_idx, out_val],
+        ));
+
+        eval.finalize_logup();
+
+        eval
+    }
+}
@@ -0,0 +1,152 @@
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::fields::m31::M31,
+    prover::backend::simd::{
+        conversion::{Pack, Unpack},
+        m31::{PackedM31, N_LANES},
+    },
+};
+
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Im2ColTraceTable {
+    pub table: PackedRows<Im2ColTraceTableRow>,
+}
+
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Im2ColTraceTableRow {
+    pub node_id: M31,
+    pub input_id: M31,
+    pub idx: M31,
+    pub is_last_idx: M31,
+    pub src_idx: M31,
+    pub is_valid: M31,
+    pub out: M31,
+    pub out_mult: M31,
+}
+
+impl Im2ColTraceTableRow {
+    pub(crate) fn padding() -> Self {
+        Self {
+            node_id: M31::zero(),
+            input_id: M31::zero(),
+            idx: M31::zero(),
+            is_last_idx: M31::one(),
+            src_idx: M31::zero(),
+            is_valid: M31::zero(),
+            out: M31::zero(),
+            out_mult: M31::zero(),
+        }
+    }
+}
+
+impl TransitionRow for Im2ColTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct PackedIm2ColTraceTableRow {
+    pub node_id: PackedM31,
+    pub input_id: PackedM31,
+    pub idx: PackedM31,
+    pub is_last_idx: PackedM31,
+    pub src_idx: PackedM31,
+    pub is_valid: PackedM31,
+    pub out: PackedM31,
+    pub out_mult: PackedM31,
+}
+
+impl Pack for Im2ColTraceTableRow {
+    type SimdType = PackedIm2ColTraceTableRow;
+
+    fn pack(inputs: [Self; N_LANES]) -> Self::SimdType {
+        PackedIm2ColTraceTableRow {
+            node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].node_id)),
+            input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_id)),
+            idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
+            is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
+            src_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].src_idx)),
+            is_valid: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_valid)),
+            out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
+            out_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out_mult)),
+        }
+    }
+}
+
+impl Unpack for PackedIm2ColTraceTableRow {
+    type CpuType = Im2ColTraceTableRow;
+
+    fn unpack(self) -> [Self::CpuType; N_LANES] {
+        let (node_id, input_id, idx, is_last_idx, src_idx, is_valid, out, out_mult) = (
+            self.node_id.to_array(),
+            self.input_id.to_array(),
+            self.idx.to_array(),
+            self.is_last_idx.to_array(),
+            self.src_idx.to_array(),
+            self.is_valid.to_array(),
+            self.out.to_array(),
+            self.out_mult.to_array(),
+        );
+
+        std::array::from_fn(|i| Im2ColTraceTableRow {
+            node_id: node_id[i],
+            input_id: input_id[i],
+            idx: idx[i],
+            is_last_idx: is_last_idx[i],
+            src_idx: src_idx[i],
+            is_valid: is_valid[i],
+            out: out[i],
+            out_mult: out_mult[i],
+        })
+    }
+}
+
+impl Im2ColTraceTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_row(&mut self, row: Im2ColTraceTableRow) {
+        self.table.push(row);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Im2ColColumn {
+    NodeId,
+    InputId,
+    Idx,
+    IsLastIdx,
+    SrcIdx,
+    IsValid,
+    Out,
+    OutMult,
+}
+
+impl Im2ColColumn {
+    pub const fn index(self) -> usize {
+        match self {
+            Self::NodeId => 0,
+            Self::InputId => 1,
+            Self::Idx => 2,
+            Self::IsLastIdx => 3,
+            Self::SrcIdx => 4,
+            Self::IsValid => 5,
+            Self::Out => 6,
+            Self::OutMult => 7,
+        }
+    }
+}
+
+impl TraceColumn for Im2ColColumn {
+    fn count() -> (usize, usize) {
+        (8, 2)
+    }
+}
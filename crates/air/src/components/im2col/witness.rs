@@ -0,0 +1,150 @@
+use crate::{
+    components::{
+        lookups::gather::GatherLookupElements, Im2ColClaim, InteractionClaim, NodeElements,
+    },
+    utils::TreeBuilder,
+};
+use luminair_utils::TraceError;
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use stwo_air_utils::trace::component_trace::ComponentTrace;
+use stwo_air_utils_derive::{IterMut, ParIterMut, Uninitialized};
+use stwo::prover::backend::simd::{
+    m31::{PackedM31, LOG_N_LANES, N_LANES},
+    qm31::PackedQM31,
+    SimdBackend,
+};
+use stwo_constraint_framework::{LogupTraceGenerator, Relation};
+
+use super::table::{
+    Im2ColColumn, Im2ColTraceTable, Im2ColTraceTableRow, PackedIm2ColTraceTableRow,
+};
+
+pub(crate) const N_TRACE_COLUMNS: usize = 8;
+
+pub struct ClaimGenerator {
+    pub inputs: Im2ColTraceTable,
+}
+
+impl ClaimGenerator {
+    pub fn new(inputs: Im2ColTraceTable) -> Self {
+        Self { inputs }
+    }
+
+    pub fn write_trace(
+        self,
+        tree_builder: &mut impl TreeBuilder<SimdBackend>,
+    ) -> Result<(Im2ColClaim, InteractionClaimGenerator), TraceError> {
+        let n_rows = self.inputs.table.len();
+
+        if n_rows == 0 {
+            return Err(TraceError::EmptyTrace);
+        }
+
+        let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
+        let log_size = size.ilog2();
+
+        let packed_inputs = self
+            .inputs
+            .table
+            .into_packed_coset_order(Im2ColTraceTableRow::padding());
+
+        let (trace, lookup_data) = write_trace_simd(packed_inputs);
+
+        tree_builder.extend_evals(trace.to_evals());
+
+        Ok((
+            Im2ColClaim::new(log_size),
+            InteractionClaimGenerator {
+                log_size,
+                lookup_data,
+            },
+        ))
+    }
+}
+
+fn write_trace_simd(
+    inputs: Vec<PackedIm2ColTraceTableRow>,
+) -> (ComponentTrace<N_TRACE_COLUMNS>, LookupData) {
+    let log_n_packed_rows = inputs.len().ilog2();
+    let log_size = log_n_packed_rows + LOG_N_LANES;
+
+    let (mut trace, mut lookup_data) = unsafe {
+        (
+            ComponentTrace::<N_TRACE_COLUMNS>::uninitialized(log_size),
+            LookupData::uninitialized(log_n_packed_rows),
+        )
+    };
+
+    (
+        trace.par_iter_mut(),
+        lookup_data.par_iter_mut(),
+        inputs.into_par_iter(),
+    )
+        .into_par_iter()
+        .for_each(|(mut row, lookup_data, input)| {
+            *row[Im2ColColumn::NodeId.index()] = input.node_id;
+            *row[Im2ColColumn::InputId.index()] = input.input_id;
+            *row[Im2ColColumn::Idx.index()] = input.idx;
+            *row[Im2ColColumn::IsLastIdx.index()] = input.is_last_idx;
+            *row[Im2ColColumn::SrcIdx.index()] = input.src_idx;
+            *row[Im2ColColumn::IsValid.index()] = input.is_valid;
+            *row[Im2ColColumn::Out.index()] = input.out;
+            *row[Im2ColColumn::OutMult.index()] = input.out_mult;
+
+            *lookup_data.out = [input.out, input.node_id];
+            *lookup_data.out_mult = input.out_mult;
+            *lookup_data.lookup = [input.node_id, input.src_idx, input.out];
+            *lookup_data.is_valid = input.is_valid;
+        });
+
+    (trace, lookup_data)
+}
+
+#[derive(Uninitialized, IterMut, ParIterMut)]
+struct LookupData {
+    out: Vec<[PackedM31; 2]>,
+    out_mult: Vec<PackedM31>,
+    lookup: Vec<[PackedM31; 3]>,
+    is_valid: Vec<PackedM31>,
+}
+
+pub struct InteractionClaimGenerator {
+    log_size: u32,
+    lookup_data: LookupData,
+}
+
+impl InteractionClaimGenerator {
+    pub fn write_interaction_trace(
+        self,
+        tree_builder: &mut impl TreeBuilder<SimdBackend>,
+        node_elements: &NodeElements,
+        lookup_elements: &GatherLookupElements, // Randomness for (node_id, idx, value) image reads
+    ) -> InteractionClaim {
+        let mut logup_gen = LogupTraceGenerator::new(self.log_size);
+
+        let mut col_gen = logup_gen.new_col();
+        for row in 0..1 << (self.log_size - LOG_N_LANES) {
+            let values = &self.lookup_data.out[row];
+            let multiplicity = &self.lookup_data.out_mult[row];
+
+            let denom: PackedQM31 = node_elements.combine(values);
+            col_gen.write_frac(row, (*multiplicity).into(), denom);
+        }
+        col_gen.finalize_col();
+
+        let mut col_gen = logup_gen.new_col();
+        for row in 0..1 << (self.log_size - LOG_N_LANES) {
+            let values = &self.lookup_data.lookup[row];
+            let multiplicity = &self.lookup_data.is_valid[row];
+
+            let denom: PackedQM31 = lookup_elements.combine(values);
+            col_gen.write_frac(row, (*multiplicity).into(), denom);
+        }
+        col_gen.finalize_col();
+
+        let (trace, claimed_sum) = logup_gen.finalize_last();
+        tree_builder.extend_evals(trace);
+
+        InteractionClaim { claimed_sum }
+    }
+}
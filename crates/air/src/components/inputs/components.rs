@@ -1,7 +1,7 @@
 use crate::components::{InputsClaim, NodeElements};
 use num_traits::One;
 use stwo_constraint_framework::{
-    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry,
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
 };
 
 pub type InputsComponent = FrameworkComponent<InputsEval>;
@@ -35,15 +35,11 @@ impl FrameworkEval for InputsEval {
 
     /// Evaluates the input tensor constraints and relations
     fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
-        // IDs
-        let node_id = eval.next_trace_mask();
-        let idx = eval.next_trace_mask();
+        // IDs, along with their value on the next row for transition constraints
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
         let is_last_idx = eval.next_trace_mask();
 
-        // Next IDs for transition constraints
-        let next_node_id = eval.next_trace_mask();
-        let next_idx: <E as EvalAtRow>::F = eval.next_trace_mask();
-
         // Value for consistency constraints
         let val = eval.next_trace_mask();
 
@@ -8,13 +8,16 @@ use stwo::{
     },
 };
 
-use crate::components::TraceColumn;
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
 
 use super::witness::N_TRACE_COLUMNS;
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct InputsTraceTable {
-    pub table: Vec<InputsTraceTableRow>,
+    pub table: PackedRows<InputsTraceTableRow>,
 }
 
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
@@ -22,8 +25,6 @@ pub struct InputsTraceTableRow {
     pub node_id: M31,
     pub idx: M31,
     pub is_last_idx: M31,
-    pub next_node_id: M31,
-    pub next_idx: M31,
     pub val: M31,
     pub multiplicity: M31,
 }
@@ -34,21 +35,23 @@ impl InputsTraceTableRow {
             node_id: M31::zero(),
             idx: M31::zero(),
             is_last_idx: M31::one(),
-            next_node_id: M31::zero(),
-            next_idx: M31::zero(),
             val: M31::zero(),
             multiplicity: M31::zero(),
         }
     }
 }
 
+impl TransitionRow for InputsTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct PackedInputsTraceTableRow {
     pub node_id: PackedM31,
     pub idx: PackedM31,
     pub is_last_idx: PackedM31,
-    pub next_node_id: PackedM31,
-    pub next_idx: PackedM31,
     pub val: PackedM31,
     pub multiplicity: PackedM31,
 }
@@ -61,8 +64,6 @@ impl Pack for InputsTraceTableRow {
             node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].node_id)),
             idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
             is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
-            next_node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_node_id)),
-            next_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_idx)),
             val: PackedM31::from_array(std::array::from_fn(|i| inputs[i].val)),
             multiplicity: PackedM31::from_array(std::array::from_fn(|i| inputs[i].multiplicity)),
         }
@@ -73,12 +74,10 @@ impl Unpack for PackedInputsTraceTableRow {
     type CpuType = InputsTraceTableRow;
 
     fn unpack(self) -> [Self::CpuType; N_LANES] {
-        let (node_id, idx, is_last_idx, next_node_id, next_idx, val, multiplicity) = (
+        let (node_id, idx, is_last_idx, val, multiplicity) = (
             self.node_id.to_array(),
             self.idx.to_array(),
             self.is_last_idx.to_array(),
-            self.next_node_id.to_array(),
-            self.next_idx.to_array(),
             self.val.to_array(),
             self.multiplicity.to_array(),
         );
@@ -87,8 +86,6 @@ impl Unpack for PackedInputsTraceTableRow {
             node_id: node_id[i],
             idx: idx[i],
             is_last_idx: is_last_idx[i],
-            next_node_id: next_node_id[i],
-            next_idx: next_idx[i],
             val: val[i],
             multiplicity: multiplicity[i],
         })
@@ -110,8 +107,6 @@ pub enum InputsColumn {
     NodeId,
     Idx,
     IsLastIdx,
-    NextNodeId,
-    NextIdx,
     Val,
     Multiplicity,
 }
@@ -122,10 +117,8 @@ impl InputsColumn {
             Self::NodeId => 0,
             Self::Idx => 1,
             Self::IsLastIdx => 2,
-            Self::NextNodeId => 3,
-            Self::NextIdx => 4,
-            Self::Val => 5,
-            Self::Multiplicity => 6,
+            Self::Val => 3,
+            Self::Multiplicity => 4,
         }
     }
 }
@@ -15,10 +15,10 @@ use crate::{
             InputsColumn, InputsTraceTable, InputsTraceTableRow, PackedInputsTraceTableRow,
         }, InputsClaim, InteractionClaim, NodeElements
     },
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
-pub(crate) const N_TRACE_COLUMNS: usize = 7;
+pub(crate) const N_TRACE_COLUMNS: usize = 5;
 
 pub struct ClaimGenerator {
     pub inputs: InputsTraceTable,
@@ -30,7 +30,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(InputsClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -42,10 +42,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs
+        let packed_inputs = self
+            .inputs
             .table
-            .resize(size, InputsTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+            .into_packed_coset_order(InputsTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -84,8 +84,6 @@ fn write_trace_simd(
             *row[InputsColumn::NodeId.index()] = input.node_id;
             *row[InputsColumn::Idx.index()] = input.idx;
             *row[InputsColumn::IsLastIdx.index()] = input.is_last_idx;
-            *row[InputsColumn::NextNodeId.index()] = input.next_node_id;
-            *row[InputsColumn::NextIdx.index()] = input.next_idx;
             *row[InputsColumn::Val.index()] = input.val;
             *row[InputsColumn::Multiplicity.index()] = input.multiplicity;
 
@@ -1,6 +1,8 @@
 use num_traits::One;
 use stwo::core::fields::m31::M31;
-use stwo_constraint_framework::{EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry};
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
+};
 
 use crate::{
     components::{lookups::range_check::RangeCheckLookupElements, LessThanClaim, NodeElements},
@@ -51,19 +53,13 @@ impl FrameworkEval for LessThanEval {
         let two_pow_k = E::F::from(M31::from_u32_unchecked(TWO_POW_31_MINUS_1));
         let scale_factor = E::F::from(M31::from_u32_unchecked(DEFAULT_FP_SCALE_FACTOR));
 
-        // IDs
-        let node_id = eval.next_trace_mask();
-        let lhs_id = eval.next_trace_mask();
-        let rhs_id = eval.next_trace_mask();
-        let idx = eval.next_trace_mask();
+        // IDs, along with their value on the next row for transition constraints
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let [lhs_id, next_lhs_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let [rhs_id, next_rhs_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
         let is_last_idx = eval.next_trace_mask();
 
-        // Next IDs for transition constraints
-        let next_node_id = eval.next_trace_mask();
-        let next_lhs_id = eval.next_trace_mask();
-        let next_rhs_id = eval.next_trace_mask();
-        let next_idx = eval.next_trace_mask();
-
         // Values for consistency constraints
         let lhs_val = eval.next_trace_mask();
         let rhs_val = eval.next_trace_mask();
@@ -9,11 +9,15 @@ use stwo::{
 };
 
 use super::witness::N_TRACE_COLUMNS;
-use crate::{components::TraceColumn, DEFAULT_FP_SCALE};
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+    DEFAULT_FP_SCALE,
+};
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct LessThanTraceTable {
-    pub table: Vec<LessThanTraceTableRow>,
+    pub table: PackedRows<LessThanTraceTableRow>,
     pub(crate) node_id: M31,
 }
 
@@ -24,10 +28,6 @@ pub struct LessThanTraceTableRow {
     pub rhs_id: M31,
     pub idx: M31,
     pub is_last_idx: M31,
-    pub next_node_id: M31,
-    pub next_lhs_id: M31,
-    pub next_rhs_id: M31,
-    pub next_idx: M31,
     pub lhs: M31,
     pub rhs: M31,
     pub out: M31,
@@ -51,10 +51,6 @@ impl LessThanTraceTableRow {
             rhs_id: M31::zero(),
             idx: M31::zero(),
             is_last_idx: M31::one(),
-            next_node_id: M31::zero(),
-            next_lhs_id: M31::zero(),
-            next_rhs_id: M31::zero(),
-            next_idx: M31::zero(),
             lhs: M31::zero(),
             rhs: M31::one(),
             out: M31::from_u32_unchecked(1 << DEFAULT_FP_SCALE),
@@ -72,6 +68,12 @@ impl LessThanTraceTableRow {
     }
 }
 
+impl TransitionRow for LessThanTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct PackedLessThanTraceTableRow {
     pub node_id: PackedM31,
@@ -79,10 +81,6 @@ pub struct PackedLessThanTraceTableRow {
     pub rhs_id: PackedM31,
     pub idx: PackedM31,
     pub is_last_idx: PackedM31,
-    pub next_node_id: PackedM31,
-    pub next_lhs_id: PackedM31,
-    pub next_rhs_id: PackedM31,
-    pub next_idx: PackedM31,
     pub lhs: PackedM31,
     pub rhs: PackedM31,
     pub out: PackedM31,
@@ -108,10 +106,6 @@ impl Pack for LessThanTraceTableRow {
             rhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs_id)),
             idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
             is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
-            next_node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_node_id)),
-            next_lhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_lhs_id)),
-            next_rhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_rhs_id)),
-            next_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_idx)),
             lhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lhs)),
             rhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs)),
             out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
@@ -141,10 +135,6 @@ impl Unpack for PackedLessThanTraceTableRow {
             rhs_id,
             idx,
             is_last_idx,
-            next_node_id,
-            next_lhs_id,
-            next_rhs_id,
-            next_idx,
             lhs,
             rhs,
             out,
@@ -164,10 +154,6 @@ impl Unpack for PackedLessThanTraceTableRow {
             self.rhs_id.to_array(),
             self.idx.to_array(),
             self.is_last_idx.to_array(),
-            self.next_node_id.to_array(),
-            self.next_lhs_id.to_array(),
-            self.next_rhs_id.to_array(),
-            self.next_idx.to_array(),
             self.lhs.to_array(),
             self.rhs.to_array(),
             self.out.to_array(),
@@ -189,10 +175,6 @@ impl Unpack for PackedLessThanTraceTableRow {
             rhs_id: rhs_id[i],
             idx: idx[i],
             is_last_idx: is_last_idx[i],
-            next_node_id: next_node_id[i],
-            next_lhs_id: next_lhs_id[i],
-            next_rhs_id: next_rhs_id[i],
-            next_idx: next_idx[i],
             lhs: lhs[i],
             rhs: rhs[i],
             out: out[i],
@@ -227,10 +209,6 @@ pub enum LessThanColumn {
     RhsId,
     Idx,
     IsLastIdx,
-    NextNodeId,
-    NextLhsId,
-    NextRhsId,
-    NextIdx,
     Lhs,
     Rhs,
     Out,
@@ -254,23 +232,19 @@ impl LessThanColumn {
             Self::RhsId => 2,
             Self::Idx => 3,
             Self::IsLastIdx => 4,
-            Self::NextNodeId => 5,
-            Self::NextLhsId => 6,
-            Self::NextRhsId => 7,
-            Self::NextIdx => 8,
-            Self::Lhs => 9,
-            Self::Rhs => 10,
-            Self::Out => 11,
-            Self::Diff => 12,
-            Self::Borrow => 13,
-            Self::Limb0 => 14,
-            Self::Limb1 => 15,
-            Self::Limb2 => 16,
-            Self::Limb3 => 17,
-            Self::LhsMult => 18,
-            Self::RhsMult => 19,
-            Self::OutMult => 20,
-            Self::RangeCheckMult => 21,
+            Self::Lhs => 5,
+            Self::Rhs => 6,
+            Self::Out => 7,
+            Self::Diff => 8,
+            Self::Borrow => 9,
+            Self::Limb0 => 10,
+            Self::Limb1 => 11,
+            Self::Limb2 => 12,
+            Self::Limb3 => 13,
+            Self::LhsMult => 14,
+            Self::RhsMult => 15,
+            Self::OutMult => 16,
+            Self::RangeCheckMult => 17,
         }
     }
 }
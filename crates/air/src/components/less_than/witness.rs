@@ -15,10 +15,10 @@ use crate::{
             LessThanColumn, LessThanTraceTable, LessThanTraceTableRow, PackedLessThanTraceTableRow,
         }, lookups::range_check::RangeCheckLookupElements, InteractionClaim, LessThanClaim, NodeElements
     },
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
-pub(crate) const N_TRACE_COLUMNS: usize = 22;
+pub(crate) const N_TRACE_COLUMNS: usize = 18;
 
 pub struct ClaimGenerator {
     pub inputs: LessThanTraceTable,
@@ -30,7 +30,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(LessThanClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -42,10 +42,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs
+        let packed_inputs = self
+            .inputs
             .table
-            .resize(size, LessThanTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+            .into_packed_coset_order(LessThanTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -86,10 +86,6 @@ fn write_trace_simd(
             *row[LessThanColumn::RhsId.index()] = input.rhs_id;
             *row[LessThanColumn::Idx.index()] = input.idx;
             *row[LessThanColumn::IsLastIdx.index()] = input.is_last_idx;
-            *row[LessThanColumn::NextNodeId.index()] = input.next_node_id;
-            *row[LessThanColumn::NextLhsId.index()] = input.next_lhs_id;
-            *row[LessThanColumn::NextRhsId.index()] = input.next_rhs_id;
-            *row[LessThanColumn::NextIdx.index()] = input.next_idx;
             *row[LessThanColumn::Lhs.index()] = input.lhs;
             *row[LessThanColumn::Rhs.index()] = input.rhs;
             *row[LessThanColumn::Out.index()] = input.out;
@@ -1,7 +1,7 @@
 use crate::components::{lookups::log2::Log2LookupElements, Log2Claim, NodeElements};
 use num_traits::One;
 use stwo_constraint_framework::{
-    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry,
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
 };
 
 pub type Log2Component = FrameworkComponent<Log2Eval>;
@@ -44,17 +44,15 @@ impl FrameworkEval for Log2Eval {
 
     /// Evaluates the logarithm base-2 constraints and relations
     fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
-        // IDs
-        let node_id = eval.next_trace_mask(); // ID of the node in the computational graph.
-        let input_id = eval.next_trace_mask(); // ID of the input tensor.
-        let idx = eval.next_trace_mask(); // Index in the flattened tensor.
+        // IDs, along with their value on the next row for transition constraints
+        // ID of the node in the computational graph.
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the input tensor.
+        let [input_id, next_input_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Index in the flattened tensor.
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
         let is_last_idx = eval.next_trace_mask(); // Flag if this is the last index for this operation.
 
-        // Next IDs for transition constraints
-        let next_node_id = eval.next_trace_mask();
-        let next_input_id = eval.next_trace_mask();
-        let next_idx = eval.next_trace_mask();
-
         // Values for consistency constraints
         let input_val = eval.next_trace_mask(); // Value from the tensor at index.
         let out_val = eval.next_trace_mask(); // Value in output tensor at index.
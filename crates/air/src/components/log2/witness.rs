@@ -13,10 +13,10 @@ use crate::{
     components::{
         log2::table::{Log2Column, Log2TraceTable, Log2TraceTableRow, PackedLog2TraceTableRow}, lookups::log2::Log2LookupElements, Log2Claim, InteractionClaim, NodeElements
     },
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
-pub(crate) const N_TRACE_COLUMNS: usize = 12;
+pub(crate) const N_TRACE_COLUMNS: usize = 9;
 
 pub struct ClaimGenerator {
     pub inputs: Log2TraceTable,
@@ -28,7 +28,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(Log2Claim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -40,8 +40,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs.table.resize(size, Log2TraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+        let packed_inputs = self
+            .inputs
+            .table
+            .into_packed_coset_order(Log2TraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -81,9 +83,6 @@ fn write_trace_simd(
             *row[Log2Column::InputId.index()] = input.input_id;
             *row[Log2Column::Idx.index()] = input.idx;
             *row[Log2Column::IsLastIdx.index()] = input.is_last_idx;
-            *row[Log2Column::NextNodeId.index()] = input.next_node_id;
-            *row[Log2Column::NextInputId.index()] = input.next_input_id;
-            *row[Log2Column::NextIdx.index()] = input.next_idx;
             *row[Log2Column::Input.index()] = input.input;
             *row[Log2Column::Out.index()] = input.out;
             *row[Log2Column::InputMult.index()] = input.input_mult;
@@ -0,0 +1,110 @@
+use num_traits::One;
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
+};
+
+use crate::components::{GatherLookupClaim, NodeElements};
+
+use super::GatherLookupElements;
+
+pub type GatherLookupComponent = FrameworkComponent<GatherLookupEval>;
+
+/// Evaluation structure for gather lookup tables
+///
+/// Consumes every element of a gathered table (or im2col input) once and re-emits it
+/// as a `(node_id, idx, value)` tuple that gather and im2col rows can look up.
+pub struct GatherLookupEval {
+    log_size: u32,
+    node_elements: NodeElements,
+    lookup_elements: GatherLookupElements,
+}
+
+impl GatherLookupEval {
+    /// Creates a new GatherLookupEval with the given claim, node elements and lookup elements
+    pub fn new(
+        claim: &GatherLookupClaim,
+        node_elements: NodeElements,
+        lookup_elements: GatherLookupElements,
+    ) -> Self {
+        Self {
+            log_size: claim.log_size,
+            node_elements,
+            lookup_elements,
+        }
+    }
+}
+
+impl FrameworkEval for GatherLookupEval {
+    /// Returns the log size of the evaluation
+    fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Returns the maximum constraint log degree bound
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.log_size + 1
+    }
+
+    /// Evaluates the gather lookup table constraints and relations
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        // IDs, along with their value on the next row for transition constraints
+        // ID of the gather node reading the table.
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the table tensor.
+        let [table_id, next_table_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Index in the flattened table tensor.
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let is_last_idx = eval.next_trace_mask(); // Flag if this is the last index for this table.
+
+        let value = eval.next_trace_mask(); // Value in the table tensor at index.
+
+        // Multiplicities for interaction constraints
+        let value_mult = eval.next_trace_mask();
+        let multiplicity = eval.next_trace_mask(); // Number of gather reads at index.
+
+        // ┌─────────────────────────────┐
+        // │   Consistency Constraints   │
+        // └─────────────────────────────┘
+
+        // The is_last_idx flag is either 0 or 1.
+        eval.add_constraint(is_last_idx.clone() * (is_last_idx.clone() - E::F::one()));
+
+        // ┌────────────────────────────┐
+        // │   Transition Constraints   │
+        // └────────────────────────────┘
+
+        // If this is not the last index for this table, then:
+        // 1. The next row should be for the same gather node on the same table.
+        // 2. The index should increment by 1.
+        let not_last = E::F::one() - is_last_idx;
+
+        // Same node ID
+        eval.add_constraint(not_last.clone() * (next_node_id - node_id.clone()));
+
+        // Same tensor ID
+        eval.add_constraint(not_last.clone() * (next_table_id - table_id.clone()));
+
+        // Index increment by 1
+        eval.add_constraint(not_last * (next_idx - idx.clone() - E::F::one()));
+
+        // ┌─────────────────────────────┐
+        // │   Interaction Constraints   │
+        // └─────────────────────────────┘
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            value_mult.into(),
+            &[value.clone(), table_id],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.lookup_elements,
+            -E::EF::from(multiplicity),
+            &[node_id, idx, value],
+        ));
+
+        eval.finalize_logup();
+
+        eval
+    }
+}
@@ -0,0 +1,10 @@
+use stwo_constraint_framework::relation;
+
+pub mod component;
+pub mod table;
+pub mod witness;
+
+// Interaction elements specifically for the Gather lookup argument.
+// Drawn from the channel, used to combine `(node_id, idx, value)` tuples of tensors read by index
+// (gathered tables and im2col input images).
+relation!(GatherLookupElements, 3);
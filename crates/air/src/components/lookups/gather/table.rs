@@ -0,0 +1,144 @@
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::fields::m31::M31,
+    prover::backend::simd::{
+        conversion::{Pack, Unpack},
+        m31::{PackedM31, N_LANES},
+    },
+};
+
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct GatherLookupTraceTable {
+    pub table: PackedRows<GatherLookupTraceTableRow>,
+}
+
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GatherLookupTraceTableRow {
+    pub node_id: M31,
+    pub table_id: M31,
+    pub idx: M31,
+    pub is_last_idx: M31,
+    pub value: M31,
+    pub value_mult: M31,
+    pub multiplicity: M31,
+}
+
+impl GatherLookupTraceTableRow {
+    pub(crate) fn padding() -> Self {
+        Self {
+            node_id: M31::zero(),
+            table_id: M31::zero(),
+            idx: M31::zero(),
+            is_last_idx: M31::one(),
+            value: M31::zero(),
+            value_mult: M31::zero(),
+            multiplicity: M31::zero(),
+        }
+    }
+}
+
+impl TransitionRow for GatherLookupTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct PackedGatherLookupTraceTableRow {
+    pub node_id: PackedM31,
+    pub table_id: PackedM31,
+    pub idx: PackedM31,
+    pub is_last_idx: PackedM31,
+    pub value: PackedM31,
+    pub value_mult: PackedM31,
+    pub multiplicity: PackedM31,
+}
+
+impl Pack for GatherLookupTraceTableRow {
+    type SimdType = PackedGatherLookupTraceTableRow;
+
+    fn pack(inputs: [Self; N_LANES]) -> Self::SimdType {
+        PackedGatherLookupTraceTableRow {
+            node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].node_id)),
+            table_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].table_id)),
+            idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
+            is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
+            value: PackedM31::from_array(std::array::from_fn(|i| inputs[i].value)),
+            value_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].value_mult)),
+            multiplicity: PackedM31::from_array(std::array::from_fn(|i| inputs[i].multiplicity)),
+        }
+    }
+}
+
+impl Unpack for PackedGatherLookupTraceTableRow {
+    type CpuType = GatherLookupTraceTableRow;
+
+    fn unpack(self) -> [Self::CpuType; N_LANES] {
+        let (node_id, table_id, idx, is_last_idx, value, value_mult, multiplicity) = (
+            self.node_id.to_array(),
+            self.table_id.to_array(),
+            self.idx.to_array(),
+            self.is_last_idx.to_array(),
+            self.value.to_array(),
+            self.value_mult.to_array(),
+            self.multiplicity.to_array(),
+        );
+
+        std::array::from_fn(|i| GatherLookupTraceTableRow {
+            node_id: node_id[i],
+            table_id: table_id[i],
+            idx: idx[i],
+            is_last_idx: is_last_idx[i],
+            value: value[i],
+            value_mult: value_mult[i],
+            multiplicity: multiplicity[i],
+        })
+    }
+}
+
+impl GatherLookupTraceTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_row(&mut self, row: GatherLookupTraceTableRow) {
+        self.table.push(row);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GatherLookupColumn {
+    NodeId,
+    TableId,
+    Idx,
+    IsLastIdx,
+    Value,
+    ValueMult,
+    Multiplicity,
+}
+
+impl GatherLookupColumn {
+    pub const fn index(self) -> usize {
+        match self {
+            Self::NodeId => 0,
+            Self::TableId => 1,
+            Self::Idx => 2,
+            Self::IsLastIdx => 3,
+            Self::Value => 4,
+            Self::ValueMult => 5,
+            Self::Multiplicity => 6,
+        }
+    }
+}
+
+impl TraceColumn for GatherLookupColumn {
+    fn count() -> (usize, usize) {
+        (7, 2)
+    }
+}
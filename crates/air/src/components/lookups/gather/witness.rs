@@ -0,0 +1,160 @@
+use crate::{
+    components::{GatherLookupClaim, InteractionClaim, NodeElements},
+    utils::TreeBuilder,
+};
+use luminair_utils::TraceError;
+use num_traits::One;
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use stwo_air_utils::trace::component_trace::ComponentTrace;
+use stwo_air_utils_derive::{IterMut, ParIterMut, Uninitialized};
+use stwo::prover::backend::simd::{
+    m31::{PackedM31, LOG_N_LANES, N_LANES},
+    qm31::PackedQM31,
+    SimdBackend,
+};
+use stwo_constraint_framework::{LogupTraceGenerator, Relation};
+
+use super::{
+    table::{
+        GatherLookupColumn, GatherLookupTraceTable, GatherLookupTraceTableRow,
+        PackedGatherLookupTraceTableRow,
+    },
+    GatherLookupElements,
+};
+
+pub(crate) const N_TRACE_COLUMNS: usize = 7;
+
+/// Generator for gather lookup table trace claims
+pub struct ClaimGenerator {
+    pub inputs: GatherLookupTraceTable,
+}
+
+impl ClaimGenerator {
+    /// Creates a new claim generator with the given gather lookup table
+    pub fn new(inputs: GatherLookupTraceTable) -> Self {
+        Self { inputs }
+    }
+
+    /// Writes the trace for gather lookup table operations
+    pub fn write_trace(
+        self,
+        tree_builder: &mut impl TreeBuilder<SimdBackend>,
+    ) -> Result<(GatherLookupClaim, InteractionClaimGenerator), TraceError> {
+        let n_rows = self.inputs.table.len();
+
+        if n_rows == 0 {
+            return Err(TraceError::EmptyTrace);
+        }
+
+        let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
+        let log_size = size.ilog2();
+
+        let packed_inputs = self
+            .inputs
+            .table
+            .into_packed_coset_order(GatherLookupTraceTableRow::padding());
+
+        let (trace, lookup_data) = write_trace_simd(packed_inputs);
+
+        tree_builder.extend_evals(trace.to_evals());
+
+        Ok((
+            GatherLookupClaim::new(log_size),
+            InteractionClaimGenerator {
+                log_size,
+                lookup_data,
+            },
+        ))
+    }
+}
+
+fn write_trace_simd(
+    inputs: Vec<PackedGatherLookupTraceTableRow>,
+) -> (ComponentTrace<N_TRACE_COLUMNS>, LookupData) {
+    let log_n_packed_rows = inputs.len().ilog2();
+    let log_size = log_n_packed_rows + LOG_N_LANES;
+
+    let (mut trace, mut lookup_data) = unsafe {
+        (
+            ComponentTrace::<N_TRACE_COLUMNS>::uninitialized(log_size),
+            LookupData::uninitialized(log_n_packed_rows),
+        )
+    };
+
+    (
+        trace.par_iter_mut(),
+        lookup_data.par_iter_mut(),
+        inputs.into_par_iter(),
+    )
+        .into_par_iter()
+        .for_each(|(mut row, lookup_data, input)| {
+            *row[GatherLookupColumn::NodeId.index()] = input.node_id;
+            *row[GatherLookupColumn::TableId.index()] = input.table_id;
+            *row[GatherLookupColumn::Idx.index()] = input.idx;
+            *row[GatherLookupColumn::IsLastIdx.index()] = input.is_last_idx;
+            *row[GatherLookupColumn::Value.index()] = input.value;
+            *row[GatherLookupColumn::ValueMult.index()] = input.value_mult;
+            *row[GatherLookupColumn::Multiplicity.index()] = input.multiplicity;
+
+            *lookup_data.value = [input.value, input.table_id];
+            *lookup_data.value_mult = input.value_mult;
+            *lookup_data.lookup = [input.node_id, input.idx, input.value];
+            *lookup_data.multiplicities = input.multiplicity;
+        });
+
+    (trace, lookup_data)
+}
+
+/// Data structure for lookup operations
+#[derive(Uninitialized, IterMut, ParIterMut)]
+struct LookupData {
+    value: Vec<[PackedM31; 2]>,
+    value_mult: Vec<PackedM31>,
+    lookup: Vec<[PackedM31; 3]>,
+    multiplicities: Vec<PackedM31>,
+}
+
+/// Generator for gather lookup table interaction claims
+pub struct InteractionClaimGenerator {
+    log_size: u32,
+    lookup_data: LookupData,
+}
+
+impl InteractionClaimGenerator {
+    /// Writes the interaction trace for gather lookup table operations
+    pub fn write_interaction_trace(
+        self,
+        tree_builder: &mut impl TreeBuilder<SimdBackend>,
+        node_elements: &NodeElements,
+        lookup_elements: &GatherLookupElements, // Randomness for (node_id, idx, value) table reads
+    ) -> InteractionClaim {
+        let mut logup_gen = LogupTraceGenerator::new(self.log_size);
+
+        let mut col_gen = logup_gen.new_col();
+        for row in 0..1 << (self.log_size - LOG_N_LANES) {
+            let values = &self.lookup_data.value[row];
+            let multiplicity = &self.lookup_data.value_mult[row];
+
+            let denom: PackedQM31 = node_elements.combine(values);
+            col_gen.write_frac(row, (*multiplicity).into(), denom);
+        }
+        col_gen.finalize_col();
+
+        let mut col_gen = logup_gen.new_col();
+        for row in 0..1 << (self.log_size - LOG_N_LANES) {
+            let values = &self.lookup_data.lookup[row];
+            let multiplicity: PackedQM31 = self.lookup_data.multiplicities[row].into();
+
+            let denom: PackedQM31 = lookup_elements.combine(values);
+            let num: PackedQM31 = -PackedQM31::one() * multiplicity;
+
+            col_gen.write_frac(row, num, denom);
+        }
+        col_gen.finalize_col();
+
+        let (trace, claimed_sum) = logup_gen.finalize_last();
+        tree_builder.extend_evals(trace);
+
+        InteractionClaim { claimed_sum }
+    }
+}
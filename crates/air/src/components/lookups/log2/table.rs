@@ -8,13 +8,13 @@ use stwo::{
     },
 };
 
-use crate::components::TraceColumn;
+use crate::{components::TraceColumn, utils::PackedRows};
 
 use super::witness::N_TRACE_COLUMNS;
 
 #[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Log2LookupTraceTable {
-    pub table: Vec<Log2LookupTraceTableRow>,
+    pub table: PackedRows<Log2LookupTraceTableRow>,
 }
 
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
@@ -4,6 +4,7 @@ use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterato
 use stwo_air_utils::trace::component_trace::ComponentTrace;
 use stwo_air_utils_derive::{IterMut, ParIterMut, Uninitialized};
 use stwo::prover::backend::simd::{
+    column::BaseColumn,
     m31::{PackedM31, LOG_N_LANES, N_LANES},
     qm31::PackedQM31,
     SimdBackend,
@@ -21,8 +22,7 @@ use crate::{
         },
         Log2LookupClaim, InteractionClaim,
     },
-    preprocessed::Log2PreProcessed,
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
 pub(crate) const N_TRACE_COLUMNS: usize = 1;
@@ -40,7 +40,7 @@ impl ClaimGenerator {
 
     /// Writes the trace and generates claims
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(Log2LookupClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -52,10 +52,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs
+        let packed_inputs = self
+            .inputs
             .table
-            .resize(size, Log2LookupTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+            .into_packed(Log2LookupTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -118,13 +118,13 @@ impl InteractionClaimGenerator {
         self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
         elements: &Log2LookupElements, // Randomness for Log2 LUT (input, output) combination
-        lut: &Vec<&Log2PreProcessed>,  // References to the two preprocessed Log2 LUT columns
+        lut: &[&BaseColumn], // Values of the preprocessed Log2 LUT columns
     ) -> InteractionClaim {
         let mut logup_gen = LogupTraceGenerator::new(self.log_size);
 
         let mut col_gen = logup_gen.new_col();
-        let lut_col_0 = &lut.get(0).expect("missing log2 col 0").evaluation().data;
-        let lut_col_1 = &lut.get(1).expect("missing log2 col 1").evaluation().data;
+        let lut_col_0 = &lut.get(0).expect("missing log2 col 0").data;
+        let lut_col_1 = &lut.get(1).expect("missing log2 col 1").data;
         for row in 0..1 << (self.log_size - LOG_N_LANES) {
             let multiplicity: PackedQM31 = self.lookup_data.multiplicities[row].into();
             let input = lut_col_0[row];
@@ -4,11 +4,13 @@ use stwo::core::channel::Channel;
 
 use crate::components::lookups::{
     exp2::{Exp2Lookup, Exp2LookupElements},
+    gather::GatherLookupElements,
     log2::{Log2Lookup, Log2LookupElements},
     range_check::{RangeCheckLookup, RangeCheckLookupElements},
 };
 
 pub mod exp2;
+pub mod gather;
 pub mod log2;
 pub mod range_check;
 pub mod sin;
@@ -37,6 +39,8 @@ pub struct LookupElements {
     pub log2: Log2LookupElements,
     /// Interaction elements for range check lookup table
     pub range_check: RangeCheckLookupElements,
+    /// Interaction elements for indexed table reads (gather and im2col)
+    pub gather: GatherLookupElements,
 }
 
 impl LookupElements {
@@ -47,6 +51,7 @@ impl LookupElements {
             exp2: Exp2LookupElements::draw(channel),
             log2: Log2LookupElements::draw(channel),
             range_check: RangeCheckLookupElements::draw(channel),
+            gather: GatherLookupElements::draw(channel),
         }
     }
 }
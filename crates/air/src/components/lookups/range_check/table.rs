@@ -8,11 +8,14 @@ use stwo::{
     },
 };
 
-use crate::components::{lookups::range_check::witness::N_TRACE_COLUMNS, TraceColumn};
+use crate::{
+    components::{lookups::range_check::witness::N_TRACE_COLUMNS, TraceColumn},
+    utils::PackedRows,
+};
 
 #[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct RangeCheckLookupTraceTable {
-    pub table: Vec<RangeCheckLookupTraceTableRow>,
+    pub table: PackedRows<RangeCheckLookupTraceTableRow>,
 }
 
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
@@ -4,6 +4,7 @@ use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterato
 use stwo_air_utils::trace::component_trace::ComponentTrace;
 use stwo_air_utils_derive::{IterMut, ParIterMut, Uninitialized};
 use stwo::prover::backend::simd::{
+    column::BaseColumn,
     m31::{PackedM31, LOG_N_LANES, N_LANES},
     qm31::PackedQM31,
     SimdBackend,
@@ -21,8 +22,7 @@ use crate::{
         },
         InteractionClaim, RangeCheckLookupClaim,
     },
-    preprocessed::RangeCheckPreProcessed,
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
 pub(crate) const N_TRACE_COLUMNS: usize = 1;
@@ -40,7 +40,7 @@ impl<const N: usize> ClaimGenerator<N> {
 
     /// Writes the trace and generates claims
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(RangeCheckLookupClaim, InteractionClaimGenerator<N>), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -52,10 +52,10 @@ impl<const N: usize> ClaimGenerator<N> {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs
+        let packed_inputs = self
+            .inputs
             .table
-            .resize(size, RangeCheckLookupTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+            .into_packed(RangeCheckLookupTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -118,7 +118,7 @@ impl<const N: usize> InteractionClaimGenerator<N> {
         self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
         elements: &RangeCheckLookupElements,
-        lut: &Vec<&RangeCheckPreProcessed<N>>,
+        lut: &[&BaseColumn],
     ) -> InteractionClaim {
         let mut logup_gen = LogupTraceGenerator::new(self.log_size);
 
@@ -126,7 +126,6 @@ impl<const N: usize> InteractionClaimGenerator<N> {
         let lut_col_0 = &lut
             .get(0)
             .expect("missing range check col 0")
-            .evaluation()
             .data;
         for row in 0..1 << (self.log_size - LOG_N_LANES) {
             let multiplicity: PackedM31 = self.lookup_data.multiplicities[row].into();
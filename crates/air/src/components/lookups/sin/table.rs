@@ -8,13 +8,13 @@ use stwo::{
     },
 };
 
-use crate::components::TraceColumn;
+use crate::{components::TraceColumn, utils::PackedRows};
 
 use super::witness::N_TRACE_COLUMNS;
 
 #[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SinLookupTraceTable {
-    pub table: Vec<SinLookupTraceTableRow>,
+    pub table: PackedRows<SinLookupTraceTableRow>,
 }
 
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
@@ -4,6 +4,7 @@ use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterato
 use stwo_air_utils::trace::component_trace::ComponentTrace;
 use stwo_air_utils_derive::{IterMut, ParIterMut, Uninitialized};
 use stwo::prover::backend::simd::{
+    column::BaseColumn,
     m31::{PackedM31, LOG_N_LANES, N_LANES},
     qm31::PackedQM31,
     SimdBackend,
@@ -12,8 +13,7 @@ use stwo_constraint_framework::{LogupTraceGenerator, Relation};
 
 use crate::{
     components::{InteractionClaim, SinLookupClaim},
-    preprocessed::SinPreProcessed,
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
 use super::{
@@ -38,7 +38,7 @@ impl ClaimGenerator {
 
     /// Writes the trace and generates claims
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(SinLookupClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -50,10 +50,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs
+        let packed_inputs = self
+            .inputs
             .table
-            .resize(size, SinLookupTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+            .into_packed(SinLookupTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -116,13 +116,13 @@ impl InteractionClaimGenerator {
         self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
         elements: &SinLookupElements,          // Randomness for Sin LUT (input, output) combination
-        lut: &Vec<&SinPreProcessed>,        // References to the two preprocessed Sin LUT columns
+        lut: &[&BaseColumn], // Values of the preprocessed Sin LUT columns
     ) -> InteractionClaim {
         let mut logup_gen = LogupTraceGenerator::new(self.log_size);
 
         let mut col_gen = logup_gen.new_col();
-        let lut_col_0 = &lut.get(0).expect("missing sin col 0").evaluation().data;
-        let lut_col_1 = &lut.get(1).expect("missing sin col 1").evaluation().data;
+        let lut_col_0 = &lut.get(0).expect("missing sin col 0").data;
+        let lut_col_1 = &lut.get(1).expect("missing sin col 1").data;
         for row in 0..1 << (self.log_size - LOG_N_LANES) {
             let multiplicity: PackedQM31 = self.lookup_data.multiplicities[row].into();
             let input = lut_col_0[row];
@@ -0,0 +1,137 @@
+use crate::{
+    components::{MatMulClaim, NodeElements},
+    DEFAULT_FP_SCALE,
+};
+use num_traits::One;
+use numerair::eval::EvalFixedPoint;
+use stwo::core::fields::m31::M31;
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
+};
+
+pub type MatMulComponent = FrameworkComponent<MatMulEval>;
+
+/// Evaluation structure for fused multiply-accumulate (matmul) operations
+pub struct MatMulEval {
+    log_size: u32,
+    node_elements: NodeElements,
+}
+
+impl MatMulEval {
+    /// Creates a new MatMulEval with the given claim and node elements
+    pub fn new(claim: &MatMulClaim, node_elements: NodeElements) -> Self {
+        Self {
+            log_size: claim.log_size,
+            node_elements,
+        }
+    }
+}
+
+impl FrameworkEval for MatMulEval {
+    /// Returns the log size of the evaluation
+    fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Returns the maximum constraint log degree bound
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.log_size + 1
+    }
+
+    /// Evaluates the multiply-accumulate constraints and relations
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        // IDs, along with their value on the next row for transition constraints
+        // ID of the node in the computational graph.
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of first input tensor.
+        let [lhs_id, next_lhs_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of second input tensor.
+        let [rhs_id, next_rhs_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Index in the flattened output tensor.
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let is_last_idx = eval.next_trace_mask(); // Flag if this is the last index for this operation.
+
+        // Values for consistency constraints
+        let lhs_val = eval.next_trace_mask(); // Value from first tensor.
+        let rhs_val = eval.next_trace_mask(); // Value from second tensor.
+        let prod_val = eval.next_trace_mask(); // Fixed-point product of lhs and rhs.
+        let rem_val = eval.next_trace_mask(); // Remainder of the fixed-point product.
+        let acc_val = eval.next_trace_mask(); // Accumulative value in result tensor at index.
+        let next_acc_val = eval.next_trace_mask(); // Next accumulative value.
+        let is_last_step = eval.next_trace_mask(); // Flag if this is the last step.
+        let out_val = eval.next_trace_mask(); // Value in output tensor at index.
+
+        // Multiplicities for interaction constraints
+        let lhs_mult = eval.next_trace_mask();
+        let rhs_mult = eval.next_trace_mask();
+        let out_mult = eval.next_trace_mask();
+
+        let scale_factor = E::F::from(M31::from_u32_unchecked(1 << DEFAULT_FP_SCALE));
+
+        // ┌─────────────────────────────┐
+        // │   Consistency Constraints   │
+        // └─────────────────────────────┘
+
+        // The is_last_idx and is_last_step flags are either 0 or 1.
+        eval.add_constraint(is_last_idx.clone() * (is_last_idx.clone() - E::F::one()));
+        eval.add_constraint(is_last_step.clone() * (is_last_step.clone() - E::F::one()));
+
+        // Evaluates fixed point multiplication of the current pair.
+        eval.eval_fixed_mul(
+            lhs_val.clone(),
+            rhs_val.clone(),
+            scale_factor,
+            prod_val.clone(),
+            rem_val,
+        );
+
+        // The output value must equal the sum of the products.
+        eval.add_constraint(next_acc_val.clone() - (acc_val + prod_val));
+        eval.add_constraint((out_val.clone() - next_acc_val) * is_last_step);
+
+        // ┌────────────────────────────┐
+        // │   Transition Constraints   │
+        // └────────────────────────────┘
+
+        // If this is not the last index for this operation, then:
+        // 1. The next row should be for the same operation on the same tensors.
+        // 2. The index should increment by 1.
+        let not_last = E::F::one() - is_last_idx;
+
+        // Same node ID
+        eval.add_constraint(not_last.clone() * (next_node_id - node_id.clone()));
+
+        // Same tensor IDs
+        eval.add_constraint(not_last.clone() * (next_lhs_id - lhs_id.clone()));
+        eval.add_constraint(not_last.clone() * (next_rhs_id - rhs_id.clone()));
+
+        // Index increment by 1
+        eval.add_constraint(not_last * (next_idx - idx - E::F::one()));
+
+        // ┌─────────────────────────────┐
+        // │   Interaction Constraints   │
+        // └─────────────────────────────┘
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            lhs_mult.into(),
+            &[lhs_val, lhs_id],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            rhs_mult.into(),
+            &[rhs_val, rhs_id],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            out_mult.into(),
+            &[out_val, node_id],
+        ));
+
+        eval.finalize_logup();
+
+        eval
+    }
+}
@@ -0,0 +1,233 @@
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::fields::m31::M31,
+    prover::backend::simd::{
+        conversion::{Pack, Unpack},
+        m31::{PackedM31, N_LANES},
+    },
+};
+
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct MatMulTraceTable {
+    pub table: PackedRows<MatMulTraceTableRow>,
+}
+
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MatMulTraceTableRow {
+    pub node_id: M31,
+    pub lhs_id: M31,
+    pub rhs_id: M31,
+    pub idx: M31,
+    pub is_last_idx: M31,
+    pub lhs: M31,
+    pub rhs: M31,
+    pub prod: M31,
+    pub rem: M31,
+    pub acc: M31,
+    pub next_acc: M31,
+    pub is_last_step: M31,
+    pub out: M31,
+    pub lhs_mult: M31,
+    pub rhs_mult: M31,
+    pub out_mult: M31,
+}
+
+impl MatMulTraceTableRow {
+    pub(crate) fn padding() -> Self {
+        Self {
+            node_id: M31::zero(),
+            lhs_id: M31::zero(),
+            rhs_id: M31::zero(),
+            idx: M31::zero(),
+            is_last_idx: M31::one(),
+            lhs: M31::zero(),
+            rhs: M31::zero(),
+            prod: M31::zero(),
+            rem: M31::zero(),
+            acc: M31::zero(),
+            next_acc: M31::zero(),
+            is_last_step: M31::zero(),
+            out: M31::zero(),
+            lhs_mult: M31::zero(),
+            rhs_mult: M31::zero(),
+            out_mult: M31::zero(),
+        }
+    }
+}
+
+impl TransitionRow for MatMulTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct PackedMatMulTraceTableRow {
+    pub node_id: PackedM31,
+    pub lhs_id: PackedM31,
+    pub rhs_id: PackedM31,
+    pub idx: PackedM31,
+    pub is_last_idx: PackedM31,
+    pub lhs: PackedM31,
+    pub rhs: PackedM31,
+    pub prod: PackedM31,
+    pub rem: PackedM31,
+    pub acc: PackedM31,
+    pub next_acc: PackedM31,
+    pub is_last_step: PackedM31,
+    pub out: PackedM31,
+    pub lhs_mult: PackedM31,
+    pub rhs_mult: PackedM31,
+    pub out_mult: PackedM31,
+}
+
+impl Pack for MatMulTraceTableRow {
+    type SimdType = PackedMatMulTraceTableRow;
+
+    fn pack(inputs: [Self; N_LANES]) -> Self::SimdType {
+        PackedMatMulTraceTableRow {
+            node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].node_id)),
+            lhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lhs_id)),
+            rhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs_id)),
+            idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
+            is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
+            lhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lhs)),
+            rhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs)),
+            prod: PackedM31::from_array(std::array::from_fn(|i| inputs[i].prod)),
+            rem: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rem)),
+            acc: PackedM31::from_array(std::array::from_fn(|i| inputs[i].acc)),
+            next_acc: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_acc)),
+            is_last_step: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_step)),
+            out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
+            lhs_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lhs_mult)),
+            rhs_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs_mult)),
+            out_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out_mult)),
+        }
+    }
+}
+
+impl Unpack for PackedMatMulTraceTableRow {
+    type CpuType = MatMulTraceTableRow;
+
+    fn unpack(self) -> [Self::CpuType; N_LANES] {
+        let (
+            node_id,
+            lhs_id,
+            rhs_id,
+            idx,
+            is_last_idx,
+            lhs,
+            rhs,
+            prod,
+            rem,
+            acc,
+            next_acc,
+            is_last_step,
+            out,
+            lhs_mult,
+            rhs_mult,
+            out_mult,
+        ) = (
+            self.node_id.to_array(),
+            self.lhs_id.to_array(),
+            self.rhs_id.to_array(),
+            self.idx.to_array(),
+            self.is_last_idx.to_array(),
+            self.lhs.to_array(),
+            self.rhs.to_array(),
+            self.prod.to_array(),
+            self.rem.to_array(),
+            self.acc.to_array(),
+            self.next_acc.to_array(),
+            self.is_last_step.to_array(),
+            self.out.to_array(),
+            self.lhs_mult.to_array(),
+            self.rhs_mult.to_array(),
+            self.out_mult.to_array(),
+        );
+
+        std::array::from_fn(|i| MatMulTraceTableRow {
+            node_id: node_id[i],
+            lhs_id: lhs_id[i],
+            rhs_id: rhs_id[i],
+            idx: idx[i],
+            is_last_idx: is_last_idx[i],
+            lhs: lhs[i],
+            rhs: rhs[i],
+            prod: prod[i],
+            rem: rem[i],
+            acc: acc[i],
+            next_acc: next_acc[i],
+            is_last_step: is_last_step[i],
+            out: out[i],
+            lhs_mult: lhs_mult[i],
+            rhs_mult: rhs_mult[i],
+            out_mult: out_mult[i],
+        })
+    }
+}
+
+impl MatMulTraceTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_row(&mut self, row: MatMulTraceTableRow) {
+        self.table.push(row);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MatMulColumn {
+    NodeId,
+    LhsId,
+    RhsId,
+    Idx,
+    IsLastIdx,
+    Lhs,
+    Rhs,
+    Prod,
+    Rem,
+    Acc,
+    NextAcc,
+    IsLastStep,
+    Out,
+    LhsMult,
+    RhsMult,
+    OutMult,
+}
+
+impl MatMulColumn {
+    pub const fn index(self) -> usize {
+        match self {
+            Self::NodeId => 0,
+            Self::LhsId => 1,
+            Self::RhsId => 2,
+            Self::Idx => 3,
+            Self::IsLastIdx => 4,
+            Self::Lhs => 5,
+            Self::Rhs => 6,
+            Self::Prod => 7,
+            Self::Rem => 8,
+            Self::Acc => 9,
+            Self::NextAcc => 10,
+            Self::IsLastStep => 11,
+            Self::Out => 12,
+            Self::LhsMult => 13,
+            Self::RhsMult => 14,
+            Self::OutMult => 15,
+        }
+    }
+}
+
+impl TraceColumn for MatMulColumn {
+    fn count() -> (usize, usize) {
+        (16, 3)
+    }
+}
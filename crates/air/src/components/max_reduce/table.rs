@@ -7,13 +7,16 @@ use stwo::{
     },
 };
 
-use crate::components::TraceColumn;
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
 
 use super::witness::N_TRACE_COLUMNS;
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct MaxReduceTraceTable {
-    pub table: Vec<MaxReduceTraceTableRow>,
+    pub table: PackedRows<MaxReduceTraceTableRow>,
 }
 
 #[derive(Debug, Default, Copy, Clone, serde::Serialize, serde::Deserialize)]
@@ -22,9 +25,6 @@ pub struct MaxReduceTraceTableRow {
     pub input_id: M31,
     pub idx: M31,
     pub is_last_idx: M31,
-    pub next_node_id: M31,
-    pub next_input_id: M31,
-    pub next_idx: M31,
     pub input: M31,
     pub out: M31,
     pub max_val: M31,
@@ -42,9 +42,6 @@ impl MaxReduceTraceTableRow {
             input_id: M31::zero(),
             idx: M31::zero(),
             is_last_idx: M31::one(),
-            next_node_id: M31::zero(),
-            next_input_id: M31::zero(),
-            next_idx: M31::zero(),
             input: M31::zero(),
             out: M31::zero(),
             max_val: M31::zero(),
@@ -57,15 +54,18 @@ impl MaxReduceTraceTableRow {
     }
 }
 
+impl TransitionRow for MaxReduceTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct PackedMaxReduceTraceTableRow {
     pub node_id: PackedM31,
     pub input_id: PackedM31,
     pub idx: PackedM31,
     pub is_last_idx: PackedM31,
-    pub next_node_id: PackedM31,
-    pub next_input_id: PackedM31,
-    pub next_idx: PackedM31,
     pub input: PackedM31,
     pub out: PackedM31,
     pub max_val: PackedM31,
@@ -85,9 +85,6 @@ impl Pack for MaxReduceTraceTableRow {
             input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_id)),
             idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
             is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
-            next_node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_node_id)),
-            next_input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_input_id)),
-            next_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_idx)),
             input: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input)),
             out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
             max_val: PackedM31::from_array(std::array::from_fn(|i| inputs[i].max_val)),
@@ -109,9 +106,6 @@ impl Unpack for PackedMaxReduceTraceTableRow {
             input_id,
             idx,
             is_last_idx,
-            next_node_id,
-            next_input_id,
-            next_idx,
             input,
             out,
             max_val,
@@ -125,9 +119,6 @@ impl Unpack for PackedMaxReduceTraceTableRow {
             self.input_id.to_array(),
             self.idx.to_array(),
             self.is_last_idx.to_array(),
-            self.next_node_id.to_array(),
-            self.next_input_id.to_array(),
-            self.next_idx.to_array(),
             self.input.to_array(),
             self.out.to_array(),
             self.max_val.to_array(),
@@ -143,9 +134,6 @@ impl Unpack for PackedMaxReduceTraceTableRow {
             input_id: input_id[i],
             idx: idx[i],
             is_last_idx: is_last_idx[i],
-            next_node_id: next_node_id[i],
-            next_input_id: next_input_id[i],
-            next_idx: next_idx[i],
             input: input[i],
             out: out[i],
             max_val: max_val[i],
@@ -170,7 +158,7 @@ impl MaxReduceTraceTable {
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum MaxReduceColumn {
-    NodeId, InputId, Idx, IsLastIdx, NextNodeId, NextInputId, NextIdx, Input, Out, MaxVal, NextMaxVal, IsLastStep, IsMax, InputMult, OutMult
+    NodeId, InputId, Idx, IsLastIdx, Input, Out, MaxVal, NextMaxVal, IsLastStep, IsMax, InputMult, OutMult
 }
 impl MaxReduceColumn {
     pub const fn index(self) -> usize {
@@ -179,17 +167,14 @@ impl MaxReduceColumn {
             Self::InputId => 1,
             Self::Idx => 2,
             Self::IsLastIdx => 3,
-            Self::NextNodeId => 4,
-            Self::NextInputId => 5,
-            Self::NextIdx => 6,
-            Self::Input => 7,
-            Self::Out => 8,
-            Self::MaxVal => 9,
-            Self::NextMaxVal => 10,
-            Self::IsLastStep => 11,
-            Self::IsMax => 12,
-            Self::InputMult => 13,
-            Self::OutMult => 14,
+            Self::Input => 4,
+            Self::Out => 5,
+            Self::MaxVal => 6,
+            Self::NextMaxVal => 7,
+            Self::IsLastStep => 8,
+            Self::IsMax => 9,
+            Self::InputMult => 10,
+            Self::OutMult => 11,
         }
     }
 }
@@ -11,14 +11,14 @@ use stwo_constraint_framework::{LogupTraceGenerator, Relation};
 
 use crate::{
     components::{InteractionClaim, MaxReduceClaim, NodeElements},
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
 use super::table::{
     MaxReduceColumn, MaxReduceTraceTable, MaxReduceTraceTableRow, PackedMaxReduceTraceTableRow,
 };
 
-pub(crate) const N_TRACE_COLUMNS: usize = 15;
+pub(crate) const N_TRACE_COLUMNS: usize = 12;
 
 pub struct ClaimGenerator {
     pub inputs: MaxReduceTraceTable,
@@ -30,7 +30,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(MaxReduceClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -42,10 +42,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs
+        let packed_inputs = self
+            .inputs
             .table
-            .resize(size, MaxReduceTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+            .into_packed_coset_order(MaxReduceTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -85,9 +85,6 @@ fn write_trace_simd(
             *row[MaxReduceColumn::InputId.index()] = input.input_id;
             *row[MaxReduceColumn::Idx.index()] = input.idx;
             *row[MaxReduceColumn::IsLastIdx.index()] = input.is_last_idx;
-            *row[MaxReduceColumn::NextNodeId.index()] = input.next_node_id;
-            *row[MaxReduceColumn::NextInputId.index()] = input.next_input_id;
-            *row[MaxReduceColumn::NextIdx.index()] = input.next_idx;
             *row[MaxReduceColumn::Input.index()] = input.input;
             *row[MaxReduceColumn::Out.index()] = input.out;
             *row[MaxReduceColumn::MaxVal.index()] = input.max_val;
@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use add::{
     component::{AddComponent, AddEval},
     table::AddColumn,
@@ -37,7 +35,7 @@ use stwo::{
         air::Component,
         channel::Channel,
         fields::{
-            m31::BaseField,
+            m31::{BaseField, M31},
             qm31::{SecureField, SECURE_EXTENSION_DEGREE},
         },
         pcs::TreeVec,
@@ -51,7 +49,9 @@ use stwo::{
 };
 use stwo_constraint_framework::relation;
 
-use stwo_constraint_framework::TraceLocationAllocator;
+use stwo_constraint_framework::{
+    preprocessed_columns::PreProcessedColumnId, TraceLocationAllocator,
+};
 
 use sum_reduce::{
     component::{SumReduceComponent, SumReduceEval},
@@ -65,6 +65,10 @@ use rem::{
 
 use crate::{
     components::{
+        add_const::{
+            component::{AddConstComponent, AddConstEval},
+            table::AddConstColumn,
+        },
         contiguous::{
             component::{ContiguousComponent, ContiguousEval},
             table::ContiguousColumn,
@@ -73,6 +77,18 @@ use crate::{
             component::{Exp2Component, Exp2Eval},
             table::Exp2Column,
         },
+        fused::{
+            component::{FusedComponent, FusedEval},
+            expr::FusedExpr,
+        },
+        gather::{
+            component::{GatherComponent, GatherEval},
+            table::GatherColumn,
+        },
+        im2col::{
+            component::{Im2ColComponent, Im2ColEval},
+            table::Im2ColColumn,
+        },
         inputs::{
             components::{InputsComponent, InputsEval},
             table::InputsColumn,
@@ -90,6 +106,10 @@ use crate::{
                 component::{Exp2LookupComponent, Exp2LookupEval},
                 table::Exp2LookupColumn,
             },
+            gather::{
+                component::{GatherLookupComponent, GatherLookupEval},
+                table::GatherLookupColumn,
+            },
             log2::{
                 component::{Log2LookupComponent, Log2LookupEval},
                 table::Log2LookupColumn,
@@ -99,22 +119,45 @@ use crate::{
                 table::RangeCheckLookupColumn,
             },
         },
+        matmul::{
+            component::{MatMulComponent, MatMulEval},
+            table::MatMulColumn,
+        },
+        mul_const::{
+            component::{MulConstComponent, MulConstEval},
+            table::MulConstColumn,
+        },
+        norm::{
+            component::{NormComponent, NormEval},
+            table::{NormColumn, NormParams},
+        },
+        select::{
+            component::{SelectComponent, SelectEval},
+            table::SelectColumn,
+        },
     },
-    preprocessed::PreProcessedTrace,
     LuminairClaim, LuminairInteractionClaim,
 };
 
 pub mod add;
+pub mod add_const;
 pub mod contiguous;
 pub mod exp2;
+pub mod fused;
+pub mod gather;
+pub mod im2col;
 pub mod inputs;
 pub mod less_than;
 pub mod log2;
 pub mod lookups;
+pub mod matmul;
 pub mod max_reduce;
 pub mod mul;
+pub mod mul_const;
+pub mod norm;
 pub mod recip;
 pub mod rem;
+pub mod select;
 pub mod sin;
 pub mod sqrt;
 pub mod sum_reduce;
@@ -138,6 +181,13 @@ pub type LessThanClaim = Claim<LessThanColumn>;
 pub type RangeCheckLookupClaim = Claim<RangeCheckLookupColumn>;
 pub type InputsClaim = Claim<InputsColumn>;
 pub type ContiguousClaim = Claim<ContiguousColumn>;
+pub type SelectClaim = Claim<SelectColumn>;
+pub type GatherClaim = Claim<GatherColumn>;
+pub type GatherLookupClaim = Claim<GatherLookupColumn>;
+pub type Im2ColClaim = Claim<Im2ColColumn>;
+pub type MatMulClaim = Claim<MatMulColumn>;
+pub type MulConstClaim = ConstClaim<MulConstColumn>;
+pub type AddConstClaim = ConstClaim<AddConstColumn>;
 
 /// Trait for trace columns to specify their count
 pub trait TraceColumn {
@@ -176,6 +226,136 @@ impl<T: TraceColumn> Claim<T> {
     }
 }
 
+/// Mixes the number of shards of a component, then the claim of each shard, into the channel
+pub fn mix_shard_claims<T: TraceColumn>(claims: &[Claim<T>], channel: &mut impl Channel) {
+    channel.mix_u64(claims.len() as u64);
+    for claim in claims {
+        claim.mix_into(channel);
+    }
+}
+
+/// Claim of a component instance whose operations share a constant operand
+///
+/// The constant is public: it is mixed into the channel with the log size and used as is by
+/// the constraints, so the trace holds no column for it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConstClaim<T: TraceColumn> {
+    pub log_size: u32,
+    /// Fixed-point value of the constant operand
+    pub constant: M31,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: TraceColumn> ConstClaim<T> {
+    /// Creates a new claim with the specified log size and constant
+    pub const fn new(log_size: u32, constant: M31) -> Self {
+        Self {
+            log_size,
+            constant,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Returns the log sizes for main and interaction trace columns
+    pub fn log_sizes(&self) -> TreeVec<Vec<u32>> {
+        Claim::<T>::new(self.log_size).log_sizes()
+    }
+
+    /// Mixes the claim's log size and constant into the given channel
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.log_size.into());
+        channel.mix_u64(self.constant.0.into());
+    }
+}
+
+/// Mixes the number of instances of a constant operand component, then the claim of each
+/// instance, into the channel
+pub fn mix_const_claims<T: TraceColumn>(claims: &[ConstClaim<T>], channel: &mut impl Channel) {
+    channel.mix_u64(claims.len() as u64);
+    for claim in claims {
+        claim.mix_into(channel);
+    }
+}
+
+/// Claim of a component instance proving a fused elementwise expression
+///
+/// The expression is public: it is mixed into the channel with the log size, and the trace
+/// layout and constraints of the instance are derived from it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FusedClaim {
+    pub log_size: u32,
+    /// Expression evaluated by each row
+    pub expr: FusedExpr,
+}
+
+impl FusedClaim {
+    /// Creates a new claim with the specified log size and expression
+    pub fn new(log_size: u32, expr: FusedExpr) -> Self {
+        Self { log_size, expr }
+    }
+
+    /// Returns the log sizes for main and interaction trace columns
+    pub fn log_sizes(&self) -> TreeVec<Vec<u32>> {
+        let trace_log_sizes = vec![self.log_size; self.expr.n_columns()];
+        let interaction_trace_log_sizes: Vec<u32> =
+            vec![self.log_size; SECURE_EXTENSION_DEGREE * self.expr.n_relations()];
+        TreeVec::new(vec![vec![], trace_log_sizes, interaction_trace_log_sizes])
+    }
+
+    /// Mixes the claim's log size and expression into the given channel
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.log_size.into());
+        self.expr.mix_into(channel);
+    }
+}
+
+/// Mixes the number of instances of the fused component, then the claim of each instance, into
+/// the channel
+pub fn mix_fused_claims(claims: &[FusedClaim], channel: &mut impl Channel) {
+    channel.mix_u64(claims.len() as u64);
+    for claim in claims {
+        claim.mix_into(channel);
+    }
+}
+
+/// Claim of a component instance proving normalizations that share their parameters
+///
+/// The parameters are public: they are mixed into the channel with the log size, and the
+/// constants of the constraints of the instance are derived from them.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NormClaim {
+    pub log_size: u32,
+    /// Parameters of the normalizations
+    pub params: NormParams,
+}
+
+impl NormClaim {
+    /// Creates a new claim with the specified log size and parameters
+    pub const fn new(log_size: u32, params: NormParams) -> Self {
+        Self { log_size, params }
+    }
+
+    /// Returns the log sizes for main and interaction trace columns
+    pub fn log_sizes(&self) -> TreeVec<Vec<u32>> {
+        Claim::<NormColumn>::new(self.log_size).log_sizes()
+    }
+
+    /// Mixes the claim's log size and parameters into the given channel
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.log_size.into());
+        self.params.mix_into(channel);
+    }
+}
+
+/// Mixes the number of instances of the norm component, then the claim of each instance, into
+/// the channel
+pub fn mix_norm_claims(claims: &[NormClaim], channel: &mut impl Channel) {
+    channel.mix_u64(claims.len() as u64);
+    for claim in claims {
+        claim.mix_into(channel);
+    }
+}
+
 /// Enumeration of all possible claim types
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub enum ClaimType {
@@ -196,6 +376,15 @@ pub enum ClaimType {
     RangeCheckLookup(Claim<RangeCheckLookupColumn>),
     Inputs(Claim<InputsColumn>),
     Contiguous(Claim<ContiguousColumn>),
+    Select(Claim<SelectColumn>),
+    Gather(Claim<GatherColumn>),
+    GatherLookup(Claim<GatherLookupColumn>),
+    Im2Col(Claim<Im2ColColumn>),
+    MatMul(Claim<MatMulColumn>),
+    Norm(NormClaim),
+    MulConst(ConstClaim<MulConstColumn>),
+    AddConst(ConstClaim<AddConstColumn>),
+    Fused(FusedClaim),
 }
 
 /// Interaction claim containing a claimed sum
@@ -235,25 +424,34 @@ impl LuminairInteractionElements {
     }
 }
 
-/// Collection of all LuminAIR components
+/// Collection of all LuminAIR components, with one instance per shard of each table
 pub struct LuminairComponents {
-    add: Option<AddComponent>,
-    mul: Option<MulComponent>,
-    recip: Option<RecipComponent>,
-    sin: Option<SinComponent>,
-    sin_lookup: Option<SinLookupComponent>,
-    sum_reduce: Option<SumReduceComponent>,
-    max_reduce: Option<MaxReduceComponent>,
-    sqrt: Option<SqrtComponent>,
-    rem: Option<RemComponent>,
-    exp2: Option<Exp2Component>,
-    exp2_lookup: Option<Exp2LookupComponent>,
-    log2: Option<Log2Component>,
-    log2_lookup: Option<Log2LookupComponent>,
-    less_than: Option<LessThanComponent>,
-    range_check_lookup: Option<RangeCheckLookupComponent>,
-    inputs: Option<InputsComponent>,
-    contiguous: Option<ContiguousComponent>,
+    add: Vec<AddComponent>,
+    mul: Vec<MulComponent>,
+    recip: Vec<RecipComponent>,
+    sin: Vec<SinComponent>,
+    sin_lookup: Vec<SinLookupComponent>,
+    sum_reduce: Vec<SumReduceComponent>,
+    max_reduce: Vec<MaxReduceComponent>,
+    sqrt: Vec<SqrtComponent>,
+    rem: Vec<RemComponent>,
+    exp2: Vec<Exp2Component>,
+    exp2_lookup: Vec<Exp2LookupComponent>,
+    log2: Vec<Log2Component>,
+    log2_lookup: Vec<Log2LookupComponent>,
+    less_than: Vec<LessThanComponent>,
+    range_check_lookup: Vec<RangeCheckLookupComponent>,
+    inputs: Vec<InputsComponent>,
+    contiguous: Vec<ContiguousComponent>,
+    select: Vec<SelectComponent>,
+    gather: Vec<GatherComponent>,
+    gather_lookup: Vec<GatherLookupComponent>,
+    im2col: Vec<Im2ColComponent>,
+    matmul: Vec<MatMulComponent>,
+    norm: Vec<NormComponent>,
+    mul_const: Vec<MulConstComponent>,
+    add_const: Vec<AddConstComponent>,
+    fused: Vec<FusedComponent>,
 }
 
 impl LuminairComponents {
@@ -262,248 +460,426 @@ impl LuminairComponents {
         claim: &LuminairClaim,
         interaction_elements: &LuminairInteractionElements,
         interaction_claim: &LuminairInteractionClaim,
-        preprocessed_trace: &PreProcessedTrace,
+        preprocessed_column_ids: &[PreProcessedColumnId],
         lookups: &Lookups,
     ) -> Self {
-        let preprocessed_column_ids = &preprocessed_trace.ids();
-        // Create a mapping from preprocessed column ID to log size
-        let mut preprocessed_column_log_sizes = HashMap::new();
-        for column in preprocessed_trace.columns.iter() {
-            preprocessed_column_log_sizes.insert(column.id().id.clone(), column.log_size());
-        }
-
         let tree_span_provider =
             &mut TraceLocationAllocator::new_with_preproccessed_columns(preprocessed_column_ids);
 
-        let add = if let Some(ref add_claim) = claim.add {
-            Some(AddComponent::new(
-                tree_span_provider,
-                AddEval::new(&add_claim, interaction_elements.node_elements.clone()),
-                interaction_claim.add.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let mul = if let Some(ref mul_claim) = claim.mul {
-            Some(MulComponent::new(
-                tree_span_provider,
-                MulEval::new(&mul_claim, interaction_elements.node_elements.clone()),
-                interaction_claim.mul.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let recip = if let Some(ref recip_claim) = claim.recip {
-            Some(RecipComponent::new(
-                tree_span_provider,
-                RecipEval::new(&recip_claim, interaction_elements.node_elements.clone()),
-                interaction_claim.recip.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let sin = if let Some(ref sin_claim) = claim.sin {
-            let lut_log_size = lookups.sin.as_ref().map(|s| s.layout.log_size).unwrap();
-            Some(SinComponent::new(
-                tree_span_provider,
-                SinEval::new(
-                    &sin_claim,
-                    interaction_elements.node_elements.clone(),
-                    interaction_elements.lookup_elements.sin.clone(),
-                    lut_log_size,
-                ),
-                interaction_claim.sin.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let sin_lookup = if let Some(ref sin_lookup_claim) = claim.sin_lookup {
-            Some(SinLookupComponent::new(
-                tree_span_provider,
-                SinLookupEval::new(
-                    &sin_lookup_claim,
-                    interaction_elements.lookup_elements.sin.clone(),
-                ),
-                interaction_claim.sin_lookup.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let sum_reduce = if let Some(ref sum_reduce_claim) = claim.sum_reduce {
-            Some(SumReduceComponent::new(
-                tree_span_provider,
-                SumReduceEval::new(
-                    &sum_reduce_claim,
-                    interaction_elements.node_elements.clone(),
-                ),
-                interaction_claim.sum_reduce.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let max_reduce = if let Some(ref max_reduce_claim) = claim.max_reduce {
-            Some(MaxReduceComponent::new(
-                tree_span_provider,
-                MaxReduceEval::new(
-                    &max_reduce_claim,
-                    interaction_elements.node_elements.clone(),
-                ),
-                interaction_claim.max_reduce.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let sqrt = if let Some(ref sqrt_claim) = claim.sqrt {
-            Some(SqrtComponent::new(
-                tree_span_provider,
-                SqrtEval::new(&sqrt_claim, interaction_elements.node_elements.clone()),
-                interaction_claim.sqrt.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let rem = if let Some(ref rem_claim) = claim.rem {
-            Some(RemComponent::new(
-                tree_span_provider,
-                RemEval::new(&rem_claim, interaction_elements.node_elements.clone()),
-                interaction_claim.rem.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let exp2 = if let Some(ref exp2_claim) = claim.exp2 {
-            let lut_log_size = lookups.exp2.as_ref().map(|s| s.layout.log_size).unwrap();
-            Some(Exp2Component::new(
-                tree_span_provider,
-                Exp2Eval::new(
-                    &exp2_claim,
-                    interaction_elements.node_elements.clone(),
-                    interaction_elements.lookup_elements.exp2.clone(),
-                    lut_log_size,
-                ),
-                interaction_claim.exp2.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let exp2_lookup = if let Some(ref exp2_lookup_claim) = claim.exp2_lookup {
-            Some(Exp2LookupComponent::new(
-                tree_span_provider,
-                Exp2LookupEval::new(
-                    &exp2_lookup_claim,
-                    interaction_elements.lookup_elements.exp2.clone(),
-                ),
-                interaction_claim.exp2_lookup.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let log2 = if let Some(ref log2_claim) = claim.log2 {
-            let lut_log_size = lookups.log2.as_ref().map(|s| s.layout.log_size).unwrap();
-            Some(Log2Component::new(
-                tree_span_provider,
-                Log2Eval::new(
-                    &log2_claim,
-                    interaction_elements.node_elements.clone(),
-                    interaction_elements.lookup_elements.log2.clone(),
-                    lut_log_size,
-                ),
-                interaction_claim.log2.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let log2_lookup = if let Some(ref log2_lookup_claim) = claim.log2_lookup {
-            Some(Log2LookupComponent::new(
-                tree_span_provider,
-                Log2LookupEval::new(
-                    &log2_lookup_claim,
-                    interaction_elements.lookup_elements.log2.clone(),
-                ),
-                interaction_claim.log2_lookup.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let less_than = if let Some(ref less_than_claim) = claim.less_than {
-            let lut_log_size = lookups
-                .range_check
-                .as_ref()
-                .map(|s| s.layout.log_size)
-                .unwrap();
-            Some(LessThanComponent::new(
-                tree_span_provider,
-                LessThanEval::new(
-                    &less_than_claim,
-                    interaction_elements.node_elements.clone(),
-                    interaction_elements.lookup_elements.range_check.clone(),
-                    lut_log_size,
-                ),
-                interaction_claim.less_than.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let range_check_lookup =
-            if let Some(ref range_check_lookup_claim) = claim.range_check_lookup {
+        let add = claim
+            .add
+            .iter()
+            .zip(&interaction_claim.add)
+            .map(|(add_claim, interaction_claim)| {
+                AddComponent::new(
+                    tree_span_provider,
+                    AddEval::new(add_claim, interaction_elements.node_elements.clone()),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let mul = claim
+            .mul
+            .iter()
+            .zip(&interaction_claim.mul)
+            .map(|(mul_claim, interaction_claim)| {
+                MulComponent::new(
+                    tree_span_provider,
+                    MulEval::new(mul_claim, interaction_elements.node_elements.clone()),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let recip = claim
+            .recip
+            .iter()
+            .zip(&interaction_claim.recip)
+            .map(|(recip_claim, interaction_claim)| {
+                RecipComponent::new(
+                    tree_span_provider,
+                    RecipEval::new(recip_claim, interaction_elements.node_elements.clone()),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let sin = claim
+            .sin
+            .iter()
+            .zip(&interaction_claim.sin)
+            .map(|(sin_claim, interaction_claim)| {
+                let lut_log_size = lookups.sin.as_ref().map(|s| s.layout.log_size).unwrap();
+                SinComponent::new(
+                    tree_span_provider,
+                    SinEval::new(
+                        sin_claim,
+                        interaction_elements.node_elements.clone(),
+                        interaction_elements.lookup_elements.sin.clone(),
+                        lut_log_size,
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let sin_lookup = claim
+            .sin_lookup
+            .iter()
+            .zip(&interaction_claim.sin_lookup)
+            .map(|(sin_lookup_claim, interaction_claim)| {
+                SinLookupComponent::new(
+                    tree_span_provider,
+                    SinLookupEval::new(
+                        sin_lookup_claim,
+                        interaction_elements.lookup_elements.sin.clone(),
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let sum_reduce = claim
+            .sum_reduce
+            .iter()
+            .zip(&interaction_claim.sum_reduce)
+            .map(|(sum_reduce_claim, interaction_claim)| {
+                SumReduceComponent::new(
+                    tree_span_provider,
+                    SumReduceEval::new(
+                        sum_reduce_claim,
+                        interaction_elements.node_elements.clone(),
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let max_reduce = claim
+            .max_reduce
+            .iter()
+            .zip(&interaction_claim.max_reduce)
+            .map(|(max_reduce_claim, interaction_claim)| {
+                MaxReduceComponent::new(
+                    tree_span_provider,
+                    MaxReduceEval::new(
+                        max_reduce_claim,
+                        interaction_elements.node_elements.clone(),
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let sqrt = claim
+            .sqrt
+            .iter()
+            .zip(&interaction_claim.sqrt)
+            .map(|(sqrt_claim, interaction_claim)| {
+                SqrtComponent::new(
+                    tree_span_provider,
+                    SqrtEval::new(sqrt_claim, interaction_elements.node_elements.clone()),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let rem = claim
+            .rem
+            .iter()
+            .zip(&interaction_claim.rem)
+            .map(|(rem_claim, interaction_claim)| {
+                RemComponent::new(
+                    tree_span_provider,
+                    RemEval::new(rem_claim, interaction_elements.node_elements.clone()),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let exp2 = claim
+            .exp2
+            .iter()
+            .zip(&interaction_claim.exp2)
+            .map(|(exp2_claim, interaction_claim)| {
+                let lut_log_size = lookups.exp2.as_ref().map(|s| s.layout.log_size).unwrap();
+                Exp2Component::new(
+                    tree_span_provider,
+                    Exp2Eval::new(
+                        exp2_claim,
+                        interaction_elements.node_elements.clone(),
+                        interaction_elements.lookup_elements.exp2.clone(),
+                        lut_log_size,
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let exp2_lookup = claim
+            .exp2_lookup
+            .iter()
+            .zip(&interaction_claim.exp2_lookup)
+            .map(|(exp2_lookup_claim, interaction_claim)| {
+                Exp2LookupComponent::new(
+                    tree_span_provider,
+                    Exp2LookupEval::new(
+                        exp2_lookup_claim,
+                        interaction_elements.lookup_elements.exp2.clone(),
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let log2 = claim
+            .log2
+            .iter()
+            .zip(&interaction_claim.log2)
+            .map(|(log2_claim, interaction_claim)| {
+                let lut_log_size = lookups.log2.as_ref().map(|s| s.layout.log_size).unwrap();
+                Log2Component::new(
+                    tree_span_provider,
+                    Log2Eval::new(
+                        log2_claim,
+                        interaction_elements.node_elements.clone(),
+                        interaction_elements.lookup_elements.log2.clone(),
+                        lut_log_size,
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let log2_lookup = claim
+            .log2_lookup
+            .iter()
+            .zip(&interaction_claim.log2_lookup)
+            .map(|(log2_lookup_claim, interaction_claim)| {
+                Log2LookupComponent::new(
+                    tree_span_provider,
+                    Log2LookupEval::new(
+                        log2_lookup_claim,
+                        interaction_elements.lookup_elements.log2.clone(),
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let less_than = claim
+            .less_than
+            .iter()
+            .zip(&interaction_claim.less_than)
+            .map(|(less_than_claim, interaction_claim)| {
+                let lut_log_size = lookups
+                    .range_check
+                    .as_ref()
+                    .map(|s| s.layout.log_size)
+                    .unwrap();
+                LessThanComponent::new(
+                    tree_span_provider,
+                    LessThanEval::new(
+                        less_than_claim,
+                        interaction_elements.node_elements.clone(),
+                        interaction_elements.lookup_elements.range_check.clone(),
+                        lut_log_size,
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let range_check_lookup = claim
+            .range_check_lookup
+            .iter()
+            .zip(&interaction_claim.range_check_lookup)
+            .map(|(range_check_lookup_claim, interaction_claim)| {
                 let bit_length = lookups
                     .range_check
                     .as_ref()
                     .map(|s| s.layout.ranges[0])
                     .unwrap();
-                Some(RangeCheckLookupComponent::new(
+                RangeCheckLookupComponent::new(
                     tree_span_provider,
                     RangeCheckLookupEval::new(
                         bit_length,
-                        &range_check_lookup_claim,
+                        range_check_lookup_claim,
+                        interaction_elements.lookup_elements.range_check.clone(),
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let inputs = claim
+            .inputs
+            .iter()
+            .zip(&interaction_claim.inputs)
+            .map(|(inputs_claim, interaction_claim)| {
+                InputsComponent::new(
+                    tree_span_provider,
+                    InputsEval::new(inputs_claim, interaction_elements.node_elements.clone()),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let contiguous = claim
+            .contiguous
+            .iter()
+            .zip(&interaction_claim.contiguous)
+            .map(|(contiguous_claim, interaction_claim)| {
+                ContiguousComponent::new(
+                    tree_span_provider,
+                    ContiguousEval::new(
+                        contiguous_claim,
+                        interaction_elements.node_elements.clone(),
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let select = claim
+            .select
+            .iter()
+            .zip(&interaction_claim.select)
+            .map(|(select_claim, interaction_claim)| {
+                SelectComponent::new(
+                    tree_span_provider,
+                    SelectEval::new(select_claim, interaction_elements.node_elements.clone()),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let gather = claim
+            .gather
+            .iter()
+            .zip(&interaction_claim.gather)
+            .map(|(gather_claim, interaction_claim)| {
+                GatherComponent::new(
+                    tree_span_provider,
+                    GatherEval::new(
+                        gather_claim,
+                        interaction_elements.node_elements.clone(),
+                        interaction_elements.lookup_elements.gather.clone(),
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let gather_lookup = claim
+            .gather_lookup
+            .iter()
+            .zip(&interaction_claim.gather_lookup)
+            .map(|(gather_lookup_claim, interaction_claim)| {
+                GatherLookupComponent::new(
+                    tree_span_provider,
+                    GatherLookupEval::new(
+                        gather_lookup_claim,
+                        interaction_elements.node_elements.clone(),
+                        interaction_elements.lookup_elements.gather.clone(),
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let im2col = claim
+            .im2col
+            .iter()
+            .zip(&interaction_claim.im2col)
+            .map(|(im2col_claim, interaction_claim)| {
+                Im2ColComponent::new(
+                    tree_span_provider,
+                    Im2ColEval::new(
+                        im2col_claim,
+                        interaction_elements.node_elements.clone(),
+                        interaction_elements.lookup_elements.gather.clone(),
+                    ),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let matmul = claim
+            .matmul
+            .iter()
+            .zip(&interaction_claim.matmul)
+            .map(|(matmul_claim, interaction_claim)| {
+                MatMulComponent::new(
+                    tree_span_provider,
+                    MatMulEval::new(matmul_claim, interaction_elements.node_elements.clone()),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let norm = claim
+            .norm
+            .iter()
+            .zip(&interaction_claim.norm)
+            .map(|(norm_claim, interaction_claim)| {
+                let lut_log_size = lookups
+                    .range_check
+                    .as_ref()
+                    .map(|s| s.layout.log_size)
+                    .unwrap();
+                NormComponent::new(
+                    tree_span_provider,
+                    NormEval::new(
+                        norm_claim,
+                        interaction_elements.node_elements.clone(),
                         interaction_elements.lookup_elements.range_check.clone(),
+                        lut_log_size,
                     ),
-                    interaction_claim
-                        .range_check_lookup
-                        .as_ref()
-                        .unwrap()
-                        .claimed_sum,
-                ))
-            } else {
-                None
-            };
-
-        let inputs = if let Some(ref inputs_claim) = claim.inputs {
-            Some(InputsComponent::new(
-                tree_span_provider,
-                InputsEval::new(&inputs_claim, interaction_elements.node_elements.clone()),
-                interaction_claim.inputs.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
-
-        let contiguous = if let Some(ref contiguous_claim) = claim.contiguous {
-            Some(ContiguousComponent::new(
-                tree_span_provider,
-                ContiguousEval::new(
-                    &contiguous_claim,
-                    interaction_elements.node_elements.clone(),
-                ),
-                interaction_claim.contiguous.as_ref().unwrap().claimed_sum,
-            ))
-        } else {
-            None
-        };
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let mul_const = claim
+            .mul_const
+            .iter()
+            .zip(&interaction_claim.mul_const)
+            .map(|(mul_const_claim, interaction_claim)| {
+                MulConstComponent::new(
+                    tree_span_provider,
+                    MulConstEval::new(mul_const_claim, interaction_elements.node_elements.clone()),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let add_const = claim
+            .add_const
+            .iter()
+            .zip(&interaction_claim.add_const)
+            .map(|(add_const_claim, interaction_claim)| {
+                AddConstComponent::new(
+                    tree_span_provider,
+                    AddConstEval::new(add_const_claim, interaction_elements.node_elements.clone()),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
+
+        let fused = claim
+            .fused
+            .iter()
+            .zip(&interaction_claim.fused)
+            .map(|(fused_claim, interaction_claim)| {
+                FusedComponent::new(
+                    tree_span_provider,
+                    FusedEval::new(fused_claim, interaction_elements.node_elements.clone()),
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect();
 
         Self {
             add,
@@ -523,6 +899,15 @@ impl LuminairComponents {
             range_check_lookup,
             inputs,
             contiguous,
+            select,
+            gather,
+            gather_lookup,
+            im2col,
+            matmul,
+            norm,
+            mul_const,
+            add_const,
+            fused,
         }
     }
 
@@ -530,70 +915,106 @@ impl LuminairComponents {
     pub fn provers(&self) -> Vec<&dyn ComponentProver<SimdBackend>> {
         let mut components: Vec<&dyn ComponentProver<SimdBackend>> = vec![];
 
-        if let Some(ref component) = self.add {
+        for component in &self.add {
             components.push(component);
         }
 
-        if let Some(ref component) = self.mul {
+        for component in &self.mul {
             components.push(component);
         }
 
-        if let Some(ref component) = self.recip {
+        for component in &self.recip {
             components.push(component);
         }
 
-        if let Some(ref component) = self.sin {
+        for component in &self.sin {
             components.push(component);
         }
 
-        if let Some(ref component) = self.sin_lookup {
+        for component in &self.sin_lookup {
             components.push(component);
         }
 
-        if let Some(ref component) = self.sum_reduce {
+        for component in &self.sum_reduce {
             components.push(component);
         }
 
-        if let Some(ref component) = self.max_reduce {
+        for component in &self.max_reduce {
             components.push(component);
         }
 
-        if let Some(ref component) = self.sqrt {
+        for component in &self.sqrt {
             components.push(component);
         }
 
-        if let Some(ref component) = self.rem {
+        for component in &self.rem {
             components.push(component);
         }
-        if let Some(ref component) = self.exp2 {
+        for component in &self.exp2 {
+            components.push(component);
+        }
+
+        for component in &self.exp2_lookup {
+            components.push(component);
+        }
+
+        for component in &self.log2 {
+            components.push(component);
+        }
+
+        for component in &self.log2_lookup {
+            components.push(component);
+        }
+
+        for component in &self.less_than {
+            components.push(component);
+        }
+
+        for component in &self.range_check_lookup {
+            components.push(component);
+        }
+
+        for component in &self.inputs {
+            components.push(component);
+        }
+
+        for component in &self.contiguous {
+            components.push(component);
+        }
+
+        for component in &self.select {
+            components.push(component);
+        }
+
+        for component in &self.gather {
             components.push(component);
         }
 
-        if let Some(ref component) = self.exp2_lookup {
+        for component in &self.gather_lookup {
             components.push(component);
         }
 
-        if let Some(ref component) = self.log2 {
+        for component in &self.im2col {
             components.push(component);
         }
 
-        if let Some(ref component) = self.log2_lookup {
+        for component in &self.matmul {
             components.push(component);
         }
 
-        if let Some(ref component) = self.less_than {
+        for component in &self.norm {
             components.push(component);
         }
 
-        if let Some(ref component) = self.range_check_lookup {
+        for component in &self.mul_const {
             components.push(component);
         }
 
-        if let Some(ref component) = self.inputs {
+        for component in &self.add_const {
             components.push(component);
         }
 
-        if let Some(ref component) = self.contiguous {
+        for component in &self.fused {
             components.push(component);
         }
 
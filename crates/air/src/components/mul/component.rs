@@ -5,7 +5,9 @@ use crate::{
 use num_traits::One;
 use numerair::eval::EvalFixedPoint;
 use stwo::core::fields::m31::M31;
-use stwo_constraint_framework::{EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry};
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
+};
 
 pub type MulComponent = FrameworkComponent<MulEval>;
 
@@ -38,19 +40,17 @@ impl FrameworkEval for MulEval {
 
     /// Evaluates the multiplication constraints and relations
     fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
-        // IDs
-        let node_id = eval.next_trace_mask(); // ID of the node in the computational graph.
-        let lhs_id = eval.next_trace_mask(); // ID of first input tensor.
-        let rhs_id = eval.next_trace_mask(); // ID of second input tensor.
-        let idx = eval.next_trace_mask(); // Index in the flattened tensor.
+        // IDs, along with their value on the next row for transition constraints
+        // ID of the node in the computational graph.
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of first input tensor.
+        let [lhs_id, next_lhs_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of second input tensor.
+        let [rhs_id, next_rhs_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Index in the flattened tensor.
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
         let is_last_idx = eval.next_trace_mask(); // Flag if this is the last index for this operation.
 
-        // Next IDs for transition constraints
-        let next_node_id = eval.next_trace_mask();
-        let next_lhs_id = eval.next_trace_mask();
-        let next_rhs_id = eval.next_trace_mask();
-        let next_idx = eval.next_trace_mask();
-
         // Values for consistency constraints
         let lhs_val = eval.next_trace_mask(); // Value from first tensor at index.
         let rhs_val = eval.next_trace_mask(); // Value from second tensor at index.
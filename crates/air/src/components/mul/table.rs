@@ -8,11 +8,14 @@ use stwo::{
     },
 };
 
-use crate::components::TraceColumn;
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct MulTraceTable {
-    pub table: Vec<MulTraceTableRow>,
+    pub table: PackedRows<MulTraceTableRow>,
 }
 
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
@@ -22,10 +25,6 @@ pub struct MulTraceTableRow {
     pub rhs_id: M31,
     pub idx: M31,
     pub is_last_idx: M31,
-    pub next_node_id: M31,
-    pub next_lhs_id: M31,
-    pub next_rhs_id: M31,
-    pub next_idx: M31,
     pub lhs: M31,
     pub rhs: M31,
     pub out: M31,
@@ -43,10 +42,6 @@ impl MulTraceTableRow {
             rhs_id: M31::zero(),
             idx: M31::zero(),
             is_last_idx: M31::one(),
-            next_node_id: M31::zero(),
-            next_lhs_id: M31::zero(),
-            next_rhs_id: M31::zero(),
-            next_idx: M31::zero(),
             lhs: M31::zero(),
             rhs: M31::zero(),
             out: M31::zero(),
@@ -58,6 +53,12 @@ impl MulTraceTableRow {
     }
 }
 
+impl TransitionRow for MulTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct PackedMulTraceTableRow {
     pub node_id: PackedM31,
@@ -65,10 +66,6 @@ pub struct PackedMulTraceTableRow {
     pub rhs_id: PackedM31,
     pub idx: PackedM31,
     pub is_last_idx: PackedM31,
-    pub next_node_id: PackedM31,
-    pub next_lhs_id: PackedM31,
-    pub next_rhs_id: PackedM31,
-    pub next_idx: PackedM31,
     pub lhs: PackedM31,
     pub rhs: PackedM31,
     pub out: PackedM31,
@@ -88,10 +85,6 @@ impl Pack for MulTraceTableRow {
             rhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs_id)),
             idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
             is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
-            next_node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_node_id)),
-            next_lhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_lhs_id)),
-            next_rhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_rhs_id)),
-            next_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_idx)),
             lhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lhs)),
             rhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs)),
             out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
@@ -113,10 +106,6 @@ impl Unpack for PackedMulTraceTableRow {
             rhs_id,
             idx,
             is_last_idx,
-            next_node_id,
-            next_lhs_id,
-            next_rhs_id,
-            next_idx,
             lhs,
             rhs,
             out,
@@ -130,10 +119,6 @@ impl Unpack for PackedMulTraceTableRow {
             self.rhs_id.to_array(),
             self.idx.to_array(),
             self.is_last_idx.to_array(),
-            self.next_node_id.to_array(),
-            self.next_lhs_id.to_array(),
-            self.next_rhs_id.to_array(),
-            self.next_idx.to_array(),
             self.lhs.to_array(),
             self.rhs.to_array(),
             self.out.to_array(),
@@ -149,10 +134,6 @@ impl Unpack for PackedMulTraceTableRow {
             rhs_id: rhs_id[i],
             idx: idx[i],
             is_last_idx: is_last_idx[i],
-            next_node_id: next_node_id[i],
-            next_lhs_id: next_lhs_id[i],
-            next_rhs_id: next_rhs_id[i],
-            next_idx: next_idx[i],
             lhs: lhs[i],
             rhs: rhs[i],
             out: out[i],
@@ -181,10 +162,6 @@ pub enum MulColumn {
     RhsId,
     Idx,
     IsLastIdx,
-    NextNodeId,
-    NextLhsId,
-    NextRhsId,
-    NextIdx,
     Lhs,
     Rhs,
     Out,
@@ -202,23 +179,19 @@ impl MulColumn {
             Self::RhsId => 2,
             Self::Idx => 3,
             Self::IsLastIdx => 4,
-            Self::NextNodeId => 5,
-            Self::NextLhsId => 6,
-            Self::NextRhsId => 7,
-            Self::NextIdx => 8,
-            Self::Lhs => 9,
-            Self::Rhs => 10,
-            Self::Out => 11,
-            Self::Rem => 12,
-            Self::LhsMult => 13,
-            Self::RhsMult => 14,
-            Self::OutMult => 15,
+            Self::Lhs => 5,
+            Self::Rhs => 6,
+            Self::Out => 7,
+            Self::Rem => 8,
+            Self::LhsMult => 9,
+            Self::RhsMult => 10,
+            Self::OutMult => 11,
         }
     }
 }
 
 impl TraceColumn for MulColumn {
     fn count() -> (usize, usize) {
-        (16, 3)
+        (12, 3)
     }
 }
@@ -0,0 +1,120 @@
+use crate::{
+    components::{MulConstClaim, NodeElements},
+    DEFAULT_FP_SCALE,
+};
+use num_traits::One;
+use numerair::eval::EvalFixedPoint;
+use stwo::core::fields::m31::M31;
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
+};
+
+pub type MulConstComponent = FrameworkComponent<MulConstEval>;
+
+/// Evaluation structure for multiplications by a constant
+pub struct MulConstEval {
+    log_size: u32,
+    constant: M31,
+    node_elements: NodeElements,
+}
+
+impl MulConstEval {
+    /// Creates a new MulConstEval with the given claim and node elements
+    pub fn new(claim: &MulConstClaim, node_elements: NodeElements) -> Self {
+        Self {
+            log_size: claim.log_size,
+            constant: claim.constant,
+            node_elements,
+        }
+    }
+}
+
+impl FrameworkEval for MulConstEval {
+    /// Returns the log size of the evaluation
+    fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Returns the maximum constraint log degree bound
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.log_size + 1
+    }
+
+    /// Evaluates the multiplication by the claimed constant
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        // IDs, along with their value on the next row for transition constraints
+        // ID of the node in the computational graph.
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the input tensor.
+        let [input_id, next_input_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Index in the flattened tensor.
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let is_last_idx = eval.next_trace_mask(); // Flag if this is the last index for this operation.
+
+        // Values for consistency constraints
+        let input_val = eval.next_trace_mask(); // Value from the tensor at index.
+        let out_val = eval.next_trace_mask(); // Value in output tensor at index.
+        let rem_val = eval.next_trace_mask(); // Rem value in result tensor at index.
+
+        // Multiplicities for interaction constraints
+        let input_mult = eval.next_trace_mask();
+        let out_mult = eval.next_trace_mask();
+
+        let constant = E::F::from(self.constant);
+        let scale_factor = E::F::from(M31::from_u32_unchecked(1 << DEFAULT_FP_SCALE));
+
+        // ┌─────────────────────────────┐
+        // │   Consistency Constraints   │
+        // └─────────────────────────────┘
+
+        // The is_last_idx flag is either 0 or 1.
+        eval.add_constraint(is_last_idx.clone() * (is_last_idx.clone() - E::F::one()));
+
+        // Evaluates fixed point multiplication by the constant of the claim.
+        eval.eval_fixed_mul(
+            input_val.clone(),
+            constant,
+            scale_factor,
+            out_val.clone(),
+            rem_val,
+        );
+
+        // ┌────────────────────────────┐
+        // │   Transition Constraints   │
+        // └────────────────────────────┘
+
+        // If this is not the last index for this operation, then:
+        // 1. The next row should be for the same operation on the same tensor.
+        // 2. The index should increment by 1.
+        let not_last = E::F::one() - is_last_idx;
+
+        // Same node ID
+        eval.add_constraint(not_last.clone() * (next_node_id - node_id.clone()));
+
+        // Same tensor ID
+        eval.add_constraint(not_last.clone() * (next_input_id - input_id.clone()));
+
+        // Index increment by 1
+        eval.add_constraint(not_last * (next_idx - idx - E::F::one()));
+
+        // ┌─────────────────────────────┐
+        // │   Interaction Constraints   │
+        // └─────────────────────────────┘
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            input_mult.into(),
+            &[input_val, input_id],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            out_mult.into(),
+            &[out_val, node_id],
+        ));
+
+        eval.finalize_logup();
+
+        eval
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{
+    components::{lookups::range_check::RangeCheckLookupElements, NodeElements, NormClaim},
+    DEFAULT_FP_SCALE,
+};
+use num_traits::One;
+use numerair::eval::EvalFixedPoint;
+use stwo::core::fields::{m31::M31, FieldExpOps};
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
+};
+
+use super::table::NormParams;
+
+pub type NormComponent = FrameworkComponent<NormEval>;
+
+/// Evaluation structure for fused normalization (LayerNorm / RMSNorm) operations with range
+/// checking of the remainders
+pub struct NormEval {
+    log_size: u32,
+    range_check_log_size: u32,
+    params: NormParams,
+    node_elements: NodeElements,
+    range_check_elements: RangeCheckLookupElements,
+}
+
+impl NormEval {
+    /// Creates a new NormEval with the given claim, node elements, range check elements, and range check log size
+    pub fn new(
+        claim: &NormClaim,
+        node_elements: NodeElements,
+        range_check_elements: RangeCheckLookupElements,
+        range_check_log_size: u32,
+    ) -> Self {
+        Self {
+            log_size: claim.log_size,
+            range_check_log_size,
+            params: claim.params,
+            node_elements,
+            range_check_elements,
+        }
+    }
+}
+
+impl FrameworkEval for NormEval {
+    /// Returns the log size of the evaluation
+    fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Returns the maximum constraint log degree bound
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        std::cmp::max(self.log_size, self.range_check_log_size) + 1
+    }
+
+    /// Evaluates the normalization constraints and relations
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        // IDs, along with their value on the next row for transition constraints
+        // ID of the node in the computational graph.
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the input tensor.
+        let [input_id, next_input_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the affine weight tensor.
+        let [weight_id, next_weight_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the affine bias tensor.
+        let [bias_id, next_bias_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Index in the flattened tensor.
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let is_last_idx = eval.next_trace_mask(); // Flag if this is the last index for this operation.
+
+        // Values for consistency constraints, along with the value on the next row of those
+        // chained along a normalized row
+        // Flag if this is the last element of the normalized row.
+        let is_last_step = eval.next_trace_mask();
+        // Position in the normalized row.
+        let [step, next_step] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Scale, zero on padding rows.
+        let [scale, next_scale] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Value from the tensor at index.
+        let [input_val, next_input_val] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Running sum of the row, up to this value.
+        let [acc_val, next_acc_val] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Sum of the row.
+        let [sum_val, next_sum_val] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Mean of the row.
+        let [mean_val, next_mean_val] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let mean_rem = eval.next_trace_mask();
+        let centered_val = eval.next_trace_mask(); // Input minus the mean.
+
+        // Square of the centered value.
+        let [sq_val, next_sq_val] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let sq_rem = eval.next_trace_mask();
+        // Running sum of squares of the row, up to this value.
+        let [sq_acc_val, next_sq_acc_val] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Sum of squares of the row.
+        let [sq_sum_val, next_sq_sum_val] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let var_val = eval.next_trace_mask(); // Variance (mean square for RMSNorm) of the row.
+        let var_rem = eval.next_trace_mask();
+        let std_val = eval.next_trace_mask(); // Square root of variance + epsilon.
+        let std_rem = eval.next_trace_mask();
+        // Reciprocal of the standard deviation.
+        let [rstd_val, next_rstd_val] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let rstd_rem = eval.next_trace_mask();
+        let normed_val = eval.next_trace_mask(); // Normalized value.
+        let normed_rem = eval.next_trace_mask();
+        let weight_val = eval.next_trace_mask(); // Affine weight at index (one if absent).
+        let weighted_val = eval.next_trace_mask(); // Normalized value scaled by the weight.
+        let weighted_rem = eval.next_trace_mask();
+        let bias_val = eval.next_trace_mask(); // Affine bias at index (zero if absent).
+        let out_val = eval.next_trace_mask(); // Value in output tensor at index.
+
+        // Multiplicities for interaction constraints
+        let input_mult = eval.next_trace_mask();
+        let weight_mult = eval.next_trace_mask();
+        let bias_mult = eval.next_trace_mask();
+        let out_mult = eval.next_trace_mask();
+
+        // 4-limb decomposition of each remainder, in the order they are declared above
+        let rem_limbs: [[E::F; 4]; 7] =
+            std::array::from_fn(|_| std::array::from_fn(|_| eval.next_trace_mask()));
+        let range_check_mult = eval.next_trace_mask();
+        let rems = [
+            mean_rem.clone(),
+            sq_rem.clone(),
+            var_rem.clone(),
+            std_rem.clone(),
+            rstd_rem.clone(),
+            normed_rem.clone(),
+            weighted_rem.clone(),
+        ];
+
+        // Parameters of the claim are given relative to the scale column, so that they vanish
+        // along with every other value on padding rows.
+        let scale_factor = M31::from_u32_unchecked(1 << DEFAULT_FP_SCALE);
+        let inv_scale_factor = scale_factor.inverse();
+        let relative = |value: M31| scale.clone() * E::F::from(value * inv_scale_factor);
+        let mean_coef = relative(self.params.mean_coef());
+        let inv_d = relative(self.params.inv_d());
+        let eps = relative(self.params.eps);
+        let last_step = relative(M31::from(self.params.row_size) - M31::one());
+
+        // ┌─────────────────────────────┐
+        // │   Consistency Constraints   │
+        // └─────────────────────────────┘
+
+        // The is_last_idx and is_last_step flags are either 0 or 1.
+        eval.add_constraint(is_last_idx.clone() * (is_last_idx.clone() - E::F::one()));
+        eval.add_constraint(is_last_step.clone() * (is_last_step.clone() - E::F::one()));
+
+        // The scale is either the fixed-point scale factor or zero on padding rows, which
+        // take no part in the relations.
+        let is_padding = scale.clone() - E::F::from(scale_factor);
+        eval.add_constraint(scale.clone() * is_padding.clone());
+        for mult in [&input_mult, &weight_mult, &bias_mult, &out_mult] {
+            eval.add_constraint(mult.clone() * is_padding.clone());
+        }
+
+        // Each limb is looked up once on the rows of a normalization, and never on padding rows.
+        eval.add_constraint(range_check_mult.clone() - relative(M31::one()));
+
+        // Limb decomposition constraint: rem = limb3*2^24 + limb2*2^16 + limb1*2^8 + limb0
+        let two_pow_8 = E::F::from(M31::from_u32_unchecked(1u32 << 8));
+        let two_pow_16 = E::F::from(M31::from_u32_unchecked(1u32 << 16));
+        let two_pow_24 = E::F::from(M31::from_u32_unchecked(1u32 << 24));
+        for (rem, [limb0, limb1, limb2, limb3]) in rems.into_iter().zip(rem_limbs.clone()) {
+            let recomposed_rem = limb3 * two_pow_24.clone()
+                + limb2 * two_pow_16.clone()
+                + limb1 * two_pow_8.clone()
+                + limb0;
+            eval.add_constraint(rem - recomposed_rem);
+        }
+
+        // Operations, and shards, end with a normalized row, which holds row_size values.
+        eval.add_constraint(is_last_idx.clone() * (E::F::one() - is_last_step.clone()));
+        eval.add_constraint(is_last_step.clone() * (step - last_step));
+
+        // The row sum accumulates the input values.
+        eval.add_constraint((sum_val.clone() - acc_val.clone()) * is_last_step.clone());
+
+        // mean = sum * mean_coef
+        eval.eval_fixed_mul(
+            sum_val.clone(),
+            mean_coef,
+            scale.clone(),
+            mean_val.clone(),
+            mean_rem,
+        );
+
+        // centered = input - mean
+        eval.add_constraint(centered_val.clone() - (input_val.clone() - mean_val.clone()));
+
+        // The row sum of squares accumulates centered * centered.
+        eval.eval_fixed_mul(
+            centered_val.clone(),
+            centered_val.clone(),
+            scale.clone(),
+            sq_val,
+            sq_rem,
+        );
+        eval.add_constraint((sq_sum_val.clone() - sq_acc_val.clone()) * is_last_step.clone());
+
+        // var = sq_sum * inv_d
+        eval.eval_fixed_mul(
+            sq_sum_val.clone(),
+            inv_d,
+            scale.clone(),
+            var_val.clone(),
+            var_rem,
+        );
+
+        // rstd = 1 / sqrt(var + eps)
+        eval.eval_fixed_sqrt(var_val + eps, std_val.clone(), std_rem, scale.clone());
+        eval.eval_fixed_recip(std_val, scale.clone(), rstd_val.clone(), rstd_rem);
+
+        // normed = centered * rstd
+        eval.eval_fixed_mul(
+            centered_val,
+            rstd_val.clone(),
+            scale.clone(),
+            normed_val.clone(),
+            normed_rem,
+        );
+
+        // out = normed * weight + bias
+        eval.eval_fixed_mul(
+            normed_val,
+            weight_val.clone(),
+            scale.clone(),
+            weighted_val.clone(),
+            weighted_rem,
+        );
+        eval.add_constraint(out_val.clone() - (weighted_val + bias_val.clone()));
+
+        // ┌────────────────────────────┐
+        // │   Transition Constraints   │
+        // └────────────────────────────┘
+
+        // If this is not the last element of the normalized row, then the next row continues
+        // it: the running sums accumulate its value, the step increments by 1 and the row
+        // statistics stay the same.
+        let not_last_step = E::F::one() - is_last_step.clone();
+        eval.add_constraint(
+            not_last_step.clone() * (next_acc_val.clone() - acc_val - next_input_val.clone()),
+        );
+        eval.add_constraint(
+            not_last_step.clone() * (next_sq_acc_val.clone() - sq_acc_val - next_sq_val.clone()),
+        );
+        eval.add_constraint(not_last_step.clone() * (next_step.clone() - step - E::F::one()));
+        eval.add_constraint(not_last_step.clone() * (next_scale - scale));
+        eval.add_constraint(not_last_step.clone() * (next_sum_val - sum_val));
+        eval.add_constraint(not_last_step.clone() * (next_sq_sum_val - sq_sum_val));
+        eval.add_constraint(not_last_step.clone() * (next_mean_val - mean_val));
+        eval.add_constraint(not_last_step * (next_rstd_val - rstd_val));
+
+        // Otherwise, the next row starts a new normalized row, and the running sums restart
+        // from its value.
+        eval.add_constraint(is_last_step.clone() * (next_acc_val - next_input_val));
+        eval.add_constraint(is_last_step.clone() * (next_sq_acc_val - next_sq_val));
+        eval.add_constraint(is_last_step * next_step);
+
+        // If this is not the last index for this operation, then:
+        // 1. The next row should be for the same operation on the same tensors.
+        // 2. The index should increment by 1.
+        let not_last = E::F::one() - is_last_idx;
+
+        // Same node ID
+        eval.add_constraint(not_last.clone() * (next_node_id - node_id.clone()));
+
+        // Same tensor IDs
+        eval.add_constraint(not_last.clone() * (next_input_id - input_id.clone()));
+        eval.add_constraint(not_last.clone() * (next_weight_id - weight_id.clone()));
+        eval.add_constraint(not_last.clone() * (next_bias_id - bias_id.clone()));
+
+        // Index increment by 1
+        eval.add_constraint(not_last * (next_idx - idx - E::F::one()));
+
+        // ┌─────────────────────────────┐
+        // │   Interaction Constraints   │
+        // └─────────────────────────────┘
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            input_mult.into(),
+            &[input_val, input_id],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            weight_mult.into(),
+            &[weight_val, weight_id],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            bias_mult.into(),
+            &[bias_val, bias_id],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            out_mult.into(),
+            &[out_val, node_id],
+        ));
+
+        // Range check on each limb of the remainders
+        for limb in rem_limbs.into_iter().flatten() {
+            eval.add_to_relation(RelationEntry::new(
+                &self.range_check_elements,
+                range_check_mult.clone().into(),
+                &[limb],
+            ));
+        }
+
+        eval.finalize_logup();
+
+        eval
+    }
+}
@@ -0,0 +1,787 @@
+use num_traits::{One, Zero};
+use numerair::Fixed;
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::{channel::Channel, fields::m31::M31},
+    prover::backend::simd::{
+        conversion::{Pack, Unpack},
+        m31::{PackedM31, N_LANES},
+    },
+};
+
+use super::witness::N_TRACE_COLUMNS;
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+    DEFAULT_FP_SCALE,
+};
+
+/// Parameters of the normalizations proven by a component instance
+///
+/// They are public: the claim of the instance holds them, and the constants of its constraints
+/// are derived from them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NormParams {
+    /// Number of values of each normalized row
+    pub row_size: u32,
+    /// Whether the mean is subtracted (LayerNorm) or not (RMSNorm)
+    pub center: bool,
+    /// Fixed-point value of the epsilon added to the variance
+    pub eps: M31,
+}
+
+impl NormParams {
+    /// Fixed-point value of `1 / row_size`, which gives the variance from the sum of squares
+    pub fn inv_d(&self) -> M31 {
+        Fixed::<DEFAULT_FP_SCALE>::from_f64(1.0 / self.row_size as f64).to_m31()
+    }
+
+    /// Fixed-point coefficient giving the mean subtracted from each value from the row sum
+    pub fn mean_coef(&self) -> M31 {
+        if self.center {
+            self.inv_d()
+        } else {
+            M31::zero()
+        }
+    }
+
+    /// Mixes the parameters into the given channel
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.row_size.into());
+        channel.mix_u64(self.center.into());
+        channel.mix_u64(self.eps.0.into());
+    }
+}
+
+/// Rows of the normalizations sharing the same parameters, proven by their own component
+/// instances
+///
+/// Each normalized row is traced as `row_size` consecutive rows.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct NormTraceTable {
+    pub table: PackedRows<NormTraceTableRow>,
+    pub params: NormParams,
+}
+
+/// Normalizations of a graph, with one table per set of parameters
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct NormTraceTables {
+    pub tables: Vec<NormTraceTable>,
+}
+
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct NormTraceTableRow {
+    pub node_id: M31,
+    pub input_id: M31,
+    pub weight_id: M31,
+    pub bias_id: M31,
+    pub idx: M31,
+    pub is_last_idx: M31,
+    pub is_last_step: M31,
+    pub step: M31,
+    pub scale: M31,
+    pub input: M31,
+    pub acc: M31,
+    pub sum: M31,
+    pub mean: M31,
+    pub mean_rem: M31,
+    pub centered: M31,
+    pub sq: M31,
+    pub sq_rem: M31,
+    pub sq_acc: M31,
+    pub sq_sum: M31,
+    pub var: M31,
+    pub var_rem: M31,
+    pub std: M31,
+    pub std_rem: M31,
+    pub rstd: M31,
+    pub rstd_rem: M31,
+    pub normed: M31,
+    pub normed_rem: M31,
+    pub weight: M31,
+    pub weighted: M31,
+    pub weighted_rem: M31,
+    pub bias: M31,
+    pub out: M31,
+    pub input_mult: M31,
+    pub weight_mult: M31,
+    pub bias_mult: M31,
+    pub out_mult: M31,
+    pub mean_rem_limb0: M31,
+    pub mean_rem_limb1: M31,
+    pub mean_rem_limb2: M31,
+    pub mean_rem_limb3: M31,
+    pub sq_rem_limb0: M31,
+    pub sq_rem_limb1: M31,
+    pub sq_rem_limb2: M31,
+    pub sq_rem_limb3: M31,
+    pub var_rem_limb0: M31,
+    pub var_rem_limb1: M31,
+    pub var_rem_limb2: M31,
+    pub var_rem_limb3: M31,
+    pub std_rem_limb0: M31,
+    pub std_rem_limb1: M31,
+    pub std_rem_limb2: M31,
+    pub std_rem_limb3: M31,
+    pub rstd_rem_limb0: M31,
+    pub rstd_rem_limb1: M31,
+    pub rstd_rem_limb2: M31,
+    pub rstd_rem_limb3: M31,
+    pub normed_rem_limb0: M31,
+    pub normed_rem_limb1: M31,
+    pub normed_rem_limb2: M31,
+    pub normed_rem_limb3: M31,
+    pub weighted_rem_limb0: M31,
+    pub weighted_rem_limb1: M31,
+    pub weighted_rem_limb2: M31,
+    pub weighted_rem_limb3: M31,
+    pub range_check_mult: M31,
+}
+
+impl NormTraceTableRow {
+    pub(crate) fn padding() -> Self {
+        Self {
+            node_id: M31::zero(),
+            input_id: M31::zero(),
+            weight_id: M31::zero(),
+            bias_id: M31::zero(),
+            idx: M31::zero(),
+            is_last_idx: M31::one(),
+            is_last_step: M31::one(),
+            step: M31::zero(),
+            scale: M31::zero(),
+            input: M31::zero(),
+            acc: M31::zero(),
+            sum: M31::zero(),
+            mean: M31::zero(),
+            mean_rem: M31::zero(),
+            centered: M31::zero(),
+            sq: M31::zero(),
+            sq_rem: M31::zero(),
+            sq_acc: M31::zero(),
+            sq_sum: M31::zero(),
+            var: M31::zero(),
+            var_rem: M31::zero(),
+            std: M31::zero(),
+            std_rem: M31::zero(),
+            rstd: M31::zero(),
+            rstd_rem: M31::zero(),
+            normed: M31::zero(),
+            normed_rem: M31::zero(),
+            weight: M31::zero(),
+            weighted: M31::zero(),
+            weighted_rem: M31::zero(),
+            bias: M31::zero(),
+            out: M31::zero(),
+            input_mult: M31::zero(),
+            weight_mult: M31::zero(),
+            bias_mult: M31::zero(),
+            out_mult: M31::zero(),
+            mean_rem_limb0: M31::zero(),
+            mean_rem_limb1: M31::zero(),
+            mean_rem_limb2: M31::zero(),
+            mean_rem_limb3: M31::zero(),
+            sq_rem_limb0: M31::zero(),
+            sq_rem_limb1: M31::zero(),
+            sq_rem_limb2: M31::zero(),
+            sq_rem_limb3: M31::zero(),
+            var_rem_limb0: M31::zero(),
+            var_rem_limb1: M31::zero(),
+            var_rem_limb2: M31::zero(),
+            var_rem_limb3: M31::zero(),
+            std_rem_limb0: M31::zero(),
+            std_rem_limb1: M31::zero(),
+            std_rem_limb2: M31::zero(),
+            std_rem_limb3: M31::zero(),
+            rstd_rem_limb0: M31::zero(),
+            rstd_rem_limb1: M31::zero(),
+            rstd_rem_limb2: M31::zero(),
+            rstd_rem_limb3: M31::zero(),
+            normed_rem_limb0: M31::zero(),
+            normed_rem_limb1: M31::zero(),
+            normed_rem_limb2: M31::zero(),
+            normed_rem_limb3: M31::zero(),
+            weighted_rem_limb0: M31::zero(),
+            weighted_rem_limb1: M31::zero(),
+            weighted_rem_limb2: M31::zero(),
+            weighted_rem_limb3: M31::zero(),
+            range_check_mult: M31::zero(),
+        }
+    }
+}
+
+impl TransitionRow for NormTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct PackedNormTraceTableRow {
+    pub node_id: PackedM31,
+    pub input_id: PackedM31,
+    pub weight_id: PackedM31,
+    pub bias_id: PackedM31,
+    pub idx: PackedM31,
+    pub is_last_idx: PackedM31,
+    pub is_last_step: PackedM31,
+    pub step: PackedM31,
+    pub scale: PackedM31,
+    pub input: PackedM31,
+    pub acc: PackedM31,
+    pub sum: PackedM31,
+    pub mean: PackedM31,
+    pub mean_rem: PackedM31,
+    pub centered: PackedM31,
+    pub sq: PackedM31,
+    pub sq_rem: PackedM31,
+    pub sq_acc: PackedM31,
+    pub sq_sum: PackedM31,
+    pub var: PackedM31,
+    pub var_rem: PackedM31,
+    pub std: PackedM31,
+    pub std_rem: PackedM31,
+    pub rstd: PackedM31,
+    pub rstd_rem: PackedM31,
+    pub normed: PackedM31,
+    pub normed_rem: PackedM31,
+    pub weight: PackedM31,
+    pub weighted: PackedM31,
+    pub weighted_rem: PackedM31,
+    pub bias: PackedM31,
+    pub out: PackedM31,
+    pub input_mult: PackedM31,
+    pub weight_mult: PackedM31,
+    pub bias_mult: PackedM31,
+    pub out_mult: PackedM31,
+    pub mean_rem_limb0: PackedM31,
+    pub mean_rem_limb1: PackedM31,
+    pub mean_rem_limb2: PackedM31,
+    pub mean_rem_limb3: PackedM31,
+    pub sq_rem_limb0: PackedM31,
+    pub sq_rem_limb1: PackedM31,
+    pub sq_rem_limb2: PackedM31,
+    pub sq_rem_limb3: PackedM31,
+    pub var_rem_limb0: PackedM31,
+    pub var_rem_limb1: PackedM31,
+    pub var_rem_limb2: PackedM31,
+    pub var_rem_limb3: PackedM31,
+    pub std_rem_limb0: PackedM31,
+    pub std_rem_limb1: PackedM31,
+    pub std_rem_limb2: PackedM31,
+    pub std_rem_limb3: PackedM31,
+    pub rstd_rem_limb0: PackedM31,
+    pub rstd_rem_limb1: PackedM31,
+    pub rstd_rem_limb2: PackedM31,
+    pub rstd_rem_limb3: PackedM31,
+    pub normed_rem_limb0: PackedM31,
+    pub normed_rem_limb1: PackedM31,
+    pub normed_rem_limb2: PackedM31,
+    pub normed_rem_limb3: PackedM31,
+    pub weighted_rem_limb0: PackedM31,
+    pub weighted_rem_limb1: PackedM31,
+    pub weighted_rem_limb2: PackedM31,
+    pub weighted_rem_limb3: PackedM31,
+    pub range_check_mult: PackedM31,
+}
+
+impl Pack for NormTraceTableRow {
+    type SimdType = PackedNormTraceTableRow;
+
+    fn pack(inputs: [Self; N_LANES]) -> Self::SimdType {
+        PackedNormTraceTableRow {
+            node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].node_id)),
+            input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_id)),
+            weight_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].weight_id)),
+            bias_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].bias_id)),
+            idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
+            is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
+            is_last_step: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_step)),
+            step: PackedM31::from_array(std::array::from_fn(|i| inputs[i].step)),
+            scale: PackedM31::from_array(std::array::from_fn(|i| inputs[i].scale)),
+            input: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input)),
+            acc: PackedM31::from_array(std::array::from_fn(|i| inputs[i].acc)),
+            sum: PackedM31::from_array(std::array::from_fn(|i| inputs[i].sum)),
+            mean: PackedM31::from_array(std::array::from_fn(|i| inputs[i].mean)),
+            mean_rem: PackedM31::from_array(std::array::from_fn(|i| inputs[i].mean_rem)),
+            centered: PackedM31::from_array(std::array::from_fn(|i| inputs[i].centered)),
+            sq: PackedM31::from_array(std::array::from_fn(|i| inputs[i].sq)),
+            sq_rem: PackedM31::from_array(std::array::from_fn(|i| inputs[i].sq_rem)),
+            sq_acc: PackedM31::from_array(std::array::from_fn(|i| inputs[i].sq_acc)),
+            sq_sum: PackedM31::from_array(std::array::from_fn(|i| inputs[i].sq_sum)),
+            var: PackedM31::from_array(std::array::from_fn(|i| inputs[i].var)),
+            var_rem: PackedM31::from_array(std::array::from_fn(|i| inputs[i].var_rem)),
+            std: PackedM31::from_array(std::array::from_fn(|i| inputs[i].std)),
+            std_rem: PackedM31::from_array(std::array::from_fn(|i| inputs[i].std_rem)),
+            rstd: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rstd)),
+            rstd_rem: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rstd_rem)),
+            normed: PackedM31::from_array(std::array::from_fn(|i| inputs[i].normed)),
+            normed_rem: PackedM31::from_array(std::array::from_fn(|i| inputs[i].normed_rem)),
+            weight: PackedM31::from_array(std::array::from_fn(|i| inputs[i].weight)),
+            weighted: PackedM31::from_array(std::array::from_fn(|i| inputs[i].weighted)),
+            weighted_rem: PackedM31::from_array(std::array::from_fn(|i| inputs[i].weighted_rem)),
+            bias: PackedM31::from_array(std::array::from_fn(|i| inputs[i].bias)),
+            out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
+            input_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_mult)),
+            weight_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].weight_mult)),
+            bias_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].bias_mult)),
+            out_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out_mult)),
+            mean_rem_limb0: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].mean_rem_limb0
+            })),
+            mean_rem_limb1: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].mean_rem_limb1
+            })),
+            mean_rem_limb2: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].mean_rem_limb2
+            })),
+            mean_rem_limb3: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].mean_rem_limb3
+            })),
+            sq_rem_limb0: PackedM31::from_array(std::array::from_fn(|i| inputs[i].sq_rem_limb0)),
+            sq_rem_limb1: PackedM31::from_array(std::array::from_fn(|i| inputs[i].sq_rem_limb1)),
+            sq_rem_limb2: PackedM31::from_array(std::array::from_fn(|i| inputs[i].sq_rem_limb2)),
+            sq_rem_limb3: PackedM31::from_array(std::array::from_fn(|i| inputs[i].sq_rem_limb3)),
+            var_rem_limb0: PackedM31::from_array(std::array::from_fn(|i| inputs[i].var_rem_limb0)),
+            var_rem_limb1: PackedM31::from_array(std::array::from_fn(|i| inputs[i].var_rem_limb1)),
+            var_rem_limb2: PackedM31::from_array(std::array::from_fn(|i| inputs[i].var_rem_limb2)),
+            var_rem_limb3: PackedM31::from_array(std::array::from_fn(|i| inputs[i].var_rem_limb3)),
+            std_rem_limb0: PackedM31::from_array(std::array::from_fn(|i| inputs[i].std_rem_limb0)),
+            std_rem_limb1: PackedM31::from_array(std::array::from_fn(|i| inputs[i].std_rem_limb1)),
+            std_rem_limb2: PackedM31::from_array(std::array::from_fn(|i| inputs[i].std_rem_limb2)),
+            std_rem_limb3: PackedM31::from_array(std::array::from_fn(|i| inputs[i].std_rem_limb3)),
+            rstd_rem_limb0: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].rstd_rem_limb0
+            })),
+            rstd_rem_limb1: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].rstd_rem_limb1
+            })),
+            rstd_rem_limb2: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].rstd_rem_limb2
+            })),
+            rstd_rem_limb3: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].rstd_rem_limb3
+            })),
+            normed_rem_limb0: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].normed_rem_limb0
+            })),
+            normed_rem_limb1: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].normed_rem_limb1
+            })),
+            normed_rem_limb2: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].normed_rem_limb2
+            })),
+            normed_rem_limb3: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].normed_rem_limb3
+            })),
+            weighted_rem_limb0: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].weighted_rem_limb0
+            })),
+            weighted_rem_limb1: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].weighted_rem_limb1
+            })),
+            weighted_rem_limb2: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].weighted_rem_limb2
+            })),
+            weighted_rem_limb3: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].weighted_rem_limb3
+            })),
+            range_check_mult: PackedM31::from_array(std::array::from_fn(|i| {
+                inputs[i].range_check_mult
+            })),
+        }
+    }
+}
+
+impl Unpack for PackedNormTraceTableRow {
+    type CpuType = NormTraceTableRow;
+
+    fn unpack(self) -> [Self::CpuType; N_LANES] {
+        let (
+            node_id,
+            input_id,
+            weight_id,
+            bias_id,
+            idx,
+            is_last_idx,
+            is_last_step,
+            step,
+            scale,
+            input,
+            acc,
+            sum,
+            mean,
+            mean_rem,
+            centered,
+            sq,
+            sq_rem,
+            sq_acc,
+            sq_sum,
+            var,
+            var_rem,
+            std,
+            std_rem,
+            rstd,
+            rstd_rem,
+            normed,
+            normed_rem,
+            weight,
+            weighted,
+            weighted_rem,
+            bias,
+            out,
+            input_mult,
+            weight_mult,
+            bias_mult,
+            out_mult,
+            mean_rem_limb0,
+            mean_rem_limb1,
+            mean_rem_limb2,
+            mean_rem_limb3,
+            sq_rem_limb0,
+            sq_rem_limb1,
+            sq_rem_limb2,
+            sq_rem_limb3,
+            var_rem_limb0,
+            var_rem_limb1,
+            var_rem_limb2,
+            var_rem_limb3,
+            std_rem_limb0,
+            std_rem_limb1,
+            std_rem_limb2,
+            std_rem_limb3,
+            rstd_rem_limb0,
+            rstd_rem_limb1,
+            rstd_rem_limb2,
+            rstd_rem_limb3,
+            normed_rem_limb0,
+            normed_rem_limb1,
+            normed_rem_limb2,
+            normed_rem_limb3,
+            weighted_rem_limb0,
+            weighted_rem_limb1,
+            weighted_rem_limb2,
+            weighted_rem_limb3,
+            range_check_mult,
+        ) = (
+            self.node_id.to_array(),
+            self.input_id.to_array(),
+            self.weight_id.to_array(),
+            self.bias_id.to_array(),
+            self.idx.to_array(),
+            self.is_last_idx.to_array(),
+            self.is_last_step.to_array(),
+            self.step.to_array(),
+            self.scale.to_array(),
+            self.input.to_array(),
+            self.acc.to_array(),
+            self.sum.to_array(),
+            self.mean.to_array(),
+            self.mean_rem.to_array(),
+            self.centered.to_array(),
+            self.sq.to_array(),
+            self.sq_rem.to_array(),
+            self.sq_acc.to_array(),
+            self.sq_sum.to_array(),
+            self.var.to_array(),
+            self.var_rem.to_array(),
+            self.std.to_array(),
+            self.std_rem.to_array(),
+            self.rstd.to_array(),
+            self.rstd_rem.to_array(),
+            self.normed.to_array(),
+            self.normed_rem.to_array(),
+            self.weight.to_array(),
+            self.weighted.to_array(),
+            self.weighted_rem.to_array(),
+            self.bias.to_array(),
+            self.out.to_array(),
+            self.input_mult.to_array(),
+            self.weight_mult.to_array(),
+            self.bias_mult.to_array(),
+            self.out_mult.to_array(),
+            self.mean_rem_limb0.to_array(),
+            self.mean_rem_limb1.to_array(),
+            self.mean_rem_limb2.to_array(),
+            self.mean_rem_limb3.to_array(),
+            self.sq_rem_limb0.to_array(),
+            self.sq_rem_limb1.to_array(),
+            self.sq_rem_limb2.to_array(),
+            self.sq_rem_limb3.to_array(),
+            self.var_rem_limb0.to_array(),
+            self.var_rem_limb1.to_array(),
+            self.var_rem_limb2.to_array(),
+            self.var_rem_limb3.to_array(),
+            self.std_rem_limb0.to_array(),
+            self.std_rem_limb1.to_array(),
+            self.std_rem_limb2.to_array(),
+            self.std_rem_limb3.to_array(),
+            self.rstd_rem_limb0.to_array(),
+            self.rstd_rem_limb1.to_array(),
+            self.rstd_rem_limb2.to_array(),
+            self.rstd_rem_limb3.to_array(),
+            self.normed_rem_limb0.to_array(),
+            self.normed_rem_limb1.to_array(),
+            self.normed_rem_limb2.to_array(),
+            self.normed_rem_limb3.to_array(),
+            self.weighted_rem_limb0.to_array(),
+            self.weighted_rem_limb1.to_array(),
+            self.weighted_rem_limb2.to_array(),
+            self.weighted_rem_limb3.to_array(),
+            self.range_check_mult.to_array(),
+        );
+
+        std::array::from_fn(|i| NormTraceTableRow {
+            node_id: node_id[i],
+            input_id: input_id[i],
+            weight_id: weight_id[i],
+            bias_id: bias_id[i],
+            idx: idx[i],
+            is_last_idx: is_last_idx[i],
+            is_last_step: is_last_step[i],
+            step: step[i],
+            scale: scale[i],
+            input: input[i],
+            acc: acc[i],
+            sum: sum[i],
+            mean: mean[i],
+            mean_rem: mean_rem[i],
+            centered: centered[i],
+            sq: sq[i],
+            sq_rem: sq_rem[i],
+            sq_acc: sq_acc[i],
+            sq_sum: sq_sum[i],
+            var: var[i],
+            var_rem: var_rem[i],
+            std: std[i],
+            std_rem: std_rem[i],
+            rstd: rstd[i],
+            rstd_rem: rstd_rem[i],
+            normed: normed[i],
+            normed_rem: normed_rem[i],
+            weight: weight[i],
+            weighted: weighted[i],
+            weighted_rem: weighted_rem[i],
+            bias: bias[i],
+            out: out[i],
+            input_mult: input_mult[i],
+            weight_mult: weight_mult[i],
+            bias_mult: bias_mult[i],
+            out_mult: out_mult[i],
+            mean_rem_limb0: mean_rem_limb0[i],
+            mean_rem_limb1: mean_rem_limb1[i],
+            mean_rem_limb2: mean_rem_limb2[i],
+            mean_rem_limb3: mean_rem_limb3[i],
+            sq_rem_limb0: sq_rem_limb0[i],
+            sq_rem_limb1: sq_rem_limb1[i],
+            sq_rem_limb2: sq_rem_limb2[i],
+            sq_rem_limb3: sq_rem_limb3[i],
+            var_rem_limb0: var_rem_limb0[i],
+            var_rem_limb1: var_rem_limb1[i],
+            var_rem_limb2: var_rem_limb2[i],
+            var_rem_limb3: var_rem_limb3[i],
+            std_rem_limb0: std_rem_limb0[i],
+            std_rem_limb1: std_rem_limb1[i],
+            std_rem_limb2: std_rem_limb2[i],
+            std_rem_limb3: std_rem_limb3[i],
+            rstd_rem_limb0: rstd_rem_limb0[i],
+            rstd_rem_limb1: rstd_rem_limb1[i],
+            rstd_rem_limb2: rstd_rem_limb2[i],
+            rstd_rem_limb3: rstd_rem_limb3[i],
+            normed_rem_limb0: normed_rem_limb0[i],
+            normed_rem_limb1: normed_rem_limb1[i],
+            normed_rem_limb2: normed_rem_limb2[i],
+            normed_rem_limb3: normed_rem_limb3[i],
+            weighted_rem_limb0: weighted_rem_limb0[i],
+            weighted_rem_limb1: weighted_rem_limb1[i],
+            weighted_rem_limb2: weighted_rem_limb2[i],
+            weighted_rem_limb3: weighted_rem_limb3[i],
+            range_check_mult: range_check_mult[i],
+        })
+    }
+}
+
+impl NormTraceTable {
+    pub fn new(params: NormParams) -> Self {
+        Self {
+            table: PackedRows::new(),
+            params,
+        }
+    }
+
+    pub fn add_row(&mut self, row: NormTraceTableRow) {
+        self.table.push(row);
+    }
+}
+
+impl NormTraceTables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the table of the normalizations with `params`, adding it if there is none yet
+    pub fn table_mut(&mut self, params: NormParams) -> &mut NormTraceTable {
+        let index = match self.tables.iter().position(|table| table.params == params) {
+            Some(index) => index,
+            None => {
+                self.tables.push(NormTraceTable::new(params));
+                self.tables.len() - 1
+            }
+        };
+        &mut self.tables[index]
+    }
+
+    /// Appends the rows of `other` to the table of their parameters
+    pub fn append(&mut self, other: Self) {
+        for table in other.tables {
+            self.table_mut(table.params).table.append(table.table);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NormColumn {
+    NodeId,
+    InputId,
+    WeightId,
+    BiasId,
+    Idx,
+    IsLastIdx,
+    IsLastStep,
+    Step,
+    Scale,
+    Input,
+    Acc,
+    Sum,
+    Mean,
+    MeanRem,
+    Centered,
+    Sq,
+    SqRem,
+    SqAcc,
+    SqSum,
+    Var,
+    VarRem,
+    Std,
+    StdRem,
+    Rstd,
+    RstdRem,
+    Normed,
+    NormedRem,
+    Weight,
+    Weighted,
+    WeightedRem,
+    Bias,
+    Out,
+    InputMult,
+    WeightMult,
+    BiasMult,
+    OutMult,
+    MeanRemLimb0,
+    MeanRemLimb1,
+    MeanRemLimb2,
+    MeanRemLimb3,
+    SqRemLimb0,
+    SqRemLimb1,
+    SqRemLimb2,
+    SqRemLimb3,
+    VarRemLimb0,
+    VarRemLimb1,
+    VarRemLimb2,
+    VarRemLimb3,
+    StdRemLimb0,
+    StdRemLimb1,
+    StdRemLimb2,
+    StdRemLimb3,
+    RstdRemLimb0,
+    RstdRemLimb1,
+    RstdRemLimb2,
+    RstdRemLimb3,
+    NormedRemLimb0,
+    NormedRemLimb1,
+    NormedRemLimb2,
+    NormedRemLimb3,
+    WeightedRemLimb0,
+    WeightedRemLimb1,
+    WeightedRemLimb2,
+    WeightedRemLimb3,
+    RangeCheckMult,
+}
+
+impl NormColumn {
+    pub const fn index(self) -> usize {
+        match self {
+            Self::NodeId => 0,
+            Self::InputId => 1,
+            Self::WeightId => 2,
+            Self::BiasId => 3,
+            Self::Idx => 4,
+            Self::IsLastIdx => 5,
+            Self::IsLastStep => 6,
+            Self::Step => 7,
+            Self::Scale => 8,
+            Self::Input => 9,
+            Self::Acc => 10,
+            Self::Sum => 11,
+            Self::Mean => 12,
+            Self::MeanRem => 13,
+            Self::Centered => 14,
+            Self::Sq => 15,
+            Self::SqRem => 16,
+            Self::SqAcc => 17,
+            Self::SqSum => 18,
+            Self::Var => 19,
+            Self::VarRem => 20,
+            Self::Std => 21,
+            Self::StdRem => 22,
+            Self::Rstd => 23,
+            Self::RstdRem => 24,
+            Self::Normed => 25,
+            Self::NormedRem => 26,
+            Self::Weight => 27,
+            Self::Weighted => 28,
+            Self::WeightedRem => 29,
+            Self::Bias => 30,
+            Self::Out => 31,
+            Self::InputMult => 32,
+            Self::WeightMult => 33,
+            Self::BiasMult => 34,
+            Self::OutMult => 35,
+            Self::MeanRemLimb0 => 36,
+            Self::MeanRemLimb1 => 37,
+            Self::MeanRemLimb2 => 38,
+            Self::MeanRemLimb3 => 39,
+            Self::SqRemLimb0 => 40,
+            Self::SqRemLimb1 => 41,
+            Self::SqRemLimb2 => 42,
+            Self::SqRemLimb3 => 43,
+            Self::VarRemLimb0 => 44,
+            Self::VarRemLimb1 => 45,
+            Self::VarRemLimb2 => 46,
+            Self::VarRemLimb3 => 47,
+            Self::StdRemLimb0 => 48,
+            Self::StdRemLimb1 => 49,
+            Self::StdRemLimb2 => 50,
+            Self::StdRemLimb3 => 51,
+            Self::RstdRemLimb0 => 52,
+            Self::RstdRemLimb1 => 53,
+            Self::RstdRemLimb2 => 54,
+            Self::RstdRemLimb3 => 55,
+            Self::NormedRemLimb0 => 56,
+            Self::NormedRemLimb1 => 57,
+            Self::NormedRemLimb2 => 58,
+            Self::NormedRemLimb3 => 59,
+            Self::WeightedRemLimb0 => 60,
+            Self::WeightedRemLimb1 => 61,
+            Self::WeightedRemLimb2 => 62,
+            Self::WeightedRemLimb3 => 63,
+            Self::RangeCheckMult => 64,
+        }
+    }
+}
+
+impl TraceColumn for NormColumn {
+    fn count() -> (usize, usize) {
+        (N_TRACE_COLUMNS, 32)
+    }
+}
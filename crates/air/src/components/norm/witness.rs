@@ -0,0 +1,285 @@
+use crate::{
+    components::{
+        lookups::range_check::RangeCheckLookupElements, InteractionClaim, NodeElements, NormClaim,
+    },
+    utils::TreeBuilder,
+};
+use luminair_utils::TraceError;
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use stwo_air_utils::trace::component_trace::ComponentTrace;
+use stwo_air_utils_derive::{IterMut, ParIterMut, Uninitialized};
+use stwo::prover::backend::simd::{
+    m31::{PackedM31, LOG_N_LANES, N_LANES},
+    qm31::PackedQM31,
+    SimdBackend,
+};
+use stwo_constraint_framework::{LogupTraceGenerator, Relation};
+
+use super::table::{
+    NormColumn, NormTraceTable, NormTraceTableRow, PackedNormTraceTableRow,
+};
+
+pub(crate) const N_TRACE_COLUMNS: usize = 65;
+
+/// Number of 8-bit limbs the remainders are decomposed into, four per remainder
+const N_REM_LIMBS: usize = 28;
+
+pub struct ClaimGenerator {
+    pub inputs: NormTraceTable,
+}
+
+impl ClaimGenerator {
+    pub fn new(inputs: NormTraceTable) -> Self {
+        Self { inputs }
+    }
+
+    pub fn write_trace(
+        self,
+        tree_builder: &mut impl TreeBuilder<SimdBackend>,
+    ) -> Result<(NormClaim, InteractionClaimGenerator), TraceError> {
+        let n_rows = self.inputs.table.len();
+
+        if n_rows == 0 {
+            return Err(TraceError::EmptyTrace);
+        }
+
+        let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
+        let log_size = size.ilog2();
+        let params = self.inputs.params;
+
+        let packed_inputs = self
+            .inputs
+            .table
+            .into_packed_coset_order(NormTraceTableRow::padding());
+
+        let (trace, lookup_data) = write_trace_simd(packed_inputs);
+
+        tree_builder.extend_evals(trace.to_evals());
+
+        Ok((
+            NormClaim::new(log_size, params),
+            InteractionClaimGenerator {
+                log_size,
+                lookup_data,
+            },
+        ))
+    }
+}
+
+fn write_trace_simd(
+    inputs: Vec<PackedNormTraceTableRow>,
+) -> (ComponentTrace<N_TRACE_COLUMNS>, LookupData) {
+    let log_n_packed_rows = inputs.len().ilog2();
+    let log_size = log_n_packed_rows + LOG_N_LANES;
+
+    let (mut trace, mut lookup_data) = unsafe {
+        (
+            ComponentTrace::<N_TRACE_COLUMNS>::uninitialized(log_size),
+            LookupData::uninitialized(log_n_packed_rows),
+        )
+    };
+
+    (
+        trace.par_iter_mut(),
+        lookup_data.par_iter_mut(),
+        inputs.into_par_iter(),
+    )
+        .into_par_iter()
+        .for_each(|(mut row, lookup_data, input)| {
+            *row[NormColumn::NodeId.index()] = input.node_id;
+            *row[NormColumn::InputId.index()] = input.input_id;
+            *row[NormColumn::WeightId.index()] = input.weight_id;
+            *row[NormColumn::BiasId.index()] = input.bias_id;
+            *row[NormColumn::Idx.index()] = input.idx;
+            *row[NormColumn::IsLastIdx.index()] = input.is_last_idx;
+            *row[NormColumn::IsLastStep.index()] = input.is_last_step;
+            *row[NormColumn::Step.index()] = input.step;
+            *row[NormColumn::Scale.index()] = input.scale;
+            *row[NormColumn::Input.index()] = input.input;
+            *row[NormColumn::Acc.index()] = input.acc;
+            *row[NormColumn::Sum.index()] = input.sum;
+            *row[NormColumn::Mean.index()] = input.mean;
+            *row[NormColumn::MeanRem.index()] = input.mean_rem;
+            *row[NormColumn::Centered.index()] = input.centered;
+            *row[NormColumn::Sq.index()] = input.sq;
+            *row[NormColumn::SqRem.index()] = input.sq_rem;
+            *row[NormColumn::SqAcc.index()] = input.sq_acc;
+            *row[NormColumn::SqSum.index()] = input.sq_sum;
+            *row[NormColumn::Var.index()] = input.var;
+            *row[NormColumn::VarRem.index()] = input.var_rem;
+            *row[NormColumn::Std.index()] = input.std;
+            *row[NormColumn::StdRem.index()] = input.std_rem;
+            *row[NormColumn::Rstd.index()] = input.rstd;
+            *row[NormColumn::RstdRem.index()] = input.rstd_rem;
+            *row[NormColumn::Normed.index()] = input.normed;
+            *row[NormColumn::NormedRem.index()] = input.normed_rem;
+            *row[NormColumn::Weight.index()] = input.weight;
+            *row[NormColumn::Weighted.index()] = input.weighted;
+            *row[NormColumn::WeightedRem.index()] = input.weighted_rem;
+            *row[NormColumn::Bias.index()] = input.bias;
+            *row[NormColumn::Out.index()] = input.out;
+            *row[NormColumn::InputMult.index()] = input.input_mult;
+            *row[NormColumn::WeightMult.index()] = input.weight_mult;
+            *row[NormColumn::BiasMult.index()] = input.bias_mult;
+            *row[NormColumn::OutMult.index()] = input.out_mult;
+            *row[NormColumn::MeanRemLimb0.index()] = input.mean_rem_limb0;
+            *row[NormColumn::MeanRemLimb1.index()] = input.mean_rem_limb1;
+            *row[NormColumn::MeanRemLimb2.index()] = input.mean_rem_limb2;
+            *row[NormColumn::MeanRemLimb3.index()] = input.mean_rem_limb3;
+            *row[NormColumn::SqRemLimb0.index()] = input.sq_rem_limb0;
+            *row[NormColumn::SqRemLimb1.index()] = input.sq_rem_limb1;
+            *row[NormColumn::SqRemLimb2.index()] = input.sq_rem_limb2;
+            *row[NormColumn::SqRemLimb3.index()] = input.sq_rem_limb3;
+            *row[NormColumn::VarRemLimb0.index()] = input.var_rem_limb0;
+            *row[NormColumn::VarRemLimb1.index()] = input.var_rem_limb1;
+            *row[NormColumn::VarRemLimb2.index()] = input.var_rem_limb2;
+            *row[NormColumn::VarRemLimb3.index()] = input.var_rem_limb3;
+            *row[NormColumn::StdRemLimb0.index()] = input.std_rem_limb0;
+            *row[NormColumn::StdRemLimb1.index()] = input.std_rem_limb1;
+            *row[NormColumn::StdRemLimb2.index()] = input.std_rem_limb2;
+            *row[NormColumn::StdRemLimb3.index()] = input.std_rem_limb3;
+            *row[NormColumn::RstdRemLimb0.index()] = input.rstd_rem_limb0;
+            *row[NormColumn::RstdRemLimb1.index()] = input.rstd_rem_limb1;
+            *row[NormColumn::RstdRemLimb2.index()] = input.rstd_rem_limb2;
+            *row[NormColumn::RstdRemLimb3.index()] = input.rstd_rem_limb3;
+            *row[NormColumn::NormedRemLimb0.index()] = input.normed_rem_limb0;
+            *row[NormColumn::NormedRemLimb1.index()] = input.normed_rem_limb1;
+            *row[NormColumn::NormedRemLimb2.index()] = input.normed_rem_limb2;
+            *row[NormColumn::NormedRemLimb3.index()] = input.normed_rem_limb3;
+            *row[NormColumn::WeightedRemLimb0.index()] = input.weighted_rem_limb0;
+            *row[NormColumn::WeightedRemLimb1.index()] = input.weighted_rem_limb1;
+            *row[NormColumn::WeightedRemLimb2.index()] = input.weighted_rem_limb2;
+            *row[NormColumn::WeightedRemLimb3.index()] = input.weighted_rem_limb3;
+            *row[NormColumn::RangeCheckMult.index()] = input.range_check_mult;
+
+            *lookup_data.input = [input.input, input.input_id];
+            *lookup_data.input_mult = input.input_mult;
+            *lookup_data.weight = [input.weight, input.weight_id];
+            *lookup_data.weight_mult = input.weight_mult;
+            *lookup_data.bias = [input.bias, input.bias_id];
+            *lookup_data.bias_mult = input.bias_mult;
+            *lookup_data.out = [input.out, input.node_id];
+            *lookup_data.out_mult = input.out_mult;
+            *lookup_data.rem_limbs = [
+                input.mean_rem_limb0,
+                input.mean_rem_limb1,
+                input.mean_rem_limb2,
+                input.mean_rem_limb3,
+                input.sq_rem_limb0,
+                input.sq_rem_limb1,
+                input.sq_rem_limb2,
+                input.sq_rem_limb3,
+                input.var_rem_limb0,
+                input.var_rem_limb1,
+                input.var_rem_limb2,
+                input.var_rem_limb3,
+                input.std_rem_limb0,
+                input.std_rem_limb1,
+                input.std_rem_limb2,
+                input.std_rem_limb3,
+                input.rstd_rem_limb0,
+                input.rstd_rem_limb1,
+                input.rstd_rem_limb2,
+                input.rstd_rem_limb3,
+                input.normed_rem_limb0,
+                input.normed_rem_limb1,
+                input.normed_rem_limb2,
+                input.normed_rem_limb3,
+                input.weighted_rem_limb0,
+                input.weighted_rem_limb1,
+                input.weighted_rem_limb2,
+                input.weighted_rem_limb3,
+            ];
+            *lookup_data.range_check_mult = input.range_check_mult;
+        });
+
+    (trace, lookup_data)
+}
+
+#[derive(Uninitialized, IterMut, ParIterMut)]
+struct LookupData {
+    input: Vec<[PackedM31; 2]>,
+    input_mult: Vec<PackedM31>,
+    weight: Vec<[PackedM31; 2]>,
+    weight_mult: Vec<PackedM31>,
+    bias: Vec<[PackedM31; 2]>,
+    bias_mult: Vec<PackedM31>,
+    out: Vec<[PackedM31; 2]>,
+    out_mult: Vec<PackedM31>,
+    rem_limbs: Vec<[PackedM31; N_REM_LIMBS]>,
+    range_check_mult: Vec<PackedM31>,
+}
+
+pub struct InteractionClaimGenerator {
+    log_size: u32,
+    lookup_data: LookupData,
+}
+
+impl InteractionClaimGenerator {
+    pub fn write_interaction_trace(
+        self,
+        tree_builder: &mut impl TreeBuilder<SimdBackend>,
+        node_elements: &NodeElements,
+        range_check_elements: &RangeCheckLookupElements,
+    ) -> InteractionClaim {
+        let mut logup_gen = LogupTraceGenerator::new(self.log_size);
+
+        let mut col_gen = logup_gen.new_col();
+        for row in 0..1 << (self.log_size - LOG_N_LANES) {
+            let values = &self.lookup_data.input[row];
+            let multiplicity = &self.lookup_data.input_mult[row];
+
+            let denom: PackedQM31 = node_elements.combine(values);
+            col_gen.write_frac(row, (*multiplicity).into(), denom);
+        }
+        col_gen.finalize_col();
+
+        let mut col_gen = logup_gen.new_col();
+        for row in 0..1 << (self.log_size - LOG_N_LANES) {
+            let values = &self.lookup_data.weight[row];
+            let multiplicity = &self.lookup_data.weight_mult[row];
+
+            let denom: PackedQM31 = node_elements.combine(values);
+            col_gen.write_frac(row, (*multiplicity).into(), denom);
+        }
+        col_gen.finalize_col();
+
+        let mut col_gen = logup_gen.new_col();
+        for row in 0..1 << (self.log_size - LOG_N_LANES) {
+            let values = &self.lookup_data.bias[row];
+            let multiplicity = &self.lookup_data.bias_mult[row];
+
+            let denom: PackedQM31 = node_elements.combine(values);
+            col_gen.write_frac(row, (*multiplicity).into(), denom);
+        }
+        col_gen.finalize_col();
+
+        let mut col_gen = logup_gen.new_col();
+        for row in 0..1 << (self.log_size - LOG_N_LANES) {
+            let values = &self.lookup_data.out[row];
+            let multiplicity = &self.lookup_data.out_mult[row];
+
+            let denom: PackedQM31 = node_elements.combine(values);
+            col_gen.write_frac(row, (*multiplicity).into(), denom);
+        }
+        col_gen.finalize_col();
+
+        // One column for each limb of the remainders
+        for limb in 0..N_REM_LIMBS {
+            let mut col_gen = logup_gen.new_col();
+            for row in 0..1 << (self.log_size - LOG_N_LANES) {
+                let value = self.lookup_data.rem_limbs[row][limb];
+                let multiplicity = self.lookup_data.range_check_mult[row];
+
+                let denom: PackedQM31 = range_check_elements.combine(&[value]);
+                col_gen.write_frac(row, multiplicity.into(), denom);
+            }
+            col_gen.finalize_col();
+        }
+
+        let (trace, claimed_sum) = logup_gen.finalize_last();
+        tree_builder.extend_evals(trace);
+
+        InteractionClaim { claimed_sum }
+    }
+}
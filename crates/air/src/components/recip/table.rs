@@ -8,13 +8,16 @@ use stwo::{
     },
 };
 
-use crate::components::TraceColumn;
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
 
 use super::witness::N_TRACE_COLUMNS;
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct RecipTraceTable {
-    pub table: Vec<RecipTraceTableRow>,
+    pub table: PackedRows<RecipTraceTableRow>,
 }
 
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
@@ -23,9 +26,6 @@ pub struct RecipTraceTableRow {
     pub input_id: M31,
     pub idx: M31,
     pub is_last_idx: M31,
-    pub next_node_id: M31,
-    pub next_input_id: M31,
-    pub next_idx: M31,
     pub input: M31,
     pub out: M31,
     pub rem: M31,
@@ -41,9 +41,6 @@ impl RecipTraceTableRow {
             input_id: M31::zero(),
             idx: M31::zero(),
             is_last_idx: M31::one(),
-            next_node_id: M31::zero(),
-            next_input_id: M31::zero(),
-            next_idx: M31::zero(),
             input: M31::zero(),
             out: M31::zero(),
             rem: M31::zero(),
@@ -54,15 +51,18 @@ impl RecipTraceTableRow {
     }
 }
 
+impl TransitionRow for RecipTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct PackedRecipTraceTableRow {
     pub node_id: PackedM31,
     pub input_id: PackedM31,
     pub idx: PackedM31,
     pub is_last_idx: PackedM31,
-    pub next_node_id: PackedM31,
-    pub next_input_id: PackedM31,
-    pub next_idx: PackedM31,
     pub input: PackedM31,
     pub out: PackedM31,
     pub rem: PackedM31,
@@ -80,9 +80,6 @@ impl Pack for RecipTraceTableRow {
             input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_id)),
             idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
             is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
-            next_node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_node_id)),
-            next_input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_input_id)),
-            next_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_idx)),
             input: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input)),
             out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
             rem: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rem)),
@@ -97,28 +94,11 @@ impl Unpack for PackedRecipTraceTableRow {
     type CpuType = RecipTraceTableRow;
 
     fn unpack(self) -> [Self::CpuType; N_LANES] {
-        let (
-            node_id,
-            input_id,
-            idx,
-            is_last_idx,
-            next_node_id,
-            next_input_id,
-            next_idx,
-            input,
-            out,
-            rem,
-            scale,
-            input_mult,
-            out_mult,
-        ) = (
+        let (node_id, input_id, idx, is_last_idx, input, out, rem, scale, input_mult, out_mult) = (
             self.node_id.to_array(),
             self.input_id.to_array(),
             self.idx.to_array(),
             self.is_last_idx.to_array(),
-            self.next_node_id.to_array(),
-            self.next_input_id.to_array(),
-            self.next_idx.to_array(),
             self.input.to_array(),
             self.out.to_array(),
             self.rem.to_array(),
@@ -132,9 +112,6 @@ impl Unpack for PackedRecipTraceTableRow {
             input_id: input_id[i],
             idx: idx[i],
             is_last_idx: is_last_idx[i],
-            next_node_id: next_node_id[i],
-            next_input_id: next_input_id[i],
-            next_idx: next_idx[i],
             input: input[i],
             out: out[i],
             rem: rem[i],
@@ -161,9 +138,6 @@ pub enum RecipColumn {
     InputId,
     Idx,
     IsLastIdx,
-    NextNodeId,
-    NextInputId,
-    NextIdx,
     Input,
     Out,
     Rem,
@@ -179,15 +153,12 @@ impl RecipColumn {
             Self::InputId => 1,
             Self::Idx => 2,
             Self::IsLastIdx => 3,
-            Self::NextNodeId => 4,
-            Self::NextInputId => 5,
-            Self::NextIdx => 6,
-            Self::Input => 7,
-            Self::Out => 8,
-            Self::Rem => 9,
-            Self::Scale => 10,
-            Self::InputMult => 11,
-            Self::OutMult => 12,
+            Self::Input => 4,
+            Self::Out => 5,
+            Self::Rem => 6,
+            Self::Scale => 7,
+            Self::InputMult => 8,
+            Self::OutMult => 9,
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     components::{InteractionClaim, NodeElements, RecipClaim},
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 use luminair_utils::TraceError;
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
@@ -15,7 +15,7 @@ use stwo_constraint_framework::{LogupTraceGenerator, Relation};
 
 use super::table::{PackedRecipTraceTableRow, RecipColumn, RecipTraceTable, RecipTraceTableRow};
 
-pub(crate) const N_TRACE_COLUMNS: usize = 13;
+pub(crate) const N_TRACE_COLUMNS: usize = 10;
 
 pub struct ClaimGenerator {
     pub inputs: RecipTraceTable,
@@ -27,7 +27,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(RecipClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -39,10 +39,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs
+        let packed_inputs = self
+            .inputs
             .table
-            .resize(size, RecipTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+            .into_packed_coset_order(RecipTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -82,9 +82,6 @@ fn write_trace_simd(
             *row[RecipColumn::InputId.index()] = input.input_id;
             *row[RecipColumn::Idx.index()] = input.idx;
             *row[RecipColumn::IsLastIdx.index()] = input.is_last_idx;
-            *row[RecipColumn::NextNodeId.index()] = input.next_node_id;
-            *row[RecipColumn::NextInputId.index()] = input.next_input_id;
-            *row[RecipColumn::NextIdx.index()] = input.next_idx;
             *row[RecipColumn::Input.index()] = input.input;
             *row[RecipColumn::Out.index()] = input.out;
             *row[RecipColumn::Rem.index()] = input.rem;
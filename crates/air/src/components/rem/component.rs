@@ -2,7 +2,7 @@ use crate::components::{NodeElements, RemClaim};
 use num_traits::One;
 use numerair::eval::EvalFixedPoint;
 use stwo_constraint_framework::{
-    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry,
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
 };
 
 pub type RemComponent = FrameworkComponent<RemEval>;
@@ -37,17 +37,13 @@ impl FrameworkEval for RemEval {
     /// Evaluates the remainder constraints and relations
     fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
         //IDs
-        let node_id = eval.next_trace_mask();
-        let lhs_id = eval.next_trace_mask();
-        let rhs_id = eval.next_trace_mask();
-        let idx = eval.next_trace_mask();
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let [lhs_id, next_lhs_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let [rhs_id, next_rhs_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
         let is_last_idx = eval.next_trace_mask();
 
         //Next IDs for transition constraints
-        let next_node_id = eval.next_trace_mask();
-        let next_lhs_id = eval.next_trace_mask();
-        let next_rhs_id = eval.next_trace_mask();
-        let next_idx = eval.next_trace_mask();
 
         // Values for consistency constraints
         let lhs_val = eval.next_trace_mask();
@@ -8,13 +8,16 @@ use stwo::{
     },
 };
 
-use crate::components::TraceColumn;
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
 
 use super::witness::N_TRACE_COLUMNS;
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct RemTraceTable {
-    pub table: Vec<RemTraceTableRow>
+    pub table: PackedRows<RemTraceTableRow>
 }
 
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
@@ -24,10 +27,6 @@ pub struct RemTraceTableRow {
     pub rhs_id: M31,
     pub idx: M31,
     pub is_last_idx: M31,
-    pub next_node_id: M31,
-    pub next_lhs_id: M31,
-    pub next_rhs_id: M31,
-    pub next_idx: M31,
     pub lhs: M31,
     pub rhs: M31,
     pub rem: M31,
@@ -45,10 +44,6 @@ impl RemTraceTableRow {
             rhs_id: M31::zero(),
             idx: M31::zero(),
             is_last_idx: M31::one(),
-            next_node_id: M31::zero(),
-            next_lhs_id: M31::zero(),
-            next_rhs_id: M31::zero(),
-            next_idx: M31::zero(),
             lhs: M31::zero(),
             rhs: M31::zero(),
             rem: M31::zero(),
@@ -60,6 +55,12 @@ impl RemTraceTableRow {
     }
 }
 
+impl TransitionRow for RemTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
 
 #[derive(Debug, Copy, Clone)]
 pub struct PackedRemTraceTableRow {
@@ -68,10 +69,6 @@ pub struct PackedRemTraceTableRow {
     pub rhs_id: PackedM31,
     pub idx: PackedM31,
     pub is_last_idx: PackedM31,
-    pub next_node_id: PackedM31,
-    pub next_lhs_id: PackedM31,
-    pub next_rhs_id: PackedM31,
-    pub next_idx: PackedM31,
     pub lhs: PackedM31,
     pub rhs: PackedM31,
     pub rem: PackedM31,
@@ -91,10 +88,6 @@ impl Pack for RemTraceTableRow {
             rhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs_id)),
             idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
             is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
-            next_node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_node_id)),
-            next_lhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_lhs_id)),
-            next_rhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_rhs_id)),
-            next_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_idx)),
             lhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lhs)),
             rhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs)),
             rem: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rem)),
@@ -116,10 +109,6 @@ impl Unpack for PackedRemTraceTableRow {
             rhs_id,
             idx,
             is_last_idx,
-            next_node_id,
-            next_lhs_id,
-            next_rhs_id,
-            next_idx,
             lhs,
             rhs,
             rem,
@@ -133,10 +122,6 @@ impl Unpack for PackedRemTraceTableRow {
             self.rhs_id.to_array(),
             self.idx.to_array(),
             self.is_last_idx.to_array(),
-            self.next_node_id.to_array(),
-            self.next_lhs_id.to_array(),
-            self.next_rhs_id.to_array(),
-            self.next_idx.to_array(),
             self.lhs.to_array(),
             self.rhs.to_array(),
             self.rem.to_array(),
@@ -152,10 +137,6 @@ impl Unpack for PackedRemTraceTableRow {
             rhs_id: rhs_id[i],
             idx: idx[i],
             is_last_idx: is_last_idx[i],
-            next_node_id: next_node_id[i],
-            next_lhs_id: next_lhs_id[i],
-            next_rhs_id: next_rhs_id[i],
-            next_idx: next_idx[i],
             lhs: lhs[i],
             rhs: rhs[i],
             rem: rem[i],
@@ -184,10 +165,6 @@ pub enum RemColumn {
     RhsId,
     Idx,
     IsLastIdx,
-    NextNodeId,
-    NextLhsId,
-    NextRhsId,
-    NextIdx,
     Lhs,
     Rhs,
     Rem,
@@ -205,17 +182,13 @@ impl RemColumn {
             Self::RhsId => 2,
             Self::Idx => 3,
             Self::IsLastIdx => 4,
-            Self::NextNodeId => 5,
-            Self::NextLhsId => 6,
-            Self::NextRhsId => 7,
-            Self::NextIdx => 8,
-            Self::Lhs => 9,
-            Self::Rhs => 10,
-            Self::Rem => 11,
-            Self::Quotient => 12,
-            Self::LhsMult => 13,
-            Self::RhsMult => 14,
-            Self::OutMult => 15,
+            Self::Lhs => 5,
+            Self::Rhs => 6,
+            Self::Rem => 7,
+            Self::Quotient => 8,
+            Self::LhsMult => 9,
+            Self::RhsMult => 10,
+            Self::OutMult => 11,
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     components::{InteractionClaim, NodeElements, RemClaim},
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 use luminair_utils::TraceError;
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
@@ -15,7 +15,7 @@ use stwo_constraint_framework::{LogupTraceGenerator, Relation};
 
 use super::table::{PackedRemTraceTableRow, RemColumn, RemTraceTable, RemTraceTableRow};
 
-pub(crate) const N_TRACE_COLUMNS: usize = 16;
+pub(crate) const N_TRACE_COLUMNS: usize = 12;
 
 pub struct ClaimGenerator {
     pub inputs: RemTraceTable,
@@ -27,7 +27,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(RemClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -39,8 +39,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs.table.resize(size, RemTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+        let packed_inputs = self
+            .inputs
+            .table
+            .into_packed_coset_order(RemTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -81,10 +83,6 @@ fn write_trace_simd(
             *row[RemColumn::RhsId.index()] = input.rhs_id;
             *row[RemColumn::Idx.index()] = input.idx;
             *row[RemColumn::IsLastIdx.index()] = input.is_last_idx;
-            *row[RemColumn::NextNodeId.index()] = input.next_node_id;
-            *row[RemColumn::NextLhsId.index()] = input.next_lhs_id;
-            *row[RemColumn::NextRhsId.index()] = input.next_rhs_id;
-            *row[RemColumn::NextIdx.index()] = input.next_idx;
             *row[RemColumn::Lhs.index()] = input.lhs;
             *row[RemColumn::Rhs.index()] = input.rhs;
             *row[RemColumn::Rem.index()] = input.rem;
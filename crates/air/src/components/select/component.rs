@@ -0,0 +1,140 @@
+use crate::{
+    components::{NodeElements, SelectClaim},
+    DEFAULT_FP_SCALE_FACTOR,
+};
+use num_traits::One;
+use stwo::core::fields::m31::M31;
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, RelationEntry, ORIGINAL_TRACE_IDX,
+};
+
+pub type SelectComponent = FrameworkComponent<SelectEval>;
+
+/// Evaluation structure for select (where) operations
+pub struct SelectEval {
+    log_size: u32,
+    node_elements: NodeElements,
+}
+
+impl SelectEval {
+    /// Creates a new SelectEval with the given claim and node elements
+    pub fn new(claim: &SelectClaim, node_elements: NodeElements) -> Self {
+        Self {
+            log_size: claim.log_size,
+            node_elements,
+        }
+    }
+}
+
+impl FrameworkEval for SelectEval {
+    /// Returns the log size of the evaluation
+    fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Returns the maximum constraint log degree bound
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.log_size + 1
+    }
+
+    /// Evaluates the select constraints and relations
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        // IDs, along with their value on the next row for transition constraints
+        // ID of the node in the computational graph.
+        let [node_id, next_node_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the condition tensor.
+        let [cond_id, next_cond_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the tensor selected when cond holds.
+        let [lhs_id, next_lhs_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // ID of the tensor selected otherwise.
+        let [rhs_id, next_rhs_id] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        // Index in the flattened tensor.
+        let [idx, next_idx] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let is_last_idx = eval.next_trace_mask(); // Flag if this is the last index for this operation.
+
+        // Values for consistency constraints
+        let cond_val = eval.next_trace_mask(); // Fixed-point condition value (0 or 1.0).
+        let cond_bit = eval.next_trace_mask(); // Condition as a field boolean.
+        let lhs_val = eval.next_trace_mask(); // Value from first tensor at index.
+        let rhs_val = eval.next_trace_mask(); // Value from second tensor at index.
+        let out_val = eval.next_trace_mask(); // Value in output tensor at index.
+
+        // Multiplicities for interaction constraints
+        let cond_mult = eval.next_trace_mask();
+        let lhs_mult = eval.next_trace_mask();
+        let rhs_mult = eval.next_trace_mask();
+        let out_mult = eval.next_trace_mask();
+
+        let scale_factor = E::F::from(M31::from_u32_unchecked(DEFAULT_FP_SCALE_FACTOR));
+
+        // ┌─────────────────────────────┐
+        // │   Consistency Constraints   │
+        // └─────────────────────────────┘
+
+        // The is_last_idx flag is either 0 or 1.
+        eval.add_constraint(is_last_idx.clone() * (is_last_idx.clone() - E::F::one()));
+
+        // The condition bit is either 0 or 1.
+        eval.add_constraint(cond_bit.clone() * (cond_bit.clone() - E::F::one()));
+
+        // The fixed-point condition is the condition bit scaled, i.e. 0 or 1.0.
+        eval.add_constraint(cond_val.clone() - cond_bit.clone() * scale_factor);
+
+        // out = cond ? lhs : rhs, without any fixed-point rescaling.
+        eval.add_constraint(
+            out_val.clone() - rhs_val.clone() - cond_bit * (lhs_val.clone() - rhs_val.clone()),
+        );
+
+        // ┌────────────────────────────┐
+        // │   Transition Constraints   │
+        // └────────────────────────────┘
+
+        // If this is not the last index for this operation, then:
+        // 1. The next row should be for the same operation on the same tensors.
+        // 2. The index should increment by 1.
+        let not_last = E::F::one() - is_last_idx;
+
+        // Same node ID
+        eval.add_constraint(not_last.clone() * (next_node_id - node_id.clone()));
+
+        // Same tensor IDs
+        eval.add_constraint(not_last.clone() * (next_cond_id - cond_id.clone()));
+        eval.add_constraint(not_last.clone() * (next_lhs_id - lhs_id.clone()));
+        eval.add_constraint(not_last.clone() * (next_rhs_id - rhs_id.clone()));
+
+        // Index increment by 1
+        eval.add_constraint(not_last * (next_idx - idx - E::F::one()));
+
+        // ┌─────────────────────────────┐
+        // │   Interaction Constraints   │
+        // └─────────────────────────────┘
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            cond_mult.into(),
+            &[cond_val, cond_id],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            lhs_mult.into(),
+            &[lhs_val, lhs_id],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            rhs_mult.into(),
+            &[rhs_val, rhs_id],
+        ));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.node_elements,
+            out_mult.into(),
+            &[out_val, node_id],
+        ));
+
+        eval.finalize_logup();
+
+        eval
+    }
+}
@@ -0,0 +1,3 @@
+pub mod component;
+pub mod table;
+pub mod witness;
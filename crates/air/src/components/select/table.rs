@@ -0,0 +1,224 @@
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::fields::m31::M31,
+    prover::backend::simd::{
+        conversion::{Pack, Unpack},
+        m31::{PackedM31, N_LANES},
+    },
+};
+
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct SelectTraceTable {
+    pub table: PackedRows<SelectTraceTableRow>,
+}
+
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SelectTraceTableRow {
+    pub node_id: M31,
+    pub cond_id: M31,
+    pub lhs_id: M31,
+    pub rhs_id: M31,
+    pub idx: M31,
+    pub is_last_idx: M31,
+    pub cond: M31,
+    pub cond_bit: M31,
+    pub lhs: M31,
+    pub rhs: M31,
+    pub out: M31,
+    pub cond_mult: M31,
+    pub lhs_mult: M31,
+    pub rhs_mult: M31,
+    pub out_mult: M31,
+}
+
+impl SelectTraceTableRow {
+    pub(crate) fn padding() -> Self {
+        Self {
+            node_id: M31::zero(),
+            cond_id: M31::zero(),
+            lhs_id: M31::zero(),
+            rhs_id: M31::zero(),
+            idx: M31::zero(),
+            is_last_idx: M31::one(),
+            cond: M31::zero(),
+            cond_bit: M31::zero(),
+            lhs: M31::zero(),
+            rhs: M31::zero(),
+            out: M31::zero(),
+            cond_mult: M31::zero(),
+            lhs_mult: M31::zero(),
+            rhs_mult: M31::zero(),
+            out_mult: M31::zero(),
+        }
+    }
+}
+
+impl TransitionRow for SelectTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct PackedSelectTraceTableRow {
+    pub node_id: PackedM31,
+    pub cond_id: PackedM31,
+    pub lhs_id: PackedM31,
+    pub rhs_id: PackedM31,
+    pub idx: PackedM31,
+    pub is_last_idx: PackedM31,
+    pub cond: PackedM31,
+    pub cond_bit: PackedM31,
+    pub lhs: PackedM31,
+    pub rhs: PackedM31,
+    pub out: PackedM31,
+    pub cond_mult: PackedM31,
+    pub lhs_mult: PackedM31,
+    pub rhs_mult: PackedM31,
+    pub out_mult: PackedM31,
+}
+
+impl Pack for SelectTraceTableRow {
+    type SimdType = PackedSelectTraceTableRow;
+
+    fn pack(inputs: [Self; N_LANES]) -> Self::SimdType {
+        PackedSelectTraceTableRow {
+            node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].node_id)),
+            cond_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].cond_id)),
+            lhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lhs_id)),
+            rhs_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs_id)),
+            idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
+            is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
+            cond: PackedM31::from_array(std::array::from_fn(|i| inputs[i].cond)),
+            cond_bit: PackedM31::from_array(std::array::from_fn(|i| inputs[i].cond_bit)),
+            lhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lhs)),
+            rhs: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs)),
+            out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
+            cond_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].cond_mult)),
+            lhs_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].lhs_mult)),
+            rhs_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].rhs_mult)),
+            out_mult: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out_mult)),
+        }
+    }
+}
+
+impl Unpack for PackedSelectTraceTableRow {
+    type CpuType = SelectTraceTableRow;
+
+    fn unpack(self) -> [Self::CpuType; N_LANES] {
+        let (
+            node_id,
+            cond_id,
+            lhs_id,
+            rhs_id,
+            idx,
+            is_last_idx,
+            cond,
+            cond_bit,
+            lhs,
+            rhs,
+            out,
+            cond_mult,
+            lhs_mult,
+            rhs_mult,
+            out_mult,
+        ) = (
+            self.node_id.to_array(),
+            self.cond_id.to_array(),
+            self.lhs_id.to_array(),
+            self.rhs_id.to_array(),
+            self.idx.to_array(),
+            self.is_last_idx.to_array(),
+            self.cond.to_array(),
+            self.cond_bit.to_array(),
+            self.lhs.to_array(),
+            self.rhs.to_array(),
+            self.out.to_array(),
+            self.cond_mult.to_array(),
+            self.lhs_mult.to_array(),
+            self.rhs_mult.to_array(),
+            self.out_mult.to_array(),
+        );
+
+        std::array::from_fn(|i| SelectTraceTableRow {
+            node_id: node_id[i],
+            cond_id: cond_id[i],
+            lhs_id: lhs_id[i],
+            rhs_id: rhs_id[i],
+            idx: idx[i],
+            is_last_idx: is_last_idx[i],
+            cond: cond[i],
+            cond_bit: cond_bit[i],
+            lhs: lhs[i],
+            rhs: rhs[i],
+            out: out[i],
+            cond_mult: cond_mult[i],
+            lhs_mult: lhs_mult[i],
+            rhs_mult: rhs_mult[i],
+            out_mult: out_mult[i],
+        })
+    }
+}
+
+impl SelectTraceTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_row(&mut self, row: SelectTraceTableRow) {
+        self.table.push(row);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SelectColumn {
+    NodeId,
+    CondId,
+    LhsId,
+    RhsId,
+    Idx,
+    IsLastIdx,
+    Cond,
+    CondBit,
+    Lhs,
+    Rhs,
+    Out,
+    CondMult,
+    LhsMult,
+    RhsMult,
+    OutMult,
+}
+
+impl SelectColumn {
+    pub const fn index(self) -> usize {
+        match self {
+            Self::NodeId => 0,
+            Self::CondId => 1,
+            Self::LhsId => 2,
+            Self::RhsId => 3,
+            Self::Idx => 4,
+            Self::IsLastIdx => 5,
+            Self::Cond => 6,
+            Self::CondBit => 7,
+            Self::Lhs => 8,
+            Self::Rhs => 9,
+            Self::Out => 10,
+            Self::CondMult => 11,
+            Self::LhsMult => 12,
+            Self::RhsMult => 13,
+            Self::OutMult => 14,
+        }
+    }
+}
+
+impl TraceColumn for SelectColumn {
+    fn count() -> (usize, usize) {
+        (15, 4)
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     components::{
         lookups::sin::SinLookupElements, InteractionClaim, NodeElements, SinClaim,
     },
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 use luminair_utils::TraceError;
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
@@ -17,7 +17,7 @@ use stwo_constraint_framework::{LogupTraceGenerator, Relation};
 
 use super::table::{PackedSinTraceTableRow, SinColumn, SinTraceTable, SinTraceTableRow};
 
-pub(crate) const N_TRACE_COLUMNS: usize = 12;
+pub(crate) const N_TRACE_COLUMNS: usize = 9;
 
 pub struct ClaimGenerator {
     pub inputs: SinTraceTable,
@@ -29,7 +29,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(SinClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -41,8 +41,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs.table.resize(size, SinTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+        let packed_inputs = self
+            .inputs
+            .table
+            .into_packed_coset_order(SinTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -82,9 +84,6 @@ fn write_trace_simd(
             *row[SinColumn::InputId.index()] = input.input_id;
             *row[SinColumn::Idx.index()] = input.idx;
             *row[SinColumn::IsLastIdx.index()] = input.is_last_idx;
-            *row[SinColumn::NextNodeId.index()] = input.next_node_id;
-            *row[SinColumn::NextInputId.index()] = input.next_input_id;
-            *row[SinColumn::NextIdx.index()] = input.next_idx;
             *row[SinColumn::Input.index()] = input.input;
             *row[SinColumn::Out.index()] = input.out;
             *row[SinColumn::InputMult.index()] = input.input_mult;
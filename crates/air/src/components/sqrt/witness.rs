@@ -11,12 +11,12 @@ use stwo_constraint_framework::{LogupTraceGenerator, Relation};
 
 use crate::{
     components::{InteractionClaim, NodeElements, SqrtClaim},
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
 use super::table::{PackedSqrtTraceTableRow, SqrtColumn, SqrtTraceTable, SqrtTraceTableRow};
 
-pub(crate) const N_TRACE_COLUMNS: usize = 13;
+pub(crate) const N_TRACE_COLUMNS: usize = 10;
 
 pub struct ClaimGenerator {
     pub inputs: SqrtTraceTable,
@@ -28,7 +28,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(SqrtClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -40,8 +40,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs.table.resize(size, SqrtTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+        let packed_inputs = self
+            .inputs
+            .table
+            .into_packed_coset_order(SqrtTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -81,9 +83,6 @@ fn write_trace_simd(
             *row[SqrtColumn::InputId.index()] = input.input_id;
             *row[SqrtColumn::Idx.index()] = input.idx;
             *row[SqrtColumn::IsLastIdx.index()] = input.is_last_idx;
-            *row[SqrtColumn::NextNodeId.index()] = input.next_node_id;
-            *row[SqrtColumn::NextInputId.index()] = input.next_input_id;
-            *row[SqrtColumn::NextIdx.index()] = input.next_idx;
             *row[SqrtColumn::Input.index()] = input.input;
             *row[SqrtColumn::Out.index()] = input.out;
             *row[SqrtColumn::Rem.index()] = input.rem;
@@ -8,13 +8,16 @@ use stwo::{
     },
 };
 
-use crate::components::TraceColumn;
+use crate::{
+    components::TraceColumn,
+    utils::{PackedRows, TransitionRow},
+};
 
 use super::witness::N_TRACE_COLUMNS;
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct SumReduceTraceTable {
-    pub table: Vec<SumReduceTraceTableRow>,
+    pub table: PackedRows<SumReduceTraceTableRow>,
 }
 
 #[derive(Debug, Default, Copy, Clone, serde::Serialize, serde::Deserialize)]
@@ -23,9 +26,6 @@ pub struct SumReduceTraceTableRow {
     pub input_id: M31,
     pub idx: M31,
     pub is_last_idx: M31,
-    pub next_node_id: M31,
-    pub next_input_id: M31,
-    pub next_idx: M31,
     pub input: M31,
     pub out: M31,
     pub acc: M31,
@@ -42,9 +42,6 @@ impl SumReduceTraceTableRow {
             input_id: M31::zero(),
             idx: M31::zero(),
             is_last_idx: M31::one(),
-            next_node_id: M31::zero(),
-            next_input_id: M31::zero(),
-            next_idx: M31::zero(),
             input: M31::zero(),
             out: M31::zero(),
             acc: M31::zero(),
@@ -56,15 +53,18 @@ impl SumReduceTraceTableRow {
     }
 }
 
+impl TransitionRow for SumReduceTraceTableRow {
+    fn end_run(&mut self) {
+        self.is_last_idx = M31::one();
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct PackedSumReduceTraceTableRow {
     pub node_id: PackedM31,
     pub input_id: PackedM31,
     pub idx: PackedM31,
     pub is_last_idx: PackedM31,
-    pub next_node_id: PackedM31,
-    pub next_input_id: PackedM31,
-    pub next_idx: PackedM31,
     pub input: PackedM31,
     pub out: PackedM31,
     pub acc: PackedM31,
@@ -83,9 +83,6 @@ impl Pack for SumReduceTraceTableRow {
             input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input_id)),
             idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].idx)),
             is_last_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].is_last_idx)),
-            next_node_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_node_id)),
-            next_input_id: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_input_id)),
-            next_idx: PackedM31::from_array(std::array::from_fn(|i| inputs[i].next_idx)),
             input: PackedM31::from_array(std::array::from_fn(|i| inputs[i].input)),
             out: PackedM31::from_array(std::array::from_fn(|i| inputs[i].out)),
             acc: PackedM31::from_array(std::array::from_fn(|i| inputs[i].acc)),
@@ -106,9 +103,6 @@ impl Unpack for PackedSumReduceTraceTableRow {
             input_id,
             idx,
             is_last_idx,
-            next_node_id,
-            next_input_id,
-            next_idx,
             input,
             out,
             acc,
@@ -121,9 +115,6 @@ impl Unpack for PackedSumReduceTraceTableRow {
             self.input_id.to_array(),
             self.idx.to_array(),
             self.is_last_idx.to_array(),
-            self.next_node_id.to_array(),
-            self.next_input_id.to_array(),
-            self.next_idx.to_array(),
             self.input.to_array(),
             self.out.to_array(),
             self.acc.to_array(),
@@ -138,9 +129,6 @@ impl Unpack for PackedSumReduceTraceTableRow {
             input_id: input_id[i],
             idx: idx[i],
             is_last_idx: is_last_idx[i],
-            next_node_id: next_node_id[i],
-            next_input_id: next_input_id[i],
-            next_idx: next_idx[i],
             input: input[i],
             out: out[i],
             acc: acc[i],
@@ -164,7 +152,7 @@ impl SumReduceTraceTable {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SumReduceColumn {
-    NodeId, InputId, Idx, IsLastIdx, NextNodeId, NextInputId, NextIdx, Input, Out, Acc, NextAcc, IsLastStep, InputMult, OutMult
+    NodeId, InputId, Idx, IsLastIdx, Input, Out, Acc, NextAcc, IsLastStep, InputMult, OutMult
 }
 
 impl SumReduceColumn {
@@ -174,16 +162,13 @@ impl SumReduceColumn {
             Self::InputId => 1,
             Self::Idx => 2,
             Self::IsLastIdx => 3,
-            Self::NextNodeId => 4,
-            Self::NextInputId => 5,
-            Self::NextIdx => 6,
-            Self::Input => 7,
-            Self::Out => 8,
-            Self::Acc => 9,
-            Self::NextAcc => 10,
-            Self::IsLastStep => 11,
-            Self::InputMult => 12,
-            Self::OutMult => 13,
+            Self::Input => 4,
+            Self::Out => 5,
+            Self::Acc => 6,
+            Self::NextAcc => 7,
+            Self::IsLastStep => 8,
+            Self::InputMult => 9,
+            Self::OutMult => 10,
         }
     }
 }
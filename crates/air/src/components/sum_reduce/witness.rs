@@ -11,14 +11,14 @@ use stwo_constraint_framework::{LogupTraceGenerator, Relation};
 
 use crate::{
     components::{InteractionClaim, NodeElements, SumReduceClaim},
-    utils::{pack_values, TreeBuilder},
+    utils::TreeBuilder,
 };
 
 use super::table::{
     PackedSumReduceTraceTableRow, SumReduceColumn, SumReduceTraceTable, SumReduceTraceTableRow,
 };
 
-pub(crate) const N_TRACE_COLUMNS: usize = 14;
+pub(crate) const N_TRACE_COLUMNS: usize = 11;
 
 pub struct ClaimGenerator {
     pub inputs: SumReduceTraceTable,
@@ -30,7 +30,7 @@ impl ClaimGenerator {
     }
 
     pub fn write_trace(
-        mut self,
+        self,
         tree_builder: &mut impl TreeBuilder<SimdBackend>,
     ) -> Result<(SumReduceClaim, InteractionClaimGenerator), TraceError> {
         let n_rows = self.inputs.table.len();
@@ -42,10 +42,10 @@ impl ClaimGenerator {
         let size = std::cmp::max(n_rows.next_power_of_two(), N_LANES);
         let log_size = size.ilog2();
 
-        self.inputs
+        let packed_inputs = self
+            .inputs
             .table
-            .resize(size, SumReduceTraceTableRow::padding());
-        let packed_inputs = pack_values(&self.inputs.table);
+            .into_packed_coset_order(SumReduceTraceTableRow::padding());
 
         let (trace, lookup_data) = write_trace_simd(packed_inputs);
 
@@ -85,9 +85,6 @@ fn write_trace_simd(
             *row[SumReduceColumn::InputId.index()] = input.input_id;
             *row[SumReduceColumn::Idx.index()] = input.idx;
             *row[SumReduceColumn::IsLastIdx.index()] = input.is_last_idx;
-            *row[SumReduceColumn::NextNodeId.index()] = input.next_node_id;
-            *row[SumReduceColumn::NextInputId.index()] = input.next_input_id;
-            *row[SumReduceColumn::NextIdx.index()] = input.next_idx;
             *row[SumReduceColumn::Input.index()] = input.input;
             *row[SumReduceColumn::Out.index()] = input.out;
             *row[SumReduceColumn::Acc.index()] = input.acc;
@@ -2,20 +2,24 @@
 
 use ::serde::{Deserialize, Serialize};
 use components::{
-    add, exp2, log2, lookups, max_reduce, mul, recip, rem, sin, sqrt, sum_reduce, AddClaim,
-    InteractionClaim, MaxReduceClaim, MulClaim, RecipClaim, RemClaim, SinClaim, SinLookupClaim,
-    SqrtClaim, SumReduceClaim,
+    add, add_const, exp2, log2, lookups, max_reduce, mix_const_claims, mix_shard_claims, mul,
+    mul_const, recip, rem, sin, sqrt, sum_reduce, AddClaim, AddConstClaim, Claim, ConstClaim,
+    InteractionClaim, MaxReduceClaim, MulClaim, MulConstClaim, RecipClaim, RemClaim, SinClaim,
+    SinLookupClaim, SqrtClaim, SumReduceClaim,
 };
 use stwo::core::{channel::Channel, pcs::TreeVec};
 
 use crate::components::{
-    contiguous, inputs, less_than, ContiguousClaim, Exp2Claim, Exp2LookupClaim, InputsClaim,
-    LessThanClaim, Log2Claim, Log2LookupClaim, RangeCheckLookupClaim,
+    contiguous, fused, gather, im2col, inputs, less_than, matmul, mix_fused_claims,
+    mix_norm_claims, norm, select, ContiguousClaim, Exp2Claim, Exp2LookupClaim, FusedClaim,
+    GatherClaim, GatherLookupClaim, Im2ColClaim, InputsClaim, LessThanClaim, Log2Claim,
+    Log2LookupClaim, MatMulClaim, NormClaim, RangeCheckLookupClaim, SelectClaim,
 };
 
 pub mod components;
 pub mod pie;
 pub mod preprocessed;
+pub mod segment;
 pub mod settings;
 pub mod utils;
 
@@ -26,239 +30,251 @@ pub const DEFAULT_FP_SCALE_FACTOR: u32 = 1 << DEFAULT_FP_SCALE;
 const TWO_POW_31_MINUS_1: u32 = (1u32 << 31) - 1;
 
 /// Main claim structure containing all component claims for LuminAIR
+///
+/// Each component holds one claim per shard of its table, so that a large table is proven by
+/// several component instances of smaller log sizes.
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct LuminairClaim {
-    pub add: Option<AddClaim>,
-    pub mul: Option<MulClaim>,
-    pub recip: Option<RecipClaim>,
-    pub sin: Option<SinClaim>,
-    pub sin_lookup: Option<SinLookupClaim>,
-    pub sum_reduce: Option<SumReduceClaim>,
-    pub max_reduce: Option<MaxReduceClaim>,
-    pub sqrt: Option<SqrtClaim>,
-    pub rem: Option<RemClaim>,
-    pub exp2: Option<Exp2Claim>,
-    pub exp2_lookup: Option<Exp2LookupClaim>,
-    pub log2: Option<Log2Claim>,
-    pub log2_lookup: Option<Log2LookupClaim>,
-    pub less_than: Option<LessThanClaim>,
-    pub range_check_lookup: Option<RangeCheckLookupClaim>,
-    pub inputs: Option<InputsClaim>,
-    pub contiguous: Option<ContiguousClaim>,
+    pub add: Vec<AddClaim>,
+    pub mul: Vec<MulClaim>,
+    pub recip: Vec<RecipClaim>,
+    pub sin: Vec<SinClaim>,
+    pub sin_lookup: Vec<SinLookupClaim>,
+    pub sum_reduce: Vec<SumReduceClaim>,
+    pub max_reduce: Vec<MaxReduceClaim>,
+    pub sqrt: Vec<SqrtClaim>,
+    pub rem: Vec<RemClaim>,
+    pub exp2: Vec<Exp2Claim>,
+    pub exp2_lookup: Vec<Exp2LookupClaim>,
+    pub log2: Vec<Log2Claim>,
+    pub log2_lookup: Vec<Log2LookupClaim>,
+    pub less_than: Vec<LessThanClaim>,
+    pub range_check_lookup: Vec<RangeCheckLookupClaim>,
+    pub inputs: Vec<InputsClaim>,
+    pub contiguous: Vec<ContiguousClaim>,
+    pub select: Vec<SelectClaim>,
+    pub gather: Vec<GatherClaim>,
+    pub gather_lookup: Vec<GatherLookupClaim>,
+    pub im2col: Vec<Im2ColClaim>,
+    pub matmul: Vec<MatMulClaim>,
+    pub norm: Vec<NormClaim>,
+    /// One claim per constant, and per shard of the operations sharing it
+    pub mul_const: Vec<MulConstClaim>,
+    pub add_const: Vec<AddConstClaim>,
+    /// One claim per expression, and per shard of the operations evaluating it
+    pub fused: Vec<FusedClaim>,
 }
 
 impl LuminairClaim {
     /// Mixes all component claims into the given channel
     pub fn mix_into(&self, channel: &mut impl Channel) {
-        if let Some(ref claim) = self.add {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.mul {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.recip {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.sin {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.sin_lookup {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.sum_reduce {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.max_reduce {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.sqrt {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.rem {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.exp2 {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.exp2_lookup {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.log2 {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.log2_lookup {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.less_than {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.range_check_lookup {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.inputs {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.contiguous {
-            claim.mix_into(channel);
-        }
+        mix_shard_claims(&self.add, channel);
+        mix_shard_claims(&self.mul, channel);
+        mix_shard_claims(&self.recip, channel);
+        mix_shard_claims(&self.sin, channel);
+        mix_shard_claims(&self.sin_lookup, channel);
+        mix_shard_claims(&self.sum_reduce, channel);
+        mix_shard_claims(&self.max_reduce, channel);
+        mix_shard_claims(&self.sqrt, channel);
+        mix_shard_claims(&self.rem, channel);
+        mix_shard_claims(&self.exp2, channel);
+        mix_shard_claims(&self.exp2_lookup, channel);
+        mix_shard_claims(&self.log2, channel);
+        mix_shard_claims(&self.log2_lookup, channel);
+        mix_shard_claims(&self.less_than, channel);
+        mix_shard_claims(&self.range_check_lookup, channel);
+        mix_shard_claims(&self.inputs, channel);
+        mix_shard_claims(&self.contiguous, channel);
+        mix_shard_claims(&self.select, channel);
+        mix_shard_claims(&self.gather, channel);
+        mix_shard_claims(&self.gather_lookup, channel);
+        mix_shard_claims(&self.im2col, channel);
+        mix_shard_claims(&self.matmul, channel);
+        mix_norm_claims(&self.norm, channel);
+        mix_const_claims(&self.mul_const, channel);
+        mix_const_claims(&self.add_const, channel);
+        mix_fused_claims(&self.fused, channel);
     }
 
     /// Returns the log sizes for all component claims
     pub fn log_sizes(&self) -> TreeVec<Vec<u32>> {
         let mut log_sizes = vec![];
 
-        if let Some(ref claim) = self.add {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.mul {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.recip {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.sin {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.sin_lookup {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.sum_reduce {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.max_reduce {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.sqrt {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.rem {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.exp2 {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.exp2_lookup {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.log2 {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.log2_lookup {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.less_than {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.range_check_lookup {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.inputs {
-            log_sizes.push(claim.log_sizes());
-        }
-        if let Some(ref claim) = self.contiguous {
-            log_sizes.push(claim.log_sizes());
-        }
+        log_sizes.extend(self.add.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.mul.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.recip.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.sin.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.sin_lookup.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.sum_reduce.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.max_reduce.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.sqrt.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.rem.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.exp2.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.exp2_lookup.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.log2.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.log2_lookup.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.less_than.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.range_check_lookup.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.inputs.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.contiguous.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.select.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.gather.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.gather_lookup.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.im2col.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.matmul.iter().map(Claim::log_sizes));
+        log_sizes.extend(self.norm.iter().map(NormClaim::log_sizes));
+        log_sizes.extend(self.mul_const.iter().map(ConstClaim::log_sizes));
+        log_sizes.extend(self.add_const.iter().map(ConstClaim::log_sizes));
+        log_sizes.extend(self.fused.iter().map(FusedClaim::log_sizes));
         TreeVec::concat_cols(log_sizes.into_iter())
     }
+
+    /// Whether every fused expression of the claim is within the limits of the fused component,
+    /// and every normalization has a non-empty row
+    pub fn is_well_formed(&self) -> bool {
+        self.fused.iter().all(|claim| claim.expr.is_valid())
+            && self.norm.iter().all(|claim| claim.params.row_size > 0)
+    }
 }
 
 /// Generator for interaction claims across all components
 #[derive(Default)]
 pub struct LuminairInteractionClaimGenerator {
-    pub add: Option<add::witness::InteractionClaimGenerator>,
-    pub mul: Option<mul::witness::InteractionClaimGenerator>,
-    pub recip: Option<recip::witness::InteractionClaimGenerator>,
-    pub sin: Option<sin::witness::InteractionClaimGenerator>,
-    pub sin_lookup: Option<lookups::sin::witness::InteractionClaimGenerator>,
-    pub sum_reduce: Option<sum_reduce::witness::InteractionClaimGenerator>,
-    pub max_reduce: Option<max_reduce::witness::InteractionClaimGenerator>,
-    pub sqrt: Option<sqrt::witness::InteractionClaimGenerator>,
-    pub rem: Option<rem::witness::InteractionClaimGenerator>,
-    pub exp2: Option<exp2::witness::InteractionClaimGenerator>,
-    pub exp2_lookup: Option<lookups::exp2::witness::InteractionClaimGenerator>,
-    pub log2: Option<log2::witness::InteractionClaimGenerator>,
-    pub log2_lookup: Option<lookups::log2::witness::InteractionClaimGenerator>,
-    pub less_than: Option<less_than::witness::InteractionClaimGenerator>,
-    pub range_check_lookup: Option<lookups::range_check::witness::InteractionClaimGenerator<1>>,
-    pub inputs: Option<inputs::witness::InteractionClaimGenerator>,
-    pub contiguous: Option<contiguous::witness::InteractionClaimGenerator>,
+    pub add: Vec<add::witness::InteractionClaimGenerator>,
+    pub mul: Vec<mul::witness::InteractionClaimGenerator>,
+    pub recip: Vec<recip::witness::InteractionClaimGenerator>,
+    pub sin: Vec<sin::witness::InteractionClaimGenerator>,
+    pub sin_lookup: Vec<lookups::sin::witness::InteractionClaimGenerator>,
+    pub sum_reduce: Vec<sum_reduce::witness::InteractionClaimGenerator>,
+    pub max_reduce: Vec<max_reduce::witness::InteractionClaimGenerator>,
+    pub sqrt: Vec<sqrt::witness::InteractionClaimGenerator>,
+    pub rem: Vec<rem::witness::InteractionClaimGenerator>,
+    pub exp2: Vec<exp2::witness::InteractionClaimGenerator>,
+    pub exp2_lookup: Vec<lookups::exp2::witness::InteractionClaimGenerator>,
+    pub log2: Vec<log2::witness::InteractionClaimGenerator>,
+    pub log2_lookup: Vec<lookups::log2::witness::InteractionClaimGenerator>,
+    pub less_than: Vec<less_than::witness::InteractionClaimGenerator>,
+    pub range_check_lookup: Vec<lookups::range_check::witness::InteractionClaimGenerator<1>>,
+    pub inputs: Vec<inputs::witness::InteractionClaimGenerator>,
+    pub contiguous: Vec<contiguous::witness::InteractionClaimGenerator>,
+    pub select: Vec<select::witness::InteractionClaimGenerator>,
+    pub gather: Vec<gather::witness::InteractionClaimGenerator>,
+    pub gather_lookup: Vec<lookups::gather::witness::InteractionClaimGenerator>,
+    pub im2col: Vec<im2col::witness::InteractionClaimGenerator>,
+    pub matmul: Vec<matmul::witness::InteractionClaimGenerator>,
+    pub norm: Vec<norm::witness::InteractionClaimGenerator>,
+    pub mul_const: Vec<mul_const::witness::InteractionClaimGenerator>,
+    pub add_const: Vec<add_const::witness::InteractionClaimGenerator>,
+    pub fused: Vec<fused::witness::InteractionClaimGenerator>,
 }
 
 /// Collection of interaction claims for all components
 #[derive(Serialize, Deserialize, Default, Debug)]
 pub struct LuminairInteractionClaim {
-    pub add: Option<InteractionClaim>,
-    pub mul: Option<InteractionClaim>,
-    pub recip: Option<InteractionClaim>,
-    pub sin: Option<InteractionClaim>,
-    pub sin_lookup: Option<InteractionClaim>,
-    pub sum_reduce: Option<InteractionClaim>,
-    pub max_reduce: Option<InteractionClaim>,
-    pub sqrt: Option<InteractionClaim>,
-    pub rem: Option<InteractionClaim>,
-    pub exp2: Option<InteractionClaim>,
-    pub exp2_lookup: Option<InteractionClaim>,
-    pub log2: Option<InteractionClaim>,
-    pub log2_lookup: Option<InteractionClaim>,
-    pub less_than: Option<InteractionClaim>,
-    pub range_check_lookup: Option<InteractionClaim>,
-    pub inputs: Option<InteractionClaim>,
-    pub contiguous: Option<InteractionClaim>,
+    pub add: Vec<InteractionClaim>,
+    pub mul: Vec<InteractionClaim>,
+    pub recip: Vec<InteractionClaim>,
+    pub sin: Vec<InteractionClaim>,
+    pub sin_lookup: Vec<InteractionClaim>,
+    pub sum_reduce: Vec<InteractionClaim>,
+    pub max_reduce: Vec<InteractionClaim>,
+    pub sqrt: Vec<InteractionClaim>,
+    pub rem: Vec<InteractionClaim>,
+    pub exp2: Vec<InteractionClaim>,
+    pub exp2_lookup: Vec<InteractionClaim>,
+    pub log2: Vec<InteractionClaim>,
+    pub log2_lookup: Vec<InteractionClaim>,
+    pub less_than: Vec<InteractionClaim>,
+    pub range_check_lookup: Vec<InteractionClaim>,
+    pub inputs: Vec<InteractionClaim>,
+    pub contiguous: Vec<InteractionClaim>,
+    pub select: Vec<InteractionClaim>,
+    pub gather: Vec<InteractionClaim>,
+    pub gather_lookup: Vec<InteractionClaim>,
+    pub im2col: Vec<InteractionClaim>,
+    pub matmul: Vec<InteractionClaim>,
+    pub norm: Vec<InteractionClaim>,
+    pub mul_const: Vec<InteractionClaim>,
+    pub add_const: Vec<InteractionClaim>,
+    pub fused: Vec<InteractionClaim>,
 }
 
 impl LuminairInteractionClaim {
     /// Mixes all interaction claims into the given channel
     pub fn mix_into(&self, channel: &mut impl Channel) {
-        if let Some(ref claim) = self.add {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.mul {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.recip {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.sin {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.sin_lookup {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.sum_reduce {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.max_reduce {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.sqrt {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.rem {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.exp2 {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.exp2_lookup {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.log2 {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.log2_lookup {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.less_than {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.range_check_lookup {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.inputs {
-            claim.mix_into(channel);
-        }
-        if let Some(ref claim) = self.contiguous {
+        for claim in self.claims() {
             claim.mix_into(channel);
         }
     }
+
+    /// Returns the interaction claims of every shard of every component, in component order
+    pub fn claims(&self) -> impl Iterator<Item = &InteractionClaim> {
+        [
+            &self.add,
+            &self.mul,
+            &self.recip,
+            &self.sin,
+            &self.sin_lookup,
+            &self.sum_reduce,
+            &self.max_reduce,
+            &self.sqrt,
+            &self.rem,
+            &self.exp2,
+            &self.exp2_lookup,
+            &self.log2,
+            &self.log2_lookup,
+            &self.less_than,
+            &self.range_check_lookup,
+            &self.inputs,
+            &self.contiguous,
+            &self.select,
+            &self.gather,
+            &self.gather_lookup,
+            &self.im2col,
+            &self.matmul,
+            &self.norm,
+            &self.mul_const,
+            &self.add_const,
+            &self.fused,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Whether each component has exactly one interaction claim per shard of `claim`
+    pub fn matches(&self, claim: &LuminairClaim) -> bool {
+        [
+            (self.add.len(), claim.add.len()),
+            (self.mul.len(), claim.mul.len()),
+            (self.recip.len(), claim.recip.len()),
+            (self.sin.len(), claim.sin.len()),
+            (self.sin_lookup.len(), claim.sin_lookup.len()),
+            (self.sum_reduce.len(), claim.sum_reduce.len()),
+            (self.max_reduce.len(), claim.max_reduce.len()),
+            (self.sqrt.len(), claim.sqrt.len()),
+            (self.rem.len(), claim.rem.len()),
+            (self.exp2.len(), claim.exp2.len()),
+            (self.exp2_lookup.len(), claim.exp2_lookup.len()),
+            (self.log2.len(), claim.log2.len()),
+            (self.log2_lookup.len(), claim.log2_lookup.len()),
+            (self.less_than.len(), claim.less_than.len()),
+            (
+                self.range_check_lookup.len(),
+                claim.range_check_lookup.len(),
+            ),
+            (self.inputs.len(), claim.inputs.len()),
+            (self.contiguous.len(), claim.contiguous.len()),
+            (self.select.len(), claim.select.len()),
+            (self.gather.len(), claim.gather.len()),
+            (self.gather_lookup.len(), claim.gather_lookup.len()),
+            (self.im2col.len(), claim.im2col.len()),
+            (self.matmul.len(), claim.matmul.len()),
+            (self.norm.len(), claim.norm.len()),
+            (self.mul_const.len(), claim.mul_const.len()),
+            (self.add_const.len(), claim.add_const.len()),
+            (self.fused.len(), claim.fused.len()),
+        ]
+        .into_iter()
+        .all(|(interaction_claims, claims)| interaction_claims == claims)
+    }
 }
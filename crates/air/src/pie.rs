@@ -1,26 +1,36 @@
 use serde::{Deserialize, Serialize};
+use stwo::prover::backend::simd::conversion::{Pack, Unpack};
 
 use crate::{
     components::{
         add::table::AddTraceTable,
+        add_const::table::AddConstTraceTable,
         contiguous::table::ContiguousTraceTable,
         exp2::table::Exp2TraceTable,
+        fused::table::FusedTraceTable,
+        gather::table::GatherTraceTable,
+        im2col::table::Im2ColTraceTable,
         inputs::table::InputsTraceTable,
         less_than::table::LessThanTraceTable,
         log2::table::Log2TraceTable,
         lookups::{
-            exp2::table::Exp2LookupTraceTable, log2::table::Log2LookupTraceTable, range_check::table::RangeCheckLookupTraceTable,
+            exp2::table::Exp2LookupTraceTable, gather::table::GatherLookupTraceTable,
+            log2::table::Log2LookupTraceTable, range_check::table::RangeCheckLookupTraceTable,
             sin::table::SinLookupTraceTable,
         },
+        matmul::table::MatMulTraceTable,
         max_reduce::table::MaxReduceTraceTable,
         mul::table::MulTraceTable,
+        mul_const::table::MulConstTraceTable,
+        norm::table::{NormTraceTable, NormTraceTableRow},
         recip::table::RecipTraceTable,
         rem::table::RemTraceTable,
+        select::table::SelectTraceTable,
         sin::table::SinTraceTable,
         sqrt::table::SqrtTraceTable,
         sum_reduce::table::SumReduceTraceTable,
     },
-    utils::AtomicMultiplicityColumn,
+    utils::{norm_shard_sizes, shard_sizes, AtomicMultiplicityColumn, PackedRows, TransitionRow},
 };
 
 /// Enumeration of all possible trace table types in LuminAIR
@@ -63,6 +73,24 @@ pub enum TraceTable {
     Inputs { table: InputsTraceTable },
     /// Contiguous operation trace table
     Contiguous { table: ContiguousTraceTable },
+    /// Select (where) operation trace table
+    Select { table: SelectTraceTable },
+    /// Gather operation trace table
+    Gather { table: GatherTraceTable },
+    /// Gather lookup table trace
+    GatherLookup { table: GatherLookupTraceTable },
+    /// Im2col (convolution patch extraction) operation trace table
+    Im2Col { table: Im2ColTraceTable },
+    /// Fused multiply-accumulate (matmul) operation trace table
+    MatMul { table: MatMulTraceTable },
+    /// Fused normalization (LayerNorm / RMSNorm) operation trace table
+    Norm { table: NormTraceTable },
+    /// Multiplication by a constant trace table
+    MulConst { table: MulConstTraceTable },
+    /// Addition of a constant trace table
+    AddConst { table: AddConstTraceTable },
+    /// Fused elementwise expression trace table
+    Fused { table: FusedTraceTable },
 }
 
 impl TraceTable {
@@ -134,6 +162,249 @@ impl TraceTable {
     pub fn from_contiguous(table: ContiguousTraceTable) -> Self {
         Self::Contiguous { table }
     }
+    /// Creates a TraceTable from a SelectTraceTable
+    pub fn from_select(table: SelectTraceTable) -> Self {
+        Self::Select { table }
+    }
+    /// Creates a TraceTable from a GatherTraceTable
+    pub fn from_gather(table: GatherTraceTable) -> Self {
+        Self::Gather { table }
+    }
+    /// Creates a TraceTable from a GatherLookupTraceTable
+    pub fn from_gather_lookup(table: GatherLookupTraceTable) -> Self {
+        Self::GatherLookup { table }
+    }
+    /// Creates a TraceTable from an Im2ColTraceTable
+    pub fn from_im2col(table: Im2ColTraceTable) -> Self {
+        Self::Im2Col { table }
+    }
+    /// Creates a TraceTable from a MatMulTraceTable
+    pub fn from_matmul(table: MatMulTraceTable) -> Self {
+        Self::MatMul { table }
+    }
+    /// Creates a TraceTable from a NormTraceTable
+    pub fn from_norm(table: NormTraceTable) -> Self {
+        Self::Norm { table }
+    }
+    /// Creates a TraceTable from a MulConstTraceTable
+    pub fn from_mul_const(table: MulConstTraceTable) -> Self {
+        Self::MulConst { table }
+    }
+    /// Creates a TraceTable from an AddConstTraceTable
+    pub fn from_add_const(table: AddConstTraceTable) -> Self {
+        Self::AddConst { table }
+    }
+    /// Creates a TraceTable from a FusedTraceTable
+    pub fn from_fused(table: FusedTraceTable) -> Self {
+        Self::Fused { table }
+    }
+
+    /// Name of the component proving the table, e.g. `add` or `sin_lookup`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Add { .. } => "add",
+            Self::Mul { .. } => "mul",
+            Self::Recip { .. } => "recip",
+            Self::Sin { .. } => "sin",
+            Self::SinLookup { .. } => "sin_lookup",
+            Self::SumReduce { .. } => "sum_reduce",
+            Self::MaxReduce { .. } => "max_reduce",
+            Self::Sqrt { .. } => "sqrt",
+            Self::Rem { .. } => "rem",
+            Self::Exp2 { .. } => "exp2",
+            Self::Exp2Lookup { .. } => "exp2_lookup",
+            Self::Log2 { .. } => "log2",
+            Self::Log2Lookup { .. } => "log2_lookup",
+            Self::LessThan { .. } => "less_than",
+            Self::RangeCheckLookup { .. } => "range_check_lookup",
+            Self::Inputs { .. } => "inputs",
+            Self::Contiguous { .. } => "contiguous",
+            Self::Select { .. } => "select",
+            Self::Gather { .. } => "gather",
+            Self::GatherLookup { .. } => "gather_lookup",
+            Self::Im2Col { .. } => "im2col",
+            Self::MatMul { .. } => "matmul",
+            Self::Norm { .. } => "norm",
+            Self::MulConst { .. } => "mul_const",
+            Self::AddConst { .. } => "add_const",
+            Self::Fused { .. } => "fused",
+        }
+    }
+
+    /// Number of rows of the table, before padding
+    pub fn n_rows(&self) -> usize {
+        match self {
+            Self::Add { table } => table.table.len(),
+            Self::Mul { table } => table.table.len(),
+            Self::Recip { table } => table.table.len(),
+            Self::Sin { table } => table.table.len(),
+            Self::SinLookup { table } => table.table.len(),
+            Self::SumReduce { table } => table.table.len(),
+            Self::MaxReduce { table } => table.table.len(),
+            Self::Sqrt { table } => table.table.len(),
+            Self::Rem { table } => table.table.len(),
+            Self::Exp2 { table } => table.table.len(),
+            Self::Exp2Lookup { table } => table.table.len(),
+            Self::Log2 { table } => table.table.len(),
+            Self::Log2Lookup { table } => table.table.len(),
+            Self::LessThan { table } => table.table.len(),
+            Self::RangeCheckLookup { table } => table.table.len(),
+            Self::Inputs { table } => table.table.len(),
+            Self::Contiguous { table } => table.table.len(),
+            Self::Select { table } => table.table.len(),
+            Self::Gather { table } => table.table.len(),
+            Self::GatherLookup { table } => table.table.len(),
+            Self::Im2Col { table } => table.table.len(),
+            Self::MatMul { table } => table.table.len(),
+            Self::Norm { table } => table.table.len(),
+            Self::MulConst { table } => table.table.len(),
+            Self::AddConst { table } => table.table.len(),
+            Self::Fused { table } => table.table.len(),
+        }
+    }
+
+    /// Splits the table into shards of at most `1 << max_log_size` rows, each proven by its
+    /// own component instance with its own log size
+    ///
+    /// Lookups of preprocessed columns hold one row per entry of their column and are never
+    /// split, and normalizations are only split between normalized rows.
+    pub fn shard(self, max_log_size: u32) -> Vec<Self> {
+        match self {
+            Self::Add { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_add(AddTraceTable { table }))
+                .collect(),
+            Self::Mul { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_mul(MulTraceTable { table }))
+                .collect(),
+            Self::Recip { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_recip(RecipTraceTable { table }))
+                .collect(),
+            Self::Sin { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_sin(SinTraceTable { table }))
+                .collect(),
+            Self::SumReduce { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_sum_reduce(SumReduceTraceTable { table }))
+                .collect(),
+            Self::MaxReduce { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_max_reduce(MaxReduceTraceTable { table }))
+                .collect(),
+            Self::Sqrt { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_sqrt(SqrtTraceTable { table }))
+                .collect(),
+            Self::Rem { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_rem(RemTraceTable { table }))
+                .collect(),
+            Self::Exp2 { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_exp2(Exp2TraceTable { table }))
+                .collect(),
+            Self::Log2 { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_log2(Log2TraceTable { table }))
+                .collect(),
+            Self::LessThan { table } => {
+                let node_id = table.node_id;
+                shard_rows(table.table, max_log_size)
+                    .map(|table| Self::from_less_than(LessThanTraceTable { table, node_id }))
+                    .collect()
+            }
+            Self::Inputs { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_inputs(InputsTraceTable { table }))
+                .collect(),
+            Self::Contiguous { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_contiguous(ContiguousTraceTable { table }))
+                .collect(),
+            Self::Select { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_select(SelectTraceTable { table }))
+                .collect(),
+            Self::Gather { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_gather(GatherTraceTable { table }))
+                .collect(),
+            Self::GatherLookup { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_gather_lookup(GatherLookupTraceTable { table }))
+                .collect(),
+            Self::Im2Col { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_im2col(Im2ColTraceTable { table }))
+                .collect(),
+            Self::MatMul { table } => shard_rows(table.table, max_log_size)
+                .map(|table| Self::from_matmul(MatMulTraceTable { table }))
+                .collect(),
+            Self::Norm { table } => {
+                let params = table.params;
+                shard_norm_rows(table.table, params.row_size as usize, max_log_size)
+                    .map(|table| Self::from_norm(NormTraceTable { table, params }))
+                    .collect()
+            }
+            Self::MulConst { table } => {
+                let constant = table.constant;
+                shard_rows(table.table, max_log_size)
+                    .map(|table| Self::from_mul_const(MulConstTraceTable { table, constant }))
+                    .collect()
+            }
+            Self::AddConst { table } => {
+                let constant = table.constant;
+                shard_rows(table.table, max_log_size)
+                    .map(|table| Self::from_add_const(AddConstTraceTable { table, constant }))
+                    .collect()
+            }
+            Self::Fused { table } => {
+                let expr = table.expr;
+                shard_rows(table.table, max_log_size)
+                    .map(|table| {
+                        Self::from_fused(FusedTraceTable {
+                            table,
+                            expr: expr.clone(),
+                        })
+                    })
+                    .collect()
+            }
+            table @ (Self::SinLookup { .. }
+            | Self::Exp2Lookup { .. }
+            | Self::Log2Lookup { .. }
+            | Self::RangeCheckLookup { .. }) => vec![table],
+        }
+    }
+}
+
+/// Splits rows into consecutive shards sized by `shard_sizes`
+///
+/// All shards but the last are powers of two of at least `N_LANES` rows, so the rows are split
+/// between packed SIMD vectors. The next row of the last row of a shard wraps around to its
+/// first one, so the run of rows of an operation cut by a shard ends at the end of the shard.
+fn shard_rows<R>(mut rows: PackedRows<R>, max_log_size: u32) -> impl Iterator<Item = PackedRows<R>>
+where
+    R: Pack + Copy + TransitionRow,
+    R::SimdType: Unpack<CpuType = R> + Copy,
+{
+    let sizes = shard_sizes(rows.len(), max_log_size);
+    let mut shards = Vec::with_capacity(sizes.len());
+    for size in sizes.into_iter().rev() {
+        shards.push(rows.split_off(rows.len() - size));
+        rows.update_last(R::end_run);
+    }
+    shards.into_iter().rev()
+}
+
+/// Splits the rows of normalizations into consecutive shards sized by `norm_shard_sizes`
+///
+/// Shards hold whole normalized rows, whose statistics are chained from row to row. As these
+/// need not be a multiple of `N_LANES` rows, the rows of each shard are repacked.
+fn shard_norm_rows(
+    rows: PackedRows<NormTraceTableRow>,
+    row_size: usize,
+    max_log_size: u32,
+) -> impl Iterator<Item = PackedRows<NormTraceTableRow>> {
+    let sizes = norm_shard_sizes(rows.len(), row_size, max_log_size);
+    if sizes.len() <= 1 {
+        return vec![rows].into_iter();
+    }
+    let mut rows = rows.iter();
+    let mut shards = Vec::with_capacity(sizes.len());
+    for size in sizes {
+        let mut shard = PackedRows::new();
+        rows.by_ref().take(size).for_each(|row| shard.push(row));
+        shard.update_last(NormTraceTableRow::end_run);
+        shards.push(shard);
+    }
+    shards.into_iter()
 }
 
 /// Main structure containing all trace tables and metadata for a LuminAIR computation
@@ -170,7 +441,7 @@ pub struct LUTMultiplicities {
 }
 
 /// Resources consumed during computation execution
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ExecutionResources {
     /// Count of each operation type performed
     pub op_counter: OpCounter,
@@ -178,8 +449,54 @@ pub struct ExecutionResources {
     pub max_log_size: u32,
 }
 
+/// Resources a graph is projected to use when proven, estimated from the shapes of its views
+/// without executing it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResourceEstimate {
+    /// Operation counts and maximum log size the trace of the graph will report
+    pub execution_resources: ExecutionResources,
+    /// Rows each operation adds to the table of its component, in execution order
+    pub ops: Vec<OpEstimate>,
+    /// Component instances proving the trace, one per shard of each table
+    pub components: Vec<ComponentEstimate>,
+    /// Log sizes of the preprocessed lookup table columns
+    pub preprocessed_log_sizes: Vec<u32>,
+    /// Number of base field values of the main and interaction traces, padding included
+    pub trace_cells: usize,
+    /// Projected peak memory of the prover, in bytes
+    pub prover_memory: usize,
+    /// Projected upper bound on the size of the proof, in bytes
+    pub proof_size: usize,
+}
+
+/// Trace rows added by an operation of the graph
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OpEstimate {
+    /// Index of the node of the operation
+    pub node: u32,
+    /// Component proving the operation, named after its `OpCounter` field
+    pub component: String,
+    /// Number of rows added to the table of the component
+    pub rows: usize,
+}
+
+/// Size of a component instance of the proof
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ComponentEstimate {
+    /// Name of the component, e.g. `add` or `sin_lookup`
+    pub component: String,
+    /// Number of rows of the instance, before padding
+    pub rows: usize,
+    /// Log size of the instance
+    pub log_size: u32,
+    /// Number of columns of the main trace
+    pub main_columns: usize,
+    /// Number of base field columns of the interaction trace
+    pub interaction_columns: usize,
+}
+
 /// Counter for different operation types performed during computation
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
 pub struct OpCounter {
     /// Number of addition operations
     pub add: usize,
@@ -207,6 +524,52 @@ pub struct OpCounter {
     pub inputs: usize,
     /// Number of contiguous operations
     pub contiguous: usize,
+    /// Number of select (where) operations
+    pub select: usize,
+    /// Number of gather operations
+    pub gather: usize,
+    /// Number of im2col operations
+    pub im2col: usize,
+    /// Number of fused multiply-accumulate (matmul) operations
+    pub matmul: usize,
+    /// Number of fused normalization operations
+    pub norm: usize,
+    /// Number of multiplications by a constant
+    pub mul_const: usize,
+    /// Number of additions of a constant
+    pub add_const: usize,
+    /// Number of fused elementwise operations
+    pub fused: usize,
+    /// Number of trace rows saved by removing operations from the graph, when counting the
+    /// operations removed by an optimization pass
+    pub rows_saved: usize,
+}
+
+impl std::ops::AddAssign for OpCounter {
+    fn add_assign(&mut self, other: Self) {
+        self.add += other.add;
+        self.mul += other.mul;
+        self.recip += other.recip;
+        self.sin += other.sin;
+        self.sum_reduce += other.sum_reduce;
+        self.max_reduce += other.max_reduce;
+        self.sqrt += other.sqrt;
+        self.rem += other.rem;
+        self.exp2 += other.exp2;
+        self.log2 += other.log2;
+        self.less_than += other.less_than;
+        self.inputs += other.inputs;
+        self.contiguous += other.contiguous;
+        self.select += other.select;
+        self.gather += other.gather;
+        self.im2col += other.im2col;
+        self.matmul += other.matmul;
+        self.norm += other.norm;
+        self.mul_const += other.mul_const;
+        self.add_const += other.add_const;
+        self.fused += other.fused;
+        self.rows_saved += other.rows_saved;
+    }
 }
 
 /// Information about an input tensor in the computation graph
@@ -123,7 +123,7 @@ fn value_count(ranges: &Vec<Range>) -> u32 {
 ///
 /// Defines the interface for generating preprocessed lookup table columns
 /// that are used during the proving process
-pub trait PreProcessedColumn: Any {
+pub trait PreProcessedColumn: Any + Send + Sync {
     /// Returns the logarithmic size of the column
     fn log_size(&self) -> u32;
     /// Returns a unique identifier for this column
@@ -172,6 +172,26 @@ impl PreProcessedTrace {
             .filter_map(|c| c.as_any().downcast_ref::<T>())
             .collect()
     }
+
+    /// Returns the values of the columns of type `T`, ordered by `col_index`
+    ///
+    /// `evals` are the columns generated by `gen_trace`.
+    pub fn values_of<'a, T: Any>(
+        &self,
+        evals: &'a TraceEval,
+        col_index: impl Fn(&T) -> usize,
+    ) -> Vec<&'a BaseColumn> {
+        self.columns
+            .iter()
+            .zip(evals)
+            .filter_map(|(c, eval)| {
+                let column = c.as_any().downcast_ref::<T>()?;
+                Some((col_index(column), &eval.values))
+            })
+            .sorted_by_key(|(index, _)| *index)
+            .map(|(_, values)| values)
+            .collect()
+    }
 }
 
 /// Converts lookup table configurations to preprocessed columns
@@ -0,0 +1,132 @@
+//! Segments of a graph proven independently
+//!
+//! A graph too large for one trace is split into segments of consecutive nodes, each with
+//! its own PIE and proof. A tensor produced before a cut and consumed after it crosses the
+//! cut as a public boundary tensor: the producing segment consumes it once as an output, and
+//! the next segment yields it back as an input, so the LogUp sum of each segment is balanced
+//! by its boundary. The verifier checks that the outputs of each segment are the inputs of
+//! the next one.
+//!
+//! The inputs and outputs of an execution of a whole graph are bound to its proof the same
+//! way: the verifier yields the runtime inputs in place of the traced copies, and consumes the
+//! retrieved outputs once each.
+
+use num_traits::Zero;
+use numerair::Fixed;
+use serde::{Deserialize, Serialize};
+use stwo::core::{
+    channel::Channel,
+    fields::{
+        m31::{M31, P},
+        qm31::SecureField,
+        FieldExpOps,
+    },
+    vcs::blake2_hash::Blake2sHasher,
+};
+
+use crate::{components::NodeElements, pie::LuminairPie, DEFAULT_FP_SCALE};
+
+/// Tensor crossing a cut between two segments
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BoundaryTensor {
+    /// Id of the node producing the tensor
+    pub node_id: u32,
+    /// Fixed-point values of the tensor
+    pub values: Vec<M31>,
+}
+
+impl BoundaryTensor {
+    /// Decodes the fixed-point values of the tensor, negative values being the upper half of
+    /// the field
+    pub fn to_f32(&self) -> Vec<f32> {
+        self.values
+            .iter()
+            .map(|val| {
+                let raw = if val.0 > P / 2 {
+                    val.0 as i64 - P as i64
+                } else {
+                    val.0 as i64
+                };
+                Fixed::<DEFAULT_FP_SCALE>(raw).to_f64() as f32
+            })
+            .collect()
+    }
+}
+
+/// Boundary tensor taken by a segment from the previous ones
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BoundaryInput {
+    pub tensor: BoundaryTensor,
+    /// Number of times the segment consumes each value, plus one when the tensor is passed
+    /// on to the next segment
+    pub multiplicity: u32,
+}
+
+/// Public inputs and outputs of a segment, or of an execution of a whole graph
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SegmentBoundary {
+    /// Tensors produced by the previous segments
+    pub inputs: Vec<BoundaryInput>,
+    /// Tensors consumed by the next segments
+    pub outputs: Vec<BoundaryTensor>,
+}
+
+impl SegmentBoundary {
+    /// Commitment to the tensors the segment takes from the previous one
+    pub fn inputs_digest(&self) -> [u8; 32] {
+        tensors_digest(self.inputs.iter().map(|input| &input.tensor).collect())
+    }
+
+    /// Commitment to the tensors the segment passes to the next one
+    pub fn outputs_digest(&self) -> [u8; 32] {
+        tensors_digest(self.outputs.iter().collect())
+    }
+
+    /// Mixes the boundary into the channel, binding the proof of the segment to it
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        for digest in [self.inputs_digest(), self.outputs_digest()] {
+            for word in digest.chunks_exact(8) {
+                channel.mix_u64(u64::from_le_bytes(word.try_into().unwrap()));
+            }
+        }
+        for input in &self.inputs {
+            channel.mix_u64(input.multiplicity.into());
+        }
+    }
+
+    /// LogUp sum of the boundary: the values yielded by the inputs minus the values consumed
+    /// by the outputs
+    ///
+    /// Added to the claimed sums of the segment's components, it must give zero.
+    pub fn logup_sum(&self, node_elements: &NodeElements) -> SecureField {
+        let fraction = |node_id: u32, val: M31| -> SecureField {
+            let denom: SecureField = node_elements.combine(&[val, M31::from(node_id)]);
+            denom.inverse()
+        };
+
+        let mut sum = SecureField::zero();
+        for input in &self.inputs {
+            let multiplicity = M31::from(input.multiplicity);
+            for &val in &input.tensor.values {
+                sum += fraction(input.tensor.node_id, val) * multiplicity;
+            }
+        }
+        for output in &self.outputs {
+            for &val in &output.values {
+                sum -= fraction(output.node_id, val);
+            }
+        }
+        sum
+    }
+}
+
+/// PIE of a segment with its boundary
+pub struct LuminairSegment {
+    pub pie: LuminairPie,
+    pub boundary: SegmentBoundary,
+}
+
+fn tensors_digest(tensors: Vec<&BoundaryTensor>) -> [u8; 32] {
+    let data = bincode::serialize(&tensors).expect("Boundary tensors always serialize");
+    Blake2sHasher::hash(&data).0
+}
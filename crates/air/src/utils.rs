@@ -1,14 +1,20 @@
 use std::sync::atomic::{AtomicU32, Ordering};
 
 use num_traits::Zero;
+use rayon::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use stwo::{
-    core::{channel::MerkleChannel, fields::m31::M31, pcs::TreeSubspan},
+    core::{
+        channel::MerkleChannel,
+        fields::{m31::M31, qm31::SecureField},
+        pcs::TreeSubspan,
+        utils::bit_reverse_index,
+    },
     prover::{
         backend::{
             simd::{
-                conversion::Pack,
+                conversion::{Pack, Unpack},
                 m31::{LOG_N_LANES, N_LANES},
-                qm31::PackedSecureField,
             },
             Backend, BackendForChannel,
         },
@@ -26,34 +32,52 @@ pub fn calculate_log_size(max_size: usize) -> u32 {
         + LOG_N_LANES
 }
 
-pub fn log_sum_valid(interaction_claim: &LuminairInteractionClaim) -> bool {
-    let mut sum = PackedSecureField::zero();
-
-    for claim_opt in [
-        &interaction_claim.add,
-        &interaction_claim.mul,
-        &interaction_claim.sum_reduce,
-        &interaction_claim.recip,
-        &interaction_claim.max_reduce,
-        &interaction_claim.sin,
-        &interaction_claim.sin_lookup,
-        &interaction_claim.sqrt,
-        &interaction_claim.rem,
-        &interaction_claim.exp2,
-        &interaction_claim.exp2_lookup,
-        &interaction_claim.log2,
-        &interaction_claim.log2_lookup,
-        &interaction_claim.less_than,
-        &interaction_claim.range_check_lookup,
-        &interaction_claim.inputs,
-        &interaction_claim.contiguous,
-    ] {
-        if let Some(ref int_cl) = claim_opt {
-            sum += int_cl.claimed_sum.into();
+/// Row counts of the shards a table of `n_rows` rows is split into, in order
+///
+/// Shards hold at most `1 << max_log_size` rows. The rows left over form one more shard when
+/// padding it to a power of two wastes at most a quarter of it, and are otherwise split again
+/// at the largest power of two they exceed.
+pub fn shard_sizes(n_rows: usize, max_log_size: u32) -> Vec<usize> {
+    let max_rows = 1 << max_log_size.max(LOG_N_LANES);
+    let mut sizes = vec![max_rows; n_rows / max_rows];
+    let mut rest = n_rows % max_rows;
+    while rest > 0 {
+        let padded = rest.next_power_of_two().max(N_LANES);
+        if padded == N_LANES || 4 * (padded - rest) <= padded {
+            sizes.push(rest);
+            break;
         }
+        sizes.push(padded / 2);
+        rest -= padded / 2;
+    }
+    sizes
+}
+
+/// Row counts of the shards the rows of normalizations of `row_size` values each are split
+/// into, in order
+///
+/// Shards hold whole normalized rows, as many as fit in `1 << max_log_size` rows and at least
+/// one, so a shard may exceed `1 << max_log_size` rows when a normalized row does.
+pub fn norm_shard_sizes(n_rows: usize, row_size: usize, max_log_size: u32) -> Vec<usize> {
+    let max_rows = 1 << max_log_size.max(LOG_N_LANES);
+    let row_size = row_size.max(1);
+    let shard_rows = (max_rows / row_size).max(1) * row_size;
+    let mut sizes = vec![shard_rows; n_rows / shard_rows];
+    if n_rows % shard_rows > 0 {
+        sizes.push(n_rows % shard_rows);
     }
+    sizes
+}
 
-    sum.is_zero()
+pub fn log_sum_valid(interaction_claim: &LuminairInteractionClaim) -> bool {
+    log_sum(interaction_claim).is_zero()
+}
+
+/// Sum of the claimed LogUp sums of all components
+pub fn log_sum(interaction_claim: &LuminairInteractionClaim) -> SecureField {
+    interaction_claim
+        .claims()
+        .fold(SecureField::zero(), |sum, claim| sum + claim.claimed_sum)
 }
 
 pub fn pack_values<T: Pack>(values: &[T]) -> Vec<T::SimdType> {
@@ -63,6 +87,229 @@ pub fn pack_values<T: Pack>(values: &[T]) -> Vec<T::SimdType> {
         .collect()
 }
 
+/// Coset index of the point at `circle_domain_index` of a circle domain of size
+/// `1 << log_domain_size`, the inverse of `coset_index_to_circle_domain_index`
+pub fn circle_domain_index_to_coset_index(
+    circle_domain_index: usize,
+    log_domain_size: u32,
+) -> usize {
+    let half_size = 1 << (log_domain_size - 1);
+    if circle_domain_index < half_size {
+        circle_domain_index * 2
+    } else {
+        (2 << log_domain_size) - circle_domain_index * 2 - 1
+    }
+}
+
+/// Row of a table whose component constrains each row against the next one
+pub trait TransitionRow {
+    /// Ends the run of rows of an operation at this row, so that it isn't constrained against
+    /// the next row
+    fn end_run(&mut self);
+}
+
+/// Rows of a trace table, packed into SIMD vectors as they are recorded
+///
+/// Full groups of `N_LANES` rows are packed right away and the last incomplete group is kept
+/// unpacked, so the witness of a component is written from the packed rows without copying the
+/// table. Rows are serialized one by one, like a `Vec` of rows.
+pub struct PackedRows<R: Pack> {
+    packed: Vec<R::SimdType>,
+    tail: Vec<R>,
+}
+
+impl<R: Pack> Default for PackedRows<R> {
+    fn default() -> Self {
+        Self {
+            packed: Vec::new(),
+            tail: Vec::with_capacity(N_LANES),
+        }
+    }
+}
+
+impl<R> PackedRows<R>
+where
+    R: Pack + Copy,
+    R::SimdType: Unpack<CpuType = R> + Copy,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of rows, before padding
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.packed.len() * N_LANES + self.tail.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.packed.is_empty() && self.tail.is_empty()
+    }
+
+    pub fn push(&mut self, row: R) {
+        self.tail.push(row);
+        if self.tail.len() == N_LANES {
+            self.packed
+                .push(R::pack(std::array::from_fn(|i| self.tail[i])));
+            self.tail.clear();
+        }
+    }
+
+    /// Moves all the rows of `other` after the rows of `self`
+    pub fn append(&mut self, other: Self) {
+        if self.tail.is_empty() {
+            self.packed.extend(other.packed);
+            self.tail = other.tail;
+        } else {
+            other.iter().for_each(|row| self.push(row));
+        }
+    }
+
+    /// Applies `f` to the last row, if any
+    pub fn update_last(&mut self, f: impl FnOnce(&mut R)) {
+        if let Some(row) = self.tail.last_mut() {
+            f(row);
+        } else if let Some(packed) = self.packed.last_mut() {
+            let mut rows = packed.unpack();
+            f(&mut rows[N_LANES - 1]);
+            *packed = R::pack(rows);
+        }
+    }
+
+    /// Iterates over the rows, unpacking them
+    pub fn iter(&self) -> impl Iterator<Item = R> + '_ {
+        self.packed
+            .iter()
+            .flat_map(|packed| packed.unpack())
+            .chain(self.tail.iter().copied())
+    }
+
+    /// Splits the rows in two at `at`, which must be a multiple of `N_LANES`
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert_eq!(
+            at % N_LANES,
+            0,
+            "rows can only be split between SIMD vectors"
+        );
+        Self {
+            packed: self.packed.split_off(at / N_LANES),
+            tail: std::mem::replace(&mut self.tail, Vec::with_capacity(N_LANES)),
+        }
+    }
+
+    /// Consumes the rows, padded with `padding` to a power of two of at least `N_LANES` rows
+    pub fn into_packed(mut self, padding: R) -> Vec<R::SimdType> {
+        let size = std::cmp::max(self.len().next_power_of_two(), N_LANES);
+        while !self.tail.is_empty() {
+            self.push(padding);
+        }
+        self.packed
+            .resize(size / N_LANES, R::pack([padding; N_LANES]));
+        self.packed
+    }
+
+    /// Consumes the rows like `into_packed`, laid out so that the `i`-th row is evaluated at the
+    /// `i`-th point of the canonic coset of the trace
+    ///
+    /// Trace columns are stored in bit-reversed circle domain order, while mask offsets step
+    /// along the coset, so in this layout the row at offset 1 of each row is the next one.
+    pub fn into_packed_coset_order(self, padding: R) -> Vec<R::SimdType>
+    where
+        R: Send + Sync,
+        R::SimdType: Send,
+    {
+        let size = std::cmp::max(self.len().next_power_of_two(), N_LANES);
+        let log_size = size.ilog2();
+        let rows: Vec<R> = self
+            .packed
+            .into_par_iter()
+            .flat_map_iter(|packed| packed.unpack())
+            .chain(self.tail)
+            .collect();
+
+        (0..size >> LOG_N_LANES)
+            .into_par_iter()
+            .map(|vec_row| {
+                R::pack(std::array::from_fn(|lane| {
+                    let index = bit_reverse_index((vec_row << LOG_N_LANES) + lane, log_size);
+                    let row = circle_domain_index_to_coset_index(index, log_size);
+                    rows.get(row).copied().unwrap_or(padding)
+                }))
+            })
+            .collect()
+    }
+}
+
+impl<R> ParallelExtend<R> for PackedRows<R>
+where
+    R: Pack + Copy + Send + Sync,
+    R::SimdType: Unpack<CpuType = R> + Copy + Send,
+{
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = R>,
+    {
+        let rows: Vec<R> = par_iter.into_par_iter().collect();
+        let n_head = ((N_LANES - self.tail.len()) % N_LANES).min(rows.len());
+        let (head, rows) = rows.split_at(n_head);
+        head.iter().for_each(|row| self.push(*row));
+
+        let chunks = rows.par_chunks_exact(N_LANES);
+        let rest = chunks.remainder();
+        self.packed
+            .par_extend(chunks.map(|chunk| R::pack(std::array::from_fn(|i| chunk[i]))));
+        rest.iter().for_each(|row| self.push(*row));
+    }
+}
+
+impl<R> Clone for PackedRows<R>
+where
+    R: Pack + Clone,
+    R::SimdType: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            packed: self.packed.clone(),
+            tail: self.tail.clone(),
+        }
+    }
+}
+
+impl<R> std::fmt::Debug for PackedRows<R>
+where
+    R: Pack + Copy + std::fmt::Debug,
+    R::SimdType: Unpack<CpuType = R> + Copy,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<R> Serialize for PackedRows<R>
+where
+    R: Pack + Copy + Serialize,
+    R::SimdType: Unpack<CpuType = R> + Copy,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, R> Deserialize<'de> for PackedRows<R>
+where
+    R: Pack + Copy + Deserialize<'de>,
+    R::SimdType: Unpack<CpuType = R> + Copy,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut rows = Self::new();
+        Vec::<R>::deserialize(deserializer)?
+            .into_iter()
+            .for_each(|row| rows.push(row));
+        Ok(rows)
+    }
+}
+
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct AtomicMultiplicityColumn {
     pub data: Vec<AtomicU32>,
@@ -76,7 +323,7 @@ impl AtomicMultiplicityColumn {
     }
 
     #[inline]
-    pub fn increase_at(&mut self, address: usize) {
+    pub fn increase_at(&self, address: usize) {
         self.data[address].fetch_add(1, Ordering::Relaxed);
     }
 
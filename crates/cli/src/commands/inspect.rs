@@ -0,0 +1,135 @@
+use std::path::Path;
+
+use luminair_air::components::InteractionClaim;
+use luminair_prover::bundle::{BundleSettings, ProofBundle, BUNDLE_VERSION};
+use stwo_constraint_framework::{INTERACTION_TRACE_IDX, ORIGINAL_TRACE_IDX};
+
+use super::{read_proof, ProofFile};
+
+/// Prints the claims, component log sizes and proof size breakdown of `proof`, along with
+/// the header, public IO and operator counts of proof bundles
+pub fn run(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let file_size = std::fs::metadata(path)?.len();
+    let file = read_proof(path)?;
+
+    println!("Proof: {} ({} bytes)", path.display(), file_size);
+    if let ProofFile::Bundle(bundle) = &file {
+        print_bundle(bundle)?;
+    }
+    let proof = file.proof();
+
+    // Claims hold one entry per shard of each component, so walk their serialized form
+    // rather than listing every component here
+    let claims = serde_json::to_value(&proof.claim)?;
+    let interaction_claims = serde_json::to_value(&proof.interaction_claim)?;
+
+    println!();
+    println!(
+        "{:<16} {:>8} {:>10}  claimed sum",
+        "component", "log size", "rows"
+    );
+    for (name, shards) in claims.as_object().into_iter().flatten() {
+        let shards = shards.as_array().map(Vec::as_slice).unwrap_or_default();
+        for (index, claim) in shards.iter().enumerate() {
+            let Some(log_size) = claim.get("log_size").and_then(|v| v.as_u64()) else {
+                continue;
+            };
+            let claimed_sum = match interaction_claims.get(name).and_then(|v| v.get(index)) {
+                Some(value) => {
+                    let claim: InteractionClaim = serde_json::from_value(value.clone())?;
+                    claim.claimed_sum.to_string()
+                }
+                None => "-".to_string(),
+            };
+            let name = if shards.len() > 1 {
+                format!("{}[{}]", name, index)
+            } else {
+                name.clone()
+            };
+            println!(
+                "{:<16} {:>8} {:>10}  {}",
+                name,
+                log_size,
+                1u64 << log_size,
+                claimed_sum
+            );
+        }
+    }
+
+    let log_sizes = proof.claim.log_sizes();
+    println!();
+    println!(
+        "Main trace columns:        {}",
+        log_sizes[ORIGINAL_TRACE_IDX].len()
+    );
+    println!(
+        "Interaction trace columns: {}",
+        log_sizes[INTERACTION_TRACE_IDX].len()
+    );
+
+    let breakdown = proof.proof.size_breakdown_estimate();
+    println!();
+    println!("Proof size breakdown (estimate, bytes):");
+    println!("  OODS samples:        {}", breakdown.oods_samples);
+    println!("  Queried values:      {}", breakdown.queries_values);
+    println!("  FRI samples:         {}", breakdown.fri_samples);
+    println!("  FRI decommitments:   {}", breakdown.fri_decommitments);
+    println!("  Trace decommitments: {}", breakdown.trace_decommitments);
+    println!("  Total:               {}", proof.proof.size_estimate());
+
+    Ok(())
+}
+
+/// Prints what a bundle holds besides its proof
+fn print_bundle(bundle: &ProofBundle) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Bundle format version:     {}", BUNDLE_VERSION);
+    println!(
+        "LuminAIR version:          {}",
+        bundle.metadata.luminair_version
+    );
+    match &bundle.settings {
+        BundleSettings::Embedded(_) => println!("Circuit settings:          embedded"),
+        BundleSettings::Digest(digest) => {
+            let digest = digest
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<String>();
+            println!("Circuit settings digest:   {}", digest);
+        }
+    }
+    if let Some(max_log_size) = bundle.metadata.max_log_size {
+        println!("Max log size:              {}", max_log_size);
+    }
+
+    let batch_size = bundle.public_io.len();
+    if batch_size == 0 {
+        println!("Public IO:                 none, the proof isn't bound to any");
+    }
+    if batch_size > 1 {
+        println!("Batch size:                {}", batch_size);
+    }
+    for (instance, io) in bundle.public_io.iter().enumerate() {
+        if batch_size > 1 {
+            println!("Execution {}:", instance);
+        }
+        for (kind, tensors) in [("Input", io.inputs()), ("Output", io.outputs())] {
+            for tensor in tensors {
+                println!("{} {}: {} values", kind, tensor.name, tensor.data.len());
+            }
+        }
+    }
+
+    // Only operators present in the graph are listed
+    if let Some(op_counter) = &bundle.metadata.op_counter {
+        println!();
+        println!("{:<16} {:>8}", "operator", "count");
+        let counts = serde_json::to_value(op_counter)?;
+        for (name, count) in counts.as_object().into_iter().flatten() {
+            if let Some(count) = count.as_u64().filter(|&count| count > 0) {
+                println!("{:<16} {:>8}", name, count);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! Subcommands of the `luminair` tool and the file helpers they share
+
+use luminair_air::settings::CircuitSettings;
+use luminair_graph::ir::GraphIr;
+use luminair_prover::{bundle::ProofBundle, LuminairProof};
+use luminair_utils::LuminairError;
+use luminal::prelude::*;
+use std::{collections::HashMap, fs::File, io::BufReader, path::Path};
+use stwo::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+
+pub mod inspect;
+pub mod prove;
+pub mod settings;
+pub mod verify;
+
+/// Returns true if the file should be read or written as JSON rather than bincode
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+/// A proof read from a file, either bundled or bare
+pub(crate) enum ProofFile {
+    Bundle(Box<ProofBundle>),
+    Proof(LuminairProof<Blake2sMerkleHasher>),
+}
+
+impl ProofFile {
+    /// Returns the proof itself
+    pub(crate) fn proof(&self) -> &LuminairProof<Blake2sMerkleHasher> {
+        match self {
+            ProofFile::Bundle(bundle) => &bundle.proof,
+            ProofFile::Proof(proof) => proof,
+        }
+    }
+}
+
+/// Reads a proof bundle, or a bare proof from a bincode or JSON file
+pub(crate) fn read_proof(path: &Path) -> Result<ProofFile, LuminairError> {
+    if is_json(path) {
+        return LuminairProof::from_json_file(path).map(ProofFile::Proof);
+    }
+    let data = std::fs::read(path).map_err(|e| {
+        LuminairError::SerializationError(format!("Failed to read bincode file: {}", e))
+    })?;
+    if ProofBundle::is_bundle(&data) {
+        ProofBundle::from_bytes(&data).map(|bundle| ProofFile::Bundle(Box::new(bundle)))
+    } else {
+        LuminairProof::from_bincode(&data).map(ProofFile::Proof)
+    }
+}
+
+/// Writes a proof bundle, or its bare proof to a JSON file
+pub(crate) fn write_proof(bundle: &ProofBundle, path: &Path) -> Result<(), LuminairError> {
+    if is_json(path) {
+        bundle.proof.to_json_file(path)
+    } else {
+        bundle.to_file(path)
+    }
+}
+
+/// Reads circuit settings from a bincode or JSON file
+pub(crate) fn read_settings(path: &Path) -> Result<CircuitSettings, LuminairError> {
+    if is_json(path) {
+        CircuitSettings::from_json_file(path)
+    } else {
+        CircuitSettings::from_bincode_file(path)
+    }
+}
+
+/// Writes circuit settings to a bincode or JSON file
+pub(crate) fn write_settings(settings: &CircuitSettings, path: &Path) -> Result<(), LuminairError> {
+    if is_json(path) {
+        settings.to_json_file(path)
+    } else {
+        settings.to_bincode_file(path)
+    }
+}
+
+/// Loads a compiled graph from a bincode or JSON graph IR file, setting its runtime inputs
+/// from `inputs`, matched by name
+///
+/// Returns the graph with its runtime inputs, in serialized order.
+pub(crate) fn read_graph(
+    path: &Path,
+    inputs: HashMap<String, Vec<f32>>,
+) -> Result<(Graph, Vec<(String, NodeIndex)>), LuminairError> {
+    let mut ir = if is_json(path) {
+        GraphIr::from_json_file(path)
+    } else {
+        GraphIr::from_bincode_file(path)
+    }?;
+    let runtime_inputs = ir.set_inputs(inputs)?;
+    Ok((ir.to_graph()?, runtime_inputs))
+}
+
+/// Reads a JSON file mapping input tensor names to their flattened values
+pub(crate) fn read_inputs(path: &Path) -> Result<HashMap<String, Vec<f32>>, LuminairError> {
+    let file = File::open(path).map_err(|e| {
+        LuminairError::SerializationError(format!("Failed to open file: {}", e))
+    })?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|e| {
+        LuminairError::SerializationError(format!("Failed to read inputs from JSON file: {}", e))
+    })
+}
+
+/// Returns the retrieved outputs of an executed graph, ordered by node
+pub(crate) fn outputs(graph: &Graph) -> Vec<(NodeIndex, Vec<f32>)> {
+    let mut nodes = graph.to_retrieve.keys().copied().collect::<Vec<_>>();
+    nodes.sort();
+    nodes
+        .into_iter()
+        .filter_map(|node| {
+            let data = graph.get_tensor_ref(node, 0)?.downcast_ref::<Vec<f32>>()?;
+            Some((node, data.clone()))
+        })
+        .collect()
+}
@@ -0,0 +1,96 @@
+use std::{path::Path, time::Instant};
+
+use luminair_graph::graph::LuminairGraph;
+use luminair_prover::{
+    bundle::{BundleMetadata, ProofBundle, PublicIo},
+    prover::prove_with_io_and_metrics,
+};
+
+use super::{
+    is_json, outputs, read_graph, read_inputs, read_settings, write_proof, write_settings,
+};
+
+/// Executes the serialized `graph` on `inputs`, proves the execution and writes the proof
+/// bundle to `output`
+///
+/// The proof is bound to the runtime inputs and the retrieved outputs, held by the bundle. A
+/// bare JSON proof has no room for them and isn't bound to any.
+///
+/// The proving metrics are written as JSON to `metrics` when given.
+///
+/// The circuit settings are read from `settings` when the file exists, otherwise they are
+/// generated from this execution and written there. They are embedded in the bundle unless
+/// `settings_digest` is set, in which case only their digest is.
+pub fn run(
+    graph: &Path,
+    inputs: &Path,
+    settings: Option<&Path>,
+    settings_digest: bool,
+    output: &Path,
+    metrics: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (mut graph, runtime_inputs) = read_graph(graph, read_inputs(inputs)?)?;
+    let (input_names, input_nodes): (Vec<_>, Vec<_>) = runtime_inputs.into_iter().unzip();
+
+    let mut settings = match settings {
+        Some(path) if path.exists() => read_settings(path)?,
+        _ => {
+            println!("Generating Circuit Settings...");
+            let generated = graph.gen_circuit_settings();
+            if let Some(path) = settings {
+                write_settings(&generated, path)?;
+            }
+            // Settings generation executes the graph, drop its outputs before tracing
+            let retrieved = graph.to_retrieve.keys().copied().collect::<Vec<_>>();
+            graph.drop_tensors(retrieved);
+            println!("✅ Circuit Settings generated");
+            generated
+        }
+    };
+
+    // Generate execution trace
+    let timing_start = Instant::now();
+    println!("Generating Trace...");
+    let (trace, mut io) = if is_json(output) {
+        (graph.gen_trace(&mut settings)?, Vec::new())
+    } else {
+        let (trace, boundary) = graph.gen_trace_with_io(&mut settings, &input_nodes)?;
+        (trace, vec![boundary])
+    };
+    println!("✅ Trace generated in {:?}", timing_start.elapsed());
+    let metadata = BundleMetadata::from_pie(&trace);
+
+    // Generate ZK proof
+    let timing_start = Instant::now();
+    println!("Generating Proof...");
+    let (proof, proving_metrics) = prove_with_io_and_metrics(trace, settings.clone(), &io)?;
+    println!("✅ Proof generated in {:?}", timing_start.elapsed());
+    if let Some(path) = metrics {
+        proving_metrics.to_json_file(path)?;
+        println!("Proving metrics written to {}", path.display());
+    }
+
+    for (node, data) in outputs(&graph) {
+        println!("Output {}: {:?}", node.index(), data);
+    }
+
+    let bundle = if settings_digest {
+        ProofBundle::with_settings_digest(proof, &settings)?
+    } else {
+        ProofBundle::new(proof, settings)
+    };
+    let mut bundle = bundle.with_metadata(metadata);
+    if let Some(boundary) = io.pop() {
+        // Outputs are named after their node
+        let output_names = boundary
+            .outputs
+            .iter()
+            .map(|tensor| tensor.node_id.to_string())
+            .collect();
+        bundle = bundle.with_public_io(PublicIo::new(input_names, output_names, boundary)?);
+    }
+    write_proof(&bundle, output)?;
+    println!("Proof written to {}", output.display());
+
+    Ok(())
+}
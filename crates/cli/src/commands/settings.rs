@@ -0,0 +1,18 @@
+use std::path::Path;
+
+use luminair_graph::graph::LuminairGraph;
+
+use super::{read_graph, read_inputs, write_settings};
+
+/// Regenerates the circuit settings of the serialized `graph`, using `inputs` to size the
+/// lookup tables, and writes them to `output`
+pub fn run(graph: &Path, inputs: &Path, output: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let (mut graph, _) = read_graph(graph, read_inputs(inputs)?)?;
+
+    println!("Generating Circuit Settings...");
+    let settings = graph.gen_circuit_settings();
+    write_settings(&settings, output)?;
+    println!("✅ Circuit Settings written to {}", output.display());
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use std::{path::Path, time::Instant};
+
+use luminair_verifier::verifier::{verify, verify_bundle};
+
+use super::{read_proof, read_settings, ProofFile};
+
+/// Verifies `proof` against `settings`, failing if the proof is invalid
+///
+/// Settings are optional for proof bundles embedding theirs, and checked against the bundle
+/// when given.
+pub fn run(proof: &Path, settings: Option<&Path>) -> Result<(), Box<dyn std::error::Error>> {
+    let proof = read_proof(proof)?;
+    let settings = settings.map(read_settings).transpose()?;
+
+    let timing_start = Instant::now();
+    println!("Verifying Proof...");
+    match proof {
+        ProofFile::Bundle(bundle) => verify_bundle(*bundle, settings)?,
+        ProofFile::Proof(proof) => {
+            let settings =
+                settings.ok_or("Circuit settings are required to verify a bare proof")?;
+            verify(proof, settings)?
+        }
+    }
+    println!("✅ Proof verified in {:?}", timing_start.elapsed());
+
+    Ok(())
+}
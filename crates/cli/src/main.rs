@@ -0,0 +1,110 @@
+//! `luminair` command-line tool
+//!
+//! Proves, verifies and inspects LuminAIR computations without writing a Rust `main`.
+
+use clap::{arg, value_parser, Command};
+use luminair_prover::metrics::PeakAllocator;
+use std::path::PathBuf;
+
+mod commands;
+
+// Tracks the peak heap usage reported in the proving metrics
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+fn cli() -> Command {
+    Command::new("luminair")
+        .about("Prove, verify and inspect LuminAIR computations")
+        .version(env!("CARGO_PKG_VERSION"))
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(
+            Command::new("verify")
+                .about("Verifies a proof bundle, or a proof against its circuit settings")
+                .arg(
+                    arg!(<PROOF> "Proof bundle or proof file")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    arg!([SETTINGS] "Circuit settings file, optional for self-contained bundles")
+                        .value_parser(value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            Command::new("inspect")
+                .about("Prints the claims, component log sizes and size breakdown of a proof")
+                .arg(
+                    arg!(<PROOF> "Proof bundle or proof file")
+                        .value_parser(value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            Command::new("prove")
+                .about("Executes a serialized graph on the given inputs and proves it")
+                .arg(arg!(<GRAPH> "Serialized graph file").value_parser(value_parser!(PathBuf)))
+                .arg(
+                    arg!(<INPUTS> "JSON file mapping input names to values")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    arg!(-s --settings <FILE> "Circuit settings file, generated when missing")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(arg!(--"settings-digest" "Store only the settings digest in the bundle"))
+                .arg(
+                    arg!(-o --output <FILE> "Where to write the proof bundle")
+                        .value_parser(value_parser!(PathBuf))
+                        .default_value("proof.bin"),
+                )
+                .arg(
+                    arg!(--metrics <FILE> "Where to write the proving metrics as JSON")
+                        .value_parser(value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            Command::new("settings")
+                .about("Regenerates the circuit settings of a serialized graph from sample inputs")
+                .arg(arg!(<GRAPH> "Serialized graph file").value_parser(value_parser!(PathBuf)))
+                .arg(
+                    arg!(<INPUTS> "JSON file mapping input names to values")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    arg!(-o --output <FILE> "Where to write the settings")
+                        .value_parser(value_parser!(PathBuf))
+                        .default_value("settings.bin"),
+                ),
+        )
+        .after_help(
+            "Proofs are written as self-contained bundles, or as bare JSON proofs when the \
+             output extension is `.json`. Settings and graph files are read and written as \
+             bincode, or as JSON when their extension is `.json`.",
+        )
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = cli().get_matches();
+    let path = |matches: &clap::ArgMatches, id: &str| matches.get_one::<PathBuf>(id).cloned();
+
+    match matches.subcommand() {
+        Some(("verify", sub)) => commands::verify::run(
+            &path(sub, "PROOF").unwrap(),
+            path(sub, "SETTINGS").as_deref(),
+        ),
+        Some(("inspect", sub)) => commands::inspect::run(&path(sub, "PROOF").unwrap()),
+        Some(("prove", sub)) => commands::prove::run(
+            &path(sub, "GRAPH").unwrap(),
+            &path(sub, "INPUTS").unwrap(),
+            path(sub, "settings").as_deref(),
+            sub.get_flag("settings-digest"),
+            &path(sub, "output").unwrap(),
+            path(sub, "metrics").as_deref(),
+        ),
+        Some(("settings", sub)) => commands::settings::run(
+            &path(sub, "GRAPH").unwrap(),
+            &path(sub, "INPUTS").unwrap(),
+            &path(sub, "output").unwrap(),
+        ),
+        _ => unreachable!("a subcommand is required"),
+    }
+}
@@ -0,0 +1,57 @@
+use luminair_graph::{ir::GraphIr, StwoCompiler};
+use luminal::prelude::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Empty directory for the files of a test
+fn test_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Runs the `luminair` tool, panicking with its output if it fails
+fn luminair(args: &[&Path]) {
+    let output = Command::new(env!("CARGO_BIN_EXE_luminair"))
+        .args(args)
+        .output()
+        .expect("Failed to run luminair");
+    assert!(
+        output.status.success(),
+        "luminair failed:\n{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_prove_and_verify_graph_with_weights() {
+    let dir = test_dir("prove_and_verify_graph_with_weights");
+    let graph = dir.join("graph.json");
+    let inputs = dir.join("inputs.json");
+    let proof = dir.join("proof.bin");
+
+    // A model with a runtime input `x` and a weight `w` shipped with the graph
+    let mut cx = Graph::new();
+    let x = cx.named_tensor("x", (3, 4));
+    let w = cx
+        .named_tensor("w", (4, 5))
+        .set((0..4 * 5).map(|i| i as f32 / 40.0).collect::<Vec<_>>());
+    let mut out = (x.matmul(w) * 0.5).exp2().sum_reduce(1).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+    GraphIr::from_graph(&cx, &[x.id])
+        .unwrap()
+        .to_json_file(&graph)
+        .unwrap();
+
+    // Only the runtime input is given
+    let x_data = (0..3 * 4).map(|i| i as f32 / 24.0).collect::<Vec<_>>();
+    fs::write(&inputs, serde_json::json!({ "x": x_data }).to_string()).unwrap();
+
+    luminair(&[Path::new("prove"), &graph, &inputs, Path::new("-o"), &proof]);
+    luminair(&[Path::new("verify"), &proof]);
+}
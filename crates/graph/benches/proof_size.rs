@@ -0,0 +1,100 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use luminair_graph::{graph::LuminairGraph, StwoCompiler};
+use luminair_prover::{compact::Compression, prover::prove, LuminairProof};
+use luminal::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use stwo::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+
+const ZSTD_LEVEL: i32 = 19;
+
+fn random_vec_rng<R: Rng>(n: usize, rng: &mut R) -> Vec<f32> {
+    (0..n).map(|_| rng.gen_range(-0.5..0.5)).collect()
+}
+
+// Proves `f(a, b)` on random tensors of the given size
+fn prove_binary(
+    f: fn(GraphTensor, GraphTensor) -> GraphTensor,
+    (rows, cols): (usize, usize),
+) -> LuminairProof<Blake2sMerkleHasher> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let a_data = random_vec_rng(rows * cols, &mut rng);
+    let b_data = random_vec_rng(rows * cols, &mut rng);
+
+    let mut cx = Graph::new();
+    let a = cx.tensor((rows, cols)).set(a_data);
+    let b = cx.tensor((rows, cols)).set(b_data);
+    let mut c = f(a, b).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut c);
+
+    let mut settings = cx.gen_circuit_settings();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    prove(trace, settings).expect("Proof generation failed")
+}
+
+fn proofs() -> Vec<(&'static str, LuminairProof<Blake2sMerkleHasher>)> {
+    vec![
+        ("add 32x32", prove_binary(|a, b| a + b, (32, 32))),
+        ("mul 32x32", prove_binary(|a, b| a * b, (32, 32))),
+        (
+            "matmul + exp2 16x16",
+            prove_binary(|a, b| a.matmul(b).exp2(), (16, 16)),
+        ),
+    ]
+}
+
+// Reports the size of each proof in every serialization format
+fn print_sizes(proofs: &[(&str, LuminairProof<Blake2sMerkleHasher>)]) {
+    println!(
+        "\n{:<22} {:>12} {:>12} {:>12} {:>14}",
+        "Proof", "bincode", "JSON", "compact", "compact+zstd"
+    );
+    for (name, proof) in proofs {
+        let bincode = proof.to_bincode().unwrap().len();
+        let json = proof.to_json().unwrap().len();
+        let compact = proof.to_compact(Compression::None).unwrap().len();
+        let zstd = proof
+            .to_compact(Compression::Zstd(ZSTD_LEVEL))
+            .unwrap()
+            .len();
+        println!(
+            "{:<22} {:>12} {:>12} {:>12} {:>14}",
+            name, bincode, json, compact, zstd
+        );
+    }
+    println!();
+}
+
+fn benchmark_proof_size(c: &mut Criterion) {
+    let proofs = proofs();
+    print_sizes(&proofs);
+
+    let mut group = c.benchmark_group("Proof Serialization");
+    for (name, proof) in &proofs {
+        group.bench_function(format!("Bincode Encode ({})", name), |b| {
+            b.iter(|| proof.to_bincode().unwrap())
+        });
+        group.bench_function(format!("Compact Encode ({})", name), |b| {
+            b.iter(|| proof.to_compact(Compression::None).unwrap())
+        });
+
+        let bincode = proof.to_bincode().unwrap();
+        let compact = proof.to_compact(Compression::None).unwrap();
+        let zstd = proof.to_compact(Compression::Zstd(ZSTD_LEVEL)).unwrap();
+        group.bench_function(format!("Bincode Decode ({})", name), |b| {
+            b.iter(|| LuminairProof::from_bincode(&bincode).unwrap())
+        });
+        group.bench_function(format!("Compact Decode ({})", name), |b| {
+            b.iter(|| LuminairProof::from_compact(&compact).unwrap())
+        });
+        group.bench_function(format!("Compact+zstd Decode ({})", name), |b| {
+            b.iter(|| LuminairProof::from_compact(&zstd).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_proof_size);
+criterion_main!(benches);
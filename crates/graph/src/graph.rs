@@ -1,115 +1,434 @@
 use crate::{
+    data::StwoData,
+    ir::OpIr,
     op::{
-        prim::{CopyFromStwo, LuminairContiguous},
-        HasProcessTrace,
+        downcast_luminair, downcast_luminair_mut, is_luminair,
+        prim::{CopyFromStwo, LuminairConstant, LuminairContiguous, LuminairIm2Col},
+        HasProcessTrace, LuminairOperator,
     },
-    utils::compute_padded_range_from_srcs,
+    utils::{compute_padded_range_from_srcs, get_buffer_from_tensor},
 };
 use itertools::Itertools;
 use luminair_air::{
     components::{
         add::table::{AddColumn, AddTraceTable},
+        add_const::table::{AddConstColumn, AddConstTraceTables},
         contiguous::table::{ContiguousColumn, ContiguousTraceTable},
         exp2::table::{Exp2Column, Exp2TraceTable},
+        fused::{
+            expr::FusedExpr,
+            table::{FusedColumn, FusedTraceTables},
+        },
+        gather::table::{GatherColumn, GatherTraceTable},
+        im2col::table::{Im2ColColumn, Im2ColTraceTable},
         inputs::table::{InputsColumn, InputsTraceTable},
         less_than::table::{LessThanColumn, LessThanTraceTable},
         log2::table::{Log2Column, Log2TraceTable},
         lookups::{
-            exp2::{table::Exp2LookupTraceTable, Exp2Lookup},
-            log2::{table::Log2LookupTraceTable, Log2Lookup},
-            range_check::{table::RangeCheckLookupTraceTable, RangeCheckLayout, RangeCheckLookup},
-            sin::{table::SinLookupTraceTable, SinLookup},
+            exp2::{
+                table::{Exp2LookupColumn, Exp2LookupTraceTable},
+                Exp2Lookup,
+            },
+            gather::table::{GatherLookupColumn, GatherLookupTraceTable},
+            log2::{
+                table::{Log2LookupColumn, Log2LookupTraceTable},
+                Log2Lookup,
+            },
+            range_check::{
+                table::{RangeCheckLookupColumn, RangeCheckLookupTraceTable},
+                RangeCheckLayout, RangeCheckLookup,
+            },
+            sin::{
+                table::{SinLookupColumn, SinLookupTraceTable},
+                SinLookup,
+            },
             Lookups,
         },
+        matmul::table::{MatMulColumn, MatMulTraceTable},
         max_reduce::table::{MaxReduceColumn, MaxReduceTraceTable},
         mul::table::{MulColumn, MulTraceTable},
+        mul_const::table::{MulConstColumn, MulConstTraceTables},
         recip::table::{RecipColumn, RecipTraceTable},
         sin::table::{SinColumn, SinTraceTable},
         sqrt::table::{SqrtColumn, SqrtTraceTable},
         sum_reduce::table::{SumReduceColumn, SumReduceTraceTable},
         rem::table::{RemColumn, RemTraceTable},
+        select::table::{SelectColumn, SelectTraceTable},
+        norm::table::{NormColumn, NormParams, NormTraceTables},
+        TraceColumn,
     },
     pie::{
-        ExecutionResources, InputInfo, LuminairPie, Metadata, NodeInfo, OpCounter, OutputInfo,
-        TraceTable,
+        ComponentEstimate, ExecutionResources, InputInfo, LuminairPie, Metadata, NodeInfo,
+        OpCounter, OpEstimate, OutputInfo, ResourceEstimate, TraceTable,
     },
-    preprocessed::{LookupLayout, Range},
+    preprocessed::{lookups_to_preprocessed_column, LookupLayout, PreProcessedTrace, Range},
+    segment::{BoundaryInput, BoundaryTensor, LuminairSegment, SegmentBoundary},
     settings::CircuitSettings,
-    utils::calculate_log_size,
+    utils::{calculate_log_size, norm_shard_sizes, shard_sizes},
+    DEFAULT_FP_SCALE,
 };
+use luminair_prover::prover::MAX_COMPONENT_LOG_SIZE;
 use luminair_utils::LuminairError;
 use luminal::{op::*, prelude::*};
 use numerair::Fixed;
 use petgraph::{stable_graph::StableGraph, visit::EdgeRef, Direction};
+use rayon::prelude::*;
 use regex::Regex;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::fmt::Debug;
+use stwo::core::{fields::qm31::SECURE_EXTENSION_DEGREE, pcs::PcsConfig};
 
 /// Trait for LuminAIR graph operations
 pub trait LuminairGraph {
     /// Generates circuit settings for the graph
     fn gen_circuit_settings(&mut self) -> CircuitSettings;
 
+    /// Generates circuit settings covering the executions of the graph on every input set of
+    /// a batch
+    fn gen_batch_circuit_settings(
+        &mut self,
+        batch: &[BatchInputs],
+    ) -> Result<CircuitSettings, LuminairError>;
+
     /// Generates a trace from the graph with the given settings
     fn gen_trace(&mut self, settings: &mut CircuitSettings) -> Result<LuminairPie, LuminairError>;
 
+    /// Generates a trace bound to the values of the runtime `inputs` and of the retrieved
+    /// outputs, returned as the public IO to prove and verify the trace with
+    fn gen_trace_with_io(
+        &mut self,
+        settings: &mut CircuitSettings,
+        inputs: &[NodeIndex],
+    ) -> Result<(LuminairPie, SegmentBoundary), LuminairError>;
+
+    /// Generates a single trace of the executions of the graph on every input set of a batch
+    fn gen_batch_trace(
+        &mut self,
+        batch: &[BatchInputs],
+        settings: &mut CircuitSettings,
+    ) -> Result<BatchTrace, LuminairError>;
+
+    /// Generates one PIE per segment of at most `nodes_per_segment` operators, each with the
+    /// tensors crossing its cuts as public boundary
+    fn gen_segmented_trace(
+        &mut self,
+        settings: &CircuitSettings,
+        nodes_per_segment: usize,
+    ) -> Result<Vec<LuminairSegment>, LuminairError>;
+
+    /// Generates the segments of `gen_segmented_trace`, handing each one to `on_segment` as
+    /// soon as it is cut so that only the trace of the current segment is held in memory
+    fn gen_segmented_trace_with(
+        &mut self,
+        settings: &CircuitSettings,
+        nodes_per_segment: usize,
+        on_segment: &mut dyn FnMut(LuminairSegment) -> Result<(), LuminairError>,
+    ) -> Result<(), LuminairError>;
+
+    /// Estimates the trace sizes and proving resources of the graph from the shapes of its
+    /// views, without executing it. Lookup tables are sized by the layouts of `settings`, or
+    /// projected from the operators reading them when no settings are given.
+    fn estimate(
+        &mut self,
+        settings: Option<&CircuitSettings>,
+    ) -> Result<ResourceEstimate, LuminairError>;
+
     /// Generates a graph visualization string
     fn graph_viz(&self) -> String;
 }
 
+/// Input tensors of one execution of a batch, as `(input node, values)` pairs
+pub type BatchInputs = Vec<(NodeIndex, Vec<f32>)>;
+
+/// Trace of a batch of executions of a graph
+pub struct BatchTrace {
+    /// PIE covering every execution, proven by a single call to `prove_with_io`
+    pub pie: LuminairPie,
+    /// Inputs and outputs of each execution, bound to the proof of the PIE
+    pub io: Vec<SegmentBoundary>,
+    /// Retrieved outputs of each execution, sorted by node
+    pub outputs: Vec<Vec<(NodeIndex, Vec<f32>)>>,
+}
+
 impl LuminairGraph for Graph {
     /// Generates circuit settings by analyzing the graph structure and operations
     fn gen_circuit_settings(&mut self) -> CircuitSettings {
-        // Track the number of views pointing to each tensor so we know when to clear
+        let mut ranges = LookupRanges::default();
+        ranges.record(self);
+        self.reset();
+        ranges.into_settings()
+    }
+
+    /// Generates circuit settings whose lookup tables cover the ranges of every execution
+    fn gen_batch_circuit_settings(
+        &mut self,
+        batch: &[BatchInputs],
+    ) -> Result<CircuitSettings, LuminairError> {
+        let mut ranges = LookupRanges::default();
+        for inputs in batch {
+            let loaders = set_batch_inputs(self, inputs)?;
+            ranges.record(self);
+            self.reset();
+            restore_inputs(self, loaders);
+        }
+        Ok(ranges.into_settings())
+    }
+
+    fn gen_trace(&mut self, settings: &mut CircuitSettings) -> Result<LuminairPie, LuminairError> {
+        let mut tables = TraceTables::default();
+        let recorded = tables.record(self, settings, 0, None, None);
+        self.reset();
+        recorded?;
+        Ok(tables.into_pie(settings))
+    }
+
+    fn gen_trace_with_io(
+        &mut self,
+        settings: &mut CircuitSettings,
+        inputs: &[NodeIndex],
+    ) -> Result<(LuminairPie, SegmentBoundary), LuminairError> {
+        let mut tables = TraceTables::default();
+        let mut io = IoRecorder::new(inputs.to_vec());
+        let recorded = tables.record(self, settings, 0, None, Some(&mut io));
+        self.reset();
+        recorded?;
+        Ok((tables.into_pie(settings), io.finish()?))
+    }
+
+    /// Executes the graph on each input set, appending every execution to the same tables
+    fn gen_batch_trace(
+        &mut self,
+        batch: &[BatchInputs],
+        settings: &mut CircuitSettings,
+    ) -> Result<BatchTrace, LuminairError> {
+        if batch.is_empty() {
+            return Err(LuminairError::InvalidBatch(
+                "the batch is empty".to_string(),
+            ));
+        }
+
+        // Node ids of each execution are shifted past those of the previous one, so that the
+        // LogUp relations of different executions never mix
+        let stride = self
+            .graph
+            .node_indices()
+            .map(|node| node.index() as u64 + 1)
+            .max()
+            .unwrap_or(0);
+        if stride * batch.len() as u64 > MAX_NODE_ID {
+            return Err(LuminairError::InvalidBatch(format!(
+                "{} executions of a graph of {} nodes exceed the node id space",
+                batch.len(),
+                stride
+            )));
+        }
+
+        let mut tables = TraceTables::default();
+        let mut io = Vec::with_capacity(batch.len());
+        let mut outputs = Vec::with_capacity(batch.len());
+        for (instance, inputs) in batch.iter().enumerate() {
+            let loaders = set_batch_inputs(self, inputs)?;
+            let mut recorder = IoRecorder::new(inputs.iter().map(|(node, _)| *node).collect());
+            let recorded = tables.record(
+                self,
+                settings,
+                (instance as u64 * stride) as u32,
+                None,
+                Some(&mut recorder),
+            );
+            outputs.push(retrieved_outputs(self));
+            self.reset();
+            restore_inputs(self, loaders);
+            recorded?;
+            io.push(recorder.finish()?);
+        }
+
+        Ok(BatchTrace {
+            pie: tables.into_pie(settings),
+            io,
+            outputs,
+        })
+    }
+
+    fn gen_segmented_trace(
+        &mut self,
+        settings: &CircuitSettings,
+        nodes_per_segment: usize,
+    ) -> Result<Vec<LuminairSegment>, LuminairError> {
+        let mut segments = Vec::new();
+        self.gen_segmented_trace_with(settings, nodes_per_segment, &mut |segment| {
+            segments.push(segment);
+            Ok(())
+        })?;
+        Ok(segments)
+    }
+
+    /// Executes the graph once, cutting the tables into a new PIE whenever the next segment
+    /// starts
+    fn gen_segmented_trace_with(
+        &mut self,
+        settings: &CircuitSettings,
+        nodes_per_segment: usize,
+        on_segment: &mut dyn FnMut(LuminairSegment) -> Result<(), LuminairError>,
+    ) -> Result<(), LuminairError> {
+        assert!(
+            nodes_per_segment > 0,
+            "Segments must hold at least one node"
+        );
         if self.linearized_graph.is_none() {
             self.toposort();
         }
-        let mut consumers = self.consumers_map.as_ref().unwrap().clone();
-        let mut dim_stack = Vec::new();
 
-        // Accumulate ranges per non-linear op
-        let mut sin_ranges: Vec<Range> = Vec::new();
-        let mut exp2_ranges: Vec<Range> = Vec::new();
-        let mut log2_ranges: Vec<Range> = Vec::new();
+        let mut segmenter = Segmenter::new(self, settings, nodes_per_segment, on_segment);
+        let mut settings = settings.clone();
+        let mut tables = TraceTables::default();
+        let recorded = tables.record(self, &mut settings, 0, Some(&mut segmenter), None);
+        self.reset();
+        recorded?;
+        segmenter.finish(tables.into_pie(&settings))
+    }
+
+    /// Walks the compiled graph, counting the rows each operator would trace
+    fn estimate(
+        &mut self,
+        settings: Option<&CircuitSettings>,
+    ) -> Result<ResourceEstimate, LuminairError> {
+        let mut estimate = TraceEstimate::default();
+        estimate.record(self)?;
+        let projected;
+        let settings = match settings {
+            Some(settings) => settings,
+            None => {
+                projected = estimate.projected_settings();
+                &projected
+            }
+        };
+        Ok(estimate.into_estimate(settings))
+    }
+
+    fn graph_viz(&self) -> String {
+        let mut new_graph: StableGraph<String, u8> = StableGraph::default();
+        let mut id_map = FxHashMap::default();
+        for (id, node) in self.graph.node_indices().zip(self.graph.node_weights()) {
+            id_map.insert(id, new_graph.add_node(format!("{node:?}")));
+        }
+
+        let mut schedule_edges = vec![];
+        for node in self.graph.node_indices() {
+            for edge in self
+                .graph
+                .edges_directed(node, Direction::Outgoing)
+                .sorted_by_key(|e| {
+                    if let Some(d) = e.weight().as_data() {
+                        d.0
+                    } else {
+                        0
+                    }
+                })
+            {
+                let new_edge = new_graph.add_edge(
+                    id_map[&edge.source()],
+                    id_map[&edge.target()],
+                    if let Some(d) = edge.weight().as_data() {
+                        d.0
+                    } else {
+                        0
+                    },
+                );
+                if edge.weight().is_schedule() {
+                    schedule_edges.push(new_edge);
+                }
+            }
+        }
+
+        let mut graph_string =
+            petgraph::dot::Dot::with_config(&new_graph, &[petgraph::dot::Config::EdgeIndexLabel])
+                .to_string();
+        let re = Regex::new(r#"label\s*=\s*"\d+""#).unwrap();
+        for e in schedule_edges {
+            graph_string =
+                graph_string.replace(&format!("label = \"{}\"", e.index()), "color=\"green\"");
+        }
+        graph_string = re.replace_all(&graph_string, "").to_string();
+        let mark_nodes: &[NodeIndex] = &[];
+        for n in mark_nodes {
+            graph_string = graph_string.replace(
+                &format!("    {} [ label =", n.index()),
+                &format!(
+                    "    {} [ style=\"filled\" fillcolor=\"yellow\" label =",
+                    n.index()
+                ),
+            );
+        }
+
+        graph_string.to_owned()
+    }
+}
+
+/// Node ids are encoded as M31 elements in the LogUp relations
+const MAX_NODE_ID: u64 = (1 << 31) - 1;
+
+/// Input ranges of the non-linear operators, accumulated over executions of a graph
+#[derive(Default)]
+struct LookupRanges {
+    sin: Vec<Range>,
+    exp2: Vec<Range>,
+    log2: Vec<Range>,
+    range_check_8: bool,
+}
 
-        let mut range_check_8_required = false;
+impl LookupRanges {
+    /// Executes the graph, recording the input ranges of its non-linear operators
+    fn record(&mut self, graph: &mut Graph) {
+        // Track the number of views pointing to each tensor so we know when to clear
+        if graph.linearized_graph.is_none() {
+            graph.toposort();
+        }
+        let mut consumers = graph.consumers_map.as_ref().unwrap().clone();
+        let mut dim_stack = Vec::new();
 
-        for (node, src_ids) in self.linearized_graph.as_ref().unwrap() {
-            if self.tensors.contains_key(&(*node, 0)) {
+        for (node, src_ids) in graph.linearized_graph.as_ref().unwrap() {
+            if graph.tensors.contains_key(&(*node, 0)) {
                 continue;
             }
 
             let mut srcs =
-                get_source_tensors(&self.no_delete, &mut self.tensors, src_ids, &consumers);
+                get_source_tensors(&graph.no_delete, &mut graph.tensors, src_ids, &consumers);
 
             // Substitute in the dyn dims
             for (_, st) in srcs.iter_mut() {
-                st.resolve_global_dyn_dims_stack(&self.dyn_map, &mut dim_stack);
+                st.resolve_global_dyn_dims_stack(&graph.dyn_map, &mut dim_stack);
             }
 
             // Range
-            let op = &*self.graph.node_weight(*node).unwrap();
+            let op = &*graph.graph.node_weight(*node).unwrap();
             if <Box<dyn Operator> as HasProcessTrace<SinColumn, SinTraceTable, SinLookup>>::has_process_trace(op) {
-                sin_ranges.push(compute_padded_range_from_srcs(&srcs));
+                self.sin.push(compute_padded_range_from_srcs(&srcs));
             }
             if <Box<dyn Operator> as HasProcessTrace<Exp2Column, Exp2TraceTable, Exp2Lookup>>::has_process_trace(op) {
-                exp2_ranges.push(compute_padded_range_from_srcs(&srcs));
+                self.exp2.push(compute_padded_range_from_srcs(&srcs));
             }
             if <Box<dyn Operator> as HasProcessTrace<Log2Column, Log2TraceTable, Log2Lookup>>::has_process_trace(op) {
-                log2_ranges.push(compute_padded_range_from_srcs(&srcs));
+                self.log2.push(compute_padded_range_from_srcs(&srcs));
             }
             if <Box<dyn Operator> as HasProcessTrace<
                 LessThanColumn,
                 LessThanTraceTable,
                 RangeCheckLookup<1>,
             >>::has_process_trace(op)
+                || <Box<dyn Operator> as HasProcessTrace<
+                    NormColumn,
+                    NormTraceTables,
+                    RangeCheckLookup<1>,
+                >>::has_process_trace(op)
             {
-                range_check_8_required = true;
+                self.range_check_8 = true;
             }
 
             // Execute
-            let tensors = self.graph.node_weight_mut(*node).unwrap().process(srcs);
+            let tensors = graph.graph.node_weight_mut(*node).unwrap().process(srcs);
             for (i, tensor) in tensors.into_iter().enumerate() {
-                self.tensors.insert((*node, i as u8), tensor);
+                graph.tensors.insert((*node, i as u8), tensor);
             }
 
             // Bookkeep remaining consumers
@@ -117,29 +436,30 @@ impl LuminairGraph for Graph {
                 *consumers.get_mut(&(*id, *ind)).unwrap() -= 1;
             }
         }
+    }
 
-        self.reset();
-
-        let sin_lookup = if !sin_ranges.is_empty() {
-            let layout = LookupLayout::new(coalesce_ranges(sin_ranges));
+    /// Builds the lookups covering every recorded range
+    fn into_settings(self) -> CircuitSettings {
+        let sin_lookup = if !self.sin.is_empty() {
+            let layout = LookupLayout::new(coalesce_ranges(self.sin));
             Some(SinLookup::new(&layout))
         } else {
             None
         };
-        let exp2_lookup = if !exp2_ranges.is_empty() {
-            let layout = LookupLayout::new(coalesce_ranges(exp2_ranges));
+        let exp2_lookup = if !self.exp2.is_empty() {
+            let layout = LookupLayout::new(coalesce_ranges(self.exp2));
             Some(Exp2Lookup::new(&layout))
         } else {
             None
         };
-        let log2_lookup = if !log2_ranges.is_empty() {
-            let layout = LookupLayout::new(coalesce_ranges(log2_ranges));
+        let log2_lookup = if !self.log2.is_empty() {
+            let layout = LookupLayout::new(coalesce_ranges(self.log2));
             Some(Log2Lookup::new(&layout))
         } else {
             None
         };
 
-        let range_check_lookup = if range_check_8_required {
+        let range_check_lookup = if self.range_check_8 {
             Some(RangeCheckLookup::new(&RangeCheckLayout {
                 ranges: [8],
                 log_size: 8,
@@ -157,509 +477,1950 @@ impl LuminairGraph for Graph {
             },
         }
     }
+}
 
-    fn gen_trace(&mut self, settings: &mut CircuitSettings) -> Result<LuminairPie, LuminairError> {
+/// Trace tables of each operator, accumulated over executions of a graph
+#[derive(Default)]
+struct TraceTables {
+    op_counter: OpCounter,
+    add: AddTraceTable,
+    mul: MulTraceTable,
+    recip: RecipTraceTable,
+    sin: SinTraceTable,
+    sin_lookup: SinLookupTraceTable,
+    sum_reduce: SumReduceTraceTable,
+    max_reduce: MaxReduceTraceTable,
+    sqrt: SqrtTraceTable,
+    rem: RemTraceTable,
+    exp2: Exp2TraceTable,
+    exp2_lookup: Exp2LookupTraceTable,
+    log2: Log2TraceTable,
+    log2_lookup: Log2LookupTraceTable,
+    less_than: LessThanTraceTable,
+    range_check_lookup: RangeCheckLookupTraceTable,
+    inputs: InputsTraceTable,
+    contiguous: ContiguousTraceTable,
+    select: SelectTraceTable,
+    gather: GatherTraceTable,
+    gather_lookup: GatherLookupTraceTable,
+    im2col: Im2ColTraceTable,
+    matmul: MatMulTraceTable,
+    norm: NormTraceTables,
+    mul_const: MulConstTraceTables,
+    add_const: AddConstTraceTables,
+    fused: FusedTraceTables,
+}
+
+impl TraceTables {
+    /// Executes the graph, appending the trace of each operator to its table with node ids
+    /// shifted by `id_offset`
+    ///
+    /// With a segmenter, the tables are cut into a PIE at the start of each segment and only
+    /// the consumers within the segment of a node count towards its multiplicity. With an IO
+    /// recorder, the runtime inputs and the retrieved outputs are recorded as the boundary of
+    /// the execution.
+    fn record(
+        &mut self,
+        graph: &mut Graph,
+        settings: &mut CircuitSettings,
+        id_offset: u32,
+        mut segmenter: Option<&mut Segmenter<'_>>,
+        mut io: Option<&mut IoRecorder>,
+    ) -> Result<(), LuminairError> {
         // Track the number of views pointing to each tensor so we know when to clear
-        if self.linearized_graph.is_none() {
-            self.toposort();
+        if graph.linearized_graph.is_none() {
+            graph.toposort();
         }
 
-        let mut consumers = self.consumers_map.as_ref().unwrap().clone();
+        let mut consumers = graph.consumers_map.as_ref().unwrap().clone();
         let mut dim_stack = Vec::new();
+        let linearized = graph.linearized_graph.as_ref().unwrap();
 
-        // Initializes operator counter
-        let mut op_counter = OpCounter::default();
-
-        // Initializes table for each operator
-        let mut add_table = AddTraceTable::new();
-        let mut mul_table = MulTraceTable::new();
-        let mut recip_table = RecipTraceTable::new();
-        let mut sin_table = SinTraceTable::new();
-        let mut sin_lookup_table = SinLookupTraceTable::new();
-        let mut sum_reduce_table = SumReduceTraceTable::new();
-        let mut max_reduce_table = MaxReduceTraceTable::new();
-        let mut sqrt_table = SqrtTraceTable::new();
-        let mut rem_table = RemTraceTable::new();
-        let mut exp2_table = Exp2TraceTable::new();
-        let mut exp2_lookup_table = Exp2LookupTraceTable::new();
-        let mut log2_table = Log2TraceTable::new();
-        let mut log2_lookup_table = Log2LookupTraceTable::new();
-        let mut less_than_table = LessThanTraceTable::new();
-        let mut range_check_lookup_table = RangeCheckLookupTraceTable::new();
-        let mut inputs_table = InputsTraceTable::new();
-        let mut contiguous_table = ContiguousTraceTable::new();
-
-        for (node, src_ids) in self.linearized_graph.as_ref().unwrap() {
-            if self.tensors.contains_key(&(*node, 0)) {
-                continue;
+        for wave in independent_waves(linearized, segmenter.as_deref()) {
+            if let Some(segmenter) = segmenter.as_deref_mut() {
+                if segmenter.segment_of[&linearized[wave.start].0] > segmenter.current {
+                    let pie = std::mem::take(self).into_pie(settings);
+                    *settings = segmenter.cut(graph, pie)?;
+                }
             }
 
-            let mut srcs =
-                get_source_tensors(&self.no_delete, &mut self.tensors, src_ids, &consumers);
+            // Nodes of the wave that only read StwoData, traced once the others are done
+            let mut pending = Vec::new();
 
-            // Substitute in the dyn dims
-            for (_, st) in srcs.iter_mut() {
-                st.resolve_global_dyn_dims_stack(&self.dyn_map, &mut dim_stack);
-            }
+            for (node, src_ids) in &linearized[wave] {
+                if graph.tensors.contains_key(&(*node, 0)) {
+                    continue;
+                }
 
-            // Gather input source information
-            let input_info: Vec<InputInfo> = src_ids
-                .iter()
-                .map(|(id, _, _)| InputInfo {
-                    id: id.index() as u32,
-                })
-                .collect();
+                let mut srcs =
+                    get_source_tensors(&graph.no_delete, &mut graph.tensors, src_ids, &consumers);
 
-            // Determine output status
-            let is_final_output = is_final_output(self, *node);
+                // Substitute in the dyn dims
+                for (_, st) in srcs.iter_mut() {
+                    st.resolve_global_dyn_dims_stack(&graph.dyn_map, &mut dim_stack);
+                }
 
-            // Calculate expansion-adjusted consumer count
-            let base_consumers = *consumers.get(&(*node, 0)).unwrap_or(&0);
-            let mut expansion_adjusted_consumers = 0u32;
+                // Gather input source information
+                let input_info: Vec<InputInfo> = src_ids
+                    .iter()
+                    .map(|(id, _, _)| InputInfo {
+                        id: id.index() as u32 + id_offset,
+                    })
+                    .collect();
 
-            if base_consumers > 0 {
-                // Iterate through each consumer edge to calculate expansion factors
-                for edge in self
-                    .graph
-                    .edges_directed(*node, petgraph::Direction::Outgoing)
-                {
-                    if let Some((_, _, shape)) = edge.weight().as_data() {
-                        // Calculate expansion factor for this consumer based on fake dimensions
-                        let expansion_factor: u32 = (0..shape.len())
-                            .map(|i| {
-                                let dim_index = shape.indexes[i];
-                                if shape.fake[dim_index] {
-                                    // This dimension is fake (expanded), so count its size
-                                    shape.dims[dim_index].to_usize().unwrap_or(1) as u32
-                                } else {
-                                    // This dimension is real, contributes factor of 1
-                                    1
-                                }
-                            })
-                            .product();
-
-                        expansion_adjusted_consumers += expansion_factor;
+                // Calculate expansion-adjusted consumer count
+                let base_consumers = *consumers.get(&(*node, 0)).unwrap_or(&0);
+                let mut expansion_adjusted_consumers = 0u32;
+
+                if base_consumers > 0 {
+                    // Iterate through each traced consumer to calculate expansion factors
+                    for (target, shape) in traced_consumers(graph, *node) {
+                        // Consumers in other segments take the tensor from the boundary
+                        if let Some(segmenter) = segmenter.as_deref() {
+                            if segmenter.segment_of[&target] != segmenter.segment_of[node] {
+                                continue;
+                            }
+                        }
+                        expansion_adjusted_consumers += expansion_factor(&shape);
                     }
                 }
-            } else {
-                expansion_adjusted_consumers = base_consumers as u32;
+
+                // A tensor crossing the next cut is consumed once more, as an output of the
+                // segment
+                if let Some(segmenter) = segmenter.as_deref() {
+                    if segmenter.crosses(graph, *node, segmenter.segment_of[node] + 1) {
+                        expansion_adjusted_consumers += 1;
+                    }
+                }
+
+                // A bound output is consumed once more, by the verifier. A bound input is
+                // yielded by the verifier in place of its copy into the trace.
+                let is_io_output = io.is_some() && is_bound_output(graph, *node);
+                if is_io_output {
+                    expansion_adjusted_consumers += 1;
+                }
+                let io_input = io
+                    .as_deref()
+                    .and_then(|io| io.input_of(graph, *node, src_ids));
+
+                // Determine output status. A retrieved tensor is still yielded to the operators
+                // consuming it in the trace.
+                let is_final_output =
+                    is_final_output(graph, *node) && expansion_adjusted_consumers == 0;
+
+                let node_info = NodeInfo {
+                    inputs: input_info,
+                    output: OutputInfo { is_final_output },
+                    num_consumers: if io_input.is_some() {
+                        0
+                    } else {
+                        expansion_adjusted_consumers
+                    },
+                    id: node.index() as u32 + id_offset,
+                };
+
+                let node_op = &mut *graph.graph.node_weight_mut(*node).unwrap();
+                let shared_srcs = if is_concurrent(node_op.as_ref()) {
+                    srcs.iter()
+                        .map(|(tensor, st)| Some((get_buffer_from_tensor(tensor)?.clone(), *st)))
+                        .collect::<Option<Vec<_>>>()
+                } else {
+                    None
+                };
+
+                match shared_srcs {
+                    Some(srcs) => pending.push((*node, srcs, node_info)),
+                    None => {
+                        let tensors = self.trace_node(node_op, srcs, &node_info, settings);
+
+                        if let Some(io) = io.as_deref_mut() {
+                            let data = tensors.first().and_then(|t| t.downcast_ref::<StwoData>());
+                            if let Some(position) = io_input {
+                                io.record_input(
+                                    position,
+                                    node_info.id,
+                                    data,
+                                    expansion_adjusted_consumers,
+                                )?;
+                            }
+                            if is_io_output {
+                                io.record_output(node_info.id, data)?;
+                            }
+                        }
+
+                        // Store output tensors
+                        for (i, tensor) in tensors.into_iter().enumerate() {
+                            graph.tensors.insert((*node, i as u8), tensor);
+                        }
+                    }
+                }
+
+                // Update remaining consumers
+                for (id, ind, _) in src_ids {
+                    *consumers.get_mut(&(*id, *ind)).unwrap() -= 1;
+                }
             }
 
-            let node_info = NodeInfo {
-                inputs: input_info,
-                output: OutputInfo { is_final_output },
-                num_consumers: expansion_adjusted_consumers,
-                id: node.index() as u32,
+            let outputs = if pending.len() > 1 {
+                let nodes: FxHashSet<NodeIndex> = pending.iter().map(|(node, ..)| *node).collect();
+                let indices = graph.graph.node_indices().collect::<Vec<_>>();
+                let mut node_ops: FxHashMap<NodeIndex, &mut Box<dyn Operator>> = indices
+                    .into_iter()
+                    .zip(graph.graph.node_weights_mut())
+                    .filter(|(node, _)| nodes.contains(node))
+                    .collect();
+                let jobs = pending
+                    .into_iter()
+                    .map(|(node, srcs, node_info)| {
+                        let node_op = node_ops.remove(&node).unwrap();
+                        (node, concurrent_job(node_op, srcs, node_info).unwrap())
+                    })
+                    .collect::<Vec<_>>();
+                self.run_concurrently(jobs)
+            } else {
+                pending
+                    .into_iter()
+                    .map(|(node, srcs, node_info)| {
+                        let node_op = graph.graph.node_weight_mut(node).unwrap();
+                        let outputs = concurrent_job(node_op, srcs, node_info).unwrap()(self);
+                        (node, outputs)
+                    })
+                    .collect()
             };
 
-            // Get operator and dispatch to appropriate process_trace handler
-            let node_op = &mut *self.graph.node_weight_mut(*node).unwrap();
-
-            let tensors =
-                match () {
-                    _
-                        if <Box<dyn Operator> as HasProcessTrace<
-                            AddColumn,
-                            AddTraceTable,
-                            (),
-                        >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.add += 1;
-                        <Box<dyn Operator> as HasProcessTrace<AddColumn, AddTraceTable, ()>>::call_process_trace(
-                        node_op, srcs, &mut add_table, &node_info, &mut ()
-                    ).unwrap()
-                    }
-                    _
-                        if <Box<dyn Operator> as HasProcessTrace<
-                            MulColumn,
-                            MulTraceTable,
-                            (),
-                        >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.mul += 1;
-                        <Box<dyn Operator> as HasProcessTrace<MulColumn, MulTraceTable, ()>>::call_process_trace(
-                        node_op, srcs, &mut mul_table, &node_info, &mut ()
-                    ).unwrap()
-                    }
-                    _ if <Box<dyn Operator> as HasProcessTrace<
-                        RecipColumn,
-                        RecipTraceTable,
-                        (),
-                    >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.recip += 1;
-                        <Box<dyn Operator> as HasProcessTrace<RecipColumn, RecipTraceTable, ()>>::call_process_trace(
-                        node_op, srcs, &mut recip_table, &node_info, &mut ()
-                    ).unwrap()
+            // Store output tensors
+            for (node, tensors) in outputs {
+                if let Some(io) = io.as_deref_mut() {
+                    if is_bound_output(graph, node) {
+                        io.record_output(node.index() as u32 + id_offset, tensors.first())?;
                     }
-                    _ if <Box<dyn Operator> as HasProcessTrace<
+                }
+                for (i, data) in tensors.into_iter().enumerate() {
+                    graph.tensors.insert((node, i as u8), Tensor::new(data));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the trace jobs of independent nodes on the rayon thread pool
+    ///
+    /// Each job records into tables of its own, appended in the order of the jobs so that the
+    /// rows are the same as with a sequential execution.
+    fn run_concurrently(
+        &mut self,
+        jobs: Vec<(NodeIndex, TraceJob<'_>)>,
+    ) -> Vec<(NodeIndex, Vec<StwoData>)> {
+        let traced = jobs
+            .into_par_iter()
+            .map(|(node, job)| {
+                let mut tables = TraceTables::default();
+                let outputs = job(&mut tables);
+                ((node, outputs), tables)
+            })
+            .collect::<Vec<_>>();
+
+        traced
+            .into_iter()
+            .map(|(outputs, tables)| {
+                self.append(tables);
+                outputs
+            })
+            .collect()
+    }
+
+    /// Appends the rows and operator counts of `other` to these tables
+    fn append(&mut self, other: TraceTables) {
+        self.op_counter += other.op_counter;
+        self.add.table.append(other.add.table);
+        self.mul.table.append(other.mul.table);
+        self.recip.table.append(other.recip.table);
+        self.sin.table.append(other.sin.table);
+        self.sin_lookup.table.append(other.sin_lookup.table);
+        self.sum_reduce.table.append(other.sum_reduce.table);
+        self.max_reduce.table.append(other.max_reduce.table);
+        self.sqrt.table.append(other.sqrt.table);
+        self.rem.table.append(other.rem.table);
+        self.exp2.table.append(other.exp2.table);
+        self.exp2_lookup.table.append(other.exp2_lookup.table);
+        self.log2.table.append(other.log2.table);
+        self.log2_lookup.table.append(other.log2_lookup.table);
+        self.less_than.table.append(other.less_than.table);
+        self.range_check_lookup
+            .table
+            .append(other.range_check_lookup.table);
+        self.inputs.table.append(other.inputs.table);
+        self.contiguous.table.append(other.contiguous.table);
+        self.select.table.append(other.select.table);
+        self.gather.table.append(other.gather.table);
+        self.gather_lookup.table.append(other.gather_lookup.table);
+        self.im2col.table.append(other.im2col.table);
+        self.matmul.table.append(other.matmul.table);
+        self.norm.append(other.norm);
+        self.mul_const.append(other.mul_const);
+        self.add_const.append(other.add_const);
+        self.fused.append(other.fused);
+    }
+
+    /// Executes a node on the calling thread, dispatching to the process_trace handler of its
+    /// operator
+    fn trace_node(
+        &mut self,
+        node_op: &mut Box<dyn Operator>,
+        srcs: Vec<(InputTensor, ShapeTracker)>,
+        node_info: &NodeInfo,
+        settings: &mut CircuitSettings,
+    ) -> Vec<Tensor> {
+        match () {
+            _
+                if <Box<dyn Operator> as HasProcessTrace<
+                    AddColumn,
+                    AddTraceTable,
+                    (),
+                >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.add += 1;
+                <Box<dyn Operator> as HasProcessTrace<AddColumn, AddTraceTable, ()>>::call_process_trace(
+                node_op, srcs, &mut self.add, node_info, &mut ()
+            ).unwrap()
+            }
+            _
+                if <Box<dyn Operator> as HasProcessTrace<
+                    MulColumn,
+                    MulTraceTable,
+                    (),
+                >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.mul += 1;
+                <Box<dyn Operator> as HasProcessTrace<MulColumn, MulTraceTable, ()>>::call_process_trace(
+                node_op, srcs, &mut self.mul, node_info, &mut ()
+            ).unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                RecipColumn,
+                RecipTraceTable,
+                (),
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.recip += 1;
+                <Box<dyn Operator> as HasProcessTrace<RecipColumn, RecipTraceTable, ()>>::call_process_trace(
+                node_op, srcs, &mut self.recip, node_info, &mut ()
+            ).unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                SinColumn,
+                SinTraceTable,
+                SinLookup,
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.sin += 1;
+                match settings.lookups.sin.as_mut() {
+                    Some(lookup) => <Box<dyn Operator> as HasProcessTrace<
                         SinColumn,
                         SinTraceTable,
                         SinLookup,
-                    >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.sin += 1;
-                        match settings.lookups.sin.as_mut() {
-                            Some(lookup) => <Box<dyn Operator> as HasProcessTrace<
-                                SinColumn,
-                                SinTraceTable,
-                                SinLookup,
-                            >>::call_process_trace(
-                                node_op,
-                                srcs,
-                                &mut sin_table,
-                                &node_info,
-                                lookup,
-                            )
-                            .unwrap(),
-                            None => unreachable!("Sin lookup table must be initialised"),
-                        }
-                    }
-                    _ if <Box<dyn Operator> as HasProcessTrace<
-                        SumReduceColumn,
-                        SumReduceTraceTable,
-                        (),
-                    >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.sum_reduce += 1;
-                        <Box<dyn Operator> as HasProcessTrace<
-                            SumReduceColumn,
-                            SumReduceTraceTable,
-                            (),
-                        >>::call_process_trace(
-                            node_op,
-                            srcs,
-                            &mut sum_reduce_table,
-                            &node_info,
-                            &mut (),
-                        )
-                        .unwrap()
-                    }
-                    _ if <Box<dyn Operator> as HasProcessTrace<
-                        MaxReduceColumn,
-                        MaxReduceTraceTable,
-                        (),
-                    >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.max_reduce += 1;
-                        <Box<dyn Operator> as HasProcessTrace<
-                            MaxReduceColumn,
-                            MaxReduceTraceTable,
-                            (),
-                        >>::call_process_trace(
-                            node_op,
-                            srcs,
-                            &mut max_reduce_table,
-                            &node_info,
-                            &mut (),
-                        )
-                        .unwrap()
-                    }
-                    _ if <Box<dyn Operator> as HasProcessTrace<
-                        SqrtColumn,
-                        SqrtTraceTable,
-                        (),
-                    >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.sqrt += 1;
-                        <Box<dyn Operator> as HasProcessTrace<SqrtColumn, SqrtTraceTable, ()>>::call_process_trace(
-                        node_op, srcs, &mut sqrt_table, &node_info, &mut ()
-                    ).unwrap()
-                    }
-                    _
-                        if <Box<dyn Operator> as HasProcessTrace<
-                            RemColumn,
-                            RemTraceTable,
-                            (),
-                        >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.rem += 1;
-                        <Box<dyn Operator> as HasProcessTrace<RemColumn, RemTraceTable, ()>>::call_process_trace(
-                        node_op, srcs, &mut rem_table, &node_info, &mut ()
-                    ).unwrap()
-                    }
-                    _ if <Box<dyn Operator> as HasProcessTrace<
+                    >>::call_process_trace(
+                        node_op,
+                        srcs,
+                        &mut self.sin,
+                        node_info,
+                        lookup,
+                    )
+                    .unwrap(),
+                    None => unreachable!("Sin lookup table must be initialised"),
+                }
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                SumReduceColumn,
+                SumReduceTraceTable,
+                (),
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.sum_reduce += 1;
+                <Box<dyn Operator> as HasProcessTrace<
+                    SumReduceColumn,
+                    SumReduceTraceTable,
+                    (),
+                >>::call_process_trace(
+                    node_op,
+                    srcs,
+                    &mut self.sum_reduce,
+                    node_info,
+                    &mut (),
+                )
+                .unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                MaxReduceColumn,
+                MaxReduceTraceTable,
+                (),
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.max_reduce += 1;
+                <Box<dyn Operator> as HasProcessTrace<
+                    MaxReduceColumn,
+                    MaxReduceTraceTable,
+                    (),
+                >>::call_process_trace(
+                    node_op,
+                    srcs,
+                    &mut self.max_reduce,
+                    node_info,
+                    &mut (),
+                )
+                .unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                SqrtColumn,
+                SqrtTraceTable,
+                (),
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.sqrt += 1;
+                <Box<dyn Operator> as HasProcessTrace<SqrtColumn, SqrtTraceTable, ()>>::call_process_trace(
+                node_op, srcs, &mut self.sqrt, node_info, &mut ()
+            ).unwrap()
+            }
+            _
+                if <Box<dyn Operator> as HasProcessTrace<
+                    RemColumn,
+                    RemTraceTable,
+                    (),
+                >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.rem += 1;
+                <Box<dyn Operator> as HasProcessTrace<RemColumn, RemTraceTable, ()>>::call_process_trace(
+                node_op, srcs, &mut self.rem, node_info, &mut ()
+            ).unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                Exp2Column,
+                Exp2TraceTable,
+                Exp2Lookup,
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.exp2 += 1;
+                match settings.lookups.exp2.as_mut() {
+                    Some(lookup) => <Box<dyn Operator> as HasProcessTrace<
                         Exp2Column,
                         Exp2TraceTable,
                         Exp2Lookup,
-                    >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.exp2 += 1;
-                        match settings.lookups.exp2.as_mut() {
-                            Some(lookup) => <Box<dyn Operator> as HasProcessTrace<
-                                Exp2Column,
-                                Exp2TraceTable,
-                                Exp2Lookup,
-                            >>::call_process_trace(
-                                node_op,
-                                srcs,
-                                &mut exp2_table,
-                                &node_info,
-                                lookup,
-                            )
-                            .unwrap(),
-                            None => unreachable!("Exp2 lookup table must be initialised"),
-                        }
-                    }
-                    _ if <Box<dyn Operator> as HasProcessTrace<
+                    >>::call_process_trace(
+                        node_op,
+                        srcs,
+                        &mut self.exp2,
+                        node_info,
+                        lookup,
+                    )
+                    .unwrap(),
+                    None => unreachable!("Exp2 lookup table must be initialised"),
+                }
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                Log2Column,
+                Log2TraceTable,
+                Log2Lookup,
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.log2 += 1;
+                match settings.lookups.log2.as_mut() {
+                    Some(lookup) => <Box<dyn Operator> as HasProcessTrace<
                         Log2Column,
                         Log2TraceTable,
                         Log2Lookup,
-                    >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.log2 += 1;
-                        match settings.lookups.log2.as_mut() {
-                            Some(lookup) => <Box<dyn Operator> as HasProcessTrace<
-                                Log2Column,
-                                Log2TraceTable,
-                                Log2Lookup,
-                            >>::call_process_trace(
-                                node_op,
-                                srcs,
-                                &mut log2_table,
-                                &node_info,
-                                lookup,
-                            )
-                            .unwrap(),
-                            None => unreachable!("Log2 lookup table must be initialised"),
-                        }
-                    }
-                    _ if <Box<dyn Operator> as HasProcessTrace<
+                    >>::call_process_trace(
+                        node_op,
+                        srcs,
+                        &mut self.log2,
+                        node_info,
+                        lookup,
+                    )
+                    .unwrap(),
+                    None => unreachable!("Log2 lookup table must be initialised"),
+                }
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                LessThanColumn,
+                LessThanTraceTable,
+                RangeCheckLookup<1>,
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.less_than += 1;
+                match settings.lookups.range_check.as_mut() {
+                    Some(lookup) => <Box<dyn Operator> as HasProcessTrace<
                         LessThanColumn,
                         LessThanTraceTable,
                         RangeCheckLookup<1>,
-                    >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.less_than += 1;
-                        match settings.lookups.range_check.as_mut() {
-                            Some(lookup) => <Box<dyn Operator> as HasProcessTrace<
-                                LessThanColumn,
-                                LessThanTraceTable,
-                                RangeCheckLookup<1>,
-                            >>::call_process_trace(
-                                node_op,
-                                srcs,
-                                &mut less_than_table,
-                                &node_info,
-                                lookup,
-                            )
-                            .unwrap(),
-                            None => unreachable!("RangeCheck lookup table must be initialised"),
-                        }
-                    }
-                    _ if <Box<dyn Operator> as HasProcessTrace<
-                        InputsColumn,
-                        InputsTraceTable,
-                        (),
-                    >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.inputs += 1;
-                        <Box<dyn Operator> as HasProcessTrace<InputsColumn, InputsTraceTable, ()>>::call_process_trace(
-                        node_op, srcs, &mut inputs_table, &node_info, &mut ()
-                    ).unwrap()
-                    }
-                    _ if <Box<dyn Operator> as HasProcessTrace<
-                        ContiguousColumn,
-                        ContiguousTraceTable,
-                        (),
-                    >>::has_process_trace(node_op) =>
-                    {
-                        op_counter.contiguous += 1;
-                        <Box<dyn Operator> as HasProcessTrace<
-                            ContiguousColumn,
-                            ContiguousTraceTable,
-                            (),
-                        >>::call_process_trace(
-                            node_op,
-                            srcs,
-                            &mut contiguous_table,
-                            &node_info,
-                            &mut (),
-                        )
-                        .unwrap()
-                    }
-
-                    _ => node_op.process(srcs),
-                };
-
-            // Store output tensors
-            for (i, tensor) in tensors.into_iter().enumerate() {
-                self.tensors.insert((*node, i as u8), tensor);
+                    >>::call_process_trace(
+                        node_op,
+                        srcs,
+                        &mut self.less_than,
+                        node_info,
+                        lookup,
+                    )
+                    .unwrap(),
+                    None => unreachable!("RangeCheck lookup table must be initialised"),
+                }
             }
-
-            // Update remaining consumers
-            for (id, ind, _) in src_ids {
-                *consumers.get_mut(&(*id, *ind)).unwrap() -= 1;
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                InputsColumn,
+                InputsTraceTable,
+                (),
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.inputs += 1;
+                <Box<dyn Operator> as HasProcessTrace<InputsColumn, InputsTraceTable, ()>>::call_process_trace(
+                node_op, srcs, &mut self.inputs, node_info, &mut ()
+            ).unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                ContiguousColumn,
+                ContiguousTraceTable,
+                (),
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.contiguous += 1;
+                <Box<dyn Operator> as HasProcessTrace<
+                    ContiguousColumn,
+                    ContiguousTraceTable,
+                    (),
+                >>::call_process_trace(
+                    node_op,
+                    srcs,
+                    &mut self.contiguous,
+                    node_info,
+                    &mut (),
+                )
+                .unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                SelectColumn,
+                SelectTraceTable,
+                (),
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.select += 1;
+                <Box<dyn Operator> as HasProcessTrace<SelectColumn, SelectTraceTable, ()>>::call_process_trace(
+                node_op, srcs, &mut self.select, node_info, &mut ()
+            ).unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                GatherColumn,
+                GatherTraceTable,
+                GatherLookupTraceTable,
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.gather += 1;
+                <Box<dyn Operator> as HasProcessTrace<
+                    GatherColumn,
+                    GatherTraceTable,
+                    GatherLookupTraceTable,
+                >>::call_process_trace(
+                    node_op,
+                    srcs,
+                    &mut self.gather,
+                    node_info,
+                    &mut self.gather_lookup,
+                )
+                .unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                Im2ColColumn,
+                Im2ColTraceTable,
+                GatherLookupTraceTable,
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.im2col += 1;
+                <Box<dyn Operator> as HasProcessTrace<
+                    Im2ColColumn,
+                    Im2ColTraceTable,
+                    GatherLookupTraceTable,
+                >>::call_process_trace(
+                    node_op,
+                    srcs,
+                    &mut self.im2col,
+                    node_info,
+                    &mut self.gather_lookup,
+                )
+                .unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                MatMulColumn,
+                MatMulTraceTable,
+                (),
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.matmul += 1;
+                <Box<dyn Operator> as HasProcessTrace<
+                    MatMulColumn,
+                    MatMulTraceTable,
+                    (),
+                >>::call_process_trace(
+                    node_op,
+                    srcs,
+                    &mut self.matmul,
+                    node_info,
+                    &mut (),
+                )
+                .unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                NormColumn,
+                NormTraceTables,
+                RangeCheckLookup<1>,
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.norm += 1;
+                match settings.lookups.range_check.as_mut() {
+                    Some(lookup) => <Box<dyn Operator> as HasProcessTrace<
+                        NormColumn,
+                        NormTraceTables,
+                        RangeCheckLookup<1>,
+                    >>::call_process_trace(
+                        node_op,
+                        srcs,
+                        &mut self.norm,
+                        node_info,
+                        lookup,
+                    )
+                    .unwrap(),
+                    None => unreachable!("RangeCheck lookup table must be initialised"),
+                }
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                MulConstColumn,
+                MulConstTraceTables,
+                (),
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.mul_const += 1;
+                <Box<dyn Operator> as HasProcessTrace<
+                    MulConstColumn,
+                    MulConstTraceTables,
+                    (),
+                >>::call_process_trace(
+                    node_op,
+                    srcs,
+                    &mut self.mul_const,
+                    node_info,
+                    &mut (),
+                )
+                .unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                AddConstColumn,
+                AddConstTraceTables,
+                (),
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.add_const += 1;
+                <Box<dyn Operator> as HasProcessTrace<
+                    AddConstColumn,
+                    AddConstTraceTables,
+                    (),
+                >>::call_process_trace(
+                    node_op,
+                    srcs,
+                    &mut self.add_const,
+                    node_info,
+                    &mut (),
+                )
+                .unwrap()
+            }
+            _ if <Box<dyn Operator> as HasProcessTrace<
+                FusedColumn,
+                FusedTraceTables,
+                (),
+            >>::has_process_trace(node_op) =>
+            {
+                self.op_counter.fused += 1;
+                <Box<dyn Operator> as HasProcessTrace<
+                    FusedColumn,
+                    FusedTraceTables,
+                    (),
+                >>::call_process_trace(
+                    node_op,
+                    srcs,
+                    &mut self.fused,
+                    node_info,
+                    &mut (),
+                )
+                .unwrap()
             }
-        }
 
-        self.reset();
+            _ => node_op.process(srcs),
+        }
+    }
 
+    /// Converts the tables to traces, with the lookup multiplicities recorded in `settings`
+    fn into_pie(mut self, settings: &CircuitSettings) -> LuminairPie {
         // Convert tables to traces - determine max log size while building
         let mut max_log_size = 0;
         let mut trace_tables = Vec::new();
+        // Less-than comparisons and normalizations share the range check lookup
+        let range_checked = !self.less_than.table.is_empty()
+            || self.norm.tables.iter().any(|table| !table.table.is_empty());
 
-        if !add_table.table.is_empty() {
-            let log_size = calculate_log_size(add_table.table.len());
+        if !self.add.table.is_empty() {
+            let log_size = calculate_log_size(self.add.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_add(add_table));
+            trace_tables.push(TraceTable::from_add(self.add));
         }
-        if !mul_table.table.is_empty() {
-            let log_size = calculate_log_size(mul_table.table.len());
+        if !self.mul.table.is_empty() {
+            let log_size = calculate_log_size(self.mul.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_mul(mul_table));
+            trace_tables.push(TraceTable::from_mul(self.mul));
         }
-        if !recip_table.table.is_empty() {
-            let log_size = calculate_log_size(recip_table.table.len());
+        if !self.recip.table.is_empty() {
+            let log_size = calculate_log_size(self.recip.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_recip(recip_table));
+            trace_tables.push(TraceTable::from_recip(self.recip));
         }
-        if !sin_table.table.is_empty() {
-            let log_size = calculate_log_size(sin_table.table.len());
+        if !self.sin.table.is_empty() {
+            let log_size = calculate_log_size(self.sin.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_sin(sin_table));
+            trace_tables.push(TraceTable::from_sin(self.sin));
 
             if let Some(lookup) = settings.lookups.sin.as_ref() {
-                lookup.add_multiplicities_to_table(&mut sin_lookup_table);
+                lookup.add_multiplicities_to_table(&mut self.sin_lookup);
                 max_log_size = max_log_size.max(lookup.layout.log_size);
-                trace_tables.push(TraceTable::from_sin_lookup(sin_lookup_table))
+                trace_tables.push(TraceTable::from_sin_lookup(self.sin_lookup))
             } // TODO (@raphaelDkhn): though error if LUT not present.
         }
-        if !sum_reduce_table.table.is_empty() {
-            let log_size = calculate_log_size(sum_reduce_table.table.len());
+        if !self.sum_reduce.table.is_empty() {
+            let log_size = calculate_log_size(self.sum_reduce.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_sum_reduce(sum_reduce_table));
+            trace_tables.push(TraceTable::from_sum_reduce(self.sum_reduce));
         }
-        if !max_reduce_table.table.is_empty() {
-            let log_size = calculate_log_size(max_reduce_table.table.len());
+        if !self.max_reduce.table.is_empty() {
+            let log_size = calculate_log_size(self.max_reduce.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_max_reduce(max_reduce_table));
+            trace_tables.push(TraceTable::from_max_reduce(self.max_reduce));
         }
-        if !sqrt_table.table.is_empty() {
-            let log_size = calculate_log_size(sqrt_table.table.len());
+        if !self.sqrt.table.is_empty() {
+            let log_size = calculate_log_size(self.sqrt.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_sqrt(sqrt_table));
+            trace_tables.push(TraceTable::from_sqrt(self.sqrt));
         }
-        if !rem_table.table.is_empty() {
-            let log_size = calculate_log_size(rem_table.table.len());
+        if !self.rem.table.is_empty() {
+            let log_size = calculate_log_size(self.rem.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_rem(rem_table));
+            trace_tables.push(TraceTable::from_rem(self.rem));
         }
-        if !exp2_table.table.is_empty() {
-            let log_size = calculate_log_size(exp2_table.table.len());
+        if !self.exp2.table.is_empty() {
+            let log_size = calculate_log_size(self.exp2.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_exp2(exp2_table));
+            trace_tables.push(TraceTable::from_exp2(self.exp2));
 
             if let Some(lookup) = settings.lookups.exp2.as_ref() {
-                lookup.add_multiplicities_to_table(&mut exp2_lookup_table);
+                lookup.add_multiplicities_to_table(&mut self.exp2_lookup);
                 max_log_size = max_log_size.max(lookup.layout.log_size);
-                trace_tables.push(TraceTable::from_exp2_lookup(exp2_lookup_table))
+                trace_tables.push(TraceTable::from_exp2_lookup(self.exp2_lookup))
             } // TODO (@raphaelDkhn): though error if LUT not present.
         }
-        if !log2_table.table.is_empty() {
-            op_counter.log2 = log2_table.table.len();
-            let log_size = calculate_log_size(log2_table.table.len());
+        if !self.log2.table.is_empty() {
+            self.op_counter.log2 = self.log2.table.len();
+            let log_size = calculate_log_size(self.log2.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_log2(log2_table));
+            trace_tables.push(TraceTable::from_log2(self.log2));
 
             if let Some(lookup) = settings.lookups.log2.as_ref() {
-                lookup.add_multiplicities_to_table(&mut log2_lookup_table);
+                lookup.add_multiplicities_to_table(&mut self.log2_lookup);
                 max_log_size = max_log_size.max(lookup.layout.log_size);
-                trace_tables.push(TraceTable::from_log2_lookup(log2_lookup_table))
+                trace_tables.push(TraceTable::from_log2_lookup(self.log2_lookup))
             } // TODO (@raphaelDkhn): though error if LUT not present.
         }
-        if !less_than_table.table.is_empty() {
-            let log_size = calculate_log_size(less_than_table.table.len());
+        if !self.less_than.table.is_empty() {
+            let log_size = calculate_log_size(self.less_than.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_less_than(less_than_table));
-
+            trace_tables.push(TraceTable::from_less_than(self.less_than));
+        }
+        if range_checked {
             if let Some(lookup) = settings.lookups.range_check.as_ref() {
-                lookup.add_multiplicities_to_table(&mut range_check_lookup_table);
+                lookup.add_multiplicities_to_table(&mut self.range_check_lookup);
                 max_log_size = max_log_size.max(lookup.layout.log_size);
-                trace_tables.push(TraceTable::from_range_check_lookup(
-                    range_check_lookup_table,
-                ))
+                trace_tables.push(TraceTable::from_range_check_lookup(self.range_check_lookup))
             } // TODO (@raphaelDkhn): though error if LUT not present.
         }
-        if !inputs_table.table.is_empty() {
-            let log_size = calculate_log_size(inputs_table.table.len());
+        if !self.inputs.table.is_empty() {
+            let log_size = calculate_log_size(self.inputs.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_inputs(inputs_table));
+            trace_tables.push(TraceTable::from_inputs(self.inputs));
         }
-        if !contiguous_table.table.is_empty() {
-            let log_size = calculate_log_size(contiguous_table.table.len());
+        if !self.contiguous.table.is_empty() {
+            let log_size = calculate_log_size(self.contiguous.table.len());
             max_log_size = max_log_size.max(log_size);
-            trace_tables.push(TraceTable::from_contiguous(contiguous_table));
+            trace_tables.push(TraceTable::from_contiguous(self.contiguous));
         }
-
-        Ok(LuminairPie {
-            trace_tables,
-            metadata: Metadata {
+        if !self.select.table.is_empty() {
+            let log_size = calculate_log_size(self.select.table.len());
+            max_log_size = max_log_size.max(log_size);
+            trace_tables.push(TraceTable::from_select(self.select));
+        }
+        if !self.gather.table.is_empty() {
+            let log_size = calculate_log_size(self.gather.table.len());
+            max_log_size = max_log_size.max(log_size);
+            trace_tables.push(TraceTable::from_gather(self.gather));
+        }
+        // Shared by gather and im2col reads
+        if !self.gather_lookup.table.is_empty() {
+            let log_size = calculate_log_size(self.gather_lookup.table.len());
+            max_log_size = max_log_size.max(log_size);
+            trace_tables.push(TraceTable::from_gather_lookup(self.gather_lookup));
+        }
+        if !self.im2col.table.is_empty() {
+            let log_size = calculate_log_size(self.im2col.table.len());
+            max_log_size = max_log_size.max(log_size);
+            trace_tables.push(TraceTable::from_im2col(self.im2col));
+        }
+        if !self.matmul.table.is_empty() {
+            let log_size = calculate_log_size(self.matmul.table.len());
+            max_log_size = max_log_size.max(log_size);
+            trace_tables.push(TraceTable::from_matmul(self.matmul));
+        }
+        // One table per set of normalization parameters
+        for table in self.norm.tables {
+            if table.table.is_empty() {
+                continue;
+            }
+            let log_size = calculate_log_size(table.table.len());
+            max_log_size = max_log_size.max(log_size);
+            trace_tables.push(TraceTable::from_norm(table));
+        }
+        // One table per constant
+        for table in self.mul_const.tables {
+            if table.table.is_empty() {
+                continue;
+            }
+            let log_size = calculate_log_size(table.table.len());
+            max_log_size = max_log_size.max(log_size);
+            trace_tables.push(TraceTable::from_mul_const(table));
+        }
+        for table in self.add_const.tables {
+            if table.table.is_empty() {
+                continue;
+            }
+            let log_size = calculate_log_size(table.table.len());
+            max_log_size = max_log_size.max(log_size);
+            trace_tables.push(TraceTable::from_add_const(table));
+        }
+        // One table per expression
+        for table in self.fused.tables {
+            if table.table.is_empty() {
+                continue;
+            }
+            let log_size = calculate_log_size(table.table.len());
+            max_log_size = max_log_size.max(log_size);
+            trace_tables.push(TraceTable::from_fused(table));
+        }
+
+        LuminairPie {
+            trace_tables,
+            metadata: Metadata {
                 execution_resources: ExecutionResources {
-                    op_counter,
+                    op_counter: self.op_counter,
                     max_log_size,
                 },
             },
-        })
+        }
     }
+}
 
-    fn graph_viz(&self) -> String {
-        let mut new_graph: StableGraph<String, u8> = StableGraph::default();
-        let mut id_map = FxHashMap::default();
-        for (id, node) in self.graph.node_indices().zip(self.graph.node_weights()) {
-            id_map.insert(id, new_graph.add_node(format!("{node:?}")));
+/// Rows of the trace tables of a graph, estimated from the shapes of its views
+#[derive(Default)]
+struct TraceEstimate {
+    op_counter: OpCounter,
+    ops: Vec<OpEstimate>,
+    /// Tables in order of first use
+    tables: Vec<EstimatedTable>,
+}
+
+/// Rows of a trace table of an estimate
+struct EstimatedTable {
+    key: TableKey,
+    /// Main and interaction columns, as counted by `TraceColumn::count`
+    columns: (usize, usize),
+    rows: usize,
+}
+
+impl EstimatedTable {
+    /// Whether this is the single table of `component`
+    fn is_component(&self, component: &str) -> bool {
+        matches!(self.key, TableKey::Component(name) if name == component)
+    }
+}
+
+/// Identifies a trace table. Components with one table per constant, expression or set of
+/// parameters are keyed by it.
+#[derive(PartialEq)]
+enum TableKey {
+    Component(&'static str),
+    Constant(&'static str, u32),
+    Fused(FusedExpr),
+    Norm(NormParams),
+}
+
+impl TableKey {
+    fn component(&self) -> &'static str {
+        match self {
+            Self::Component(component) | Self::Constant(component, _) => component,
+            Self::Fused(_) => "fused",
+            Self::Norm(_) => "norm",
         }
+    }
+}
 
-        let mut schedule_edges = vec![];
-        for node in self.graph.node_indices() {
-            for edge in self
-                .graph
-                .edges_directed(node, Direction::Outgoing)
-                .sorted_by_key(|e| {
-                    if let Some(d) = e.weight().as_data() {
-                        d.0
-                    } else {
-                        0
-                    }
+/// Rows of an operator traced in the table of a component
+fn component_rows<C: TraceColumn>(
+    component: &'static str,
+    rows: usize,
+) -> (TableKey, (usize, usize), usize) {
+    (TableKey::Component(component), C::count(), rows)
+}
+
+impl TraceEstimate {
+    /// Walks the graph in execution order, adding the rows each operator would trace to its
+    /// tables
+    fn record(&mut self, graph: &mut Graph) -> Result<(), LuminairError> {
+        if graph.linearized_graph.is_none() {
+            graph.toposort();
+        }
+
+        let mut dim_stack = Vec::new();
+        for (node, src_ids) in graph.linearized_graph.as_ref().unwrap() {
+            // Substitute in the dyn dims
+            let views = src_ids
+                .iter()
+                .map(|(_, _, st)| {
+                    let mut st = *st;
+                    st.resolve_global_dyn_dims_stack(&graph.dyn_map, &mut dim_stack);
+                    st
                 })
-            {
-                let new_edge = new_graph.add_edge(
-                    id_map[&edge.source()],
-                    id_map[&edge.target()],
-                    if let Some(d) = edge.weight().as_data() {
-                        d.0
-                    } else {
-                        0
-                    },
-                );
-                if edge.weight().is_schedule() {
-                    schedule_edges.push(new_edge);
+                .collect::<Vec<_>>();
+            let op = &**graph.node_weight(*node).unwrap();
+
+            // Constants take a single row whatever their value, which may only be known at
+            // runtime
+            let op_ir =
+                if downcast_luminair::<InputsColumn, InputsTraceTable, (), LuminairConstant>(op)
+                    .is_some()
+                {
+                    OpIr::Constant { value: 0.0 }
+                } else {
+                    OpIr::from_operator(op).map_err(|_| {
+                        LuminairError::EstimateError(format!(
+                            "unsupported operator {op:?}, was the graph compiled with the \
+                             StwoCompiler?"
+                        ))
+                    })?
+                };
+
+            let (key, columns, rows) = match op_ir {
+                // Loaded and retrieved on the CPU, without a trace
+                OpIr::Input { .. } | OpIr::CopyFromStwo => continue,
+                OpIr::CopyToStwo => {
+                    self.op_counter.inputs += 1;
+                    let rows = copied_size(graph, *node)?;
+                    component_rows::<InputsColumn>("inputs", rows)
+                }
+                OpIr::Constant { .. } => {
+                    self.op_counter.inputs += 1;
+                    component_rows::<InputsColumn>("inputs", 1)
+                }
+                OpIr::Contiguous => {
+                    self.op_counter.contiguous += 1;
+                    // Every value of the input is read, even when the output is smaller
+                    let rows = physical_size(&views[0])?.max(view_size(&views[0])?);
+                    component_rows::<ContiguousColumn>("contiguous", rows)
+                }
+                OpIr::Recip => {
+                    self.op_counter.recip += 1;
+                    let rows = view_size(&views[0])?;
+                    component_rows::<RecipColumn>("recip", rows)
+                }
+                OpIr::Sin => {
+                    self.op_counter.sin += 1;
+                    component_rows::<SinColumn>("sin", view_size(&views[0])?)
+                }
+                OpIr::Sqrt => {
+                    self.op_counter.sqrt += 1;
+                    component_rows::<SqrtColumn>("sqrt", view_size(&views[0])?)
+                }
+                OpIr::Exp2 => {
+                    self.op_counter.exp2 += 1;
+                    component_rows::<Exp2Column>("exp2", view_size(&views[0])?)
+                }
+                OpIr::Log2 => {
+                    self.op_counter.log2 += 1;
+                    component_rows::<Log2Column>("log2", view_size(&views[0])?)
+                }
+                OpIr::Add => {
+                    self.op_counter.add += 1;
+                    component_rows::<AddColumn>("add", view_size(&views[0])?)
+                }
+                OpIr::Mul => {
+                    self.op_counter.mul += 1;
+                    component_rows::<MulColumn>("mul", view_size(&views[0])?)
+                }
+                OpIr::LessThan => {
+                    self.op_counter.less_than += 1;
+                    let rows = view_size(&views[0])?;
+                    component_rows::<LessThanColumn>("less_than", rows)
+                }
+                OpIr::Rem => {
+                    self.op_counter.rem += 1;
+                    component_rows::<RemColumn>("rem", view_size(&views[0])?)
+                }
+                OpIr::Select => {
+                    self.op_counter.select += 1;
+                    let rows = view_size(&views[0])?;
+                    component_rows::<SelectColumn>("select", rows)
+                }
+                OpIr::Gather => {
+                    self.op_counter.gather += 1;
+                    // One row per gathered value, and one lookup row per value of the table
+                    let table_size = view_size(&views[0])?;
+                    // An empty table has no row to gather
+                    let table_rows = views[0].shape_usize().first().copied().unwrap_or(0);
+                    let row_size = table_size.checked_div(table_rows).unwrap_or(0);
+                    let rows = view_size(&views[1])? * row_size;
+                    let lookup = TableKey::Component("gather_lookup");
+                    self.add_rows(lookup, GatherLookupColumn::count(), table_size);
+                    component_rows::<GatherColumn>("gather", rows)
+                }
+                OpIr::Im2Col {
+                    kernel,
+                    stride,
+                    padding,
+                } => {
+                    self.op_counter.im2col += 1;
+                    // One row per output value, and one lookup row per value of the image
+                    let input_size = view_size(&views[0])?;
+                    let sh = views[0].shape_usize();
+                    let (out_h, out_w) =
+                        LuminairIm2Col::new(kernel, stride, padding).output_size((sh[1], sh[2]));
+                    let rows = sh[0] * kernel.0 * kernel.1 * out_h * out_w;
+                    let lookup = TableKey::Component("gather_lookup");
+                    self.add_rows(lookup, GatherLookupColumn::count(), input_size);
+                    component_rows::<Im2ColColumn>("im2col", rows)
+                }
+                // One row per step of each reduction
+                OpIr::SumReduce { .. } => {
+                    self.op_counter.sum_reduce += 1;
+                    let rows = view_size(&views[0])?;
+                    component_rows::<SumReduceColumn>("sum_reduce", rows)
+                }
+                OpIr::MaxReduce { .. } => {
+                    self.op_counter.max_reduce += 1;
+                    let rows = view_size(&views[0])?;
+                    component_rows::<MaxReduceColumn>("max_reduce", rows)
+                }
+                OpIr::MatMul { .. } => {
+                    self.op_counter.matmul += 1;
+                    let rows = view_size(&views[0])?;
+                    component_rows::<MatMulColumn>("matmul", rows)
+                }
+                OpIr::Norm { eps, center, .. } => {
+                    self.op_counter.norm += 1;
+                    let key = TableKey::Norm(NormParams {
+                        row_size: last_dim_size(&views[0])? as u32,
+                        center,
+                        eps: Fixed::<DEFAULT_FP_SCALE>::from_f64(eps as f64).to_m31(),
+                    });
+                    (key, NormColumn::count(), view_size(&views[0])?)
+                }
+                OpIr::MulConst { value } => {
+                    self.op_counter.mul_const += 1;
+                    let key = TableKey::Constant("mul_const", value.to_bits());
+                    (key, MulConstColumn::count(), view_size(&views[0])?)
+                }
+                OpIr::AddConst { value } => {
+                    self.op_counter.add_const += 1;
+                    let key = TableKey::Constant("add_const", value.to_bits());
+                    (key, AddConstColumn::count(), view_size(&views[0])?)
+                }
+                OpIr::Fused { expr } => {
+                    self.op_counter.fused += 1;
+                    let columns = (expr.n_columns(), expr.n_relations());
+                    (TableKey::Fused(expr), columns, view_size(&views[0])?)
+                }
+            };
+
+            self.ops.push(OpEstimate {
+                node: node.index() as u32,
+                component: key.component().to_string(),
+                rows,
+            });
+            self.add_rows(key, columns, rows);
+        }
+        Ok(())
+    }
+
+    /// Adds rows to a table, creating it on first use
+    fn add_rows(&mut self, key: TableKey, columns: (usize, usize), rows: usize) {
+        match self.tables.iter_mut().find(|table| table.key == key) {
+            Some(table) => table.rows += rows,
+            None => self.tables.push(EstimatedTable { key, columns, rows }),
+        }
+    }
+
+    /// Returns true if rows were added to the table of a component
+    fn uses(&self, component: &str) -> bool {
+        self.tables
+            .iter()
+            .any(|table| table.is_component(component) && table.rows > 0)
+    }
+
+    /// Returns true if rows were added to a table whose values are range checked, i.e. of a
+    /// less-than comparison or a normalization
+    fn range_checked(&self) -> bool {
+        self.uses("less_than")
+            || self
+                .tables
+                .iter()
+                .any(|table| matches!(table.key, TableKey::Norm(_)) && table.rows > 0)
+    }
+
+    /// Settings with a lookup table for each lookup component used, holding one entry per
+    /// value its operators read
+    ///
+    /// The tables of an execution cover the range of these values instead, so their size is
+    /// only known once the graph runs.
+    fn projected_settings(&self) -> CircuitSettings {
+        let ranges = |component: &str| -> Vec<Range> {
+            self.tables
+                .iter()
+                .filter(|table| table.is_component(component) && table.rows > 0)
+                .map(|table| Range(Fixed(0), Fixed(table.rows as i64 - 1)))
+                .collect()
+        };
+        LookupRanges {
+            sin: ranges("sin"),
+            exp2: ranges("exp2"),
+            log2: ranges("log2"),
+            range_check_8: self.range_checked(),
+        }
+        .into_settings()
+    }
+
+    /// Sizes the component instances proving the tables, sharded as `prove` does, with the
+    /// lookup tables of `settings`
+    fn into_estimate(self, settings: &CircuitSettings) -> ResourceEstimate {
+        let lookups = &settings.lookups;
+        // Lookup tables hold one row per entry of their layout and are never sharded
+        let mut luts = Vec::new();
+        if let Some(lookup) = lookups.sin.as_ref().filter(|_| self.uses("sin")) {
+            let (rows, log_size) = (lookup.multiplicities.data.len(), lookup.layout.log_size);
+            luts.push(("sin_lookup", SinLookupColumn::count(), rows, log_size));
+        }
+        if let Some(lookup) = lookups.exp2.as_ref().filter(|_| self.uses("exp2")) {
+            let (rows, log_size) = (lookup.multiplicities.data.len(), lookup.layout.log_size);
+            luts.push(("exp2_lookup", Exp2LookupColumn::count(), rows, log_size));
+        }
+        if let Some(lookup) = lookups.log2.as_ref().filter(|_| self.uses("log2")) {
+            let (rows, log_size) = (lookup.multiplicities.data.len(), lookup.layout.log_size);
+            luts.push(("log2_lookup", Log2LookupColumn::count(), rows, log_size));
+        }
+        if let Some(lookup) = lookups
+            .range_check
+            .as_ref()
+            .filter(|_| self.range_checked())
+        {
+            let (rows, log_size) = (lookup.multiplicities.data.len(), lookup.layout.log_size);
+            let columns = RangeCheckLookupColumn::count();
+            luts.push(("range_check_lookup", columns, rows, log_size));
+        }
+
+        let max_log_size = self
+            .tables
+            .iter()
+            .filter(|table| table.rows > 0)
+            .map(|table| calculate_log_size(table.rows))
+            .chain(luts.iter().map(|(_, _, _, log_size)| *log_size))
+            .max()
+            .unwrap_or(0);
+
+        // The proving key of `prove` is sized for the preprocessed trace
+        let preprocessed_log_sizes =
+            PreProcessedTrace::new(lookups_to_preprocessed_column(lookups)).log_sizes();
+        let shard_log_size = max_log_size
+            .min(MAX_COMPONENT_LOG_SIZE)
+            .max(preprocessed_log_sizes.iter().copied().max().unwrap_or(0));
+
+        let shards = self.tables.iter().flat_map(|table| {
+            let component = table.key.component();
+            // Normalizations are only sharded between normalized rows
+            let sizes = match &table.key {
+                TableKey::Norm(params) => {
+                    norm_shard_sizes(table.rows, params.row_size as usize, shard_log_size)
                 }
+                _ => shard_sizes(table.rows, shard_log_size),
+            };
+            sizes
+                .into_iter()
+                .map(move |rows| (component, table.columns, rows, calculate_log_size(rows)))
+        });
+        let components = shards
+            .chain(luts)
+            .map(
+                |(component, (main, interaction), rows, log_size)| ComponentEstimate {
+                    component: component.to_string(),
+                    rows,
+                    log_size,
+                    main_columns: main,
+                    interaction_columns: SECURE_EXTENSION_DEGREE * interaction,
+                },
+            )
+            .collect::<Vec<_>>();
+
+        let trace_cells = components
+            .iter()
+            .map(|c| (c.main_columns + c.interaction_columns) << c.log_size)
+            .sum();
+        let column_log_sizes = preprocessed_log_sizes
+            .iter()
+            .copied()
+            .chain(components.iter().flat_map(|c| {
+                std::iter::repeat(c.log_size).take(c.main_columns + c.interaction_columns)
+            }))
+            .collect::<Vec<_>>();
+        let config = PcsConfig::default();
+
+        ResourceEstimate {
+            execution_resources: ExecutionResources {
+                op_counter: self.op_counter,
+                max_log_size,
+            },
+            ops: self.ops,
+            components,
+            prover_memory: projected_prover_memory(&column_log_sizes, shard_log_size, &config),
+            proof_size: projected_proof_size(&column_log_sizes, &config),
+            preprocessed_log_sizes,
+            trace_cells,
+        }
+    }
+}
+
+/// Trace generation of a node that may run on another thread, recording into the given
+/// tables and returning the output tensors of the node
+type TraceJob<'a> = Box<dyn FnOnce(&mut TraceTables) -> Vec<StwoData> + Send + 'a>;
+
+/// Records the trace of an operator with trace types `C`, `T` and `L` into the tables
+type RecordFn<C, T, L> = fn(
+    &mut dyn LuminairOperator<C, T, L>,
+    Vec<(InputTensor, ShapeTracker)>,
+    &mut TraceTables,
+    &NodeInfo,
+) -> Vec<Tensor>;
+
+/// Splits a linearized graph into waves of consecutive nodes that don't depend on each other
+///
+/// Waves never straddle two segments.
+fn independent_waves(
+    linearized: &[(NodeIndex, Vec<(NodeIndex, u8, ShapeTracker)>)],
+    segmenter: Option<&Segmenter>,
+) -> Vec<std::ops::Range<usize>> {
+    let mut waves = Vec::new();
+    let mut start = 0;
+    let mut members = FxHashSet::default();
+    for (i, (node, src_ids)) in linearized.iter().enumerate() {
+        let new_segment = segmenter.is_some_and(|segmenter| {
+            segmenter.segment_of[node] != segmenter.segment_of[&linearized[start].0]
+        });
+        if new_segment || src_ids.iter().any(|(id, _, _)| members.contains(id)) {
+            waves.push(start..i);
+            start = i;
+            members.clear();
+        }
+        members.insert(*node);
+    }
+    if start < linearized.len() {
+        waves.push(start..linearized.len());
+    }
+    waves
+}
+
+/// Whether the trace of an operator only depends on its inputs and its own tables
+///
+/// Operators recording into a lookup of the circuit settings, and copies of the graph
+/// inputs, are traced on the calling thread.
+fn is_concurrent(node_op: &dyn Operator) -> bool {
+    is_luminair::<AddColumn, AddTraceTable, ()>(node_op)
+        || is_luminair::<MulColumn, MulTraceTable, ()>(node_op)
+        || is_luminair::<RecipColumn, RecipTraceTable, ()>(node_op)
+        || is_luminair::<SumReduceColumn, SumReduceTraceTable, ()>(node_op)
+        || is_luminair::<MaxReduceColumn, MaxReduceTraceTable, ()>(node_op)
+        || is_luminair::<SqrtColumn, SqrtTraceTable, ()>(node_op)
+        || is_luminair::<RemColumn, RemTraceTable, ()>(node_op)
+        || is_luminair::<ContiguousColumn, ContiguousTraceTable, ()>(node_op)
+        || is_luminair::<SelectColumn, SelectTraceTable, ()>(node_op)
+        || is_luminair::<GatherColumn, GatherTraceTable, GatherLookupTraceTable>(node_op)
+        || is_luminair::<Im2ColColumn, Im2ColTraceTable, GatherLookupTraceTable>(node_op)
+        || is_luminair::<MatMulColumn, MatMulTraceTable, ()>(node_op)
+        || is_luminair::<MulConstColumn, MulConstTraceTables, ()>(node_op)
+        || is_luminair::<AddConstColumn, AddConstTraceTables, ()>(node_op)
+        || is_luminair::<FusedColumn, FusedTraceTables, ()>(node_op)
+}
+
+/// Prepares the trace generation of a node whose operator `is_concurrent`
+fn concurrent_job(
+    node_op: &mut Box<dyn Operator>,
+    srcs: Vec<(StwoData, ShapeTracker)>,
+    node_info: NodeInfo,
+) -> Option<TraceJob<'_>> {
+    let op: &dyn Operator = &**node_op;
+    match () {
+        _ if is_luminair::<AddColumn, AddTraceTable, ()>(op) => {
+            trace_job::<AddColumn, AddTraceTable, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.add += 1;
+                    op.process_trace(srcs, &mut tables.add, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<MulColumn, MulTraceTable, ()>(op) => {
+            trace_job::<MulColumn, MulTraceTable, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.mul += 1;
+                    op.process_trace(srcs, &mut tables.mul, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<RecipColumn, RecipTraceTable, ()>(op) => {
+            trace_job::<RecipColumn, RecipTraceTable, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.recip += 1;
+                    op.process_trace(srcs, &mut tables.recip, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<SumReduceColumn, SumReduceTraceTable, ()>(op) => {
+            trace_job::<SumReduceColumn, SumReduceTraceTable, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.sum_reduce += 1;
+                    op.process_trace(srcs, &mut tables.sum_reduce, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<MaxReduceColumn, MaxReduceTraceTable, ()>(op) => {
+            trace_job::<MaxReduceColumn, MaxReduceTraceTable, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.max_reduce += 1;
+                    op.process_trace(srcs, &mut tables.max_reduce, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<SqrtColumn, SqrtTraceTable, ()>(op) => {
+            trace_job::<SqrtColumn, SqrtTraceTable, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.sqrt += 1;
+                    op.process_trace(srcs, &mut tables.sqrt, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<RemColumn, RemTraceTable, ()>(op) => {
+            trace_job::<RemColumn, RemTraceTable, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.rem += 1;
+                    op.process_trace(srcs, &mut tables.rem, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<ContiguousColumn, ContiguousTraceTable, ()>(op) => {
+            trace_job::<ContiguousColumn, ContiguousTraceTable, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.contiguous += 1;
+                    op.process_trace(srcs, &mut tables.contiguous, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<SelectColumn, SelectTraceTable, ()>(op) => {
+            trace_job::<SelectColumn, SelectTraceTable, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.select += 1;
+                    op.process_trace(srcs, &mut tables.select, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<GatherColumn, GatherTraceTable, GatherLookupTraceTable>(op) => {
+            trace_job::<GatherColumn, GatherTraceTable, GatherLookupTraceTable>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.gather += 1;
+                    op.process_trace(
+                        srcs,
+                        &mut tables.gather,
+                        node_info,
+                        &mut tables.gather_lookup,
+                    )
+                },
+            )
+        }
+        _ if is_luminair::<Im2ColColumn, Im2ColTraceTable, GatherLookupTraceTable>(op) => {
+            trace_job::<Im2ColColumn, Im2ColTraceTable, GatherLookupTraceTable>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.im2col += 1;
+                    op.process_trace(
+                        srcs,
+                        &mut tables.im2col,
+                        node_info,
+                        &mut tables.gather_lookup,
+                    )
+                },
+            )
+        }
+        _ if is_luminair::<MatMulColumn, MatMulTraceTable, ()>(op) => {
+            trace_job::<MatMulColumn, MatMulTraceTable, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.matmul += 1;
+                    op.process_trace(srcs, &mut tables.matmul, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<MulConstColumn, MulConstTraceTables, ()>(op) => {
+            trace_job::<MulConstColumn, MulConstTraceTables, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.mul_const += 1;
+                    op.process_trace(srcs, &mut tables.mul_const, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<AddConstColumn, AddConstTraceTables, ()>(op) => {
+            trace_job::<AddConstColumn, AddConstTraceTables, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.add_const += 1;
+                    op.process_trace(srcs, &mut tables.add_const, node_info, &mut ())
+                },
+            )
+        }
+        _ if is_luminair::<FusedColumn, FusedTraceTables, ()>(op) => {
+            trace_job::<FusedColumn, FusedTraceTables, ()>(
+                node_op,
+                srcs,
+                node_info,
+                |op, srcs, tables, node_info| {
+                    tables.op_counter.fused += 1;
+                    op.process_trace(srcs, &mut tables.fused, node_info, &mut ())
+                },
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Wraps the trace generation of a LuminAIR operator into a job that owns its inputs
+fn trace_job<C, T, L>(
+    node_op: &mut Box<dyn Operator>,
+    srcs: Vec<(StwoData, ShapeTracker)>,
+    node_info: NodeInfo,
+    record: RecordFn<C, T, L>,
+) -> Option<TraceJob<'_>>
+where
+    C: TraceColumn + Debug + 'static,
+    T: Debug + 'static,
+    L: Debug + 'static,
+{
+    let op = downcast_luminair_mut::<C, T, L>(&mut **node_op)?;
+    Some(Box::new(move |tables: &mut TraceTables| {
+        let srcs = srcs
+            .into_iter()
+            .map(|(data, st)| (InputTensor::Owned(Tensor::new(data)), st))
+            .collect();
+        record(op, srcs, tables, &node_info)
+            .into_iter()
+            .map(|tensor| tensor.downcast_ref::<StwoData>().unwrap().clone())
+            .collect()
+    }))
+}
+
+/// Static assignment of the nodes of a graph to segments, handing each segment over once cut
+struct Segmenter<'a> {
+    /// Segment of each node of the linearized graph
+    segment_of: FxHashMap<NodeIndex, usize>,
+    /// Segment being recorded
+    current: usize,
+    /// Settings with the lookup multiplicities reset, cloned for each new segment
+    settings: CircuitSettings,
+    /// Boundary inputs of the segment being recorded
+    inputs: Vec<BoundaryInput>,
+    /// Receives each segment as soon as it is cut
+    on_segment: &'a mut dyn FnMut(LuminairSegment) -> Result<(), LuminairError>,
+}
+
+impl<'a> Segmenter<'a> {
+    /// Splits the linearized graph into segments of `nodes_per_segment` operators
+    ///
+    /// Input functions and copies out of the trace don't count towards the size of a segment
+    /// and stay in the segment of the operator before them.
+    fn new(
+        graph: &Graph,
+        settings: &CircuitSettings,
+        nodes_per_segment: usize,
+        on_segment: &'a mut dyn FnMut(LuminairSegment) -> Result<(), LuminairError>,
+    ) -> Self {
+        let mut segment_of = FxHashMap::default();
+        let mut segment = 0;
+        let mut counted = 0;
+        for (node, _) in graph.linearized_graph.as_ref().unwrap() {
+            let op = graph.node_weight(*node).unwrap().as_any();
+            if !op.is::<Function>() && !op.is::<CopyFromStwo>() {
+                if counted == nodes_per_segment {
+                    segment += 1;
+                    counted = 0;
+                }
+                counted += 1;
             }
+            segment_of.insert(*node, segment);
         }
 
-        let mut graph_string =
-            petgraph::dot::Dot::with_config(&new_graph, &[petgraph::dot::Config::EdgeIndexLabel])
-                .to_string();
-        let re = Regex::new(r#"label\s*=\s*"\d+""#).unwrap();
-        for e in schedule_edges {
-            graph_string =
-                graph_string.replace(&format!("label = \"{}\"", e.index()), "color=\"green\"");
+        Self {
+            segment_of,
+            current: 0,
+            settings: settings.clone(),
+            inputs: Vec::new(),
+            on_segment,
         }
-        graph_string = re.replace_all(&graph_string, "").to_string();
-        let mark_nodes: &[NodeIndex] = &[];
-        for n in mark_nodes {
-            graph_string = graph_string.replace(
-                &format!("    {} [ label =", n.index()),
-                &format!(
-                    "    {} [ style=\"filled\" fillcolor=\"yellow\" label =",
-                    n.index()
-                ),
-            );
+    }
+
+    /// Whether the tensor of `node` is produced before segment `cut` and consumed in the trace
+    /// from it on
+    fn crosses(&self, graph: &Graph, node: NodeIndex, cut: usize) -> bool {
+        self.segment_of[&node] < cut
+            && traced_consumers(graph, node).any(|(target, _)| self.segment_of[&target] >= cut)
+    }
+
+    /// Ends the current segment with its PIE, returning the settings of the next one
+    fn cut(&mut self, graph: &Graph, pie: LuminairPie) -> Result<CircuitSettings, LuminairError> {
+        let next = self.current + 1;
+        let outputs = self
+            .segment_of
+            .keys()
+            .filter(|node| self.crosses(graph, **node, next))
+            .sorted()
+            .map(|node| -> Result<_, LuminairError> {
+                let data = graph
+                    .get_tensor_ref(*node, 0)
+                    .and_then(|tensor| tensor.downcast_ref::<StwoData>())
+                    .ok_or_else(|| {
+                        LuminairError::InvalidSegmentChain(format!(
+                            "the tensor of node {} crossing a cut isn't held in the trace",
+                            node.index()
+                        ))
+                    })?;
+                Ok(BoundaryTensor {
+                    node_id: node.index() as u32,
+                    values: data.0.iter().map(|val| val.to_m31()).collect(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The next segment yields each output once per consumer it holds, and once more when
+        // the tensor also crosses its own end
+        let inputs = outputs
+            .iter()
+            .map(|tensor| {
+                let node = NodeIndex::new(tensor.node_id as usize);
+                let consumers: u32 = traced_consumers(graph, node)
+                    .filter(|(target, _)| self.segment_of[target] == next)
+                    .map(|(_, shape)| expansion_factor(&shape))
+                    .sum();
+                BoundaryInput {
+                    tensor: tensor.clone(),
+                    multiplicity: consumers + self.crosses(graph, node, next + 1) as u32,
+                }
+            })
+            .collect();
+
+        (self.on_segment)(LuminairSegment {
+            pie,
+            boundary: SegmentBoundary {
+                inputs: std::mem::replace(&mut self.inputs, inputs),
+                outputs,
+            },
+        })?;
+        self.current = next;
+        Ok(self.settings.clone())
+    }
+
+    /// Ends the last segment with its PIE
+    fn finish(mut self, pie: LuminairPie) -> Result<(), LuminairError> {
+        (self.on_segment)(LuminairSegment {
+            pie,
+            boundary: SegmentBoundary {
+                inputs: self.inputs,
+                outputs: Vec::new(),
+            },
+        })
+    }
+}
+
+/// Inputs and outputs of one execution of a graph, recorded as the boundary binding them to
+/// its proof
+struct IoRecorder {
+    /// Runtime input functions, in the order of the boundary inputs
+    inputs: Vec<NodeIndex>,
+    /// Boundary input of each runtime input, once its copy into the trace is traced
+    copies: Vec<Option<BoundaryInput>>,
+    /// Retrieved outputs traced so far
+    outputs: Vec<BoundaryTensor>,
+}
+
+impl IoRecorder {
+    fn new(inputs: Vec<NodeIndex>) -> Self {
+        Self {
+            copies: vec![None; inputs.len()],
+            inputs,
+            outputs: Vec::new(),
         }
+    }
 
-        graph_string.to_owned()
+    /// Position among the runtime inputs of the function copied into the trace by `node`, if
+    /// it is one
+    fn input_of(
+        &self,
+        graph: &Graph,
+        node: NodeIndex,
+        src_ids: &[(NodeIndex, u8, ShapeTracker)],
+    ) -> Option<usize> {
+        let op = graph.graph.node_weight(node)?;
+        if !is_luminair::<InputsColumn, InputsTraceTable, ()>(op.as_ref()) {
+            return None;
+        }
+        let (source, ..) = src_ids.first()?;
+        self.inputs.iter().position(|input| input == source)
+    }
+
+    /// Records the copy of a runtime input, yielded by the verifier `multiplicity` times
+    fn record_input(
+        &mut self,
+        position: usize,
+        node_id: u32,
+        data: Option<&StwoData>,
+        multiplicity: u32,
+    ) -> Result<(), LuminairError> {
+        self.copies[position] = Some(BoundaryInput {
+            tensor: io_tensor(node_id, data)?,
+            multiplicity,
+        });
+        Ok(())
+    }
+
+    /// Records a retrieved output, consumed once by the verifier
+    fn record_output(
+        &mut self,
+        node_id: u32,
+        data: Option<&StwoData>,
+    ) -> Result<(), LuminairError> {
+        self.outputs.push(io_tensor(node_id, data)?);
+        Ok(())
+    }
+
+    /// Returns the boundary of the execution, its inputs in the order they were given and its
+    /// outputs sorted by node
+    fn finish(self) -> Result<SegmentBoundary, LuminairError> {
+        let inputs = self
+            .inputs
+            .iter()
+            .zip(self.copies)
+            .map(|(node, copy)| {
+                copy.ok_or_else(|| {
+                    LuminairError::InvalidPublicIo(format!(
+                        "input {} is not read by the trace",
+                        node.index()
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut outputs = self.outputs;
+        outputs.sort_by_key(|tensor| tensor.node_id);
+        Ok(SegmentBoundary { inputs, outputs })
+    }
+}
+
+/// Boundary tensor of an input or output of an execution
+fn io_tensor(node_id: u32, data: Option<&StwoData>) -> Result<BoundaryTensor, LuminairError> {
+    let data = data.ok_or_else(|| {
+        LuminairError::InvalidPublicIo(format!(
+            "the tensor of node {} isn't held in the trace",
+            node_id
+        ))
+    })?;
+    Ok(BoundaryTensor {
+        node_id,
+        values: data.0.iter().map(|val| val.to_m31()).collect(),
+    })
+}
+
+/// Whether the tensor of traced `node` is handed to the caller, directly or through a copy
+/// out of the trace
+fn is_bound_output(graph: &Graph, node: NodeIndex) -> bool {
+    let op = graph.node_weight(node).unwrap().as_any();
+    if op.is::<Function>() || op.is::<CopyFromStwo>() {
+        return false;
+    }
+    graph.to_retrieve.contains_key(&node)
+        || graph
+            .graph
+            .edges_directed(node, Direction::Outgoing)
+            .any(|e| {
+                graph.to_retrieve.contains_key(&e.target())
+                    && graph
+                        .node_weight(e.target())
+                        .unwrap()
+                        .as_any()
+                        .is::<CopyFromStwo>()
+            })
+}
+
+/// Consumers of the tensor of `node` that are traced, along with the view they read it
+/// through. Copies out of the trace only hand the tensor over to the caller.
+fn traced_consumers(
+    graph: &Graph,
+    node: NodeIndex,
+) -> impl Iterator<Item = (NodeIndex, ShapeTracker)> + '_ {
+    graph
+        .graph
+        .edges_directed(node, Direction::Outgoing)
+        .filter(|e| {
+            !graph
+                .node_weight(e.target())
+                .unwrap()
+                .as_any()
+                .is::<CopyFromStwo>()
+        })
+        .filter_map(|e| Some((e.target(), e.weight().as_data()?.2)))
+}
+
+/// Number of times a consumer reads each value of a tensor, counting the sizes of the fake
+/// (expanded) dimensions of its view
+fn expansion_factor(shape: &ShapeTracker) -> u32 {
+    (0..shape.len())
+        .map(|i| {
+            let dim_index = shape.indexes[i];
+            if shape.fake[dim_index] {
+                // This dimension is fake (expanded), so count its size
+                shape.dims[dim_index].to_usize().unwrap_or(1) as u32
+            } else {
+                // This dimension is real, contributes factor of 1
+                1
+            }
+        })
+        .product()
+}
+
+/// Number of values of the tensor read through a view, its fake (expanded) dimensions aside
+fn physical_size(shape: &ShapeTracker) -> Result<usize, LuminairError> {
+    (0..shape.len())
+        .filter(|&i| !shape.fake[shape.indexes[i]])
+        .map(|i| shape.dims[shape.indexes[i]].to_usize())
+        .product::<Option<usize>>()
+        .ok_or_else(|| unresolved_dims(shape))
+}
+
+/// Number of elements of a view
+fn view_size(shape: &ShapeTracker) -> Result<usize, LuminairError> {
+    shape
+        .n_elements()
+        .to_usize()
+        .ok_or_else(|| unresolved_dims(shape))
+}
+
+/// Size of the last dimension of a view, along which normalizations reduce
+fn last_dim_size(shape: &ShapeTracker) -> Result<usize, LuminairError> {
+    shape
+        .shape()
+        .last()
+        .and_then(|dim| dim.to_usize())
+        .ok_or_else(|| unresolved_dims(shape))
+}
+
+fn unresolved_dims(shape: &ShapeTracker) -> LuminairError {
+    LuminairError::EstimateError(format!("unresolved dynamic dimensions in {shape:?}"))
+}
+
+/// Number of values copied to Stwo by a node, read from the views of its consumers since its
+/// input is only loaded at runtime
+fn copied_size(graph: &Graph, node: NodeIndex) -> Result<usize, LuminairError> {
+    let mut dim_stack = Vec::new();
+    let mut size = None;
+    let consumer_views = graph
+        .graph
+        .edges_directed(node, Direction::Outgoing)
+        .filter_map(|e| e.weight().as_data().map(|(_, _, shape)| shape))
+        .chain(graph.to_retrieve.get(&node).map(|(_, shape)| *shape));
+    for mut shape in consumer_views {
+        shape.resolve_global_dyn_dims_stack(&graph.dyn_map, &mut dim_stack);
+        size = size.max(Some(physical_size(&shape)?));
     }
+    size.ok_or_else(|| {
+        LuminairError::EstimateError(format!("input {node:?} is neither read nor retrieved"))
+    })
+}
+
+/// Projected peak memory of the prover for committed columns of the given log sizes, with the
+/// twiddles of a proving key sized for `max_log_size`
+///
+/// Each column is held as the coefficients of its polynomial and as its evaluation on the
+/// blown-up domain, the composition polynomial as well.
+fn projected_prover_memory(
+    column_log_sizes: &[u32],
+    max_log_size: u32,
+    config: &PcsConfig,
+) -> usize {
+    const BASE_FIELD_BYTES: usize = 4;
+    let log_blowup_factor = config.fri_config.log_blowup_factor;
+    let column_memory = |log_size: u32| {
+        BASE_FIELD_BYTES * ((1 << log_size) + (1 << (log_size + log_blowup_factor)))
+    };
+
+    let composition_log_size = column_log_sizes
+        .iter()
+        .max()
+        .map_or(0, |log_size| log_size + 1);
+    let columns = column_log_sizes
+        .iter()
+        .map(|&log_size| column_memory(log_size))
+        .sum::<usize>();
+    let composition = SECURE_EXTENSION_DEGREE * column_memory(composition_log_size);
+    // Twiddles and inverse twiddles, precomputed as `ProvingKey::new` does
+    let twiddles = 2 * BASE_FIELD_BYTES * (1 << (max_log_size + log_blowup_factor + 2));
+    columns + composition + twiddles
+}
+
+/// Projected upper bound on the size of a proof committing columns of the given log sizes
+///
+/// Every query is counted with its own authentication paths, while the paths of actual
+/// queries share their nodes near the roots.
+fn projected_proof_size(column_log_sizes: &[u32], config: &PcsConfig) -> usize {
+    const BASE_FIELD_BYTES: usize = 4;
+    const SECURE_FIELD_BYTES: usize = SECURE_EXTENSION_DEGREE * BASE_FIELD_BYTES;
+    const HASH_BYTES: usize = 32;
+    // Preprocessed, main, interaction and composition trees
+    const N_TREES: usize = 4;
+    let fri = &config.fri_config;
+    let n_queries = fri.n_queries;
+
+    let composition_log_size = column_log_sizes
+        .iter()
+        .max()
+        .map_or(0, |log_size| log_size + 1);
+    let n_columns = column_log_sizes.len() + SECURE_EXTENSION_DEGREE;
+    let depth = (composition_log_size + fri.log_blowup_factor) as usize;
+    // Out-of-domain samples, then the queried values and their paths in each tree
+    let sampled_values = n_columns * SECURE_FIELD_BYTES;
+    let queries = n_queries * (n_columns * BASE_FIELD_BYTES + N_TREES * depth * HASH_BYTES)
+        + N_TREES * HASH_BYTES;
+    // One FRI layer per folding down to the last layer, each with a root and per query a
+    // sibling value and its path
+    let n_layers = composition_log_size.saturating_sub(fri.log_last_layer_degree_bound) as usize;
+    let layers = (0..n_layers)
+        .map(|layer| HASH_BYTES + n_queries * (SECURE_FIELD_BYTES + (depth - layer) * HASH_BYTES))
+        .sum::<usize>();
+    let last_layer = SECURE_FIELD_BYTES << fri.log_last_layer_degree_bound;
+    sampled_values + queries + layers + last_layer
+}
+
+/// Closure of an input function, loading its tensor
+type InputLoader = Box<dyn Fn(Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor>>;
+
+/// Sets the input tensors of one execution of a batch, dropping every tensor of the previous
+/// execution so that the whole graph runs again
+///
+/// Returns the loaders of the caller, to be put back with `restore_inputs` once the execution
+/// is traced.
+fn set_batch_inputs(
+    graph: &mut Graph,
+    inputs: &BatchInputs,
+) -> Result<Vec<(NodeIndex, InputLoader)>, LuminairError> {
+    graph.tensors.clear();
+    let mut loaders = Vec::with_capacity(inputs.len());
+    for (node, data) in inputs {
+        let Some(function) = graph
+            .graph
+            .node_weight_mut(*node)
+            .and_then(|op| op.as_any_mut().downcast_mut::<Function>())
+        else {
+            restore_inputs(graph, loaders);
+            return Err(LuminairError::InvalidBatch(format!(
+                "node {} is not an input",
+                node.index()
+            )));
+        };
+        let data = data.clone();
+        let loader = std::mem::replace(
+            &mut function.1,
+            Box::new(move |_| vec![Tensor::new(data.clone())]),
+        );
+        loaders.push((*node, loader));
+    }
+    Ok(loaders)
+}
+
+/// Puts back the input loaders replaced by `set_batch_inputs`
+fn restore_inputs(graph: &mut Graph, loaders: Vec<(NodeIndex, InputLoader)>) {
+    for (node, loader) in loaders {
+        if let Some(function) = graph
+            .graph
+            .node_weight_mut(node)
+            .and_then(|op| op.as_any_mut().downcast_mut::<Function>())
+        {
+            function.1 = loader;
+        }
+    }
+}
+
+/// Retrieved outputs of the last execution of the graph, sorted by node
+fn retrieved_outputs(graph: &Graph) -> Vec<(NodeIndex, Vec<f32>)> {
+    graph
+        .to_retrieve
+        .keys()
+        .sorted()
+        .filter_map(|node| {
+            let data = graph.get_tensor_ref(*node, 0)?.downcast_ref::<Vec<f32>>()?;
+            Some((*node, data.clone()))
+        })
+        .collect()
 }
 
 fn coalesce_ranges(mut ranges: Vec<Range>) -> Vec<Range> {
@@ -0,0 +1,735 @@
+//! Portable representation of compiled LuminAIR graphs
+//!
+//! A [`GraphIr`] describes the nodes and edges of a graph compiled with the
+//! [`StwoCompiler`](crate::StwoCompiler), so that a model can be shipped as a JSON or
+//! bincode file and rebuilt into an equivalent provable graph with [`GraphIr::to_graph`].
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+use luminair_air::components::{
+    add::table::{AddColumn, AddTraceTable},
+    add_const::table::{AddConstColumn, AddConstTraceTables},
+    contiguous::table::{ContiguousColumn, ContiguousTraceTable},
+    exp2::table::{Exp2Column, Exp2TraceTable},
+    fused::{
+        expr::FusedExpr,
+        table::{FusedColumn, FusedTraceTables},
+    },
+    gather::table::{GatherColumn, GatherTraceTable},
+    im2col::table::{Im2ColColumn, Im2ColTraceTable},
+    inputs::table::{InputsColumn, InputsTraceTable},
+    less_than::table::{LessThanColumn, LessThanTraceTable},
+    log2::table::{Log2Column, Log2TraceTable},
+    lookups::{
+        exp2::Exp2Lookup, gather::table::GatherLookupTraceTable, log2::Log2Lookup,
+        range_check::RangeCheckLookup, sin::SinLookup,
+    },
+    matmul::table::{MatMulColumn, MatMulTraceTable},
+    max_reduce::table::{MaxReduceColumn, MaxReduceTraceTable},
+    mul::table::{MulColumn, MulTraceTable},
+    mul_const::table::{MulConstColumn, MulConstTraceTables},
+    norm::table::{NormColumn, NormTraceTables},
+    recip::table::{RecipColumn, RecipTraceTable},
+    rem::table::{RemColumn, RemTraceTable},
+    select::table::{SelectColumn, SelectTraceTable},
+    sin::table::{SinColumn, SinTraceTable},
+    sqrt::table::{SqrtColumn, SqrtTraceTable},
+    sum_reduce::table::{SumReduceColumn, SumReduceTraceTable},
+};
+use luminair_utils::LuminairError;
+use luminal::{op::Function, prelude::*};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::op::{
+    downcast_luminair,
+    prim::{
+        CopyFromStwo, CopyToStwo, LuminairAdd, LuminairAddConst, LuminairConstant,
+        LuminairContiguous, LuminairExp2, LuminairFused, LuminairGather, LuminairIm2Col,
+        LuminairLessThan, LuminairLog2, LuminairMatMul, LuminairMaxReduce, LuminairMul,
+        LuminairMulConst, LuminairNorm, LuminairRecip, LuminairRem, LuminairSelect, LuminairSin,
+        LuminairSqrt, LuminairSumReduce,
+    },
+    IntoOperator,
+};
+
+/// Version of the IR format, bumped on every incompatible change
+pub const IR_VERSION: u32 = 1;
+
+/// Serializable description of a compiled LuminAIR graph
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GraphIr {
+    /// Version of the IR format the graph was written with
+    pub version: u32,
+    /// Nodes of the graph, in node index order
+    pub nodes: Vec<NodeIr>,
+    /// Edges between the nodes
+    pub edges: Vec<EdgeIr>,
+    /// Outputs retrieved after execution
+    pub outputs: Vec<OutputIr>,
+    /// Nodes whose tensors are kept after execution
+    pub no_delete: Vec<u32>,
+}
+
+/// A node of the graph and the operator it runs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NodeIr {
+    /// Index of the node in the serialized graph
+    pub id: u32,
+    /// Operator run by the node
+    pub op: OpIr,
+}
+
+/// Operators of a compiled LuminAIR graph
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum OpIr {
+    /// Tensor loaded into the graph. Inputs without a value are set at runtime by name,
+    /// the others (e.g. model weights) are shipped with the graph
+    Input {
+        name: String,
+        value: Option<Vec<f32>>,
+    },
+    CopyToStwo,
+    CopyFromStwo,
+    Constant {
+        value: f32,
+    },
+    Contiguous,
+    Recip,
+    Sin,
+    Sqrt,
+    Exp2,
+    Log2,
+    Add,
+    Mul,
+    LessThan,
+    Rem,
+    Select,
+    Gather,
+    Im2Col {
+        kernel: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    },
+    SumReduce {
+        dim: usize,
+    },
+    MaxReduce {
+        dim: usize,
+    },
+    MatMul {
+        dim: usize,
+    },
+    Norm {
+        eps: f32,
+        center: bool,
+        has_weight: bool,
+        has_bias: bool,
+    },
+    MulConst {
+        value: f32,
+    },
+    AddConst {
+        value: f32,
+    },
+    Fused {
+        expr: FusedExpr,
+    },
+}
+
+/// An edge of the graph
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum EdgeIr {
+    /// The target reads an output of the source through a view
+    Data {
+        source: u32,
+        target: u32,
+        input_order: u8,
+        output_index: u8,
+        shape: ShapeIr,
+    },
+    /// The target must run after the source
+    Schedule { source: u32, target: u32 },
+}
+
+/// A retrieved output of the graph
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutputIr {
+    /// Node producing the output
+    pub node: u32,
+    /// Index of the output of the node
+    pub output_index: u8,
+    /// View of the output
+    pub shape: ShapeIr,
+}
+
+/// Static view of a tensor, mirroring a `ShapeTracker`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShapeIr {
+    /// Size of each dimension
+    pub dims: Vec<usize>,
+    /// Permutation of the dimensions
+    pub indexes: Vec<usize>,
+    /// Whether each dimension is expanded
+    pub fake: Vec<bool>,
+    /// Valid range of each dimension
+    pub mask: Vec<(usize, usize)>,
+    /// Padding before and after each dimension
+    pub padding: Vec<(usize, usize)>,
+}
+
+impl ShapeIr {
+    /// Captures a view, failing if any of its dimensions is dynamic
+    pub fn from_tracker(shape: &ShapeTracker) -> Result<Self, LuminairError> {
+        let size = |e: &Expression| {
+            e.to_usize().ok_or_else(|| {
+                LuminairError::SerializationError(format!(
+                    "Failed to serialize shape: dynamic dimension {:?}",
+                    e
+                ))
+            })
+        };
+        let range = |(a, b): &(Expression, Expression)| Ok((size(a)?, size(b)?));
+
+        Ok(Self {
+            dims: shape.dims.iter().map(size).collect::<Result<_, _>>()?,
+            indexes: shape.indexes.iter().copied().collect(),
+            fake: shape.fake.iter().copied().collect(),
+            mask: shape.mask.iter().map(range).collect::<Result<_, _>>()?,
+            padding: shape.padding.iter().map(range).collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Rebuilds the view
+    pub fn to_tracker(&self) -> ShapeTracker {
+        let mut shape = ShapeTracker::new(());
+        for &dim in &self.dims {
+            shape.dims.push(dim.into());
+        }
+        for &index in &self.indexes {
+            shape.indexes.push(index);
+        }
+        for &fake in &self.fake {
+            shape.fake.push(fake);
+        }
+        for &(start, end) in &self.mask {
+            shape.mask.push((start.into(), end.into()));
+        }
+        for &(before, after) in &self.padding {
+            shape.padding.push((before.into(), after.into()));
+        }
+        shape
+    }
+}
+
+impl OpIr {
+    /// Describes the operator of a compiled graph node
+    pub(crate) fn from_operator(op: &dyn Operator) -> Result<Self, LuminairError> {
+        if let Some(function) = op.as_any().downcast_ref::<Function>() {
+            return Ok(OpIr::Input {
+                name: function.0.clone(),
+                value: None,
+            });
+        }
+        if op.as_any().is::<CopyFromStwo>() {
+            return Ok(OpIr::CopyFromStwo);
+        }
+
+        if downcast_luminair::<InputsColumn, InputsTraceTable, (), CopyToStwo>(op).is_some() {
+            Ok(OpIr::CopyToStwo)
+        } else if let Some(c) =
+            downcast_luminair::<InputsColumn, InputsTraceTable, (), LuminairConstant>(op)
+        {
+            let value = match &c.value {
+                ConstantValue::Float(f) => Some(*f),
+                ConstantValue::Expression(e) => e.to_usize().map(|v| v as f32),
+            };
+            value.map(|value| OpIr::Constant { value }).ok_or_else(|| {
+                LuminairError::SerializationError(format!(
+                    "Failed to serialize graph: dynamic constant {:?}",
+                    c.value
+                ))
+            })
+        } else if downcast_luminair::<
+            ContiguousColumn,
+            ContiguousTraceTable,
+            (),
+            LuminairContiguous,
+        >(op)
+        .is_some()
+        {
+            Ok(OpIr::Contiguous)
+        } else if downcast_luminair::<RecipColumn, RecipTraceTable, (), LuminairRecip>(op).is_some()
+        {
+            Ok(OpIr::Recip)
+        } else if downcast_luminair::<SinColumn, SinTraceTable, SinLookup, LuminairSin>(op)
+            .is_some()
+        {
+            Ok(OpIr::Sin)
+        } else if downcast_luminair::<SqrtColumn, SqrtTraceTable, (), LuminairSqrt>(op).is_some() {
+            Ok(OpIr::Sqrt)
+        } else if downcast_luminair::<Exp2Column, Exp2TraceTable, Exp2Lookup, LuminairExp2>(op)
+            .is_some()
+        {
+            Ok(OpIr::Exp2)
+        } else if downcast_luminair::<Log2Column, Log2TraceTable, Log2Lookup, LuminairLog2>(op)
+            .is_some()
+        {
+            Ok(OpIr::Log2)
+        } else if downcast_luminair::<AddColumn, AddTraceTable, (), LuminairAdd>(op).is_some() {
+            Ok(OpIr::Add)
+        } else if downcast_luminair::<MulColumn, MulTraceTable, (), LuminairMul>(op).is_some() {
+            Ok(OpIr::Mul)
+        } else if downcast_luminair::<
+            LessThanColumn,
+            LessThanTraceTable,
+            RangeCheckLookup<1>,
+            LuminairLessThan,
+        >(op)
+        .is_some()
+        {
+            Ok(OpIr::LessThan)
+        } else if downcast_luminair::<RemColumn, RemTraceTable, (), LuminairRem>(op).is_some() {
+            Ok(OpIr::Rem)
+        } else if downcast_luminair::<SelectColumn, SelectTraceTable, (), LuminairSelect>(op)
+            .is_some()
+        {
+            Ok(OpIr::Select)
+        } else if downcast_luminair::<
+            GatherColumn,
+            GatherTraceTable,
+            GatherLookupTraceTable,
+            LuminairGather,
+        >(op)
+        .is_some()
+        {
+            Ok(OpIr::Gather)
+        } else if let Some(im2col) = downcast_luminair::<
+            Im2ColColumn,
+            Im2ColTraceTable,
+            GatherLookupTraceTable,
+            LuminairIm2Col,
+        >(op)
+        {
+            Ok(OpIr::Im2Col {
+                kernel: im2col.kernel,
+                stride: im2col.stride,
+                padding: im2col.padding,
+            })
+        } else if let Some(reduce) = downcast_luminair::<
+            SumReduceColumn,
+            SumReduceTraceTable,
+            (),
+            LuminairSumReduce,
+        >(op)
+        {
+            Ok(OpIr::SumReduce { dim: reduce.0 })
+        } else if let Some(reduce) = downcast_luminair::<
+            MaxReduceColumn,
+            MaxReduceTraceTable,
+            (),
+            LuminairMaxReduce,
+        >(op)
+        {
+            Ok(OpIr::MaxReduce { dim: reduce.0 })
+        } else if let Some(matmul) =
+            downcast_luminair::<MatMulColumn, MatMulTraceTable, (), LuminairMatMul>(op)
+        {
+            Ok(OpIr::MatMul { dim: matmul.0 })
+        } else if let Some(norm) =
+            downcast_luminair::<NormColumn, NormTraceTables, RangeCheckLookup<1>, LuminairNorm>(op)
+        {
+            Ok(OpIr::Norm {
+                eps: norm.eps,
+                center: norm.center,
+                has_weight: norm.has_weight,
+                has_bias: norm.has_bias,
+            })
+        } else if let Some(mul_const) = downcast_luminair::<
+            MulConstColumn,
+            MulConstTraceTables,
+            (),
+            LuminairMulConst,
+        >(op)
+        {
+            Ok(OpIr::MulConst {
+                value: mul_const.constant,
+            })
+        } else if let Some(add_const) = downcast_luminair::<
+            AddConstColumn,
+            AddConstTraceTables,
+            (),
+            LuminairAddConst,
+        >(op)
+        {
+            Ok(OpIr::AddConst {
+                value: add_const.constant,
+            })
+        } else if let Some(fused) =
+            downcast_luminair::<FusedColumn, FusedTraceTables, (), LuminairFused>(op)
+        {
+            Ok(OpIr::Fused {
+                expr: fused.expr.clone(),
+            })
+        } else {
+            Err(LuminairError::SerializationError(format!(
+                "Failed to serialize graph: unsupported operator {:?}, was the graph compiled \
+                 with the StwoCompiler?",
+                op
+            )))
+        }
+    }
+
+    /// Builds the operator described by this node
+    fn to_operator(&self) -> Box<dyn Operator> {
+        match self {
+            OpIr::Input { name, value } => {
+                let load: Box<dyn Fn(Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor>> =
+                    match value.clone() {
+                        Some(data) => Box::new(move |_| vec![Tensor::new(data.clone())]),
+                        None => Box::new(|_| panic!("You must set a value for this tensor!")),
+                    };
+                Box::new(Function(name.clone(), load))
+            }
+            OpIr::CopyToStwo => {
+                <CopyToStwo as IntoOperator<InputsColumn, InputsTraceTable, ()>>::into_operator(
+                    CopyToStwo::new(),
+                )
+            }
+            OpIr::CopyFromStwo => Box::new(CopyFromStwo::new()),
+            OpIr::Constant { value } => <LuminairConstant as IntoOperator<
+                InputsColumn,
+                InputsTraceTable,
+                (),
+            >>::into_operator(LuminairConstant::new(
+                ConstantValue::Float(*value),
+            )),
+            OpIr::Contiguous => LuminairContiguous::new().into_operator(),
+            OpIr::Recip => LuminairRecip::new().into_operator(),
+            OpIr::Sin => LuminairSin::new().into_operator(),
+            OpIr::Sqrt => LuminairSqrt::new().into_operator(),
+            OpIr::Exp2 => LuminairExp2::new().into_operator(),
+            OpIr::Log2 => LuminairLog2::new().into_operator(),
+            OpIr::Add => LuminairAdd::new().into_operator(),
+            OpIr::Mul => LuminairMul::new().into_operator(),
+            OpIr::LessThan => LuminairLessThan::new().into_operator(),
+            OpIr::Rem => LuminairRem::new().into_operator(),
+            OpIr::Select => LuminairSelect::new().into_operator(),
+            OpIr::Gather => LuminairGather::new().into_operator(),
+            OpIr::Im2Col {
+                kernel,
+                stride,
+                padding,
+            } => LuminairIm2Col::new(*kernel, *stride, *padding).into_operator(),
+            OpIr::SumReduce { dim } => LuminairSumReduce::new(*dim).into_operator(),
+            OpIr::MaxReduce { dim } => LuminairMaxReduce::new(*dim).into_operator(),
+            OpIr::MatMul { dim } => LuminairMatMul::new(*dim).into_operator(),
+            OpIr::Norm {
+                eps,
+                center,
+                has_weight,
+                has_bias,
+            } => LuminairNorm::new(*eps, *center, *has_weight, *has_bias).into_operator(),
+            OpIr::MulConst { value } => LuminairMulConst::new(*value).into_operator(),
+            OpIr::AddConst { value } => LuminairAddConst::new(*value).into_operator(),
+            OpIr::Fused { expr } => LuminairFused::new(expr.clone()).into_operator(),
+        }
+    }
+}
+
+/// Returns the value of a loaded tensor that isn't a runtime input
+///
+/// Unset tensors load by panicking, so the load is caught and reported as an error.
+fn loaded_value(graph: &Graph, node: NodeIndex, name: &str) -> Result<Vec<f32>, LuminairError> {
+    let error = |reason: &str| {
+        LuminairError::SerializationError(format!(
+            "Failed to serialize graph: input `{}` {}",
+            name, reason
+        ))
+    };
+    if !graph.get_sources(node).is_empty() {
+        return Err(error("reads other tensors"));
+    }
+    let function = graph
+        .node_weight(node)
+        .unwrap()
+        .as_any()
+        .downcast_ref::<Function>()
+        .unwrap();
+    let tensors = panic::catch_unwind(AssertUnwindSafe(|| (function.1)(vec![])))
+        .map_err(|_| error("has no value and isn't a runtime input"))?;
+    tensors
+        .first()
+        .and_then(|tensor| tensor.downcast_ref::<Vec<f32>>())
+        .cloned()
+        .ok_or_else(|| error("doesn't hold f32 values"))
+}
+
+impl GraphIr {
+    /// Describes a graph compiled with the StwoCompiler
+    ///
+    /// The tensors in `inputs` are left to be set at runtime and must have unique names.
+    /// Every other loaded tensor (e.g. model weights) must already be set, its value is
+    /// shipped with the graph.
+    pub fn from_graph(graph: &Graph, inputs: &[NodeIndex]) -> Result<Self, LuminairError> {
+        let mut nodes = Vec::new();
+        let mut input_names = HashSet::new();
+        for node in graph.node_indices() {
+            let op = graph.node_weight(node).unwrap();
+            let mut op_ir = OpIr::from_operator(&**op)?;
+
+            if let OpIr::Input { name, value } = &mut op_ir {
+                if inputs.contains(&node) {
+                    if !input_names.insert(name.clone()) {
+                        return Err(LuminairError::SerializationError(format!(
+                            "Failed to serialize graph: duplicate input name `{}`",
+                            name
+                        )));
+                    }
+                } else if graph.edges(node).count() != 0 {
+                    // Loaded tensors that aren't runtime inputs ship with their value
+                    *value = Some(loaded_value(graph, node, name)?);
+                }
+            }
+
+            nodes.push(NodeIr {
+                id: node.index() as u32,
+                op: op_ir,
+            });
+        }
+
+        let mut edges = Vec::new();
+        for edge in graph.graph.edge_indices() {
+            let (source, target) = graph.graph.edge_endpoints(edge).unwrap();
+            let (source, target) = (source.index() as u32, target.index() as u32);
+            edges.push(match graph.graph.edge_weight(edge).unwrap().as_data() {
+                Some((input_order, output_index, shape)) => EdgeIr::Data {
+                    source,
+                    target,
+                    input_order,
+                    output_index,
+                    shape: ShapeIr::from_tracker(&shape)?,
+                },
+                None => EdgeIr::Schedule { source, target },
+            });
+        }
+
+        let mut outputs = graph
+            .to_retrieve
+            .iter()
+            .map(|(node, (output_index, shape))| {
+                Ok(OutputIr {
+                    node: node.index() as u32,
+                    output_index: *output_index,
+                    shape: ShapeIr::from_tracker(shape)?,
+                })
+            })
+            .collect::<Result<Vec<_>, LuminairError>>()?;
+        outputs.sort_by_key(|output| output.node);
+
+        let mut no_delete = graph
+            .no_delete
+            .iter()
+            .map(|node| node.index() as u32)
+            .collect::<Vec<_>>();
+        no_delete.sort();
+
+        Ok(Self {
+            version: IR_VERSION,
+            nodes,
+            edges,
+            outputs,
+            no_delete,
+        })
+    }
+
+    /// Rebuilds an equivalent provable graph
+    ///
+    /// Nodes are added in their serialized order, so the relative order of node indices
+    /// (and therefore of the retrieved outputs) is preserved.
+    pub fn to_graph(&self) -> Result<Graph, LuminairError> {
+        if self.version != IR_VERSION {
+            return Err(LuminairError::SerializationError(format!(
+                "Failed to load graph: unsupported IR version {} (expected {})",
+                self.version, IR_VERSION
+            )));
+        }
+
+        let mut graph = Graph::new();
+        let mut ids = FxHashMap::default();
+        for node in &self.nodes {
+            ids.insert(node.id, graph.graph.add_node(node.op.to_operator()));
+        }
+        let id = |node: &u32| {
+            ids.get(node).copied().ok_or_else(|| {
+                LuminairError::SerializationError(format!(
+                    "Failed to load graph: unknown node {}",
+                    node
+                ))
+            })
+        };
+
+        for edge in &self.edges {
+            match edge {
+                EdgeIr::Data {
+                    source,
+                    target,
+                    input_order,
+                    output_index,
+                    shape,
+                } => {
+                    graph.graph.add_edge(
+                        id(source)?,
+                        id(target)?,
+                        Dependency::Data {
+                            input_order: *input_order,
+                            output_index: *output_index,
+                            shape: shape.to_tracker(),
+                        },
+                    );
+                }
+                EdgeIr::Schedule { source, target } => {
+                    graph
+                        .graph
+                        .add_edge(id(source)?, id(target)?, Dependency::Schedule);
+                }
+            }
+        }
+
+        for output in &self.outputs {
+            graph.to_retrieve.insert(
+                id(&output.node)?,
+                (output.output_index, output.shape.to_tracker()),
+            );
+        }
+        for node in &self.no_delete {
+            graph.no_delete.insert(id(node)?);
+        }
+
+        Ok(graph)
+    }
+
+    /// Sets the value of every runtime input from `inputs`, matched by name
+    ///
+    /// Inputs shipped with the graph keep their value. Every runtime input must be given,
+    /// and every given value must be for a runtime input. Returns the runtime inputs in
+    /// serialized order, with the node each one is given by `to_graph`.
+    pub fn set_inputs(
+        &mut self,
+        mut inputs: HashMap<String, Vec<f32>>,
+    ) -> Result<Vec<(String, NodeIndex)>, LuminairError> {
+        let mut runtime_inputs = Vec::new();
+        for (index, node) in self.nodes.iter_mut().enumerate() {
+            if let OpIr::Input {
+                name,
+                value: value @ None,
+            } = &mut node.op
+            {
+                let data = inputs.remove(name.as_str()).ok_or_else(|| {
+                    LuminairError::SerializationError(format!("Missing value for input `{}`", name))
+                })?;
+                *value = Some(data);
+                runtime_inputs.push((name.clone(), NodeIndex::new(index)));
+            }
+        }
+
+        match inputs.keys().next() {
+            Some(name) => Err(LuminairError::SerializationError(format!(
+                "Unknown input `{}`",
+                name
+            ))),
+            None => Ok(runtime_inputs),
+        }
+    }
+
+    // --- Serde Binary ---
+    /// Serializes the graph to bincode format
+    pub fn to_bincode(&self) -> Result<Vec<u8>, LuminairError> {
+        bincode::serialize(self).map_err(|e| {
+            LuminairError::SerializationError(format!(
+                "Failed to serialize graph to bincode: {}",
+                e
+            ))
+        })
+    }
+
+    /// Deserializes a graph from bincode format
+    pub fn from_bincode(data: &[u8]) -> Result<Self, LuminairError> {
+        bincode::deserialize(data).map_err(|e| {
+            LuminairError::SerializationError(format!(
+                "Failed to deserialize graph from bincode: {}",
+                e
+            ))
+        })
+    }
+
+    /// Writes the graph to a bincode file
+    pub fn to_bincode_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LuminairError> {
+        let data = self.to_bincode()?;
+        std::fs::write(path, data).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to write bincode file: {}", e))
+        })
+    }
+
+    /// Reads a graph from a bincode file
+    pub fn from_bincode_file<P: AsRef<Path>>(path: P) -> Result<Self, LuminairError> {
+        let data = std::fs::read(path).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to read bincode file: {}", e))
+        })?;
+        Self::from_bincode(&data)
+    }
+
+    // --- Serde JSON ---
+    /// Serializes the graph to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, LuminairError> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to serialize graph to JSON: {}", e))
+        })
+    }
+
+    /// Deserializes a graph from JSON
+    pub fn from_json(json: &str) -> Result<Self, LuminairError> {
+        serde_json::from_str(json).map_err(|e| {
+            LuminairError::SerializationError(format!(
+                "Failed to deserialize graph from JSON: {}",
+                e
+            ))
+        })
+    }
+
+    /// Writes the graph to a pretty-printed JSON file
+    pub fn to_json_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LuminairError> {
+        let file = File::create(path).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to create file: {}", e))
+        })?;
+        let mut writer = BufWriter::new(file);
+
+        serde_json::to_writer_pretty(&mut writer, self).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to write graph to JSON file: {}", e))
+        })?;
+
+        writer.flush().map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to flush writer: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Reads a graph from a JSON file
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self, LuminairError> {
+        let file = File::open(path).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to open file: {}", e))
+        })?;
+        let reader = BufReader::new(file);
+
+        serde_json::from_reader(reader).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to read graph from JSON file: {}", e))
+        })
+    }
+}
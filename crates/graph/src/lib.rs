@@ -1,10 +1,21 @@
 pub mod data;
 pub mod graph;
+pub mod ir;
 pub mod op;
+pub mod tensor;
 pub mod utils;
 
 #[cfg(test)]
 mod tests;
 
 /// Type alias for the STWO compiler used in LuminAIR
-pub type StwoCompiler = (op::prim::PrimitiveCompiler, op::other::CopyCompiler);
+pub type StwoCompiler = (
+    op::other::TraceCostCompiler,
+    op::other::SelectCompiler,
+    op::other::NormCompiler,
+    op::other::MatMulCompiler,
+    op::other::ElementwiseFusionCompiler,
+    op::other::ConstOperandCompiler,
+    op::prim::PrimitiveCompiler,
+    op::other::CopyCompiler,
+);
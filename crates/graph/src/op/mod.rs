@@ -14,7 +14,7 @@ pub(crate) trait LuminairOperator<
     C: TraceColumn + Debug + 'static, // The specific column structure for this op's trace
     T: Debug + 'static,             // The table type to store trace entries (e.g., AddTraceTable)
     L: Debug + 'static,             // Auxiliary lookup data/helper (e.g., SinLookup)
->: Operator
+>: Operator + Send
 {
     /// Processes the operation and generates trace data for proving
     /// 
@@ -159,3 +159,44 @@ where
         Box::new(LuminairWrapper(Box::new(self)))
     }
 }
+
+/// Returns the LuminairOperator of type `O` held by a boxed operator, if any
+///
+/// The trace types must be the ones the operator was wrapped with by `into_operator`
+pub(crate) fn downcast_luminair<C, T, L, O>(op: &dyn Operator) -> Option<&O>
+where
+    C: TraceColumn + Debug + 'static,
+    T: Debug + 'static,
+    L: Debug + 'static,
+    O: 'static,
+{
+    op.as_any()
+        .downcast_ref::<LuminairWrapper<C, T, L>>()
+        .and_then(|wrapper| (*wrapper.0).as_any().downcast_ref::<O>())
+}
+
+/// Whether a boxed operator is a LuminairOperator wrapped with the given trace types
+pub(crate) fn is_luminair<C, T, L>(op: &dyn Operator) -> bool
+where
+    C: TraceColumn + Debug + 'static,
+    T: Debug + 'static,
+    L: Debug + 'static,
+{
+    op.as_any().is::<LuminairWrapper<C, T, L>>()
+}
+
+/// Returns the LuminairOperator held by a boxed operator with the given trace types, if any
+///
+/// LuminairOperators are `Send`, so the operator can be traced on another thread.
+pub(crate) fn downcast_luminair_mut<C, T, L>(
+    op: &mut dyn Operator,
+) -> Option<&mut dyn LuminairOperator<C, T, L>>
+where
+    C: TraceColumn + Debug + 'static,
+    T: Debug + 'static,
+    L: Debug + 'static,
+{
+    op.as_any_mut()
+        .downcast_mut::<LuminairWrapper<C, T, L>>()
+        .map(|wrapper| &mut *wrapper.0)
+}
@@ -1,7 +1,27 @@
+use std::{any::TypeId, collections::HashMap};
+
 use itertools::Itertools;
-use luminal::prelude::{petgraph::visit::EdgeRef, *};
+use luminair_air::{
+    components::fused::expr::{
+        FusedExpr, FusedOp, FusedOperand, MAX_FUSED_INPUTS, MAX_FUSED_STEPS,
+    },
+    pie::OpCounter,
+    DEFAULT_FP_SCALE,
+};
+use luminal::{
+    op::{
+        Add, Constant, ConstantValue, Contiguous, Exp2, LessThan, Log2, MaxReduce, Mod, Mul, Recip,
+        Sin, Sqrt, SumReduce,
+    },
+    prelude::{petgraph::visit::EdgeRef, *},
+};
+
+use numerair::Fixed;
 
-use super::prim::{CopyFromStwo, CopyToStwo};
+use super::prim::{
+    CopyFromStwo, CopyToStwo, LuminairAddConst, LuminairFused, LuminairMatMul, LuminairMulConst,
+    LuminairNorm, LuminairSelect,
+};
 
 /// Compiler that optimizes copy operations in the computational graph
 /// 
@@ -72,3 +92,1051 @@ impl Compiler for CopyCompiler {
         }
     }
 }
+
+/// Compiler that removes the operations which only add trace rows to the graph
+/// 
+/// Folds the operations reading nothing but constants into a constant, removes the
+/// operations leaving their operand unchanged (`x * 1`, `x + 0` and a Contiguous reading
+/// an already contiguous tensor), and merges the operations computing the same value.
+/// Returns the removed operations along with the number of trace rows saved
+#[derive(Debug, Default)]
+pub struct TraceCostCompiler();
+
+impl Compiler for TraceCostCompiler {
+    type Output = OpCounter;
+
+    /// Compiles the graph by removing the operations that don't need to be traced
+    /// 
+    /// This process:
+    /// 1. Replaces the elementwise operations on broadcast constants by their value
+    /// 2. Moves the consumers of identity operations to their operand
+    /// 3. Moves the consumers of duplicated operations to their first occurrence
+    fn compile<To: ToIdsMut>(&self, graph: &mut Graph, mut ids: To) -> Self::Output {
+        let rows = trace_rows(graph);
+        let mut removed = OpCounter::default();
+
+        fold_constants(graph, &mut ids, &mut removed);
+        remove_identities(graph, &mut ids, &mut removed);
+        merge_duplicates(graph, &mut ids, &mut removed);
+
+        removed.rows_saved = rows.saturating_sub(trace_rows(graph));
+        removed
+    }
+}
+
+/// Replaces each elementwise operation whose operands are broadcast constants by a constant
+/// holding its value, consumers first reading the producers they made constant
+fn fold_constants<To: ToIdsMut>(graph: &mut Graph, ids: &mut To, removed: &mut OpCounter) {
+    let nodes = petgraph::algo::toposort(&graph.graph, None).unwrap();
+    for node in nodes {
+        if !graph.contains_node(node)
+            || graph.no_delete.contains(&node)
+            || graph.to_retrieve.contains_key(&node)
+        {
+            continue;
+        }
+        let srcs = graph.get_sources(node);
+        // Every element of the operation reads the same values
+        let Some(values) = srcs
+            .iter()
+            .map(|(src, _, view)| is_broadcast(view).then(|| folded_value(graph, *src))?)
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+        let Some(value) = fold(graph, node, &values) else {
+            continue;
+        };
+        // A padded view reads zeros outside of the constant
+        if graph
+            .edges_directed(node, petgraph::Direction::Outgoing)
+            .filter_map(|e| e.weight().as_data())
+            .any(|(_, _, view)| view.is_padded())
+        {
+            continue;
+        }
+
+        let mut constant = graph
+            .node_weight(srcs[0].0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Constant>()
+            .unwrap()
+            .clone();
+        constant.0 = ConstantValue::Float(value.to_f64() as f32);
+        let folded = graph.add_op(constant).finish();
+
+        // Consumers read the single value of the constant on every element of their view
+        for (edge, weight, dest) in graph
+            .edges_directed(node, petgraph::Direction::Outgoing)
+            .map(|e| (e.id(), *e.weight(), e.target()))
+            .collect::<Vec<_>>()
+        {
+            let weight = match weight {
+                Dependency::Data {
+                    input_order,
+                    output_index,
+                    mut shape,
+                } => {
+                    shape.fake.iter_mut().for_each(|fake| *fake = true);
+                    Dependency::Data {
+                        input_order,
+                        output_index,
+                        shape,
+                    }
+                }
+                schedule => schedule,
+            };
+            graph.add_edge(folded, dest, weight);
+            graph.remove_edge(edge);
+        }
+        remap(node, folded, &mut *ids, graph);
+        count_removed(graph, node, removed);
+        graph.remove_node(node);
+
+        for (src, _, _) in srcs {
+            remove_unused_constant(graph, src, removed);
+        }
+    }
+}
+
+/// Value of the elementwise operation `node` on constant operands, computed in fixed point
+/// the same way as its trace
+fn fold(
+    graph: &Graph,
+    node: NodeIndex,
+    values: &[Fixed<DEFAULT_FP_SCALE>],
+) -> Option<Fixed<DEFAULT_FP_SCALE>> {
+    let op = graph.node_weight(node)?.as_any();
+    // Transcendental operations are traced from their floating-point value
+    let from_f64 = |value: f64| value.is_finite().then(|| Fixed::from_f64(value));
+    match *values {
+        [lhs, rhs] if op.is::<Add>() => Some(lhs + rhs),
+        [lhs, rhs] if op.is::<Mul>() => Some((lhs * rhs).0),
+        [lhs, rhs] if op.is::<Mod>() && rhs.0 != 0 => Some(lhs.div_rem(rhs).1),
+        [lhs, rhs] if op.is::<LessThan>() => Some(Fixed::from_f64((lhs.0 < rhs.0) as u8 as f64)),
+        [input] if op.is::<Recip>() && input.0 != 0 => Some(input.recip().0),
+        [input] if op.is::<Sqrt>() && input.0 >= 0 => Some(input.sqrt().0),
+        [input] if op.is::<Sin>() => from_f64(input.to_f64().sin()),
+        [input] if op.is::<Exp2>() => from_f64(input.to_f64().exp2()),
+        [input] if op.is::<Log2>() => from_f64(input.to_f64().log2()),
+        [input] if op.is::<Contiguous>() => Some(input),
+        _ => None,
+    }
+}
+
+/// Moves the consumers of each identity operation to the operand it leaves unchanged
+fn remove_identities<To: ToIdsMut>(graph: &mut Graph, ids: &mut To, removed: &mut OpCounter) {
+    for node in graph.node_indices().collect::<Vec<_>>() {
+        if !graph.contains_node(node)
+            || graph.no_delete.contains(&node)
+            || graph.to_retrieve.contains_key(&node)
+        {
+            continue;
+        }
+        let Some((operand, constant)) = match_identity(graph, node) else {
+            continue;
+        };
+
+        move_outgoing_edge(node, operand, graph);
+        remap(node, operand, &mut *ids, graph);
+        count_removed(graph, node, removed);
+        graph.remove_node(node);
+
+        if let Some(constant) = constant {
+            remove_unused_constant(graph, constant, removed);
+        }
+    }
+}
+
+/// Matches `x * 1`, `x + 0` and `contiguous(x)` rooted at `node`, where `x` is read in order,
+/// and returns `x` with the constant operand if there is one
+fn match_identity(graph: &Graph, node: NodeIndex) -> Option<(NodeIndex, Option<NodeIndex>)> {
+    let srcs = graph.get_sources(node);
+    // The consumers of the operation read its first output
+    let is_operand = |(_, output, view): &Source| *output == 0 && is_plain(view);
+
+    if graph.check_node_type::<Contiguous>(node) {
+        return is_operand(&srcs[0]).then(|| (srcs[0].0, None));
+    }
+    let neutral = if graph.check_node_type::<Mul>(node) {
+        1.0
+    } else if graph.check_node_type::<Add>(node) {
+        0.0
+    } else {
+        return None;
+    };
+    if srcs.len() != 2 {
+        return None;
+    }
+
+    // A padded view reads zeros outside of the constant
+    [(srcs[0], srcs[1]), (srcs[1], srcs[0])]
+        .into_iter()
+        .find(|(operand, constant)| {
+            is_operand(operand)
+                && !constant.2.is_padded()
+                && constant_value(graph, constant.0) == Some(neutral)
+        })
+        .map(|(operand, constant)| (operand.0, Some(constant.0)))
+}
+
+/// Moves the consumers of each operation to the first operation of the same kind reading the
+/// same operands through the same views, producers first so that merges cascade
+fn merge_duplicates<To: ToIdsMut>(graph: &mut Graph, ids: &mut To, removed: &mut OpCounter) {
+    // Operations kept so far, by their type
+    let mut kept: HashMap<TypeId, Vec<NodeIndex>> = HashMap::new();
+    let nodes = petgraph::algo::toposort(&graph.graph, None).unwrap();
+    for node in nodes {
+        let srcs = graph.get_sources(node);
+        let op = graph.node_weight(node).unwrap().as_any().type_id();
+        let candidates = kept.entry(op).or_default();
+        let Some(&original) = candidates.iter().find(|candidate| {
+            same_op(graph, **candidate, node) && graph.get_sources(**candidate) == srcs
+        }) else {
+            candidates.push(node);
+            continue;
+        };
+        if graph.no_delete.contains(&node) || graph.to_retrieve.contains_key(&node) {
+            continue;
+        }
+
+        move_outgoing_edge(node, original, graph);
+        remap(node, original, &mut *ids, graph);
+        count_removed(graph, node, removed);
+        graph.remove_node(node);
+    }
+}
+
+/// Returns true if both nodes hold the same operation with the same parameters. Operations
+/// this pass doesn't know, such as inputs, may hold different data and never match
+fn same_op(graph: &Graph, a: NodeIndex, b: NodeIndex) -> bool {
+    let a = graph.node_weight(a).unwrap().as_any();
+    let b = graph.node_weight(b).unwrap().as_any();
+    if let (Some(a), Some(b)) = (a.downcast_ref::<Constant>(), b.downcast_ref::<Constant>()) {
+        return match (&a.0, &b.0) {
+            (ConstantValue::Float(a), ConstantValue::Float(b)) => a == b,
+            (ConstantValue::Expression(a), ConstantValue::Expression(b)) => a == b,
+            _ => false,
+        };
+    }
+    if let (Some(a), Some(b)) = (a.downcast_ref::<SumReduce>(), b.downcast_ref::<SumReduce>()) {
+        return a.0 == b.0;
+    }
+    if let (Some(a), Some(b)) = (a.downcast_ref::<MaxReduce>(), b.downcast_ref::<MaxReduce>()) {
+        return a.0 == b.0;
+    }
+    // The remaining elementwise operations hold no parameters
+    a.type_id() == b.type_id()
+        && (a.is::<Add>()
+            || a.is::<Mul>()
+            || a.is::<Mod>()
+            || a.is::<LessThan>()
+            || a.is::<Recip>()
+            || a.is::<Sqrt>()
+            || a.is::<Sin>()
+            || a.is::<Exp2>()
+            || a.is::<Log2>()
+            || a.is::<Contiguous>())
+}
+
+/// Removes a constant node once it is no longer consumed
+fn remove_unused_constant(graph: &mut Graph, node: NodeIndex, removed: &mut OpCounter) {
+    if graph.contains_node(node)
+        && graph.check_node_type::<Constant>(node)
+        && graph
+            .edges_directed(node, petgraph::Direction::Outgoing)
+            .count()
+            == 0
+        && !graph.no_delete.contains(&node)
+        && !graph.to_retrieve.contains_key(&node)
+    {
+        count_removed(graph, node, removed);
+        graph.remove_node(node);
+    }
+}
+
+/// Counts `node` among the removed operations of its kind, constants being traced as inputs
+fn count_removed(graph: &Graph, node: NodeIndex, removed: &mut OpCounter) {
+    let op = graph.node_weight(node).unwrap().as_any();
+    let count = if op.is::<Add>() {
+        &mut removed.add
+    } else if op.is::<Mul>() {
+        &mut removed.mul
+    } else if op.is::<Recip>() {
+        &mut removed.recip
+    } else if op.is::<Sin>() {
+        &mut removed.sin
+    } else if op.is::<SumReduce>() {
+        &mut removed.sum_reduce
+    } else if op.is::<MaxReduce>() {
+        &mut removed.max_reduce
+    } else if op.is::<Sqrt>() {
+        &mut removed.sqrt
+    } else if op.is::<Mod>() {
+        &mut removed.rem
+    } else if op.is::<Exp2>() {
+        &mut removed.exp2
+    } else if op.is::<Log2>() {
+        &mut removed.log2
+    } else if op.is::<LessThan>() {
+        &mut removed.less_than
+    } else if op.is::<Constant>() {
+        &mut removed.inputs
+    } else if op.is::<Contiguous>() {
+        &mut removed.contiguous
+    } else {
+        return;
+    };
+    *count += 1;
+}
+
+/// Number of trace rows of the graph, before it is lowered: one per constant, and one per
+/// element read from the first operand of every other operation
+fn trace_rows(graph: &Graph) -> usize {
+    graph
+        .node_indices()
+        .map(|node| {
+            if graph.check_node_type::<Constant>(node) {
+                return 1;
+            }
+            graph
+                .get_sources(node)
+                .first()
+                .and_then(|(_, _, view)| view.n_elements().to_usize())
+                .unwrap_or(0)
+        })
+        .sum()
+}
+
+/// Returns the fixed-point value of a constant node, including expressions of a known size
+fn folded_value(graph: &Graph, node: NodeIndex) -> Option<Fixed<DEFAULT_FP_SCALE>> {
+    let constant = graph
+        .node_weight(node)?
+        .as_any()
+        .downcast_ref::<Constant>()?;
+    let value = match &constant.0 {
+        ConstantValue::Float(f) => *f,
+        ConstantValue::Expression(e) => e.to_usize()? as f32,
+    };
+    Some(Fixed::from_f64(value as f64))
+}
+
+/// Returns true if every element of the view reads the same value
+fn is_broadcast(st: &ShapeTracker) -> bool {
+    !st.is_padded() && st.fake.iter().all(|fake| *fake)
+}
+
+/// Returns true if the view reads every value of its source once, in order
+fn is_plain(st: &ShapeTracker) -> bool {
+    !st.is_reshaped()
+        && !st.is_padded()
+        && st.fake.iter().all(|fake| !fake)
+        && st
+            .mask
+            .iter()
+            .zip(st.dims.iter())
+            .all(|((start, end), dim)| {
+                start.to_usize() == Some(0)
+                    && end
+                        .to_usize()
+                        .zip(dim.to_usize())
+                        .is_some_and(|(end, dim)| end >= dim)
+            })
+}
+
+/// Compiler that rewrites boolean mask blends into select operations
+/// 
+/// Recognises `mask * a + (1 - mask) * b` where `mask` is produced by a LessThan,
+/// and replaces the two multiplications, the negation and the addition with a
+/// single Select node that picks `a` or `b` without a fixed-point multiply
+#[derive(Debug, Default)]
+pub struct SelectCompiler();
+
+impl Compiler for SelectCompiler {
+    type Output = ();
+
+    /// Compiles the graph by fusing mask blends into Select operations
+    /// 
+    /// This process:
+    /// 1. Finds Add nodes whose operands are `mask * a` and `(mask * -1 + 1) * b`
+    /// 2. Inserts a Select node reading `(mask, a, b)` with the original views
+    /// 3. Removes the intermediate nodes that are no longer consumed
+    fn compile<To: ToIdsMut>(&self, graph: &mut Graph, mut ids: To) {
+        for add in graph
+            .node_indices()
+            .filter(|n| graph.check_node_type::<Add>(*n))
+            .collect::<Vec<_>>()
+        {
+            if !graph.contains_node(add) {
+                continue;
+            }
+            let Some(pattern) = match_select(graph, add) else {
+                continue;
+            };
+
+            let (cond, lhs, rhs) = (pattern.cond, pattern.lhs, pattern.rhs);
+            let select = graph
+                .add_op(LuminairSelect::new())
+                .input(cond.0, cond.1, cond.2)
+                .input(lhs.0, lhs.1, lhs.2)
+                .input(rhs.0, rhs.1, rhs.2)
+                .finish();
+
+            move_outgoing_edge(add, select, graph);
+            remap(add, select, &mut ids, graph);
+            graph.remove_node(add);
+
+            for node in pattern.intermediates {
+                if graph.contains_node(node)
+                    && graph
+                        .edges_directed(node, petgraph::Direction::Outgoing)
+                        .count()
+                        == 0
+                    && !graph.no_delete.contains(&node)
+                {
+                    graph.remove_node(node);
+                }
+            }
+        }
+    }
+}
+
+/// Compiler that fuses multiply-then-sum-reduce chains into matmul operations
+/// 
+/// Recognises a SumReduce whose only input is a Mul consumed nowhere else, which is
+/// how matmuls and convolutions (after their im2col view) are lowered, and replaces
+/// both nodes with a single MatMul node that multiplies and accumulates in one trace
+#[derive(Debug, Default)]
+pub struct MatMulCompiler();
+
+impl Compiler for MatMulCompiler {
+    type Output = ();
+
+    /// Compiles the graph by fusing Mul -> SumReduce chains into MatMul operations
+    /// 
+    /// This process:
+    /// 1. Finds SumReduce nodes reading an unreshaped view of a private Mul
+    /// 2. Inserts a MatMul node reading the Mul operands with their original views
+    /// 3. Removes the Mul and SumReduce nodes
+    fn compile<To: ToIdsMut>(&self, graph: &mut Graph, mut ids: To) {
+        for sum_reduce in graph
+            .node_indices()
+            .filter(|n| graph.check_node_type::<SumReduce>(*n))
+            .collect::<Vec<_>>()
+        {
+            let srcs = graph.get_sources(sum_reduce);
+            let (mul, _, view) = srcs[0];
+            if view.is_reshaped() || !is_private::<Mul>(graph, mul) {
+                continue;
+            }
+            let dim = graph
+                .node_weight(sum_reduce)
+                .unwrap()
+                .as_any()
+                .downcast_ref::<SumReduce>()
+                .unwrap()
+                .0;
+
+            let mul_srcs = graph.get_sources(mul);
+            let (lhs, rhs) = (mul_srcs[0], mul_srcs[1]);
+            let matmul = graph
+                .add_op(LuminairMatMul::new(dim))
+                .input(lhs.0, lhs.1, lhs.2)
+                .input(rhs.0, rhs.1, rhs.2)
+                .finish();
+
+            move_outgoing_edge(sum_reduce, matmul, graph);
+            remap(sum_reduce, matmul, &mut ids, graph);
+            graph.remove_node(sum_reduce);
+            graph.remove_node(mul);
+        }
+    }
+}
+
+/// Compiler that fuses normalization subgraphs into norm operations
+/// 
+/// Recognises `x * rsqrt(mean(x * x) + eps)` over the last dimension (RMSNorm) and its
+/// mean-centered variant (LayerNorm), optionally followed by a per-feature weight and
+/// bias, and replaces the whole subgraph with a single Norm node
+#[derive(Debug, Default)]
+pub struct NormCompiler();
+
+impl Compiler for NormCompiler {
+    type Output = ();
+
+    /// Compiles the graph by fusing normalization subgraphs into Norm operations
+    /// 
+    /// This process:
+    /// 1. Finds Mul nodes scaling a tensor by the broadcast reciprocal of its RMS
+    /// 2. Absorbs the mean subtraction and the affine weight and bias when present
+    /// 3. Inserts a Norm node and removes the intermediate nodes no longer consumed
+    fn compile<To: ToIdsMut>(&self, graph: &mut Graph, mut ids: To) {
+        for root in graph
+            .node_indices()
+            .filter(|n| graph.check_node_type::<Mul>(*n))
+            .collect::<Vec<_>>()
+        {
+            if !graph.contains_node(root) {
+                continue;
+            }
+            let Some(pattern) = match_norm(graph, root) else {
+                continue;
+            };
+
+            let mut builder = graph.add_op(LuminairNorm::new(
+                pattern.eps,
+                pattern.center,
+                pattern.weight.is_some(),
+                pattern.bias.is_some(),
+            ));
+            for (node, output, view) in
+                [Some(pattern.input), pattern.weight, pattern.bias].into_iter().flatten()
+            {
+                builder = builder.input(node, output, view);
+            }
+            let norm = builder.finish();
+
+            move_outgoing_edge(pattern.output, norm, graph);
+            remap(pattern.output, norm, &mut ids, graph);
+            graph.remove_node(pattern.output);
+
+            for node in pattern.intermediates {
+                if graph.contains_node(node)
+                    && graph
+                        .edges_directed(node, petgraph::Direction::Outgoing)
+                        .count()
+                        == 0
+                    && !graph.no_delete.contains(&node)
+                {
+                    graph.remove_node(node);
+                }
+            }
+        }
+    }
+}
+
+/// Compiler that fuses chains of elementwise operations into fused operations
+/// 
+/// Recognises Add, Mul, Recip and Sqrt nodes feeding one another through unreshaped
+/// views, and replaces each chain with a single Fused node evaluating the whole
+/// expression, so that its intermediate tensors are never looked up
+#[derive(Debug, Default)]
+pub struct ElementwiseFusionCompiler();
+
+impl Compiler for ElementwiseFusionCompiler {
+    type Output = ();
+
+    /// Compiles the graph by fusing elementwise chains into Fused operations
+    /// 
+    /// This process:
+    /// 1. Walks the elementwise nodes from the consumers to the producers
+    /// 2. Absorbs the private elementwise sources of each node, within the fused limits
+    /// 3. Inserts a Fused node reading the remaining sources and removes the chain
+    fn compile<To: ToIdsMut>(&self, graph: &mut Graph, mut ids: To) {
+        // Consumers first, so that a chain is fused from its last node
+        let nodes = petgraph::algo::toposort(&graph.graph, None).unwrap();
+        for root in nodes.into_iter().rev() {
+            if !graph.contains_node(root) || !is_elementwise(graph, root) {
+                continue;
+            }
+            let mut fusion = Fusion::default();
+            if fusion.absorb(graph, root).is_none()
+                || fusion.steps.len() < 2
+                || fusion.inputs.is_empty()
+            {
+                continue;
+            }
+
+            let expr = FusedExpr::new(fusion.inputs.len(), fusion.steps);
+            let mut builder = graph.add_op(LuminairFused::new(expr));
+            for (node, output, view) in fusion.inputs {
+                builder = builder.input(node, output, view);
+            }
+            let fused = builder.finish();
+
+            move_outgoing_edge(root, fused, graph);
+            remap(root, fused, &mut ids, graph);
+            graph.remove_node(root);
+            for node in fusion.absorbed {
+                graph.remove_node(node);
+            }
+
+            for constant in fusion.constants {
+                if graph.contains_node(constant)
+                    && graph
+                        .edges_directed(constant, petgraph::Direction::Outgoing)
+                        .count()
+                        == 0
+                    && !graph.no_delete.contains(&constant)
+                    && !graph.to_retrieve.contains_key(&constant)
+                {
+                    graph.remove_node(constant);
+                }
+            }
+        }
+    }
+}
+
+/// Expression of an elementwise chain being fused, with the nodes it reads and replaces
+#[derive(Clone, Default)]
+struct Fusion {
+    inputs: Vec<Source>,
+    steps: Vec<FusedOp>,
+    /// Elementwise nodes evaluated by the expression, apart from its root
+    absorbed: Vec<NodeIndex>,
+    /// Constant nodes folded into the expression
+    constants: Vec<NodeIndex>,
+}
+
+impl Fusion {
+    /// Adds the operation of an elementwise node, after the operations of the sources it
+    /// absorbs, and returns its step
+    ///
+    /// Returns `None`, leaving the expression in an unspecified state, if the node doesn't fit
+    /// within the fused limits.
+    fn absorb(&mut self, graph: &Graph, node: NodeIndex) -> Option<usize> {
+        let operands = graph
+            .get_sources(node)
+            .into_iter()
+            .map(|src| self.operand(graph, src))
+            .collect::<Option<Vec<_>>>()?;
+        let step = if graph.check_node_type::<Add>(node) {
+            FusedOp::Add(operands[0], operands[1])
+        } else if graph.check_node_type::<Mul>(node) {
+            FusedOp::Mul(operands[0], operands[1])
+        } else if graph.check_node_type::<Recip>(node) {
+            FusedOp::Recip(operands[0])
+        } else {
+            FusedOp::Sqrt(operands[0])
+        };
+
+        if self.steps.len() == MAX_FUSED_STEPS {
+            return None;
+        }
+        self.steps.push(step);
+        Some(self.steps.len() - 1)
+    }
+
+    /// Returns the operand reading `src`, absorbing it when it is a private elementwise node
+    /// read elementwise, and folding it when it is a float constant
+    fn operand(&mut self, graph: &Graph, src: Source) -> Option<FusedOperand> {
+        let (node, _, view) = &src;
+        if !view.is_reshaped() && is_elementwise(graph, *node) && is_private_node(graph, *node) {
+            let snapshot = self.clone();
+            if let Some(step) = self.absorb(graph, *node) {
+                self.absorbed.push(*node);
+                return Some(FusedOperand::Step(step));
+            }
+            *self = snapshot;
+        }
+        // A padded view reads zeros outside of the constant
+        if !view.is_padded() {
+            if let Some(value) = constant_value(graph, *node) {
+                self.constants.push(*node);
+                let value = Fixed::<DEFAULT_FP_SCALE>::from_f64(value as f64);
+                return Some(FusedOperand::Const(value.0));
+            }
+        }
+
+        let input = match self.inputs.iter().position(|input| *input == src) {
+            Some(input) => input,
+            None if self.inputs.len() < MAX_FUSED_INPUTS => {
+                self.inputs.push(src);
+                self.inputs.len() - 1
+            }
+            None => return None,
+        };
+        Some(FusedOperand::Input(input))
+    }
+}
+
+/// Returns true if `node` is an elementwise op the fused component evaluates
+fn is_elementwise(graph: &Graph, node: NodeIndex) -> bool {
+    graph.check_node_type::<Add>(node)
+        || graph.check_node_type::<Mul>(node)
+        || graph.check_node_type::<Recip>(node)
+        || graph.check_node_type::<Sqrt>(node)
+}
+
+/// Compiler that rewrites operations on a constant operand into constant operations
+/// 
+/// Recognises Mul and Add nodes with a float constant as one of their operands, and
+/// replaces them with MulConst and AddConst nodes holding the constant, so that it
+/// is neither traced as an input nor looked up on every row
+#[derive(Debug, Default)]
+pub struct ConstOperandCompiler();
+
+impl Compiler for ConstOperandCompiler {
+    type Output = ();
+
+    /// Compiles the graph by folding constant operands into their operations
+    /// 
+    /// This process:
+    /// 1. Finds Mul and Add nodes reading a float constant through an unpadded view
+    /// 2. Inserts a MulConst or AddConst node reading the other operand with its view
+    /// 3. Removes the constant once it is no longer consumed
+    fn compile<To: ToIdsMut>(&self, graph: &mut Graph, mut ids: To) {
+        for node in graph
+            .node_indices()
+            .filter(|n| graph.check_node_type::<Mul>(*n) || graph.check_node_type::<Add>(*n))
+            .collect::<Vec<_>>()
+        {
+            let srcs = graph.get_sources(node);
+            if srcs.len() != 2 {
+                continue;
+            }
+            // A padded view reads zeros outside of the constant
+            let Some((input, (constant, value))) = [(srcs[0], srcs[1]), (srcs[1], srcs[0])]
+                .into_iter()
+                .filter(|(_, constant)| !constant.2.is_padded())
+                .find_map(|(input, constant)| {
+                    Some((input, (constant.0, constant_value(graph, constant.0)?)))
+                })
+            else {
+                continue;
+            };
+
+            let builder = if graph.check_node_type::<Mul>(node) {
+                graph.add_op(LuminairMulConst::new(value))
+            } else {
+                graph.add_op(LuminairAddConst::new(value))
+            };
+            let op = builder.input(input.0, input.1, input.2).finish();
+
+            move_outgoing_edge(node, op, graph);
+            remap(node, op, &mut ids, graph);
+            graph.remove_node(node);
+
+            if graph
+                .edges_directed(constant, petgraph::Direction::Outgoing)
+                .count()
+                == 0
+                && !graph.no_delete.contains(&constant)
+                && !graph.to_retrieve.contains_key(&constant)
+            {
+                graph.remove_node(constant);
+            }
+        }
+    }
+}
+
+/// Operands of a recognised normalization and the nodes it makes redundant
+struct NormPattern {
+    input: Source,
+    weight: Option<Source>,
+    bias: Option<Source>,
+    eps: f32,
+    center: bool,
+    /// Last node of the subgraph, whose consumers are moved to the Norm node
+    output: NodeIndex,
+    /// Remaining nodes of the subgraph, consumers first
+    intermediates: Vec<NodeIndex>,
+}
+
+/// Matches `c * recip(sqrt(mean(c * c) + eps))` rooted at `root`, where `c` is either the
+/// normalized input or `x - mean(x)`, followed by an optional `* weight` and `+ bias`
+fn match_norm(graph: &Graph, root: NodeIndex) -> Option<NormPattern> {
+    let srcs = graph.get_sources(root);
+    if srcs.len() != 2 {
+        return None;
+    }
+
+    for (centered, rstd) in [(srcs[0], srcs[1]), (srcs[1], srcs[0])] {
+        if !is_row_broadcast(&rstd.2) || !is_private::<Recip>(graph, rstd.0) {
+            continue;
+        }
+        let std = graph.get_sources(rstd.0)[0];
+        if !is_private::<Sqrt>(graph, std.0) {
+            continue;
+        }
+        let shifted = graph.get_sources(std.0)[0];
+        if !is_private::<Add>(graph, shifted.0) {
+            continue;
+        }
+        let shifted_srcs = graph.get_sources(shifted.0);
+
+        for (var, eps) in [
+            (shifted_srcs[0], shifted_srcs[1]),
+            (shifted_srcs[1], shifted_srcs[0]),
+        ] {
+            let Some(eps_value) = constant_value(graph, eps.0) else {
+                continue;
+            };
+            let Some((squared, var_nodes)) = match_mean(graph, var.0) else {
+                continue;
+            };
+            if !is_private::<Mul>(graph, squared.0) {
+                continue;
+            }
+            let squared_srcs = graph.get_sources(squared.0);
+            if squared_srcs[0] != centered || squared_srcs[1] != centered {
+                continue;
+            }
+
+            // The affine weight and bias, when present, extend the subgraph downstream
+            let mut chain = vec![root];
+            let weight = match_affine::<Mul>(graph, root).map(|(mul, weight)| {
+                chain.push(mul);
+                weight
+            });
+            let bias = match_affine::<Add>(graph, *chain.last().unwrap()).map(|(add, bias)| {
+                chain.push(add);
+                bias
+            });
+            let output = chain.pop().unwrap();
+
+            let mut intermediates = chain.into_iter().rev().collect::<Vec<_>>();
+            intermediates.extend([rstd.0, std.0, shifted.0, eps.0]);
+            intermediates.extend(var_nodes);
+            intermediates.push(squared.0);
+
+            let (input, center) = match match_centering(graph, centered.0) {
+                Some((input, centering_nodes)) => {
+                    intermediates.extend(centering_nodes);
+                    (input, true)
+                }
+                None => (centered, false),
+            };
+
+            return Some(NormPattern {
+                input,
+                weight,
+                bias,
+                eps: eps_value,
+                center,
+                output,
+                intermediates,
+            });
+        }
+    }
+
+    None
+}
+
+/// Matches `x + mean(x) * -1` rooted at `node` and returns `x` with the nodes it is built from
+fn match_centering(graph: &Graph, node: NodeIndex) -> Option<(Source, Vec<NodeIndex>)> {
+    if !graph.check_node_type::<Add>(node) {
+        return None;
+    }
+    let srcs = graph.get_sources(node);
+
+    for (x, negated) in [(srcs[0], srcs[1]), (srcs[1], srcs[0])] {
+        if negated.2.is_reshaped() || !is_private::<Mul>(graph, negated.0) {
+            continue;
+        }
+        let negated_srcs = graph.get_sources(negated.0);
+        for (mean, minus_one) in [
+            (negated_srcs[0], negated_srcs[1]),
+            (negated_srcs[1], negated_srcs[0]),
+        ] {
+            if !is_row_broadcast(&mean.2) || constant_value(graph, minus_one.0) != Some(-1.0) {
+                continue;
+            }
+            if let Some((mean_input, mean_nodes)) = match_mean(graph, mean.0) {
+                if mean_input == x {
+                    let mut nodes = vec![node, negated.0, minus_one.0];
+                    nodes.extend(mean_nodes);
+                    return Some((x, nodes));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Matches `t.sum_reduce(last) * (1 / n)` rooted at `node` and returns `t` with the nodes
+/// it is built from
+fn match_mean(graph: &Graph, node: NodeIndex) -> Option<(Source, Vec<NodeIndex>)> {
+    if !is_private::<Mul>(graph, node) {
+        return None;
+    }
+    let srcs = graph.get_sources(node);
+
+    for (sum, coef) in [(srcs[0], srcs[1]), (srcs[1], srcs[0])] {
+        if sum.2.is_reshaped() || !is_private::<SumReduce>(graph, sum.0) {
+            continue;
+        }
+        let dim = graph
+            .node_weight(sum.0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<SumReduce>()
+            .unwrap()
+            .0;
+        let input = graph.get_sources(sum.0)[0];
+        let shape = input.2.shape_usize();
+        if dim + 1 != shape.len() {
+            continue;
+        }
+        if let Some(coef_nodes) = match_inverse(graph, coef.0, shape[dim]) {
+            let mut nodes = vec![node, sum.0];
+            nodes.extend(coef_nodes);
+            return Some((input, nodes));
+        }
+    }
+
+    None
+}
+
+/// Matches a constant `1 / n`, either folded or as the reciprocal of a constant `n`, which
+/// is how `mean_reduce` divides by the reduced dimension size
+fn match_inverse(graph: &Graph, node: NodeIndex, n: usize) -> Option<Vec<NodeIndex>> {
+    if let Some(value) = constant_value(graph, node) {
+        return ((value - 1.0 / n as f32).abs() <= f32::EPSILON).then(|| vec![node]);
+    }
+    if !is_private::<Recip>(graph, node) {
+        return None;
+    }
+    let src = graph.get_sources(node)[0];
+    let size = match &graph.node_weight(src.0)?.as_any().downcast_ref::<Constant>()?.0 {
+        ConstantValue::Float(f) => *f == n as f32,
+        ConstantValue::Expression(e) => e.to_usize() == Some(n),
+    };
+    size.then(|| vec![node, src.0])
+}
+
+/// Matches the single `O` consumer of `node` when its other operand is a per-feature tensor,
+/// and returns the consumer with that operand
+fn match_affine<O: Operator + 'static>(
+    graph: &Graph,
+    node: NodeIndex,
+) -> Option<(NodeIndex, Source)> {
+    if graph.no_delete.contains(&node) || graph.to_retrieve.contains_key(&node) {
+        return None;
+    }
+    let mut edges = graph.edges_directed(node, petgraph::Direction::Outgoing);
+    let consumer = edges.next()?.target();
+    if edges.next().is_some() || !graph.check_node_type::<O>(consumer) {
+        return None;
+    }
+    let srcs = graph.get_sources(consumer);
+
+    for (own, other) in [(srcs[0], srcs[1]), (srcs[1], srcs[0])] {
+        if own.0 == node && !own.2.is_reshaped() && is_feature_broadcast(&other.2) {
+            return Some((consumer, other));
+        }
+    }
+
+    None
+}
+
+/// Returns true if only the last dimension of the view is fake, i.e. one value per row
+fn is_row_broadcast(st: &ShapeTracker) -> bool {
+    let n = st.len();
+    n > 0 && (0..n).all(|i| st.fake[st.indexes[i]] == (i + 1 == n))
+}
+
+/// Returns true if only the last dimension of the view is real, i.e. one value per feature
+fn is_feature_broadcast(st: &ShapeTracker) -> bool {
+    let n = st.len();
+    n > 0 && (0..n).all(|i| st.fake[st.indexes[i]] == (i + 1 != n))
+}
+
+/// An input edge of a node: source node, source output index and view
+type Source = (NodeIndex, u8, ShapeTracker);
+
+/// Operands of a recognised mask blend and the nodes it makes redundant
+struct SelectPattern {
+    cond: Source,
+    lhs: Source,
+    rhs: Source,
+    intermediates: Vec<NodeIndex>,
+}
+
+/// Matches `mask * lhs + (mask * -1 + 1) * rhs` rooted at `add`, in any operand order
+fn match_select(graph: &Graph, add: NodeIndex) -> Option<SelectPattern> {
+    let srcs = graph.get_sources(add);
+    if srcs.len() != 2 || srcs.iter().any(|(_, _, st)| st.is_reshaped()) {
+        return None;
+    }
+
+    for (masked, inverted) in [(srcs[0], srcs[1]), (srcs[1], srcs[0])] {
+        if !is_private::<Mul>(graph, masked.0) || !is_private::<Mul>(graph, inverted.0) {
+            continue;
+        }
+        let masked_srcs = graph.get_sources(masked.0);
+        let inverted_srcs = graph.get_sources(inverted.0);
+
+        for (cond, lhs) in [
+            (masked_srcs[0], masked_srcs[1]),
+            (masked_srcs[1], masked_srcs[0]),
+        ] {
+            if !graph.check_node_type::<LessThan>(cond.0) {
+                continue;
+            }
+            for (not_cond, rhs) in [
+                (inverted_srcs[0], inverted_srcs[1]),
+                (inverted_srcs[1], inverted_srcs[0]),
+            ] {
+                if not_cond.2.is_reshaped() {
+                    continue;
+                }
+                if let Some(mut intermediates) = match_negation(graph, not_cond.0, &cond) {
+                    intermediates.extend([masked.0, inverted.0]);
+                    return Some(SelectPattern {
+                        cond,
+                        lhs,
+                        rhs,
+                        intermediates,
+                    });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Matches `cond * -1 + 1` rooted at `node` and returns the nodes it is built from
+fn match_negation(graph: &Graph, node: NodeIndex, cond: &Source) -> Option<Vec<NodeIndex>> {
+    if !is_private::<Add>(graph, node) {
+        return None;
+    }
+    let srcs = graph.get_sources(node);
+
+    for (negated, one) in [(srcs[0], srcs[1]), (srcs[1], srcs[0])] {
+        if constant_value(graph, one.0) != Some(1.0)
+            || negated.2.is_reshaped()
+            || !is_private::<Mul>(graph, negated.0)
+        {
+            continue;
+        }
+        let negated_srcs = graph.get_sources(negated.0);
+        for (x, minus_one) in [
+            (negated_srcs[0], negated_srcs[1]),
+            (negated_srcs[1], negated_srcs[0]),
+        ] {
+            if x == *cond && constant_value(graph, minus_one.0) == Some(-1.0) {
+                return Some(vec![node, negated.0, one.0, minus_one.0]);
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns true if `node` is an `O` op whose output only feeds a single consumer
+fn is_private<O: Operator + 'static>(graph: &Graph, node: NodeIndex) -> bool {
+    graph.check_node_type::<O>(node) && is_private_node(graph, node)
+}
+
+/// Returns true if the output of `node` only feeds a single consumer and is neither kept nor
+/// retrieved
+fn is_private_node(graph: &Graph, node: NodeIndex) -> bool {
+    graph
+        .edges_directed(node, petgraph::Direction::Outgoing)
+        .count()
+        == 1
+        && !graph.no_delete.contains(&node)
+        && !graph.to_retrieve.contains_key(&node)
+}
+
+/// Returns the value of a float constant node
+fn constant_value(graph: &Graph, node: NodeIndex) -> Option<f32> {
+    match &graph.node_weight(node)?.as_any().downcast_ref::<Constant>()?.0 {
+        ConstantValue::Float(f) => Some(*f),
+        _ => None,
+    }
+}
@@ -2,16 +2,33 @@ use itertools::{EitherOrBoth, Itertools};
 use luminair_air::{
     components::{
         add::table::{AddColumn, AddTraceTable, AddTraceTableRow},
+        add_const::table::{AddConstColumn, AddConstTraceTableRow, AddConstTraceTables},
         contiguous::table::{ContiguousColumn, ContiguousTraceTable, ContiguousTraceTableRow},
         exp2::table::{Exp2Column, Exp2TraceTable, Exp2TraceTableRow},
+        fused::{
+            expr::{FusedExpr, FusedOp, FusedOperand, MAX_FUSED_INPUTS, MAX_FUSED_STEPS},
+            table::{FusedColumn, FusedTraceTableRow, FusedTraceTables},
+        },
+        gather::table::{GatherColumn, GatherTraceTable, GatherTraceTableRow},
+        im2col::table::{Im2ColColumn, Im2ColTraceTable, Im2ColTraceTableRow},
         inputs::table::{InputsColumn, InputsTraceTable, InputsTraceTableRow},
         less_than::table::{LessThanColumn, LessThanTraceTable, LessThanTraceTableRow},
         log2::table::{Log2Column, Log2TraceTable, Log2TraceTableRow},
-        lookups::{exp2::Exp2Lookup, log2::Log2Lookup, range_check::RangeCheckLookup, sin::SinLookup},
+        lookups::{
+            exp2::Exp2Lookup,
+            gather::table::{GatherLookupTraceTable, GatherLookupTraceTableRow},
+            log2::Log2Lookup,
+            range_check::RangeCheckLookup,
+            sin::SinLookup,
+        },
+        matmul::table::{MatMulColumn, MatMulTraceTable, MatMulTraceTableRow},
         max_reduce::table::{MaxReduceColumn, MaxReduceTraceTable, MaxReduceTraceTableRow},
         mul::table::{MulColumn, MulTraceTable, MulTraceTableRow},
+        mul_const::table::{MulConstColumn, MulConstTraceTableRow, MulConstTraceTables},
+        norm::table::{NormColumn, NormParams, NormTraceTableRow, NormTraceTables},
         recip::table::{RecipColumn, RecipTraceTable, RecipTraceTableRow},
         rem::table::{RemColumn, RemTraceTable, RemTraceTableRow},
+        select::table::{SelectColumn, SelectTraceTable, SelectTraceTableRow},
         sin::table::{SinColumn, SinTraceTable, SinTraceTableRow},
         sqrt::table::{SqrtColumn, SqrtTraceTable, SqrtTraceTableRow},
         sum_reduce::table::{SumReduceColumn, SumReduceTraceTable, SumReduceTraceTableRow},
@@ -25,12 +42,13 @@ use luminal::{
 };
 use num_traits::{identities::Zero, One};
 use numerair::Fixed;
+use rayon::prelude::*;
 use std::{ops::Deref, sync::Arc};
 use stwo::core::fields::m31::{BaseField, M31};
 
 use crate::{
     data::StwoData,
-    utils::{get_buffer_from_tensor, get_index, is},
+    utils::{get_buffer_from_tensor, get_index, is, par_map_indices, PARALLEL_MIN_LEN},
 };
 
 use super::{IntoOperator, LuminairOperator};
@@ -76,8 +94,6 @@ impl LuminairOperator<InputsColumn, InputsTraceTable, ()> for CopyToStwo {
                 node_id,
                 idx: idx.into(),
                 is_last_idx: is_last_idx.into(),
-                next_node_id: node_id,
-                next_idx: (idx + 1).into(),
                 val: val.to_m31(),
                 multiplicity,
             });
@@ -180,8 +196,6 @@ impl LuminairOperator<InputsColumn, InputsTraceTable, ()> for LuminairConstant {
             node_id,
             idx: M31::zero(),
             is_last_idx: M31::one(),
-            next_node_id: node_id,
-            next_idx: M31::zero(),
             val: data[0].to_m31(),
             multiplicity,
         });
@@ -284,9 +298,6 @@ impl LuminairOperator<ContiguousColumn, ContiguousTraceTable, ()> for LuminairCo
                 input_id,
                 idx: idx.into(),
                 is_last_idx: is_last_idx.into(),
-                next_node_id: node_id,
-                next_input_id: input_id,
-                next_idx: (idx + 1).into(),
                 input: input_val.to_m31(),
                 out: output_val.to_m31(),
                 input_mult: -BaseField::one(),
@@ -359,29 +370,22 @@ impl LuminairRecip {
         let input = get_buffer_from_tensor(&inp[0].0).unwrap();
         let expr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
 
-        let mut stack: Vec<i64> = vec![];
         let output_size = inp[0].1.n_elements().to_usize().unwrap();
-        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
-
-        // Only allocate for intermediate values if in trace mode
-        let mut intermediate_values = if trace_mode {
-            Some(Vec::with_capacity(output_size))
-        } else {
-            None
-        };
-
-        for (idx, out) in out_data.iter_mut().enumerate() {
-            let input_val = get_index(input, &expr, &mut stack, idx);
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let input_val = get_index(input, &expr, stack, idx);
             let (out_val, rem_val) = input_val.recip();
-            *out = out_val;
+            (input_val, out_val, rem_val)
+        };
 
-            // Only collect intermediate values if in trace mode
-            if let Some(values) = &mut intermediate_values {
-                values.push((input_val, out_val, rem_val));
-            }
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values.par_iter().map(|value| value.1).collect();
+            (out_data, Some(values))
+        } else {
+            let out_data = par_map_indices(output_size, |stack, idx| eval(stack, idx).1);
+            (out_data, None)
         }
-
-        (out_data, intermediate_values)
     }
 }
 
@@ -406,25 +410,28 @@ impl LuminairOperator<RecipColumn, RecipTraceTable, ()> for LuminairRecip {
             BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
         };
 
-        for (idx, (input_val, out_val, rem_val)) in intermediate_values.into_iter().enumerate() {
-            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
-
-            table.add_row(RecipTraceTableRow {
-                node_id,
-                input_id,
-                idx: idx.into(),
-                is_last_idx: (is_last_idx).into(),
-                next_idx: (idx + 1).into(),
-                next_node_id: node_id,
-                next_input_id: input_id,
-                input: input_val.to_m31(),
-                out: out_val.to_m31(),
-                rem: rem_val.to_m31(),
-                scale: M31::from_u32_unchecked(1 << DEFAULT_FP_SCALE),
-                input_mult: -BaseField::one(),
-                out_mult,
-            });
-        }
+        table.table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, (input_val, out_val, rem_val))| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    RecipTraceTableRow {
+                        node_id,
+                        input_id,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        input: input_val.to_m31(),
+                        out: out_val.to_m31(),
+                        rem: rem_val.to_m31(),
+                        scale: M31::from_u32_unchecked(1 << DEFAULT_FP_SCALE),
+                        input_mult: -BaseField::one(),
+                        out_mult,
+                    }
+                }),
+        );
 
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
     }
@@ -467,29 +474,22 @@ impl LuminairSin {
         let input = get_buffer_from_tensor(&inp[0].0).unwrap();
         let expr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
 
-        let mut stack: Vec<i64> = vec![];
         let output_size = inp[0].1.n_elements().to_usize().unwrap();
-        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
-
-        // Only allocate for intermediate values if in trace mode
-        let mut intermediate_values = if trace_mode {
-            Some(Vec::with_capacity(output_size))
-        } else {
-            None
-        };
-
-        for (idx, out) in out_data.iter_mut().enumerate() {
-            let input_val = get_index(input, &expr, &mut stack, idx);
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let input_val = get_index(input, &expr, stack, idx);
             let out_val = Fixed::<DEFAULT_FP_SCALE>::from_f64(input_val.to_f64().sin());
-            *out = out_val;
+            (input_val, out_val)
+        };
 
-            // Only collect intermediate values if in trace mode
-            if let Some(values) = &mut intermediate_values {
-                values.push((input_val, out_val));
-            }
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values.par_iter().map(|value| value.1).collect();
+            (out_data, Some(values))
+        } else {
+            let out_data = par_map_indices(output_size, |stack, idx| eval(stack, idx).1);
+            (out_data, None)
         }
-
-        (out_data, intermediate_values)
     }
 }
 
@@ -514,32 +514,35 @@ impl LuminairOperator<SinColumn, SinTraceTable, SinLookup> for LuminairSin {
             BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
         };
 
-        for (idx, (input_val, out_val)) in intermediate_values.into_iter().enumerate() {
-            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
-
-            table.add_row(SinTraceTableRow {
-                node_id,
-                input_id,
-                idx: idx.into(),
-                is_last_idx: (is_last_idx).into(),
-                next_idx: (idx + 1).into(),
-                next_node_id: node_id,
-                next_input_id: input_id,
-                input: input_val.to_m31(),
-                out: out_val.to_m31(),
-                input_mult: -BaseField::one(),
-                out_mult,
-                lookup_mult: M31::one(),
-            });
-
-            // Update multiplicities of the lookup.
-            // Allows you to track the occurrence of a specific Sin operation.
-            let mult_address = lookup
-                .layout
-                .find_index(input_val.0)
-                .expect("Value should fit in range.");
-            lookup.multiplicities.increase_at(mult_address);
-        }
+        table.table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, (input_val, out_val))| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    // Update multiplicities of the lookup.
+                    // Allows you to track the occurrence of a specific Sin operation.
+                    let mult_address = lookup
+                        .layout
+                        .find_index(input_val.0)
+                        .expect("Value should fit in range.");
+                    lookup.multiplicities.increase_at(mult_address);
+
+                    SinTraceTableRow {
+                        node_id,
+                        input_id,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        input: input_val.to_m31(),
+                        out: out_val.to_m31(),
+                        input_mult: -BaseField::one(),
+                        out_mult,
+                        lookup_mult: M31::one(),
+                    }
+                }),
+        );
 
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
     }
@@ -588,29 +591,22 @@ impl LuminairSqrt {
         let input = get_buffer_from_tensor(&inp[0].0).unwrap();
         let expr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
 
-        let mut stack: Vec<i64> = vec![];
         let output_size = inp[0].1.n_elements().to_usize().unwrap();
-        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
-
-        // Only allocate for intermediate values if in trace mode
-        let mut intermediate_values = if trace_mode {
-            Some(Vec::with_capacity(output_size))
-        } else {
-            None
-        };
-
-        for (idx, out) in out_data.iter_mut().enumerate() {
-            let input_val = get_index(input, &expr, &mut stack, idx);
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let input_val = get_index(input, &expr, stack, idx);
             let (out_val, rem_val) = input_val.sqrt();
-            *out = out_val;
+            (input_val, out_val, rem_val)
+        };
 
-            // Only collect intermediate values if in trace mode
-            if let Some(values) = &mut intermediate_values {
-                values.push((input_val, out_val, rem_val));
-            }
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values.par_iter().map(|value| value.1).collect();
+            (out_data, Some(values))
+        } else {
+            let out_data = par_map_indices(output_size, |stack, idx| eval(stack, idx).1);
+            (out_data, None)
         }
-
-        (out_data, intermediate_values)
     }
 }
 
@@ -635,25 +631,28 @@ impl LuminairOperator<SqrtColumn, SqrtTraceTable, ()> for LuminairSqrt {
             BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
         };
 
-        for (idx, (input_val, out_val, rem_val)) in intermediate_values.into_iter().enumerate() {
-            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
-
-            table.add_row(SqrtTraceTableRow {
-                node_id,
-                input_id,
-                idx: idx.into(),
-                is_last_idx: (is_last_idx).into(),
-                next_idx: (idx + 1).into(),
-                next_node_id: node_id,
-                next_input_id: input_id,
-                input: input_val.to_m31(),
-                out: out_val.to_m31(),
-                rem: rem_val.to_m31(),
-                scale: M31::from_u32_unchecked(1 << DEFAULT_FP_SCALE),
-                input_mult: -BaseField::one(),
-                out_mult,
-            });
-        }
+        table.table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, (input_val, out_val, rem_val))| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    SqrtTraceTableRow {
+                        node_id,
+                        input_id,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        input: input_val.to_m31(),
+                        out: out_val.to_m31(),
+                        rem: rem_val.to_m31(),
+                        scale: M31::from_u32_unchecked(1 << DEFAULT_FP_SCALE),
+                        input_mult: -BaseField::one(),
+                        out_mult,
+                    }
+                }),
+        );
 
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
     }
@@ -696,29 +695,22 @@ impl LuminairExp2 {
         let input = get_buffer_from_tensor(&inp[0].0).unwrap();
         let expr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
 
-        let mut stack: Vec<i64> = vec![];
         let output_size = inp[0].1.n_elements().to_usize().unwrap();
-        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
-
-        // Only allocate for intermediate values if in trace mode
-        let mut intermediate_values = if trace_mode {
-            Some(Vec::with_capacity(output_size))
-        } else {
-            None
-        };
-
-        for (idx, out) in out_data.iter_mut().enumerate() {
-            let input_val = get_index(input, &expr, &mut stack, idx);
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let input_val = get_index(input, &expr, stack, idx);
             let out_val = Fixed::<DEFAULT_FP_SCALE>::from_f64(input_val.to_f64().exp2());
-            *out = out_val;
+            (input_val, out_val)
+        };
 
-            // Only collect intermediate values if in trace mode
-            if let Some(values) = &mut intermediate_values {
-                values.push((input_val, out_val));
-            }
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values.par_iter().map(|value| value.1).collect();
+            (out_data, Some(values))
+        } else {
+            let out_data = par_map_indices(output_size, |stack, idx| eval(stack, idx).1);
+            (out_data, None)
         }
-
-        (out_data, intermediate_values)
     }
 }
 
@@ -743,32 +735,35 @@ impl LuminairOperator<Exp2Column, Exp2TraceTable, Exp2Lookup> for LuminairExp2 {
             BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
         };
 
-        for (idx, (input_val, out_val)) in intermediate_values.into_iter().enumerate() {
-            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
-
-            table.add_row(Exp2TraceTableRow {
-                node_id,
-                input_id,
-                idx: idx.into(),
-                is_last_idx: (is_last_idx).into(),
-                next_idx: (idx + 1).into(),
-                next_node_id: node_id,
-                next_input_id: input_id,
-                input: input_val.to_m31(),
-                out: out_val.to_m31(),
-                input_mult: -BaseField::one(),
-                out_mult,
-                lookup_mult: M31::one(),
-            });
-
-            // Update multiplicities of the lookup.
-            // Allows you to track the occurrence of a specific Exp2 operation.
-            let mult_address = lookup
-                .layout
-                .find_index(input_val.0)
-                .expect("Value should fit in range.");
-            lookup.multiplicities.increase_at(mult_address);
-        }
+        table.table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, (input_val, out_val))| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    // Update multiplicities of the lookup.
+                    // Allows you to track the occurrence of a specific Exp2 operation.
+                    let mult_address = lookup
+                        .layout
+                        .find_index(input_val.0)
+                        .expect("Value should fit in range.");
+                    lookup.multiplicities.increase_at(mult_address);
+
+                    Exp2TraceTableRow {
+                        node_id,
+                        input_id,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        input: input_val.to_m31(),
+                        out: out_val.to_m31(),
+                        input_mult: -BaseField::one(),
+                        out_mult,
+                        lookup_mult: M31::one(),
+                    }
+                }),
+        );
 
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
     }
@@ -811,29 +806,22 @@ impl LuminairLog2 {
         let input = get_buffer_from_tensor(&inp[0].0).unwrap();
         let expr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
 
-        let mut stack: Vec<i64> = vec![];
         let output_size = inp[0].1.n_elements().to_usize().unwrap();
-        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
-
-        // Only allocate for intermediate values if in trace mode
-        let mut intermediate_values = if trace_mode {
-            Some(Vec::with_capacity(output_size))
-        } else {
-            None
-        };
-
-        for (idx, out) in out_data.iter_mut().enumerate() {
-            let input_val = get_index(input, &expr, &mut stack, idx);
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let input_val = get_index(input, &expr, stack, idx);
             let out_val = Fixed::<DEFAULT_FP_SCALE>::from_f64(input_val.to_f64().log2());
-            *out = out_val;
+            (input_val, out_val)
+        };
 
-            // Only collect intermediate values if in trace mode
-            if let Some(values) = &mut intermediate_values {
-                values.push((input_val, out_val));
-            }
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values.par_iter().map(|value| value.1).collect();
+            (out_data, Some(values))
+        } else {
+            let out_data = par_map_indices(output_size, |stack, idx| eval(stack, idx).1);
+            (out_data, None)
         }
-
-        (out_data, intermediate_values)
     }
 }
 
@@ -858,32 +846,35 @@ impl LuminairOperator<Log2Column, Log2TraceTable, Log2Lookup> for LuminairLog2 {
             BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
         };
 
-        for (idx, (input_val, out_val)) in intermediate_values.into_iter().enumerate() {
-            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
-
-            table.add_row(Log2TraceTableRow {
-                node_id,
-                input_id,
-                idx: idx.into(),
-                is_last_idx: (is_last_idx).into(),
-                next_idx: (idx + 1).into(),
-                next_node_id: node_id,
-                next_input_id: input_id,
-                input: input_val.to_m31(),
-                out: out_val.to_m31(),
-                input_mult: -BaseField::one(),
-                out_mult,
-                lookup_mult: M31::one(),
-            });
-
-            // Update multiplicities of the lookup.
-            // Allows you to track the occurrence of a specific Log2 operation.
-            let mult_address = lookup
-                .layout
-                .find_index(input_val.0)
-                .expect("Value should fit in range.");
-            lookup.multiplicities.increase_at(mult_address);
-        }
+        table.table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, (input_val, out_val))| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    // Update multiplicities of the lookup.
+                    // Allows you to track the occurrence of a specific Log2 operation.
+                    let mult_address = lookup
+                        .layout
+                        .find_index(input_val.0)
+                        .expect("Value should fit in range.");
+                    lookup.multiplicities.increase_at(mult_address);
+
+                    Log2TraceTableRow {
+                        node_id,
+                        input_id,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        input: input_val.to_m31(),
+                        out: out_val.to_m31(),
+                        input_mult: -BaseField::one(),
+                        out_mult,
+                        lookup_mult: M31::one(),
+                    }
+                }),
+        );
 
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
     }
@@ -902,7 +893,7 @@ impl Operator for LuminairLog2 {
 /// 
 /// Performs fixed-point addition operations for STARK proving
 #[derive(Clone, Default, PartialEq)]
-struct LuminairAdd {}
+pub(crate) struct LuminairAdd {}
 impl core::fmt::Debug for LuminairAdd {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Add")
@@ -938,29 +929,22 @@ impl LuminairAdd {
         let lexpr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
         let rexpr = (inp[1].1.index_expression(), inp[1].1.valid_expression());
 
-        let mut stack: Vec<i64> = vec![];
         let output_size = inp[0].1.n_elements().to_usize().unwrap();
-        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
-
-        // Only allocate for intermediate values if in trace mode
-        let mut intermediate_values = if trace_mode {
-            Some(Vec::with_capacity(output_size))
-        } else {
-            None
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let lhs_val = get_index(lhs, &lexpr, stack, idx);
+            let rhs_val = get_index(rhs, &rexpr, stack, idx);
+            (lhs_val, rhs_val, lhs_val + rhs_val)
         };
 
-        for (idx, out) in out_data.iter_mut().enumerate() {
-            let lhs_val = get_index(lhs, &lexpr, &mut stack, idx);
-            let rhs_val = get_index(rhs, &rexpr, &mut stack, idx);
-            let out_val = lhs_val + rhs_val;
-            *out = out_val;
-            // Only collect intermediate values if in trace mode
-            if let Some(values) = &mut intermediate_values {
-                values.push((lhs_val, rhs_val, out_val));
-            }
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values.par_iter().map(|value| value.2).collect();
+            (out_data, Some(values))
+        } else {
+            let out_data = par_map_indices(output_size, |stack, idx| eval(stack, idx).2);
+            (out_data, None)
         }
-
-        (out_data, intermediate_values)
     }
 }
 
@@ -986,27 +970,29 @@ impl LuminairOperator<AddColumn, AddTraceTable, ()> for LuminairAdd {
             BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
         };
 
-        for (idx, (lhs_val, rhs_val, out_val)) in intermediate_values.into_iter().enumerate() {
-            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
-
-            table.add_row(AddTraceTableRow {
-                node_id,
-                lhs_id,
-                rhs_id,
-                idx: idx.into(),
-                is_last_idx: (is_last_idx).into(),
-                next_idx: (idx + 1).into(),
-                next_node_id: node_id,
-                next_lhs_id: lhs_id,
-                next_rhs_id: rhs_id,
-                lhs: lhs_val.to_m31(),
-                rhs: rhs_val.to_m31(),
-                out: out_val.to_m31(),
-                lhs_mult: -BaseField::one(),
-                rhs_mult: -BaseField::one(),
-                out_mult,
-            })
-        }
+        table.table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, (lhs_val, rhs_val, out_val))| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    AddTraceTableRow {
+                        node_id,
+                        lhs_id,
+                        rhs_id,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        lhs: lhs_val.to_m31(),
+                        rhs: rhs_val.to_m31(),
+                        out: out_val.to_m31(),
+                        lhs_mult: -BaseField::one(),
+                        rhs_mult: -BaseField::one(),
+                        out_mult,
+                    }
+                }),
+        );
 
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
     }
@@ -1023,7 +1009,7 @@ impl Operator for LuminairAdd {
 /// 
 /// Performs fixed-point multiplication operations with remainder tracking for STARK proving
 #[derive(Clone, Default, PartialEq)]
-struct LuminairMul {}
+pub(crate) struct LuminairMul {}
 impl core::fmt::Debug for LuminairMul {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Mul")
@@ -1060,30 +1046,23 @@ impl LuminairMul {
         let lexpr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
         let rexpr = (inp[1].1.index_expression(), inp[1].1.valid_expression());
 
-        let mut stack: Vec<i64> = vec![];
         let output_size = inp[0].1.n_elements().to_usize().unwrap();
-        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
-
-        // Only allocate for intermediate values if in trace mode
-        let mut intermediate_values = if trace_mode {
-            Some(Vec::with_capacity(output_size))
-        } else {
-            None
-        };
-
-        for (idx, out) in out_data.iter_mut().enumerate() {
-            let lhs_val = get_index(lhs, &lexpr, &mut stack, idx);
-            let rhs_val = get_index(rhs, &rexpr, &mut stack, idx);
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let lhs_val = get_index(lhs, &lexpr, stack, idx);
+            let rhs_val = get_index(rhs, &rexpr, stack, idx);
             let (out_val, rem_val) = lhs_val * rhs_val;
-            *out = out_val;
+            (lhs_val, rhs_val, out_val, rem_val)
+        };
 
-            // Only collect intermediate values if in trace mode
-            if let Some(values) = &mut intermediate_values {
-                values.push((lhs_val, rhs_val, out_val, rem_val));
-            }
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values.par_iter().map(|value| value.2).collect();
+            (out_data, Some(values))
+        } else {
+            let out_data = par_map_indices(output_size, |stack, idx| eval(stack, idx).2);
+            (out_data, None)
         }
-
-        (out_data, intermediate_values)
     }
 }
 
@@ -1109,30 +1088,30 @@ impl LuminairOperator<MulColumn, MulTraceTable, ()> for LuminairMul {
             BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
         };
 
-        for (idx, (lhs_val, rhs_val, out_val, rem_val)) in
-            intermediate_values.into_iter().enumerate()
-        {
-            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
-
-            table.add_row(MulTraceTableRow {
-                node_id,
-                lhs_id,
-                rhs_id,
-                idx: idx.into(),
-                is_last_idx: (is_last_idx).into(),
-                next_idx: (idx + 1).into(),
-                next_node_id: node_id,
-                next_lhs_id: lhs_id,
-                next_rhs_id: rhs_id,
-                lhs: lhs_val.to_m31(),
-                rhs: rhs_val.to_m31(),
-                out: out_val.to_m31(),
-                rem: rem_val.to_m31(),
-                lhs_mult: -BaseField::one(),
-                rhs_mult: -BaseField::one(),
-                out_mult,
-            })
-        }
+        table.table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, (lhs_val, rhs_val, out_val, rem_val))| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    MulTraceTableRow {
+                        node_id,
+                        lhs_id,
+                        rhs_id,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        lhs: lhs_val.to_m31(),
+                        rhs: rhs_val.to_m31(),
+                        out: out_val.to_m31(),
+                        rem: rem_val.to_m31(),
+                        lhs_mult: -BaseField::one(),
+                        rhs_mult: -BaseField::one(),
+                        out_mult,
+                    }
+                }),
+        );
 
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
     }
@@ -1149,7 +1128,7 @@ impl Operator for LuminairMul {
 /// 
 /// Performs fixed-point comparison operations with range checking for STARK proving
 #[derive(Clone, Default, PartialEq)]
-struct LuminairLessThan {}
+pub(crate) struct LuminairLessThan {}
 impl core::fmt::Debug for LuminairLessThan {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "LessThan")
@@ -1190,35 +1169,28 @@ impl LuminairLessThan {
         let lexpr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
         let rexpr = (inp[1].1.index_expression(), inp[1].1.valid_expression());
 
-        let mut stack: Vec<i64> = vec![];
         let output_size = inp[0].1.n_elements().to_usize().unwrap();
-        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
-
-        // Only allocate for intermediate values if in trace mode
-        let mut intermediate_values = if trace_mode {
-            Some(Vec::with_capacity(output_size))
-        } else {
-            None
-        };
-
-        for (idx, out) in out_data.iter_mut().enumerate() {
-            let lhs_val = get_index(lhs, &lexpr, &mut stack, idx);
-            let rhs_val = get_index(rhs, &rexpr, &mut stack, idx);
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let lhs_val = get_index(lhs, &lexpr, stack, idx);
+            let rhs_val = get_index(rhs, &rexpr, stack, idx);
 
             let (out_val, borrow, diff) = if lhs_val.0 < rhs_val.0 {
                 (Fixed::from_f64(1.), 0, rhs_val.0 - lhs_val.0)
             } else {
                 (Fixed::zero(), 1, rhs_val.0 - lhs_val.0 + two_pow_k)
             };
-            *out = out_val;
+            (lhs_val, rhs_val, out_val, borrow, diff)
+        };
 
-            // Only collect intermediate values if in trace mode
-            if let Some(values) = &mut intermediate_values {
-                values.push((lhs_val, rhs_val, out_val, borrow, diff));
-            }
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values.par_iter().map(|value| value.2).collect();
+            (out_data, Some(values))
+        } else {
+            let out_data = par_map_indices(output_size, |stack, idx| eval(stack, idx).2);
+            (out_data, None)
         }
-
-        (out_data, intermediate_values)
     }
 }
 
@@ -1246,49 +1218,49 @@ impl LuminairOperator<LessThanColumn, LessThanTraceTable, RangeCheckLookup<1>>
             BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
         };
 
-        for (idx, (lhs_val, rhs_val, out_val, borrow, diff)) in
-            intermediate_values.into_iter().enumerate()
-        {
-            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
-
-            // Decompose diff into four 8-bit limbs
-            let diff_u32 = diff as u32;
-            let limb0 = (diff_u32 & 0xFF) as u32;
-            let limb1 = ((diff_u32 >> 8) & 0xFF) as u32;
-            let limb2 = ((diff_u32 >> 16) & 0xFF) as u32;
-            let limb3 = ((diff_u32 >> 24) & 0xFF) as u32;
-
-            table.add_row(LessThanTraceTableRow {
-                node_id,
-                lhs_id,
-                rhs_id,
-                idx: idx.into(),
-                is_last_idx: (is_last_idx).into(),
-                next_idx: (idx + 1).into(),
-                next_node_id: node_id,
-                next_lhs_id: lhs_id,
-                next_rhs_id: rhs_id,
-                lhs: lhs_val.to_m31(),
-                rhs: rhs_val.to_m31(),
-                out: out_val.to_m31(),
-                borrow: M31::from_u32_unchecked(borrow as u32),
-                diff: M31::from_u32_unchecked(diff as u32),
-                limb0: M31::from_u32_unchecked(limb0),
-                limb1: M31::from_u32_unchecked(limb1),
-                limb2: M31::from_u32_unchecked(limb2),
-                limb3: M31::from_u32_unchecked(limb3),
-                lhs_mult: -BaseField::one(),
-                rhs_mult: -BaseField::one(),
-                out_mult,
-                range_check_mult: M31::one(),
-            });
-
-            // Update multiplicities of the lookup for each limb
-            lookup.multiplicities.increase_at(limb0 as usize);
-            lookup.multiplicities.increase_at(limb1 as usize);
-            lookup.multiplicities.increase_at(limb2 as usize);
-            lookup.multiplicities.increase_at(limb3 as usize);
-        }
+        table.table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, (lhs_val, rhs_val, out_val, borrow, diff))| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    // Decompose diff into four 8-bit limbs
+                    let diff_u32 = diff as u32;
+                    let limb0 = (diff_u32 & 0xFF) as u32;
+                    let limb1 = ((diff_u32 >> 8) & 0xFF) as u32;
+                    let limb2 = ((diff_u32 >> 16) & 0xFF) as u32;
+                    let limb3 = ((diff_u32 >> 24) & 0xFF) as u32;
+
+                    // Update multiplicities of the lookup for each limb
+                    lookup.multiplicities.increase_at(limb0 as usize);
+                    lookup.multiplicities.increase_at(limb1 as usize);
+                    lookup.multiplicities.increase_at(limb2 as usize);
+                    lookup.multiplicities.increase_at(limb3 as usize);
+
+                    LessThanTraceTableRow {
+                        node_id,
+                        lhs_id,
+                        rhs_id,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        lhs: lhs_val.to_m31(),
+                        rhs: rhs_val.to_m31(),
+                        out: out_val.to_m31(),
+                        borrow: M31::from_u32_unchecked(borrow as u32),
+                        diff: M31::from_u32_unchecked(diff as u32),
+                        limb0: M31::from_u32_unchecked(limb0),
+                        limb1: M31::from_u32_unchecked(limb1),
+                        limb2: M31::from_u32_unchecked(limb2),
+                        limb3: M31::from_u32_unchecked(limb3),
+                        lhs_mult: -BaseField::one(),
+                        rhs_mult: -BaseField::one(),
+                        out_mult,
+                        range_check_mult: M31::one(),
+                    }
+                }),
+        );
 
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
     }
@@ -1305,7 +1277,7 @@ impl Operator for LuminairLessThan {
 /// 
 /// Performs fixed-point remainder operations with quotient tracking for STARK proving
 #[derive(Clone, Default, PartialEq)]
-struct LuminairRem {}
+pub(crate) struct LuminairRem {}
 impl core::fmt::Debug for LuminairRem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Rem")
@@ -1342,30 +1314,23 @@ impl LuminairRem {
         let lexpr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
         let rexpr = (inp[1].1.index_expression(), inp[1].1.valid_expression());
 
-        let mut stack: Vec<i64> = vec![];
         let output_size = inp[0].1.n_elements().to_usize().unwrap();
-        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
-
-        // Only allocate for intermediate values if in trace mode
-        let mut intermediate_values = if trace_mode {
-            Some(Vec::with_capacity(output_size))
-        } else {
-            None
-        };
-
-        for (idx, out) in out_data.iter_mut().enumerate() {
-            let lhs_val = get_index(lhs, &lexpr, &mut stack, idx);
-            let rhs_val = get_index(rhs, &rexpr, &mut stack, idx);
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let lhs_val = get_index(lhs, &lexpr, stack, idx);
+            let rhs_val = get_index(rhs, &rexpr, stack, idx);
             let (quotient, remainder) = lhs_val.div_rem(rhs_val);
-            *out = remainder;
+            (lhs_val, rhs_val, quotient, remainder)
+        };
 
-            // Only collect intermediate values if in trace mode
-            if let Some(values) = &mut intermediate_values {
-                values.push((lhs_val, rhs_val, quotient, remainder));
-            }
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values.par_iter().map(|value| value.3).collect();
+            (out_data, Some(values))
+        } else {
+            let out_data = par_map_indices(output_size, |stack, idx| eval(stack, idx).3);
+            (out_data, None)
         }
-
-        (out_data, intermediate_values)
     }
 }
 
@@ -1391,30 +1356,30 @@ impl LuminairOperator<RemColumn, RemTraceTable, ()> for LuminairRem {
             BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
         };
 
-        for (idx, (lhs_val, rhs_val, quotient, remainder)) in
-            intermediate_values.into_iter().enumerate()
-        {
-            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
-
-            table.add_row(RemTraceTableRow {
-                node_id,
-                lhs_id,
-                rhs_id,
-                idx: idx.into(),
-                is_last_idx: (is_last_idx).into(),
-                next_idx: (idx + 1).into(),
-                next_node_id: node_id,
-                next_lhs_id: lhs_id,
-                next_rhs_id: rhs_id,
-                lhs: lhs_val.to_m31(),
-                rhs: rhs_val.to_m31(),
-                rem: remainder.to_m31(),
-                quotient: quotient.to_m31(),
-                lhs_mult: -BaseField::one(),
-                rhs_mult: -BaseField::one(),
-                out_mult,
-            })
-        }
+        table.table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, (lhs_val, rhs_val, quotient, remainder))| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    RemTraceTableRow {
+                        node_id,
+                        lhs_id,
+                        rhs_id,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        lhs: lhs_val.to_m31(),
+                        rhs: rhs_val.to_m31(),
+                        rem: remainder.to_m31(),
+                        quotient: quotient.to_m31(),
+                        lhs_mult: -BaseField::one(),
+                        rhs_mult: -BaseField::one(),
+                        out_mult,
+                    }
+                }),
+        );
 
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
     }
@@ -1427,27 +1392,31 @@ impl Operator for LuminairRem {
     }
 }
 
-// ================== REDUCE ==================
+// ================== CONSTANT OPERAND ==================
 
-/// Operator for reducing a tensor along a specified dimension by summing elements
-/// 
-/// Performs fixed-point sum reduction operations for STARK proving
+/// Operator for multiplying a tensor by a constant element-wise
+///
+/// The constant is part of the claim of its component instead of being read from a tensor,
+/// which halves the LogUp lookups of a multiplication
 #[derive(Clone, Default, PartialEq)]
-struct LuminairSumReduce(pub usize);
-impl core::fmt::Debug for LuminairSumReduce {
+pub(crate) struct LuminairMulConst {
+    /// Constant operand of the multiplication
+    pub constant: f32,
+}
+impl core::fmt::Debug for LuminairMulConst {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "SumReduce({:?})", self.0)
+        write!(f, "MulConst({:?})", self.constant)
     }
 }
 
-impl LuminairSumReduce {
-    /// Creates a new LuminairSumReduce operator for the specified dimension
-    pub fn new(value: usize) -> Self {
-        Self(value)
+impl LuminairMulConst {
+    /// Creates a new LuminairMulConst operator
+    pub fn new(constant: f32) -> Self {
+        Self { constant }
     }
 }
 
-impl LuminairSumReduce {
+impl LuminairMulConst {
     fn compute(
         &self,
         inp: &[(InputTensor, ShapeTracker)],
@@ -1456,78 +1425,50 @@ impl LuminairSumReduce {
         Vec<Fixed<DEFAULT_FP_SCALE>>,
         Option<
             Vec<(
-                usize,
                 Fixed<DEFAULT_FP_SCALE>,
                 Fixed<DEFAULT_FP_SCALE>,
                 Fixed<DEFAULT_FP_SCALE>,
-                Fixed<DEFAULT_FP_SCALE>,
-                BaseField,
             )>,
         >,
     ) {
-        let sh = inp[0].1.shape_usize();
-        let front_size = sh.iter().take(self.0).product::<usize>().max(1);
-        let back_size = sh.iter().skip(self.0 + 1).product::<usize>().max(1);
-        let dim_size = sh[self.0];
-
-        let output_size = front_size * back_size;
-        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
         let input = get_buffer_from_tensor(&inp[0].0).unwrap();
         let expr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
-        let mut stack: Vec<i64> = vec![];
+        let constant = Fixed::<DEFAULT_FP_SCALE>::from_f64(self.constant as f64);
 
-        // Only allocate for intermediate values if in trace mode
-        let mut intermediate_values = if trace_mode {
-            Some(Vec::with_capacity(output_size))
-        } else {
-            None
+        let output_size = inp[0].1.n_elements().to_usize().unwrap();
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let input_val = get_index(input, &expr, stack, idx);
+            let (out_val, rem_val) = input_val * constant;
+            (input_val, out_val, rem_val)
         };
 
-        for i in 0..front_size {
-            for j in 0..back_size {
-                let mut acc = Fixed::<DEFAULT_FP_SCALE>::zero(); // Initialize accumulator for each (i, j)
-                for k in 0..dim_size {
-                    let orig_index = i * dim_size * back_size + k * back_size + j;
-                    let input_val = get_index(input, &expr, &mut stack, orig_index);
-                    let next_acc = acc + input_val; // Compute next accumulator
-                    let idx = i * back_size + j; // Index for out_data
-
-                    // Set out_data only in the last reduction step
-                    let (out_val, is_last_step) = if k == dim_size - 1 {
-                        out_data[idx] = next_acc;
-                        (next_acc, BaseField::one())
-                    } else {
-                        (Fixed::<DEFAULT_FP_SCALE>::zero(), BaseField::zero()) // Placeholder for incomplete reductions
-                    };
-
-                    // Record intermediate values if in trace mode
-                    if let Some(values) = &mut intermediate_values {
-                        values.push((idx, input_val, out_val, acc, next_acc, is_last_step));
-                    }
-                    // Update running sum
-                    acc = next_acc;
-                }
-            }
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values.par_iter().map(|value| value.1).collect();
+            (out_data, Some(values))
+        } else {
+            let out_data = par_map_indices(output_size, |stack, idx| eval(stack, idx).1);
+            (out_data, None)
         }
-
-        (out_data, intermediate_values)
     }
 }
 
-impl LuminairOperator<SumReduceColumn, SumReduceTraceTable, ()> for LuminairSumReduce {
+impl LuminairOperator<MulConstColumn, MulConstTraceTables, ()> for LuminairMulConst {
     fn process_trace(
         &mut self,
         inp: Vec<(InputTensor, ShapeTracker)>,
-        table: &mut SumReduceTraceTable,
+        tables: &mut MulConstTraceTables,
         node_info: &NodeInfo,
         _lookup: &mut (),
     ) -> Vec<Tensor> {
         let (out_data, intermediate_values) = self.compute(&inp, true);
         let intermediate_values = intermediate_values.unwrap();
 
+        let output_size = inp[0].1.n_elements().to_usize().unwrap();
         let node_id: BaseField = node_info.id.into();
         let input_id: BaseField = node_info.inputs[0].id.into();
-        let output_size = out_data.len();
+        let constant = Fixed::<DEFAULT_FP_SCALE>::from_f64(self.constant as f64).to_m31();
 
         let out_mult = if node_info.output.is_final_output {
             BaseField::zero()
@@ -1535,167 +1476,107 @@ impl LuminairOperator<SumReduceColumn, SumReduceTraceTable, ()> for LuminairSumR
             BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
         };
 
-        for entry in intermediate_values {
-            let (idx, input_val, out_val, acc, next_acc, is_last_step) = entry;
-
-            let out_mult = out_mult * is_last_step;
-
-            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
-
-            table.add_row(SumReduceTraceTableRow {
-                node_id,
-                input_id,
-                idx: idx.into(),
-                is_last_idx: (is_last_idx).into(),
-                next_node_id: node_id,
-                next_input_id: input_id,
-                next_idx: (idx + 1).into(),
-                input: input_val.to_m31(),
-                out: out_val.to_m31(),
-                acc: acc.to_m31(),
-                next_acc: next_acc.to_m31(),
-                is_last_step,
-                input_mult: -BaseField::one(),
-                out_mult,
-            });
-        }
+        tables.table_mut(constant).table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, (input_val, out_val, rem_val))| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    MulConstTraceTableRow {
+                        node_id,
+                        input_id,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        input: input_val.to_m31(),
+                        out: out_val.to_m31(),
+                        rem: rem_val.to_m31(),
+                        input_mult: -BaseField::one(),
+                        out_mult,
+                    }
+                }),
+        );
 
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
     }
 }
 
-impl Operator for LuminairSumReduce {
+impl Operator for LuminairMulConst {
     fn process(&mut self, inp: Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor> {
         let (out_data, _) = self.compute(&inp, false);
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
     }
 }
 
-/// Operator for reducing a tensor along a specified dimension by finding maximum elements
-/// 
-/// Performs fixed-point maximum reduction operations for STARK proving
+/// Operator for adding a constant to a tensor element-wise
+///
+/// The constant is part of the claim of its component instead of being read from a tensor,
+/// which halves the LogUp lookups of an addition
 #[derive(Clone, Default, PartialEq)]
-struct LuminairMaxReduce(pub usize);
-impl core::fmt::Debug for LuminairMaxReduce {
+pub(crate) struct LuminairAddConst {
+    /// Constant operand of the addition
+    pub constant: f32,
+}
+impl core::fmt::Debug for LuminairAddConst {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "MaxReduce({:?})", self.0)
+        write!(f, "AddConst({:?})", self.constant)
     }
 }
-impl LuminairMaxReduce {
-    /// Creates a new LuminairMaxReduce operator for the specified dimension
-    pub fn new(value: usize) -> Self {
-        Self(value)
+
+impl LuminairAddConst {
+    /// Creates a new LuminairAddConst operator
+    pub fn new(constant: f32) -> Self {
+        Self { constant }
     }
 }
 
-impl LuminairMaxReduce {
+impl LuminairAddConst {
     fn compute(
         &self,
         inp: &[(InputTensor, ShapeTracker)],
         trace_mode: bool,
     ) -> (
         Vec<Fixed<DEFAULT_FP_SCALE>>,
-        Option<
-            Vec<(
-                usize,
-                Fixed<DEFAULT_FP_SCALE>,
-                Fixed<DEFAULT_FP_SCALE>,
-                Fixed<DEFAULT_FP_SCALE>,
-                Fixed<DEFAULT_FP_SCALE>,
-                BaseField,
-                BaseField,
-            )>,
-        >,
+        Option<Vec<(Fixed<DEFAULT_FP_SCALE>, Fixed<DEFAULT_FP_SCALE>)>>,
     ) {
-        let sh = inp[0].1.shape_usize();
-        let front_size = sh.iter().take(self.0).product::<usize>().max(1);
-        let back_size = sh.iter().skip(self.0 + 1).product::<usize>().max(1);
-        let dim_size = sh[self.0];
-
-        let output_size = front_size * back_size;
-        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
         let input = get_buffer_from_tensor(&inp[0].0).unwrap();
         let expr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
-        let mut stack: Vec<i64> = vec![];
+        let constant = Fixed::<DEFAULT_FP_SCALE>::from_f64(self.constant as f64);
 
-        // Only allocate for intermediate values if in trace mode
-        let mut intermediate_values = if trace_mode {
-            Some(Vec::with_capacity(output_size))
-        } else {
-            None
+        let output_size = inp[0].1.n_elements().to_usize().unwrap();
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let input_val = get_index(input, &expr, stack, idx);
+            (input_val, input_val + constant)
         };
 
-        for i in 0..front_size {
-            for j in 0..back_size {
-                // Initialize with the first element instead of negative infinity
-                let orig_first_index = i * dim_size * back_size + 0 * back_size + j;
-                let mut max_val = get_index(input, &expr, &mut stack, orig_first_index);
-
-                for k in 0..dim_size {
-                    let orig_index = i * dim_size * back_size + k * back_size + j;
-                    let input_val = get_index(input, &expr, &mut stack, orig_index);
-
-                    // Determine if this value is the new max
-                    let is_max = if input_val.to_f64() > max_val.to_f64() {
-                        BaseField::one()
-                    } else {
-                        BaseField::zero()
-                    };
-
-                    // Update max_val if needed
-                    let next_max_val = if is_max == BaseField::one() {
-                        input_val
-                    } else {
-                        max_val
-                    };
-
-                    // Set out_data only in the last reduction step
-                    let (out_val, is_last_step) = if k == dim_size - 1 {
-                        out_data[i * back_size + j] = next_max_val;
-                        (next_max_val, BaseField::one())
-                    } else {
-                        (Fixed::<DEFAULT_FP_SCALE>::zero(), BaseField::zero()) // Placeholder for incomplete reductions
-                    };
-
-                    let idx = i * back_size + j; // Index for out_data
-
-                    // Record intermediate values if in trace mode
-                    if let Some(values) = &mut intermediate_values {
-                        values.push((
-                            idx,
-                            input_val,
-                            out_val,
-                            max_val,
-                            next_max_val,
-                            is_max,
-                            is_last_step,
-                        ));
-                    }
-
-                    // Update running maximum
-                    max_val = next_max_val;
-                }
-            }
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values.par_iter().map(|value| value.1).collect();
+            (out_data, Some(values))
+        } else {
+            let out_data = par_map_indices(output_size, |stack, idx| eval(stack, idx).1);
+            (out_data, None)
         }
-
-        (out_data, intermediate_values)
     }
 }
 
-impl LuminairOperator<MaxReduceColumn, MaxReduceTraceTable, ()> for LuminairMaxReduce {
+impl LuminairOperator<AddConstColumn, AddConstTraceTables, ()> for LuminairAddConst {
     fn process_trace(
         &mut self,
         inp: Vec<(InputTensor, ShapeTracker)>,
-        table: &mut MaxReduceTraceTable,
+        tables: &mut AddConstTraceTables,
         node_info: &NodeInfo,
         _lookup: &mut (),
     ) -> Vec<Tensor> {
         let (out_data, intermediate_values) = self.compute(&inp, true);
         let intermediate_values = intermediate_values.unwrap();
 
+        let output_size = inp[0].1.n_elements().to_usize().unwrap();
         let node_id: BaseField = node_info.id.into();
         let input_id: BaseField = node_info.inputs[0].id.into();
-        let output_size = out_data.len();
+        let constant = Fixed::<DEFAULT_FP_SCALE>::from_f64(self.constant as f64).to_m31();
 
         let out_mult = if node_info.output.is_final_output {
             BaseField::zero()
@@ -1703,29 +1584,1334 @@ impl LuminairOperator<MaxReduceColumn, MaxReduceTraceTable, ()> for LuminairMaxR
             BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
         };
 
-        for entry in intermediate_values {
-            let (idx, input_val, out_val, max_val, next_max_val, is_max, is_last_step_flag) = entry;
+        tables.table_mut(constant).table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, (input_val, out_val))| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    AddConstTraceTableRow {
+                        node_id,
+                        input_id,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        input: input_val.to_m31(),
+                        out: out_val.to_m31(),
+                        input_mult: -BaseField::one(),
+                        out_mult,
+                    }
+                }),
+        );
+
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+impl Operator for LuminairAddConst {
+    fn process(&mut self, inp: Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor> {
+        let (out_data, _) = self.compute(&inp, false);
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+// ================== TERNARY ==================
+
+/// Operator for selecting between two tensors element-wise with a boolean mask
+/// 
+/// Computes `cond ? lhs : rhs` without a fixed-point multiplication.
+/// The condition must be a 0/1 fixed-point mask, such as the output of LessThan
+#[derive(Clone, Default, PartialEq)]
+pub(crate) struct LuminairSelect {}
+impl core::fmt::Debug for LuminairSelect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Select")
+    }
+}
+
+impl LuminairSelect {
+    /// Creates a new LuminairSelect operator
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl LuminairSelect {
+    fn compute(
+        &self,
+        inp: &[(InputTensor, ShapeTracker)],
+        trace_mode: bool,
+    ) -> (
+        Vec<Fixed<DEFAULT_FP_SCALE>>,
+        Option<
+            Vec<(
+                Fixed<DEFAULT_FP_SCALE>, // Cond
+                Fixed<DEFAULT_FP_SCALE>, // Lhs
+                Fixed<DEFAULT_FP_SCALE>, // Rhs
+                Fixed<DEFAULT_FP_SCALE>, // Out
+            )>,
+        >,
+    ) {
+        let (cond, lhs, rhs) = (
+            get_buffer_from_tensor(&inp[0].0).unwrap(),
+            get_buffer_from_tensor(&inp[1].0).unwrap(),
+            get_buffer_from_tensor(&inp[2].0).unwrap(),
+        );
+        let cexpr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
+        let lexpr = (inp[1].1.index_expression(), inp[1].1.valid_expression());
+        let rexpr = (inp[2].1.index_expression(), inp[2].1.valid_expression());
+
+        let mut stack: Vec<i64> = vec![];
+        let output_size = inp[0].1.n_elements().to_usize().unwrap();
+        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
+
+        // Only allocate for intermediate values if in trace mode
+        let mut intermediate_values = if trace_mode {
+            Some(Vec::with_capacity(output_size))
+        } else {
+            None
+        };
+
+        for (idx, out) in out_data.iter_mut().enumerate() {
+            let cond_val = get_index(cond, &cexpr, &mut stack, idx);
+            let lhs_val = get_index(lhs, &lexpr, &mut stack, idx);
+            let rhs_val = get_index(rhs, &rexpr, &mut stack, idx);
+            let out_val = if cond_val.0 != 0 { lhs_val } else { rhs_val };
+            *out = out_val;
+
+            // Only collect intermediate values if in trace mode
+            if let Some(values) = &mut intermediate_values {
+                values.push((cond_val, lhs_val, rhs_val, out_val));
+            }
+        }
+
+        (out_data, intermediate_values)
+    }
+}
+
+impl LuminairOperator<SelectColumn, SelectTraceTable, ()> for LuminairSelect {
+    fn process_trace(
+        &mut self,
+        inp: Vec<(InputTensor, ShapeTracker)>,
+        table: &mut SelectTraceTable,
+        node_info: &NodeInfo,
+        _lookup: &mut (),
+    ) -> Vec<Tensor> {
+        let (out_data, intermediate_values) = self.compute(&inp, true);
+        let intermediate_values = intermediate_values.unwrap();
+
+        let output_size = inp[0].1.n_elements().to_usize().unwrap();
+        let node_id: BaseField = node_info.id.into();
+        let cond_id: BaseField = node_info.inputs[0].id.into();
+        let lhs_id: BaseField = node_info.inputs[1].id.into();
+        let rhs_id: BaseField = node_info.inputs[2].id.into();
+
+        let out_mult = if node_info.output.is_final_output {
+            BaseField::zero()
+        } else {
+            BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
+        };
+
+        for (idx, (cond_val, lhs_val, rhs_val, out_val)) in
+            intermediate_values.into_iter().enumerate()
+        {
+            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+            let cond_bit: u32 = if cond_val.0 != 0 { 1 } else { 0 };
+
+            table.add_row(SelectTraceTableRow {
+                node_id,
+                cond_id,
+                lhs_id,
+                rhs_id,
+                idx: idx.into(),
+                is_last_idx: (is_last_idx).into(),
+                cond: cond_val.to_m31(),
+                cond_bit: cond_bit.into(),
+                lhs: lhs_val.to_m31(),
+                rhs: rhs_val.to_m31(),
+                out: out_val.to_m31(),
+                cond_mult: -BaseField::one(),
+                lhs_mult: -BaseField::one(),
+                rhs_mult: -BaseField::one(),
+                out_mult,
+            })
+        }
+
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+impl Operator for LuminairSelect {
+    fn process(&mut self, inp: Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor> {
+        let (out_data, _) = self.compute(&inp, false);
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+// ================== GATHER ==================
+
+/// Operator for gathering rows of a table at data-dependent indices
+/// 
+/// Computes `out[i, ..] = table[indices[i], ..]`, i.e. an embedding lookup.
+/// Indices must be non-negative integers smaller than the number of table rows
+#[derive(Clone, Default, PartialEq)]
+pub(crate) struct LuminairGather {}
+impl core::fmt::Debug for LuminairGather {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Gather")
+    }
+}
+
+impl LuminairGather {
+    /// Creates a new LuminairGather operator
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl LuminairGather {
+    fn compute(
+        &self,
+        inp: &[(InputTensor, ShapeTracker)],
+        trace_mode: bool,
+    ) -> (
+        Vec<Fixed<DEFAULT_FP_SCALE>>,
+        Option<
+            Vec<(
+                Fixed<DEFAULT_FP_SCALE>, // Index
+                usize,                   // Flattened position in the table
+                Fixed<DEFAULT_FP_SCALE>, // Out
+            )>,
+        >,
+    ) {
+        let (table, indices) = (
+            get_buffer_from_tensor(&inp[0].0).unwrap(),
+            get_buffer_from_tensor(&inp[1].0).unwrap(),
+        );
+        let texpr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
+        let iexpr = (inp[1].1.index_expression(), inp[1].1.valid_expression());
+
+        let mut stack: Vec<i64> = vec![];
+        let table_size = inp[0].1.n_elements().to_usize().unwrap();
+        let n_rows = inp[0].1.shape_usize()[0];
+        let row_size = table_size / n_rows;
+        let n_indices = inp[1].1.n_elements().to_usize().unwrap();
+        let output_size = n_indices * row_size;
+        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
+
+        // Only allocate for intermediate values if in trace mode
+        let mut intermediate_values = if trace_mode {
+            Some(Vec::with_capacity(output_size))
+        } else {
+            None
+        };
+
+        for i in 0..n_indices {
+            let index_val = get_index(indices, &iexpr, &mut stack, i);
+            assert!(
+                index_val.0 >= 0 && index_val.0 % (1 << DEFAULT_FP_SCALE) == 0,
+                "Gather indices must be non-negative integers"
+            );
+            let row = (index_val.0 >> DEFAULT_FP_SCALE) as usize;
+            assert!(row < n_rows, "Gather index {row} out of bounds for {n_rows} rows");
+
+            for col in 0..row_size {
+                let lookup_idx = row * row_size + col;
+                let out_val = get_index(table, &texpr, &mut stack, lookup_idx);
+                out_data[i * row_size + col] = out_val;
+
+                // Only collect intermediate values if in trace mode
+                if let Some(values) = &mut intermediate_values {
+                    values.push((index_val, lookup_idx, out_val));
+                }
+            }
+        }
+
+        (out_data, intermediate_values)
+    }
+}
+
+impl LuminairOperator<GatherColumn, GatherTraceTable, GatherLookupTraceTable> for LuminairGather {
+    fn process_trace(
+        &mut self,
+        inp: Vec<(InputTensor, ShapeTracker)>,
+        table: &mut GatherTraceTable,
+        node_info: &NodeInfo,
+        lookup: &mut GatherLookupTraceTable,
+    ) -> Vec<Tensor> {
+        let (out_data, intermediate_values) = self.compute(&inp, true);
+        let intermediate_values = intermediate_values.unwrap();
+
+        let table_size = inp[0].1.n_elements().to_usize().unwrap();
+        let row_size = table_size / inp[0].1.shape_usize()[0];
+        let output_size = out_data.len();
+        let node_id: BaseField = node_info.id.into();
+        let table_id: BaseField = node_info.inputs[0].id.into();
+        let indices_id: BaseField = node_info.inputs[1].id.into();
+
+        let out_mult = if node_info.output.is_final_output {
+            BaseField::zero()
+        } else {
+            BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
+        };
+
+        // Number of reads of each table element, emitted by the gather lookup table
+        let mut multiplicities = vec![0u32; table_size];
+
+        for (idx, &(index_val, lookup_idx, out_val)) in intermediate_values.iter().enumerate() {
+            let col = idx % row_size;
+            let index_int = lookup_idx / row_size;
+            let is_last_idx = idx == output_size - 1;
+            let is_last_col = col == row_size - 1;
+            let same_row = !is_last_idx && !is_last_col;
+
+            multiplicities[lookup_idx] += 1;
+
+            table.add_row(GatherTraceTableRow {
+                node_id,
+                table_id,
+                indices_id,
+                idx: idx.into(),
+                is_last_idx: (is_last_idx as u32).into(),
+                row_size: row_size.into(),
+                col: col.into(),
+                is_last_col: (is_last_col as u32).into(),
+                same_row: (same_row as u32).into(),
+                index: index_val.to_m31(),
+                index_int: index_int.into(),
+                lookup_idx: lookup_idx.into(),
+                out: out_val.to_m31(),
+                // Each index is consumed once, on the first element of its gathered row
+                index_mult: if col == 0 {
+                    -BaseField::one()
+                } else {
+                    BaseField::zero()
+                },
+                out_mult,
+                lookup_mult: BaseField::one(),
+            })
+        }
+
+        add_lookup_reads(lookup, &inp[0], node_id, table_id, multiplicities);
+
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+/// Consumes every element of `table` once and exposes it to the gather lookup,
+/// `multiplicities[i]` being the number of times element `i` is read by `node_id`
+fn add_lookup_reads(
+    lookup: &mut GatherLookupTraceTable,
+    table: &(InputTensor, ShapeTracker),
+    node_id: BaseField,
+    table_id: BaseField,
+    multiplicities: Vec<u32>,
+) {
+    let table_data = get_buffer_from_tensor(&table.0).unwrap();
+    let texpr = (table.1.index_expression(), table.1.valid_expression());
+    let table_size = multiplicities.len();
+    let mut stack: Vec<i64> = vec![];
+    for (idx, multiplicity) in multiplicities.into_iter().enumerate() {
+        let is_last_idx: u32 = if idx == (table_size - 1) { 1 } else { 0 };
+
+        lookup.add_row(GatherLookupTraceTableRow {
+            node_id,
+            table_id,
+            idx: idx.into(),
+            is_last_idx: is_last_idx.into(),
+            value: get_index(table_data, &texpr, &mut stack, idx).to_m31(),
+            value_mult: -BaseField::one(),
+            multiplicity: BaseField::from_u32_unchecked(multiplicity),
+        })
+    }
+}
+
+impl Operator for LuminairGather {
+    fn process(&mut self, inp: Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor> {
+        let (out_data, _) = self.compute(&inp, false);
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+// ================== IM2COL ==================
+
+/// Operator for extracting convolution patches from a `(channels, height, width)` image
+/// 
+/// Produces a `(channels * kernel_h * kernel_w, out_h * out_w)` matrix whose columns are
+/// the flattened receptive fields, so that a convolution becomes a single matmul.
+/// Positions falling in the zero padding read zero
+#[derive(Clone, Default, PartialEq)]
+pub(crate) struct LuminairIm2Col {
+    pub kernel: (usize, usize),
+    pub stride: (usize, usize),
+    pub padding: (usize, usize),
+}
+impl core::fmt::Debug for LuminairIm2Col {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Im2Col(kernel={:?}, stride={:?}, padding={:?})",
+            self.kernel, self.stride, self.padding
+        )
+    }
+}
+
+impl LuminairIm2Col {
+    /// Creates a new LuminairIm2Col operator with the given kernel, stride and padding
+    pub fn new(kernel: (usize, usize), stride: (usize, usize), padding: (usize, usize)) -> Self {
+        Self {
+            kernel,
+            stride,
+            padding,
+        }
+    }
+
+    /// Returns the spatial output size `(out_h, out_w)` for an input of size `(h, w)`
+    pub fn output_size(&self, (h, w): (usize, usize)) -> (usize, usize) {
+        let (kh, kw) = self.kernel;
+        let (sh, sw) = self.stride;
+        let (ph, pw) = self.padding;
+        assert!(
+            h + 2 * ph >= kh && w + 2 * pw >= kw,
+            "Convolution kernel is larger than the padded input"
+        );
+        ((h + 2 * ph - kh) / sh + 1, (w + 2 * pw - kw) / sw + 1)
+    }
+}
+
+impl LuminairIm2Col {
+    fn compute(
+        &self,
+        inp: &[(InputTensor, ShapeTracker)],
+        trace_mode: bool,
+    ) -> (
+        Vec<Fixed<DEFAULT_FP_SCALE>>,
+        Option<
+            Vec<(
+                Option<usize>,           // Flattened position in the image, None for padding
+                Fixed<DEFAULT_FP_SCALE>, // Out
+            )>,
+        >,
+    ) {
+        let input = get_buffer_from_tensor(&inp[0].0).unwrap();
+        let expr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
+        let mut stack: Vec<i64> = vec![];
+
+        let sh = inp[0].1.shape_usize();
+        let (channels, h, w) = (sh[0], sh[1], sh[2]);
+        let (kh, kw) = self.kernel;
+        let (out_h, out_w) = self.output_size((h, w));
+
+        let output_size = channels * kh * kw * out_h * out_w;
+        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
+
+        // Only allocate for intermediate values if in trace mode
+        let mut intermediate_values = if trace_mode {
+            Some(Vec::with_capacity(output_size))
+        } else {
+            None
+        };
+
+        let mut idx = 0;
+        for c in 0..channels {
+            for i in 0..kh {
+                for j in 0..kw {
+                    for oy in 0..out_h {
+                        for ox in 0..out_w {
+                            // Position in the unpadded image, if inside it
+                            let y = (oy * self.stride.0 + i).checked_sub(self.padding.0);
+                            let x = (ox * self.stride.1 + j).checked_sub(self.padding.1);
+                            let src_idx = match (y, x) {
+                                (Some(y), Some(x)) if y < h && x < w => Some((c * h + y) * w + x),
+                                _ => None,
+                            };
+
+                            let out_val = src_idx.map_or(Fixed::<DEFAULT_FP_SCALE>::zero(), |s| {
+                                get_index(input, &expr, &mut stack, s)
+                            });
+                            out_data[idx] = out_val;
+
+                            // Only collect intermediate values if in trace mode
+                            if let Some(values) = &mut intermediate_values {
+                                values.push((src_idx, out_val));
+                            }
+                            idx += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        (out_data, intermediate_values)
+    }
+}
+
+impl LuminairOperator<Im2ColColumn, Im2ColTraceTable, GatherLookupTraceTable> for LuminairIm2Col {
+    fn process_trace(
+        &mut self,
+        inp: Vec<(InputTensor, ShapeTracker)>,
+        table: &mut Im2ColTraceTable,
+        node_info: &NodeInfo,
+        lookup: &mut GatherLookupTraceTable,
+    ) -> Vec<Tensor> {
+        let (out_data, intermediate_values) = self.compute(&inp, true);
+        let intermediate_values = intermediate_values.unwrap();
+
+        let input_size = inp[0].1.n_elements().to_usize().unwrap();
+        let output_size = out_data.len();
+        let node_id: BaseField = node_info.id.into();
+        let input_id: BaseField = node_info.inputs[0].id.into();
+
+        let out_mult = if node_info.output.is_final_output {
+            BaseField::zero()
+        } else {
+            BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
+        };
+
+        // Number of reads of each image element, emitted by the gather lookup table
+        let mut multiplicities = vec![0u32; input_size];
+
+        for (idx, (src_idx, out_val)) in intermediate_values.into_iter().enumerate() {
+            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+            if let Some(src_idx) = src_idx {
+                multiplicities[src_idx] += 1;
+            }
+
+            table.add_row(Im2ColTraceTableRow {
+                node_id,
+                input_id,
+                idx: idx.into(),
+                is_last_idx: is_last_idx.into(),
+                src_idx: src_idx.unwrap_or(0).into(),
+                is_valid: (src_idx.is_some() as u32).into(),
+                out: out_val.to_m31(),
+                out_mult,
+            })
+        }
+
+        add_lookup_reads(lookup, &inp[0], node_id, input_id, multiplicities);
+
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+impl Operator for LuminairIm2Col {
+    fn process(&mut self, inp: Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor> {
+        let (out_data, _) = self.compute(&inp, false);
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+// ================== REDUCE ==================
+
+/// Operator for reducing a tensor along a specified dimension by summing elements
+/// 
+/// Performs fixed-point sum reduction operations for STARK proving
+#[derive(Clone, Default, PartialEq)]
+pub(crate) struct LuminairSumReduce(pub usize);
+impl core::fmt::Debug for LuminairSumReduce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SumReduce({:?})", self.0)
+    }
+}
+
+impl LuminairSumReduce {
+    /// Creates a new LuminairSumReduce operator for the specified dimension
+    pub fn new(value: usize) -> Self {
+        Self(value)
+    }
+}
+
+impl LuminairSumReduce {
+    fn compute(
+        &self,
+        inp: &[(InputTensor, ShapeTracker)],
+        trace_mode: bool,
+    ) -> (
+        Vec<Fixed<DEFAULT_FP_SCALE>>,
+        Option<
+            Vec<(
+                usize,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                BaseField,
+            )>,
+        >,
+    ) {
+        let sh = inp[0].1.shape_usize();
+        let front_size = sh.iter().take(self.0).product::<usize>().max(1);
+        let back_size = sh.iter().skip(self.0 + 1).product::<usize>().max(1);
+        let dim_size = sh[self.0];
+
+        let output_size = front_size * back_size;
+        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
+        let input = get_buffer_from_tensor(&inp[0].0).unwrap();
+        let expr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
+        let mut stack: Vec<i64> = vec![];
+
+        // Only allocate for intermediate values if in trace mode
+        let mut intermediate_values = if trace_mode {
+            Some(Vec::with_capacity(output_size))
+        } else {
+            None
+        };
+
+        for i in 0..front_size {
+            for j in 0..back_size {
+                let mut acc = Fixed::<DEFAULT_FP_SCALE>::zero(); // Initialize accumulator for each (i, j)
+                for k in 0..dim_size {
+                    let orig_index = i * dim_size * back_size + k * back_size + j;
+                    let input_val = get_index(input, &expr, &mut stack, orig_index);
+                    let next_acc = acc + input_val; // Compute next accumulator
+                    let idx = i * back_size + j; // Index for out_data
+
+                    // Set out_data only in the last reduction step
+                    let (out_val, is_last_step) = if k == dim_size - 1 {
+                        out_data[idx] = next_acc;
+                        (next_acc, BaseField::one())
+                    } else {
+                        (Fixed::<DEFAULT_FP_SCALE>::zero(), BaseField::zero()) // Placeholder for incomplete reductions
+                    };
+
+                    // Record intermediate values if in trace mode
+                    if let Some(values) = &mut intermediate_values {
+                        values.push((idx, input_val, out_val, acc, next_acc, is_last_step));
+                    }
+                    // Update running sum
+                    acc = next_acc;
+                }
+            }
+        }
+
+        (out_data, intermediate_values)
+    }
+}
+
+impl LuminairOperator<SumReduceColumn, SumReduceTraceTable, ()> for LuminairSumReduce {
+    fn process_trace(
+        &mut self,
+        inp: Vec<(InputTensor, ShapeTracker)>,
+        table: &mut SumReduceTraceTable,
+        node_info: &NodeInfo,
+        _lookup: &mut (),
+    ) -> Vec<Tensor> {
+        let (out_data, intermediate_values) = self.compute(&inp, true);
+        let intermediate_values = intermediate_values.unwrap();
+
+        let node_id: BaseField = node_info.id.into();
+        let input_id: BaseField = node_info.inputs[0].id.into();
+        let output_size = out_data.len();
+
+        let out_mult = if node_info.output.is_final_output {
+            BaseField::zero()
+        } else {
+            BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
+        };
+
+        for entry in intermediate_values {
+            let (idx, input_val, out_val, acc, next_acc, is_last_step) = entry;
+
+            let out_mult = out_mult * is_last_step;
+
+            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+            table.add_row(SumReduceTraceTableRow {
+                node_id,
+                input_id,
+                idx: idx.into(),
+                is_last_idx: (is_last_idx).into(),
+                input: input_val.to_m31(),
+                out: out_val.to_m31(),
+                acc: acc.to_m31(),
+                next_acc: next_acc.to_m31(),
+                is_last_step,
+                input_mult: -BaseField::one(),
+                out_mult,
+            });
+        }
+
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+impl Operator for LuminairSumReduce {
+    fn process(&mut self, inp: Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor> {
+        let (out_data, _) = self.compute(&inp, false);
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+/// Operator for reducing a tensor along a specified dimension by finding maximum elements
+/// 
+/// Performs fixed-point maximum reduction operations for STARK proving
+#[derive(Clone, Default, PartialEq)]
+pub(crate) struct LuminairMaxReduce(pub usize);
+impl core::fmt::Debug for LuminairMaxReduce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MaxReduce({:?})", self.0)
+    }
+}
+impl LuminairMaxReduce {
+    /// Creates a new LuminairMaxReduce operator for the specified dimension
+    pub fn new(value: usize) -> Self {
+        Self(value)
+    }
+}
+
+impl LuminairMaxReduce {
+    fn compute(
+        &self,
+        inp: &[(InputTensor, ShapeTracker)],
+        trace_mode: bool,
+    ) -> (
+        Vec<Fixed<DEFAULT_FP_SCALE>>,
+        Option<
+            Vec<(
+                usize,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                BaseField,
+                BaseField,
+            )>,
+        >,
+    ) {
+        let sh = inp[0].1.shape_usize();
+        let front_size = sh.iter().take(self.0).product::<usize>().max(1);
+        let back_size = sh.iter().skip(self.0 + 1).product::<usize>().max(1);
+        let dim_size = sh[self.0];
+
+        let output_size = front_size * back_size;
+        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
+        let input = get_buffer_from_tensor(&inp[0].0).unwrap();
+        let expr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
+        let mut stack: Vec<i64> = vec![];
+
+        // Only allocate for intermediate values if in trace mode
+        let mut intermediate_values = if trace_mode {
+            Some(Vec::with_capacity(output_size))
+        } else {
+            None
+        };
+
+        for i in 0..front_size {
+            for j in 0..back_size {
+                // Initialize with the first element instead of negative infinity
+                let orig_first_index = i * dim_size * back_size + 0 * back_size + j;
+                let mut max_val = get_index(input, &expr, &mut stack, orig_first_index);
+
+                for k in 0..dim_size {
+                    let orig_index = i * dim_size * back_size + k * back_size + j;
+                    let input_val = get_index(input, &expr, &mut stack, orig_index);
+
+                    // Determine if this value is the new max
+                    let is_max = if input_val.to_f64() > max_val.to_f64() {
+                        BaseField::one()
+                    } else {
+                        BaseField::zero()
+                    };
+
+                    // Update max_val if needed
+                    let next_max_val = if is_max == BaseField::one() {
+                        input_val
+                    } else {
+                        max_val
+                    };
+
+                    // Set out_data only in the last reduction step
+                    let (out_val, is_last_step) = if k == dim_size - 1 {
+                        out_data[i * back_size + j] = next_max_val;
+                        (next_max_val, BaseField::one())
+                    } else {
+                        (Fixed::<DEFAULT_FP_SCALE>::zero(), BaseField::zero()) // Placeholder for incomplete reductions
+                    };
+
+                    let idx = i * back_size + j; // Index for out_data
+
+                    // Record intermediate values if in trace mode
+                    if let Some(values) = &mut intermediate_values {
+                        values.push((
+                            idx,
+                            input_val,
+                            out_val,
+                            max_val,
+                            next_max_val,
+                            is_max,
+                            is_last_step,
+                        ));
+                    }
+
+                    // Update running maximum
+                    max_val = next_max_val;
+                }
+            }
+        }
+
+        (out_data, intermediate_values)
+    }
+}
+
+impl LuminairOperator<MaxReduceColumn, MaxReduceTraceTable, ()> for LuminairMaxReduce {
+    fn process_trace(
+        &mut self,
+        inp: Vec<(InputTensor, ShapeTracker)>,
+        table: &mut MaxReduceTraceTable,
+        node_info: &NodeInfo,
+        _lookup: &mut (),
+    ) -> Vec<Tensor> {
+        let (out_data, intermediate_values) = self.compute(&inp, true);
+        let intermediate_values = intermediate_values.unwrap();
+
+        let node_id: BaseField = node_info.id.into();
+        let input_id: BaseField = node_info.inputs[0].id.into();
+        let output_size = out_data.len();
+
+        let out_mult = if node_info.output.is_final_output {
+            BaseField::zero()
+        } else {
+            BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
+        };
+
+        for entry in intermediate_values {
+            let (idx, input_val, out_val, max_val, next_max_val, is_max, is_last_step_flag) = entry;
+
+            let out_mult = out_mult * is_last_step_flag;
+
+            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+            table.add_row(MaxReduceTraceTableRow {
+                node_id,
+                input_id,
+                idx: idx.into(),
+                is_last_idx: (is_last_idx).into(),
+                input: input_val.to_m31(),
+                out: out_val.to_m31(),
+                max_val: max_val.to_m31(),
+                next_max_val: next_max_val.to_m31(),
+                is_max,
+                is_last_step: is_last_step_flag,
+                input_mult: -BaseField::one(),
+                out_mult,
+            });
+        }
+
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+impl Operator for LuminairMaxReduce {
+    fn process(&mut self, inp: Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor> {
+        let (out_data, _) = self.compute(&inp, false);
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+// ================== MATMUL ==================
+
+/// Operator for multiplying two tensors and summing the products along a dimension
+/// 
+/// Fuses a Mul followed by a SumReduce, as produced by matmuls and convolutions,
+/// into a single fixed-point multiply-accumulate for STARK proving
+#[derive(Clone, Default, PartialEq)]
+pub(crate) struct LuminairMatMul(pub usize);
+impl core::fmt::Debug for LuminairMatMul {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MatMul({:?})", self.0)
+    }
+}
+
+impl LuminairMatMul {
+    /// Creates a new LuminairMatMul operator reducing over the specified dimension
+    pub fn new(value: usize) -> Self {
+        Self(value)
+    }
+}
+
+impl LuminairMatMul {
+    fn compute(
+        &self,
+        inp: &[(InputTensor, ShapeTracker)],
+        trace_mode: bool,
+    ) -> (
+        Vec<Fixed<DEFAULT_FP_SCALE>>,
+        Option<
+            Vec<(
+                usize,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                Fixed<DEFAULT_FP_SCALE>,
+                BaseField,
+            )>,
+        >,
+    ) {
+        let sh = inp[0].1.shape_usize();
+        let front_size = sh.iter().take(self.0).product::<usize>().max(1);
+        let back_size = sh.iter().skip(self.0 + 1).product::<usize>().max(1);
+        let dim_size = sh[self.0];
 
-            let out_mult = out_mult * is_last_step_flag;
+        let output_size = front_size * back_size;
+        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
+        let (lhs, rhs) = (
+            get_buffer_from_tensor(&inp[0].0).unwrap(),
+            get_buffer_from_tensor(&inp[1].0).unwrap(),
+        );
+        let lexpr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
+        let rexpr = (inp[1].1.index_expression(), inp[1].1.valid_expression());
+        let mut stack: Vec<i64> = vec![];
+
+        // Only allocate for intermediate values if in trace mode
+        let mut intermediate_values = if trace_mode {
+            Some(Vec::with_capacity(output_size * dim_size))
+        } else {
+            None
+        };
+
+        for i in 0..front_size {
+            for j in 0..back_size {
+                let mut acc = Fixed::<DEFAULT_FP_SCALE>::zero(); // Initialize accumulator for each (i, j)
+                for k in 0..dim_size {
+                    let orig_index = i * dim_size * back_size + k * back_size + j;
+                    let lhs_val = get_index(lhs, &lexpr, &mut stack, orig_index);
+                    let rhs_val = get_index(rhs, &rexpr, &mut stack, orig_index);
+                    let (prod_val, rem_val) = lhs_val * rhs_val;
+                    let next_acc = acc + prod_val; // Compute next accumulator
+                    let idx = i * back_size + j; // Index for out_data
+
+                    // Set out_data only in the last reduction step
+                    let (out_val, is_last_step) = if k == dim_size - 1 {
+                        out_data[idx] = next_acc;
+                        (next_acc, BaseField::one())
+                    } else {
+                        (Fixed::<DEFAULT_FP_SCALE>::zero(), BaseField::zero()) // Placeholder for incomplete reductions
+                    };
+
+                    // Record intermediate values if in trace mode
+                    if let Some(values) = &mut intermediate_values {
+                        values.push((
+                            idx,
+                            lhs_val,
+                            rhs_val,
+                            prod_val,
+                            rem_val,
+                            acc,
+                            next_acc,
+                            out_val,
+                            is_last_step,
+                        ));
+                    }
+                    // Update running sum
+                    acc = next_acc;
+                }
+            }
+        }
+
+        (out_data, intermediate_values)
+    }
+}
+
+impl LuminairOperator<MatMulColumn, MatMulTraceTable, ()> for LuminairMatMul {
+    fn process_trace(
+        &mut self,
+        inp: Vec<(InputTensor, ShapeTracker)>,
+        table: &mut MatMulTraceTable,
+        node_info: &NodeInfo,
+        _lookup: &mut (),
+    ) -> Vec<Tensor> {
+        let (out_data, intermediate_values) = self.compute(&inp, true);
+        let intermediate_values = intermediate_values.unwrap();
+
+        let node_id: BaseField = node_info.id.into();
+        let lhs_id: BaseField = node_info.inputs[0].id.into();
+        let rhs_id: BaseField = node_info.inputs[1].id.into();
+        let output_size = out_data.len();
+
+        let out_mult = if node_info.output.is_final_output {
+            BaseField::zero()
+        } else {
+            BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
+        };
+
+        for entry in intermediate_values {
+            let (idx, lhs_val, rhs_val, prod_val, rem_val, acc, next_acc, out_val, is_last_step) =
+                entry;
+
+            let out_mult = out_mult * is_last_step;
 
             let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
 
-            table.add_row(MaxReduceTraceTableRow {
+            table.add_row(MatMulTraceTableRow {
                 node_id,
-                input_id,
+                lhs_id,
+                rhs_id,
                 idx: idx.into(),
                 is_last_idx: (is_last_idx).into(),
-                next_node_id: node_id,
-                next_input_id: input_id,
-                next_idx: (idx + 1).into(),
-                input: input_val.to_m31(),
+                lhs: lhs_val.to_m31(),
+                rhs: rhs_val.to_m31(),
+                prod: prod_val.to_m31(),
+                rem: rem_val.to_m31(),
+                acc: acc.to_m31(),
+                next_acc: next_acc.to_m31(),
+                is_last_step,
                 out: out_val.to_m31(),
-                max_val: max_val.to_m31(),
-                next_max_val: next_max_val.to_m31(),
-                is_max,
-                is_last_step: is_last_step_flag,
+                lhs_mult: -BaseField::one(),
+                rhs_mult: -BaseField::one(),
+                out_mult,
+            });
+        }
+
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+impl Operator for LuminairMatMul {
+    fn process(&mut self, inp: Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor> {
+        let (out_data, _) = self.compute(&inp, false);
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+// ================== NORM ==================
+
+/// Operator for normalizing a tensor over its last dimension
+///
+/// Fuses the primitive graph of a LayerNorm (mean subtraction) or RMSNorm
+/// (no mean subtraction), followed by an optional per-feature affine weight
+/// and bias, into a single fixed-point normalization for STARK proving
+#[derive(Clone, Default, PartialEq)]
+pub(crate) struct LuminairNorm {
+    /// Epsilon added to the variance before the square root
+    pub eps: f32,
+    /// Whether the mean is subtracted (LayerNorm) or not (RMSNorm)
+    pub center: bool,
+    /// Whether an affine weight tensor is read as the second input
+    pub has_weight: bool,
+    /// Whether an affine bias tensor is read as the last input
+    pub has_bias: bool,
+}
+impl core::fmt::Debug for LuminairNorm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = if self.center { "LayerNorm" } else { "RMSNorm" };
+        write!(f, "{name}(eps: {:?})", self.eps)
+    }
+}
+
+impl LuminairNorm {
+    /// Creates a new LuminairNorm operator
+    pub fn new(eps: f32, center: bool, has_weight: bool, has_bias: bool) -> Self {
+        Self {
+            eps,
+            center,
+            has_weight,
+            has_bias,
+        }
+    }
+
+    /// Position of the affine weight and bias tensors in the inputs
+    fn affine_inputs(&self) -> (Option<usize>, Option<usize>) {
+        let weight = self.has_weight.then_some(1);
+        let bias = self.has_bias.then_some(if self.has_weight { 2 } else { 1 });
+        (weight, bias)
+    }
+}
+
+/// Intermediate values of a single element of a normalized row
+struct NormStep {
+    idx: usize,
+    /// Position in the normalized row
+    step: usize,
+    is_last_step: BaseField,
+    input: Fixed<DEFAULT_FP_SCALE>,
+    /// Running sum of the row, up to and including this value
+    acc: Fixed<DEFAULT_FP_SCALE>,
+    sum: Fixed<DEFAULT_FP_SCALE>,
+    mean: Fixed<DEFAULT_FP_SCALE>,
+    mean_rem: Fixed<DEFAULT_FP_SCALE>,
+    centered: Fixed<DEFAULT_FP_SCALE>,
+    sq: Fixed<DEFAULT_FP_SCALE>,
+    sq_rem: Fixed<DEFAULT_FP_SCALE>,
+    /// Running sum of squares of the row, up to and including this value
+    sq_acc: Fixed<DEFAULT_FP_SCALE>,
+    sq_sum: Fixed<DEFAULT_FP_SCALE>,
+    var: Fixed<DEFAULT_FP_SCALE>,
+    var_rem: Fixed<DEFAULT_FP_SCALE>,
+    std: Fixed<DEFAULT_FP_SCALE>,
+    std_rem: Fixed<DEFAULT_FP_SCALE>,
+    rstd: Fixed<DEFAULT_FP_SCALE>,
+    rstd_rem: Fixed<DEFAULT_FP_SCALE>,
+    normed: Fixed<DEFAULT_FP_SCALE>,
+    normed_rem: Fixed<DEFAULT_FP_SCALE>,
+    weight: Fixed<DEFAULT_FP_SCALE>,
+    weighted: Fixed<DEFAULT_FP_SCALE>,
+    weighted_rem: Fixed<DEFAULT_FP_SCALE>,
+    bias: Fixed<DEFAULT_FP_SCALE>,
+    out: Fixed<DEFAULT_FP_SCALE>,
+}
+
+impl LuminairNorm {
+    fn compute(
+        &self,
+        inp: &[(InputTensor, ShapeTracker)],
+        trace_mode: bool,
+    ) -> (Vec<Fixed<DEFAULT_FP_SCALE>>, Option<Vec<NormStep>>) {
+        let sh = inp[0].1.shape_usize();
+        let dim_size = *sh.last().unwrap();
+        let output_size = sh.iter().product::<usize>();
+        let front_size = output_size / dim_size;
+
+        let input = get_buffer_from_tensor(&inp[0].0).unwrap();
+        let expr = (inp[0].1.index_expression(), inp[0].1.valid_expression());
+        let (weight_input, bias_input) = self.affine_inputs();
+        let affine = |i: Option<usize>| {
+            i.map(|i| {
+                (
+                    get_buffer_from_tensor(&inp[i].0).unwrap(),
+                    (inp[i].1.index_expression(), inp[i].1.valid_expression()),
+                )
+            })
+        };
+        let (weight, bias) = (affine(weight_input), affine(bias_input));
+        let mut stack: Vec<i64> = vec![];
+
+        let inv_d = Fixed::<DEFAULT_FP_SCALE>::from_f64(1.0 / dim_size as f64);
+        let mean_coef = if self.center {
+            inv_d
+        } else {
+            Fixed::<DEFAULT_FP_SCALE>::zero()
+        };
+        let eps = Fixed::<DEFAULT_FP_SCALE>::from_f64(self.eps as f64);
+        let one = Fixed::<DEFAULT_FP_SCALE>::from_f64(1.0);
+
+        let mut out_data = vec![Fixed::<DEFAULT_FP_SCALE>::zero(); output_size];
+        // Only allocate for intermediate values if in trace mode
+        let mut intermediate_values = if trace_mode {
+            Some(Vec::with_capacity(output_size))
+        } else {
+            None
+        };
+
+        for i in 0..front_size {
+            let row = i * dim_size..(i + 1) * dim_size;
+            let inputs = row
+                .clone()
+                .map(|idx| get_index(input, &expr, &mut stack, idx))
+                .collect::<Vec<_>>();
+
+            // Row statistics
+            let sum = inputs
+                .iter()
+                .fold(Fixed::<DEFAULT_FP_SCALE>::zero(), |acc, x| acc + *x);
+            let (mean, mean_rem) = sum * mean_coef;
+            let centered = inputs
+                .iter()
+                .map(|x| Fixed::<DEFAULT_FP_SCALE>(x.0 - mean.0))
+                .collect::<Vec<_>>();
+            let squares = centered.iter().map(|c| *c * *c).collect::<Vec<_>>();
+            let sq_sum = squares
+                .iter()
+                .fold(Fixed::<DEFAULT_FP_SCALE>::zero(), |acc, (sq, _)| acc + *sq);
+            let (var, var_rem) = sq_sum * inv_d;
+            let (std, std_rem) = (var + eps).sqrt();
+            let (rstd, rstd_rem) = std.recip();
+
+            let mut acc = Fixed::<DEFAULT_FP_SCALE>::zero();
+            let mut sq_acc = Fixed::<DEFAULT_FP_SCALE>::zero();
+            for (j, idx) in row.enumerate() {
+                let (normed, normed_rem) = centered[j] * rstd;
+                let weight_val = weight
+                    .as_ref()
+                    .map_or(one, |(buf, expr)| get_index(buf, expr, &mut stack, idx));
+                let bias_val = bias
+                    .as_ref()
+                    .map_or(Fixed::<DEFAULT_FP_SCALE>::zero(), |(buf, expr)| {
+                        get_index(buf, expr, &mut stack, idx)
+                    });
+                let (weighted, weighted_rem) = normed * weight_val;
+                let out = weighted + bias_val;
+                out_data[idx] = out;
+
+                // Update running sums
+                let (sq, sq_rem) = squares[j];
+                acc = acc + inputs[j];
+                sq_acc = sq_acc + sq;
+
+                // Record intermediate values if in trace mode
+                if let Some(values) = &mut intermediate_values {
+                    values.push(NormStep {
+                        idx,
+                        step: j,
+                        is_last_step: if j == dim_size - 1 {
+                            BaseField::one()
+                        } else {
+                            BaseField::zero()
+                        },
+                        input: inputs[j],
+                        acc,
+                        sum,
+                        mean,
+                        mean_rem,
+                        centered: centered[j],
+                        sq,
+                        sq_rem,
+                        sq_acc,
+                        sq_sum,
+                        var,
+                        var_rem,
+                        std,
+                        std_rem,
+                        rstd,
+                        rstd_rem,
+                        normed,
+                        normed_rem,
+                        weight: weight_val,
+                        weighted,
+                        weighted_rem,
+                        bias: bias_val,
+                        out,
+                    });
+                }
+            }
+        }
+
+        (out_data, intermediate_values)
+    }
+}
+
+impl LuminairOperator<NormColumn, NormTraceTables, RangeCheckLookup<1>> for LuminairNorm {
+    fn process_trace(
+        &mut self,
+        inp: Vec<(InputTensor, ShapeTracker)>,
+        tables: &mut NormTraceTables,
+        node_info: &NodeInfo,
+        lookup: &mut RangeCheckLookup<1>,
+    ) -> Vec<Tensor> {
+        let (out_data, intermediate_values) = self.compute(&inp, true);
+        let intermediate_values = intermediate_values.unwrap();
+
+        let dim_size = *inp[0].1.shape_usize().last().unwrap();
+        let (weight_input, bias_input) = self.affine_inputs();
+        let node_id: BaseField = node_info.id.into();
+        let input_id: BaseField = node_info.inputs[0].id.into();
+        // Absent affine tensors are read from no node with a zero multiplicity
+        let affine_read = |i: Option<usize>| match i {
+            Some(i) => (node_info.inputs[i].id.into(), -BaseField::one()),
+            None => (BaseField::zero(), BaseField::zero()),
+        };
+        let (weight_id, weight_mult) = affine_read(weight_input);
+        let (bias_id, bias_mult) = affine_read(bias_input);
+        let output_size = out_data.len();
+
+        // Normalizations sharing their parameters are proven together
+        let table = tables.table_mut(NormParams {
+            row_size: dim_size as u32,
+            center: self.center,
+            eps: Fixed::<DEFAULT_FP_SCALE>::from_f64(self.eps as f64).to_m31(),
+        });
+
+        let out_mult = if node_info.output.is_final_output {
+            BaseField::zero()
+        } else {
+            BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
+        };
+
+        // Decompose a remainder into four 8-bit limbs, updating the multiplicities of the lookup
+        let limbs = |rem: Fixed<DEFAULT_FP_SCALE>| -> [M31; 4] {
+            let rem = rem.to_m31().0;
+            std::array::from_fn(|i| {
+                let limb = (rem >> (8 * i)) & 0xFF;
+                lookup.multiplicities.increase_at(limb as usize);
+                M31::from_u32_unchecked(limb)
+            })
+        };
+
+        for step in intermediate_values {
+            let idx = step.idx;
+            let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+            let [mean_rem_limb0, mean_rem_limb1, mean_rem_limb2, mean_rem_limb3] =
+                limbs(step.mean_rem);
+            let [sq_rem_limb0, sq_rem_limb1, sq_rem_limb2, sq_rem_limb3] = limbs(step.sq_rem);
+            let [var_rem_limb0, var_rem_limb1, var_rem_limb2, var_rem_limb3] = limbs(step.var_rem);
+            let [std_rem_limb0, std_rem_limb1, std_rem_limb2, std_rem_limb3] = limbs(step.std_rem);
+            let [rstd_rem_limb0, rstd_rem_limb1, rstd_rem_limb2, rstd_rem_limb3] =
+                limbs(step.rstd_rem);
+            let [normed_rem_limb0, normed_rem_limb1, normed_rem_limb2, normed_rem_limb3] =
+                limbs(step.normed_rem);
+            let [weighted_rem_limb0, weighted_rem_limb1, weighted_rem_limb2, weighted_rem_limb3] =
+                limbs(step.weighted_rem);
+
+            table.add_row(NormTraceTableRow {
+                node_id,
+                input_id,
+                weight_id,
+                bias_id,
+                idx: idx.into(),
+                is_last_idx: (is_last_idx).into(),
+                is_last_step: step.is_last_step,
+                step: step.step.into(),
+                scale: M31::from_u32_unchecked(1 << DEFAULT_FP_SCALE),
+                input: step.input.to_m31(),
+                acc: step.acc.to_m31(),
+                sum: step.sum.to_m31(),
+                mean: step.mean.to_m31(),
+                mean_rem: step.mean_rem.to_m31(),
+                centered: step.centered.to_m31(),
+                sq: step.sq.to_m31(),
+                sq_rem: step.sq_rem.to_m31(),
+                sq_acc: step.sq_acc.to_m31(),
+                sq_sum: step.sq_sum.to_m31(),
+                var: step.var.to_m31(),
+                var_rem: step.var_rem.to_m31(),
+                std: step.std.to_m31(),
+                std_rem: step.std_rem.to_m31(),
+                rstd: step.rstd.to_m31(),
+                rstd_rem: step.rstd_rem.to_m31(),
+                normed: step.normed.to_m31(),
+                normed_rem: step.normed_rem.to_m31(),
+                weight: step.weight.to_m31(),
+                weighted: step.weighted.to_m31(),
+                weighted_rem: step.weighted_rem.to_m31(),
+                bias: step.bias.to_m31(),
+                out: step.out.to_m31(),
                 input_mult: -BaseField::one(),
+                weight_mult,
+                bias_mult,
                 out_mult,
+                mean_rem_limb0,
+                mean_rem_limb1,
+                mean_rem_limb2,
+                mean_rem_limb3,
+                sq_rem_limb0,
+                sq_rem_limb1,
+                sq_rem_limb2,
+                sq_rem_limb3,
+                var_rem_limb0,
+                var_rem_limb1,
+                var_rem_limb2,
+                var_rem_limb3,
+                std_rem_limb0,
+                std_rem_limb1,
+                std_rem_limb2,
+                std_rem_limb3,
+                rstd_rem_limb0,
+                rstd_rem_limb1,
+                rstd_rem_limb2,
+                rstd_rem_limb3,
+                normed_rem_limb0,
+                normed_rem_limb1,
+                normed_rem_limb2,
+                normed_rem_limb3,
+                weighted_rem_limb0,
+                weighted_rem_limb1,
+                weighted_rem_limb2,
+                weighted_rem_limb3,
+                range_check_mult: M31::one(),
             });
         }
 
@@ -1733,7 +2919,161 @@ impl LuminairOperator<MaxReduceColumn, MaxReduceTraceTable, ()> for LuminairMaxR
     }
 }
 
-impl Operator for LuminairMaxReduce {
+impl Operator for LuminairNorm {
+    fn process(&mut self, inp: Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor> {
+        let (out_data, _) = self.compute(&inp, false);
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+// ================== FUSED ==================
+
+/// Operator for evaluating a chain of elementwise operations at once
+///
+/// The intermediate results of the chain are columns of a single fused row
+/// rather than tensors written and read back through the node relation
+#[derive(Clone, PartialEq)]
+pub(crate) struct LuminairFused {
+    /// Expression evaluated at each index of the inputs
+    pub expr: FusedExpr,
+}
+impl core::fmt::Debug for LuminairFused {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fused({:?})", self.expr.steps)
+    }
+}
+
+impl LuminairFused {
+    /// Creates a new LuminairFused operator
+    pub fn new(expr: FusedExpr) -> Self {
+        Self { expr }
+    }
+}
+
+/// Input values and operation results of a single element of a fused expression
+#[derive(Clone, Copy)]
+struct FusedValues {
+    inputs: [Fixed<DEFAULT_FP_SCALE>; MAX_FUSED_INPUTS],
+    steps: [Fixed<DEFAULT_FP_SCALE>; MAX_FUSED_STEPS],
+    rems: [Fixed<DEFAULT_FP_SCALE>; MAX_FUSED_STEPS],
+}
+
+impl LuminairFused {
+    fn compute(
+        &self,
+        inp: &[(InputTensor, ShapeTracker)],
+        trace_mode: bool,
+    ) -> (Vec<Fixed<DEFAULT_FP_SCALE>>, Option<Vec<FusedValues>>) {
+        let inputs = inp
+            .iter()
+            .map(|(tensor, shape)| {
+                (
+                    get_buffer_from_tensor(tensor).unwrap(),
+                    (shape.index_expression(), shape.valid_expression()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let last_step = self.expr.steps.len() - 1;
+
+        let output_size = inp[0].1.n_elements().to_usize().unwrap();
+        let eval = |stack: &mut Vec<i64>, idx: usize| {
+            let mut values = FusedValues {
+                inputs: [Fixed::zero(); MAX_FUSED_INPUTS],
+                steps: [Fixed::zero(); MAX_FUSED_STEPS],
+                rems: [Fixed::zero(); MAX_FUSED_STEPS],
+            };
+            for (i, (input, expr)) in inputs.iter().enumerate() {
+                values.inputs[i] = get_index(input, expr, stack, idx);
+            }
+            for (i, step) in self.expr.steps.iter().enumerate() {
+                let operand = |operand: FusedOperand| match operand {
+                    FusedOperand::Input(input) => values.inputs[input],
+                    FusedOperand::Step(step) => values.steps[step],
+                    FusedOperand::Const(raw) => Fixed(raw),
+                };
+                let (out_val, rem_val) = match *step {
+                    FusedOp::Add(lhs, rhs) => (operand(lhs) + operand(rhs), Fixed::zero()),
+                    FusedOp::Mul(lhs, rhs) => operand(lhs) * operand(rhs),
+                    FusedOp::Recip(input) => operand(input).recip(),
+                    FusedOp::Sqrt(input) => operand(input).sqrt(),
+                };
+                values.steps[i] = out_val;
+                values.rems[i] = rem_val;
+            }
+            values
+        };
+
+        // Only collect intermediate values if in trace mode
+        if trace_mode {
+            let values = par_map_indices(output_size, eval);
+            let out_data = values
+                .par_iter()
+                .map(|value| value.steps[last_step])
+                .collect();
+            (out_data, Some(values))
+        } else {
+            let out_data =
+                par_map_indices(output_size, |stack, idx| eval(stack, idx).steps[last_step]);
+            (out_data, None)
+        }
+    }
+}
+
+impl LuminairOperator<FusedColumn, FusedTraceTables, ()> for LuminairFused {
+    fn process_trace(
+        &mut self,
+        inp: Vec<(InputTensor, ShapeTracker)>,
+        tables: &mut FusedTraceTables,
+        node_info: &NodeInfo,
+        _lookup: &mut (),
+    ) -> Vec<Tensor> {
+        let (out_data, intermediate_values) = self.compute(&inp, true);
+        let intermediate_values = intermediate_values.unwrap();
+
+        let output_size = inp[0].1.n_elements().to_usize().unwrap();
+        let node_id: BaseField = node_info.id.into();
+        // Unused input slots are read from no node with a zero multiplicity
+        let mut input_ids = [BaseField::zero(); MAX_FUSED_INPUTS];
+        let mut input_mults = [BaseField::zero(); MAX_FUSED_INPUTS];
+        for (i, input) in node_info.inputs.iter().enumerate() {
+            input_ids[i] = input.id.into();
+            input_mults[i] = -BaseField::one();
+        }
+
+        let out_mult = if node_info.output.is_final_output {
+            BaseField::zero()
+        } else {
+            BaseField::one() * BaseField::from_u32_unchecked(node_info.num_consumers)
+        };
+
+        tables.table_mut(&self.expr).table.par_extend(
+            intermediate_values
+                .into_par_iter()
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_LEN)
+                .map(|(idx, values)| {
+                    let is_last_idx: u32 = if idx == (output_size - 1) { 1 } else { 0 };
+
+                    FusedTraceTableRow {
+                        node_id,
+                        input_ids,
+                        idx: idx.into(),
+                        is_last_idx: (is_last_idx).into(),
+                        scale: M31::from_u32_unchecked(1 << DEFAULT_FP_SCALE),
+                        inputs: values.inputs.map(|value| value.to_m31()),
+                        steps: values.steps.map(|value| value.to_m31()),
+                        rems: values.rems.map(|value| value.to_m31()),
+                        input_mults,
+                        out_mult,
+                    }
+                }),
+        );
+
+        vec![Tensor::new(StwoData(Arc::new(out_data)))]
+    }
+}
+
+impl Operator for LuminairFused {
     fn process(&mut self, inp: Vec<(InputTensor, ShapeTracker)>) -> Vec<Tensor> {
         let (out_data, _) = self.compute(&inp, false);
         vec![Tensor::new(StwoData(Arc::new(out_data)))]
@@ -1859,6 +3199,22 @@ impl Compiler for PrimitiveCompiler {
                     <CopyToStwo as IntoOperator<InputsColumn, InputsTraceTable, ()>>::into_operator(
                         CopyToStwo::new(),
                     );
+            } else if op_ref.as_any().is::<LuminairSelect>() {
+                *op_ref = LuminairSelect::new().into_operator()
+            } else if op_ref.as_any().is::<LuminairGather>() {
+                *op_ref = LuminairGather::new().into_operator()
+            } else if let Some(im2col) = op_ref.as_any().downcast_ref::<LuminairIm2Col>() {
+                *op_ref = im2col.clone().into_operator()
+            } else if let Some(matmul) = op_ref.as_any().downcast_ref::<LuminairMatMul>() {
+                *op_ref = LuminairMatMul::new(matmul.0).into_operator()
+            } else if let Some(norm) = op_ref.as_any().downcast_ref::<LuminairNorm>() {
+                *op_ref = norm.clone().into_operator()
+            } else if let Some(mul_const) = op_ref.as_any().downcast_ref::<LuminairMulConst>() {
+                *op_ref = mul_const.clone().into_operator()
+            } else if let Some(add_const) = op_ref.as_any().downcast_ref::<LuminairAddConst>() {
+                *op_ref = add_const.clone().into_operator()
+            } else if let Some(fused) = op_ref.as_any().downcast_ref::<LuminairFused>() {
+                *op_ref = fused.clone().into_operator()
             } else if is::<luminal::op::Add>(op) {
                 *op_ref = LuminairAdd::new().into_operator()
             } else if is::<luminal::op::Mul>(op) {
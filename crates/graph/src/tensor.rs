@@ -0,0 +1,79 @@
+use luminal::prelude::*;
+
+use crate::op::prim::{LuminairGather, LuminairIm2Col};
+
+/// Trait for LuminAIR-specific tensor operations
+pub trait LuminairTensor {
+    /// Gathers rows of the table at the positions held by `indices`
+    ///
+    /// The first dimension of the table is indexed; the output has the shape of
+    /// `indices` followed by the remaining table dimensions, e.g. an embedding lookup
+    /// of token ids `(seq,)` into a `(vocab, dim)` table yields `(seq, dim)`.
+    /// Indices must be integers in `[0, vocab)`.
+    fn gather_rows(self, indices: GraphTensor) -> GraphTensor;
+
+    /// 2D convolution of a `(ch_in, h, w)` image with a `(ch_out, ch_in, kernel_h, kernel_w)` weight
+    ///
+    /// The image is unfolded into patches by an im2col view proven element by element, and the
+    /// convolution itself runs as a single fused matmul. `padding` zero-pads both sides of each
+    /// spatial dimension. The output has shape `(ch_out, out_h, out_w)`.
+    fn conv2d(
+        self,
+        weight: GraphTensor,
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> GraphTensor;
+}
+
+impl LuminairTensor for GraphTensor {
+    fn gather_rows(self, indices: GraphTensor) -> GraphTensor {
+        // The gather component reads both inputs element by element, so views must be materialized
+        let table = self.contiguous();
+        let indices = indices.contiguous();
+
+        let mut dims = indices.dims();
+        dims.extend(table.dims().into_iter().skip(1));
+
+        let id = self
+            .graph()
+            .add_op(LuminairGather::new())
+            .input(table.id, 0, table.shape)
+            .input(indices.id, 0, indices.shape)
+            .finish();
+        GraphTensor::from_id(id, ShapeTracker::new(dims), self.graph_ref)
+    }
+
+    fn conv2d(
+        self,
+        weight: GraphTensor,
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> GraphTensor {
+        let image = self.contiguous();
+        let w_shape = weight.shape.shape_usize();
+        let i_shape = image.shape.shape_usize();
+        assert_eq!(i_shape.len(), 3, "Conv2D expects a (ch_in, h, w) input");
+        assert_eq!(w_shape.len(), 4, "Conv2D expects a (ch_out, ch_in, kh, kw) weight");
+        assert_eq!(w_shape[1], i_shape[0], "Conv2D channel mismatch");
+        let (ch_out, ch_in, kh, kw) = (w_shape[0], w_shape[1], w_shape[2], w_shape[3]);
+
+        let op = LuminairIm2Col::new((kh, kw), stride, padding);
+        let (out_h, out_w) = op.output_size((i_shape[1], i_shape[2]));
+        let id = self
+            .graph()
+            .add_op(op)
+            .input(image.id, 0, image.shape)
+            .finish();
+        let patches = GraphTensor::from_id(
+            id,
+            ShapeTracker::new((ch_in * kh * kw, out_h * out_w)),
+            self.graph_ref,
+        );
+
+        weight
+            .contiguous()
+            .reshape((ch_out, ch_in * kh * kw))
+            .matmul(patches)
+            .reshape((ch_out, out_h, out_w))
+    }
+}
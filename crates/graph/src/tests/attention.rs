@@ -0,0 +1,214 @@
+use super::{assert_close_precision, random_vec_rng};
+use crate::graph::LuminairGraph;
+use crate::StwoCompiler;
+use luminair_prover::prover::prove;
+use luminair_verifier::verifier::verify;
+use luminal::prelude::*;
+use luminal_cpu::CPUCompiler;
+use luminal_nn::{LayerNorm, Linear};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Shape of the attention block under test
+#[derive(Clone, Copy)]
+struct BlockConfig {
+    seq: usize,
+    dim: usize,
+    heads: usize,
+    ff_dim: usize,
+    /// Whether each position only attends to itself and the previous ones
+    causal: bool,
+    /// Whether the attention is wrapped in norms, residual adds and a feed-forward layer
+    full_block: bool,
+}
+
+/// Multi-head self-attention with learned Q/K/V and output projections
+struct Attention {
+    q: Linear,
+    k: Linear,
+    v: Linear,
+    o: Linear,
+    heads: usize,
+}
+
+impl Attention {
+    fn new(cx: &mut Graph, dim: usize, heads: usize, rng: &mut StdRng) -> Self {
+        Self {
+            q: linear(cx, dim, dim, rng),
+            k: linear(cx, dim, dim, rng),
+            v: linear(cx, dim, dim, rng),
+            o: linear(cx, dim, dim, rng),
+            heads,
+        }
+    }
+
+    /// Attends over a `(seq, dim)` input, with an optional `(seq, seq)` 0/1 mask
+    fn forward(&self, x: GraphTensor, mask: Option<GraphTensor>) -> GraphTensor {
+        let (seq, dim) = (x.dims()[0].to_usize().unwrap(), x.dims()[1].to_usize().unwrap());
+        let head_dim = dim / self.heads;
+
+        // (seq, dim) -> (heads, seq, head_dim)
+        let split = |t: GraphTensor| t.reshape((seq, self.heads, head_dim)).permute((1, 0, 2));
+        let q = split(self.q.forward(x));
+        let k = split(self.k.forward(x));
+        let v = split(self.v.forward(x));
+
+        let scores = q.matmul(k.permute((0, 2, 1))) * (1.0 / (head_dim as f32).sqrt());
+        let weights = match mask {
+            Some(mask) => masked_softmax(scores, mask.expand(0, self.heads)),
+            None => scores.softmax(2),
+        };
+
+        // (heads, seq, head_dim) -> (seq, dim)
+        let out = weights
+            .matmul(v)
+            .permute((1, 0, 2))
+            .contiguous()
+            .reshape((seq, dim));
+        self.o.forward(out)
+    }
+}
+
+/// Softmax over the last dimension of `(heads, seq, seq)` scores, giving no weight to the
+/// positions where the mask is zero
+fn masked_softmax(scores: GraphTensor, mask: GraphTensor) -> GraphTensor {
+    let seq = scores.dims()[2];
+    let exp = (scores - scores.max_reduce(2).expand(2, seq)).exp() * mask;
+    exp / exp.sum_reduce(2).expand(2, seq)
+}
+
+/// Creates a linear layer with deterministic weights and bias
+fn linear(cx: &mut Graph, inp: usize, out: usize, rng: &mut StdRng) -> Linear {
+    let layer = Linear::new(inp, out, true, cx);
+    layer.weight.set(random_vec_rng(inp * out, rng, false));
+    if let Some(bias) = &layer.bias {
+        bias.set(random_vec_rng(out, rng, false));
+    }
+    layer
+}
+
+/// Creates a layer norm with deterministic affine weight (around one) and bias
+fn layer_norm(cx: &mut Graph, dim: usize, rng: &mut StdRng) -> LayerNorm {
+    let norm = LayerNorm::new(dim, true, true, true, 1e-5, cx);
+    if let Some(weight) = &norm.weight {
+        weight.set(
+            random_vec_rng(dim, rng, false)
+                .into_iter()
+                .map(|w| w + 1.0)
+                .collect::<Vec<_>>(),
+        );
+    }
+    if let Some(bias) = &norm.bias {
+        bias.set(random_vec_rng(dim, rng, false));
+    }
+    norm
+}
+
+/// Builds the block described by `cfg` on `x`, with weights drawn from a fixed seed so that
+/// the STWO and CPU graphs compute the same function
+fn build_block(cx: &mut Graph, x: GraphTensor, cfg: BlockConfig) -> GraphTensor {
+    let mut rng = StdRng::seed_from_u64(11);
+    let mask = cfg.causal.then(|| {
+        let mask_data = (0..cfg.seq * cfg.seq)
+            .map(|i| if i % cfg.seq <= i / cfg.seq { 1.0 } else { 0.0 })
+            .collect::<Vec<f32>>();
+        cx.tensor((cfg.seq, cfg.seq)).set(mask_data)
+    });
+
+    if !cfg.full_block {
+        let attention = Attention::new(cx, cfg.dim, cfg.heads, &mut rng);
+        return attention.forward(x, mask);
+    }
+
+    // Pre-norm transformer block
+    let norm1 = layer_norm(cx, cfg.dim, &mut rng);
+    let attention = Attention::new(cx, cfg.dim, cfg.heads, &mut rng);
+    let norm2 = layer_norm(cx, cfg.dim, &mut rng);
+    let ff1 = linear(cx, cfg.dim, cfg.ff_dim, &mut rng);
+    let ff2 = linear(cx, cfg.ff_dim, cfg.dim, &mut rng);
+
+    let h = x + attention.forward(norm1.forward(x), mask);
+    h + ff2.forward(ff1.forward(norm2.forward(h)).relu())
+}
+
+/// Runs the block through the StwoCompiler, proves and verifies it, and compares the
+/// output with the CPUCompiler
+fn run_block_test(cfg: BlockConfig, threshold: f32) {
+    let mut rng = StdRng::seed_from_u64(5);
+    let x_data = random_vec_rng(cfg.seq * cfg.dim, &mut rng, false);
+
+    // Graph setup
+    let mut cx = Graph::new();
+    let x = cx.tensor((cfg.seq, cfg.dim)).set(x_data.clone());
+    let mut out = build_block(&mut cx, x, cfg).retrieve();
+
+    // Compilation and execution using StwoCompiler
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+
+    // Q/K/V/O projections and the two attention products, plus the feed-forward layers
+    // and the two layer norms in a full block
+    let op_counter = &trace.metadata.execution_resources.op_counter;
+    assert_eq!(op_counter.matmul, if cfg.full_block { 8 } else { 6 });
+    assert_eq!(op_counter.norm, if cfg.full_block { 2 } else { 0 });
+
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+
+    // CPUCompiler comparison
+    let mut cx_cpu = Graph::new();
+    let x_cpu = cx_cpu.tensor((cfg.seq, cfg.dim)).set(x_data);
+    let mut out_cpu = build_block(&mut cx_cpu, x_cpu, cfg).retrieve();
+    cx_cpu.compile(<(GenericCompiler, CPUCompiler)>::default(), &mut out_cpu);
+    cx_cpu.execute();
+
+    assert_close_precision(&out.data(), &out_cpu.data(), threshold);
+}
+
+#[test]
+fn test_attention_single_head() {
+    run_block_test(
+        BlockConfig {
+            seq: 4,
+            dim: 4,
+            heads: 1,
+            ff_dim: 0,
+            causal: false,
+            full_block: false,
+        },
+        5e-2,
+    );
+}
+
+#[test]
+fn test_attention_multi_head_causal() {
+    run_block_test(
+        BlockConfig {
+            seq: 4,
+            dim: 8,
+            heads: 2,
+            ff_dim: 0,
+            causal: true,
+            full_block: false,
+        },
+        5e-2,
+    );
+}
+
+#[test]
+fn test_transformer_block() {
+    run_block_test(
+        BlockConfig {
+            seq: 4,
+            dim: 8,
+            heads: 2,
+            ff_dim: 16,
+            causal: true,
+            full_block: true,
+        },
+        1e-1,
+    );
+}
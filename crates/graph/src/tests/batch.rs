@@ -0,0 +1,151 @@
+use super::random_vec_rng;
+use crate::graph::{BatchInputs, LuminairGraph};
+use crate::StwoCompiler;
+use luminair_air::segment::SegmentBoundary;
+use luminair_prover::{
+    bundle::{ProofBundle, PublicIo},
+    prover::prove_with_io,
+    LuminairProof,
+};
+use luminair_utils::LuminairError;
+use luminair_verifier::verifier::{verify_bundle, verify_with_io};
+use luminal::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+use stwo::core::fields::m31::M31;
+
+const BATCH_SIZE: usize = 3;
+
+fn f(a: GraphTensor, b: GraphTensor) -> GraphTensor {
+    ((a * b).exp2() + a).sum_reduce(1)
+}
+
+/// Random values of `a` and `b` for each execution of the batch
+fn batch_data() -> Vec<(Vec<f32>, Vec<f32>)> {
+    let mut rng = StdRng::seed_from_u64(11);
+    (0..BATCH_SIZE)
+        .map(|_| {
+            (
+                random_vec_rng(4 * 4, &mut rng, false),
+                random_vec_rng(4 * 4, &mut rng, false),
+            )
+        })
+        .collect()
+}
+
+/// Output of a single execution of `f`, traced on its own
+fn single_output(a_data: &[f32], b_data: &[f32]) -> Vec<f32> {
+    let mut cx = Graph::new();
+    let a = cx.tensor((4, 4)).set(a_data.to_vec());
+    let b = cx.tensor((4, 4)).set(b_data.to_vec());
+    let mut out = f(a, b).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    cx.gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    out.data()
+}
+
+#[test]
+fn test_batch_proof() {
+    let data = batch_data();
+
+    let mut cx = Graph::new();
+    let mut a = cx.tensor((4, 4)).set(data[0].0.clone());
+    let mut b = cx.tensor((4, 4)).set(data[0].1.clone());
+    let mut out = f(a, b).retrieve();
+    cx.compile(
+        <(GenericCompiler, StwoCompiler)>::default(),
+        (&mut a, &mut b, &mut out),
+    );
+
+    let batch = data
+        .iter()
+        .map(|(a_data, b_data)| vec![(a.id, a_data.clone()), (b.id, b_data.clone())])
+        .collect::<Vec<BatchInputs>>();
+    let mut settings = cx.gen_batch_circuit_settings(&batch).unwrap();
+    let trace = cx
+        .gen_batch_trace(&batch, &mut settings)
+        .expect("Trace generation failed");
+    let op_counter = &trace.pie.metadata.execution_resources.op_counter;
+    assert_eq!(op_counter.exp2, BATCH_SIZE);
+
+    // Each execution computes what it would on its own
+    assert_eq!(trace.outputs.len(), BATCH_SIZE);
+    for ((a_data, b_data), outputs) in data.iter().zip(&trace.outputs) {
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].0, out.id);
+        assert_eq!(outputs[0].1, single_output(a_data, b_data));
+    }
+
+    // One bound IO section per execution, with the inputs in the order they were given
+    assert_eq!(trace.io.len(), BATCH_SIZE);
+    for (io, outputs) in trace.io.iter().zip(&trace.outputs) {
+        assert_eq!(io.inputs.len(), 2);
+        assert_eq!(io.outputs.len(), 1);
+        assert_eq!(io.outputs[0].to_f32(), outputs[0].1);
+    }
+
+    // A single proof, only verifying against the inputs and outputs of every execution
+    let proof =
+        prove_with_io(trace.pie, settings.clone(), &trace.io).expect("Proof generation failed");
+    let encoded = proof.to_bincode().unwrap();
+    let tampers: [fn(&mut Vec<SegmentBoundary>); 4] = [
+        |io| io[1].outputs[0].values[0] += M31::from(1),
+        |io| io[2].inputs[0].tensor.values[0] += M31::from(1),
+        |io| io.swap(0, 1),
+        |io| {
+            io.pop();
+        },
+    ];
+    for tamper in tampers {
+        let mut tampered = trace.io.clone();
+        tamper(&mut tampered);
+        let proof = LuminairProof::from_bincode(&encoded).unwrap();
+        assert!(verify_with_io(proof, settings.clone(), &tampered).is_err());
+    }
+
+    let public_io = trace
+        .io
+        .iter()
+        .map(|io| {
+            let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+            PublicIo::new(names(&["a", "b"]), names(&["out"]), io.clone()).unwrap()
+        })
+        .collect::<Vec<_>>();
+    let bundle = ProofBundle::new(proof, settings.clone()).with_public_batch_io(public_io.clone());
+    let decoded = ProofBundle::from_bytes(&bundle.to_bytes().unwrap()).unwrap();
+    assert_eq!(decoded.public_io, public_io);
+    verify_bundle(decoded, None).expect("Proof verification failed");
+
+    // The inputs of the caller are left as they were
+    out.drop();
+    cx.gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    assert_eq!(out.data(), trace.outputs[0][0].1);
+}
+
+#[test]
+fn test_batch_rejects_invalid_inputs() {
+    let mut cx = Graph::new();
+    let mut a = cx.tensor((4, 4)).set(vec![0.5; 16]);
+    let mut out = (a.exp2() + a).retrieve();
+    cx.compile(
+        <(GenericCompiler, StwoCompiler)>::default(),
+        (&mut a, &mut out),
+    );
+    let mut settings = cx.gen_circuit_settings();
+
+    assert!(matches!(
+        cx.gen_batch_trace(&[], &mut settings),
+        Err(LuminairError::InvalidBatch(_))
+    ));
+
+    // Only input nodes can be set
+    let batch = vec![vec![(out.id, vec![0.5; 16])]];
+    assert!(matches!(
+        cx.gen_batch_trace(&batch, &mut settings),
+        Err(LuminairError::InvalidBatch(_))
+    ));
+}
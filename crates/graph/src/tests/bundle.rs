@@ -0,0 +1,167 @@
+use super::random_vec_rng;
+use crate::graph::LuminairGraph;
+use crate::StwoCompiler;
+use luminair_air::settings::CircuitSettings;
+use luminair_prover::{
+    bundle::{BundleMetadata, BundleSettings, ProofBundle, PublicIo, BUNDLE_VERSION},
+    prover::prove_with_io,
+};
+use luminair_utils::LuminairError;
+use luminair_verifier::verifier::verify_bundle;
+use luminal::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+use stwo::core::fields::m31::M31;
+
+/// Proves a small graph, returning its bundle with embedded settings and the settings
+fn bundle() -> (ProofBundle, CircuitSettings) {
+    let mut rng = StdRng::seed_from_u64(11);
+    let a_data = random_vec_rng(4 * 4, &mut rng, false);
+
+    let mut cx = Graph::new();
+    let mut a = cx.tensor((4, 4)).set(a_data);
+    let mut out = (a.exp2() + a).retrieve();
+    cx.compile(
+        <(GenericCompiler, StwoCompiler)>::default(),
+        (&mut a, &mut out),
+    );
+
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let (trace, boundary) = cx
+        .gen_trace_with_io(&mut settings, &[a.id])
+        .expect("Trace generation failed");
+    let metadata = BundleMetadata::from_pie(&trace);
+    let proof = prove_with_io(trace, settings.clone(), std::slice::from_ref(&boundary))
+        .expect("Proof generation failed");
+
+    let public_io =
+        PublicIo::new(vec!["a".to_string()], vec!["out".to_string()], boundary).unwrap();
+    assert_eq!(public_io.outputs()[0].data, out.data());
+    let bundle = ProofBundle::new(proof, settings.clone())
+        .with_metadata(metadata)
+        .with_public_io(public_io);
+    (bundle, settings)
+}
+
+/// Settings differing from the ones `bundle` was proven with
+fn other_settings(settings: &CircuitSettings) -> CircuitSettings {
+    let mut other = settings.clone();
+    other.lookups.exp2 = None;
+    other
+}
+
+#[test]
+fn test_bundle_round_trip() {
+    let (bundle, settings) = bundle();
+    let bytes = bundle.to_bytes().unwrap();
+    assert!(ProofBundle::is_bundle(&bytes));
+
+    let decoded = ProofBundle::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.public_io, bundle.public_io);
+    let op_counter = decoded.metadata.op_counter.clone().unwrap();
+    assert_eq!((op_counter.exp2, op_counter.add), (1, 1));
+
+    // Self-contained, or checked against the settings it was proven with
+    verify_bundle(
+        ProofBundle::from_bytes(&bytes).unwrap(),
+        Some(settings.clone()),
+    )
+    .expect("Proof verification failed");
+    verify_bundle(decoded, None).expect("Proof verification failed");
+}
+
+#[test]
+fn test_bundle_settings_digest() {
+    let (bundle, settings) = bundle();
+    let bundle = ProofBundle::with_settings_digest(bundle.proof, &settings)
+        .unwrap()
+        .with_public_batch_io(bundle.public_io);
+    assert!(matches!(bundle.settings, BundleSettings::Digest(_)));
+    let bytes = bundle.to_bytes().unwrap();
+
+    let decoded = ProofBundle::from_bytes(&bytes).unwrap();
+    assert!(matches!(
+        decoded.resolve_settings(None),
+        Err(LuminairError::InvalidBundle(_))
+    ));
+    assert!(matches!(
+        decoded.resolve_settings(Some(other_settings(&settings))),
+        Err(LuminairError::SettingsMismatch)
+    ));
+    verify_bundle(decoded, Some(settings)).expect("Proof verification failed");
+}
+
+#[test]
+fn test_bundle_rejects_mismatched_settings() {
+    let (bundle, settings) = bundle();
+    assert!(matches!(
+        verify_bundle(bundle, Some(other_settings(&settings))),
+        Err(LuminairError::SettingsMismatch)
+    ));
+}
+
+#[test]
+fn test_bundle_rejects_other_io() {
+    let (bundle, _) = bundle();
+    let bytes = bundle.to_bytes().unwrap();
+
+    let mut other_output = ProofBundle::from_bytes(&bytes).unwrap();
+    other_output.public_io[0].boundary.outputs[0].values[0] += M31::from(1);
+    assert!(verify_bundle(other_output, None).is_err());
+
+    let mut other_input = ProofBundle::from_bytes(&bytes).unwrap();
+    other_input.public_io[0].boundary.inputs[0].tensor.values[0] += M31::from(1);
+    assert!(verify_bundle(other_input, None).is_err());
+
+    let mut unbound = ProofBundle::from_bytes(&bytes).unwrap();
+    unbound.public_io.clear();
+    assert!(verify_bundle(unbound, None).is_err());
+
+    let mut unnamed = ProofBundle::from_bytes(&bytes).unwrap();
+    unnamed.public_io[0].output_names.clear();
+    assert!(matches!(
+        verify_bundle(unnamed, None),
+        Err(LuminairError::InvalidPublicIo(_))
+    ));
+}
+
+#[test]
+fn test_bundle_rejects_invalid_headers() {
+    let (bundle, _) = bundle();
+    let bytes = bundle.to_bytes().unwrap();
+
+    // Bare bincode proofs aren't bundles
+    let proof = bundle.proof.to_bincode().unwrap();
+    assert!(!ProofBundle::is_bundle(&proof));
+    assert!(matches!(
+        ProofBundle::from_bytes(&proof),
+        Err(LuminairError::InvalidBundle(_))
+    ));
+
+    let mut newer = bytes.clone();
+    newer[8..12].copy_from_slice(&(BUNDLE_VERSION + 1).to_le_bytes());
+    assert!(matches!(
+        ProofBundle::from_bytes(&newer),
+        Err(LuminairError::UnsupportedBundleVersion { found, supported })
+            if found == BUNDLE_VERSION + 1 && supported == BUNDLE_VERSION
+    ));
+
+    let mut unknown_hasher = bytes.clone();
+    unknown_hasher[12] = 0xff;
+    assert!(matches!(
+        ProofBundle::from_bytes(&unknown_hasher),
+        Err(LuminairError::InvalidBundle(_))
+    ));
+
+    let mut corrupted = bytes.clone();
+    *corrupted.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        ProofBundle::from_bytes(&corrupted),
+        Err(LuminairError::InvalidBundle(_))
+    ));
+
+    assert!(matches!(
+        ProofBundle::from_bytes(&bytes[..20]),
+        Err(LuminairError::InvalidBundle(_))
+    ));
+}
@@ -0,0 +1,75 @@
+use super::random_vec_rng;
+use crate::graph::LuminairGraph;
+use crate::StwoCompiler;
+use luminair_air::settings::CircuitSettings;
+use luminair_prover::{compact::Compression, prover::prove, LuminairProof};
+use luminair_verifier::verifier::verify;
+use luminal::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+use stwo::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+
+/// Proves a graph using a few components and a lookup
+fn proof() -> (LuminairProof<Blake2sMerkleHasher>, CircuitSettings) {
+    let mut rng = StdRng::seed_from_u64(5);
+    let a_data = random_vec_rng(8 * 8, &mut rng, false);
+    let b_data = random_vec_rng(8 * 8, &mut rng, false);
+
+    let mut cx = Graph::new();
+    let a = cx.tensor((8, 8)).set(a_data);
+    let b = cx.tensor((8, 8)).set(b_data);
+    let mut out = ((a * b).exp2() + a).sum_reduce(1).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    (proof, settings)
+}
+
+/// Decodes a compact proof, checks it is unchanged and still verifies
+fn check_round_trip(
+    proof: &LuminairProof<Blake2sMerkleHasher>,
+    settings: &CircuitSettings,
+    data: &[u8],
+) {
+    let decoded = LuminairProof::from_compact(data).expect("Decoding failed");
+    assert_eq!(decoded.to_bincode().unwrap(), proof.to_bincode().unwrap());
+    verify(decoded, settings.clone()).expect("Proof verification failed");
+}
+
+#[test]
+fn test_compact_round_trip() {
+    let (proof, settings) = proof();
+    let bincode = proof.to_bincode().unwrap();
+
+    let compact = proof.to_compact(Compression::None).unwrap();
+    assert!(compact.len() < bincode.len());
+    check_round_trip(&proof, &settings, &compact);
+
+    let compressed = proof.to_compact(Compression::Zstd(19)).unwrap();
+    assert!(compressed.len() < compact.len());
+    check_round_trip(&proof, &settings, &compressed);
+}
+
+#[test]
+fn test_compact_rejects_invalid_data() {
+    let (proof, _) = proof();
+    let compact = proof.to_compact(Compression::None).unwrap();
+
+    let mut newer = compact.clone();
+    newer[0] += 1;
+    assert!(LuminairProof::from_compact(&newer).is_err());
+
+    let mut unknown_compression = compact.clone();
+    unknown_compression[1] = 0xff;
+    assert!(LuminairProof::from_compact(&unknown_compression).is_err());
+
+    assert!(LuminairProof::from_compact(&compact[..compact.len() / 2]).is_err());
+
+    let mut trailing = compact.clone();
+    trailing.push(0);
+    assert!(LuminairProof::from_compact(&trailing).is_err());
+}
@@ -0,0 +1,120 @@
+use super::random_vec_rng;
+use crate::graph::{BatchInputs, LuminairGraph};
+use crate::StwoCompiler;
+use luminair_air::utils::calculate_log_size;
+use luminal::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn f(a: GraphTensor, b: GraphTensor, w: GraphTensor) -> GraphTensor {
+    (a * b + a).exp2().sum_reduce(1) + a.matmul(w).max_reduce(1)
+}
+
+#[test]
+fn test_estimate_matches_trace() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut cx = Graph::new();
+    let mut a = cx.tensor((4, 5));
+    let mut b = cx.tensor((4, 5));
+    let mut w = cx.tensor((5, 3));
+    let mut out = f(a, b, w).retrieve();
+    cx.compile(
+        <(GenericCompiler, StwoCompiler)>::default(),
+        (&mut a, &mut b, &mut w, &mut out),
+    );
+
+    let inputs: BatchInputs = vec![
+        (a.id, random_vec_rng(4 * 5, &mut rng, false)),
+        (b.id, random_vec_rng(4 * 5, &mut rng, false)),
+        (w.id, random_vec_rng(5 * 3, &mut rng, false)),
+    ];
+    let batch = [inputs];
+    let mut settings = cx.gen_batch_circuit_settings(&batch).unwrap();
+    let estimate = cx.estimate(Some(&settings)).unwrap();
+    let trace = cx
+        .gen_batch_trace(&batch, &mut settings)
+        .expect("Trace generation failed");
+
+    let resources = &trace.pie.metadata.execution_resources;
+    assert_eq!(
+        estimate.execution_resources.op_counter,
+        resources.op_counter
+    );
+    assert_eq!(
+        estimate.execution_resources.max_log_size,
+        resources.max_log_size
+    );
+
+    // The components are the shards of the traced tables, lookup tables included
+    let mut traced_rows = trace
+        .pie
+        .trace_tables
+        .into_iter()
+        .flat_map(|table| table.shard(resources.max_log_size))
+        .map(|table| table.n_rows())
+        .collect::<Vec<_>>();
+    let mut estimated_rows = estimate
+        .components
+        .iter()
+        .map(|component| component.rows)
+        .collect::<Vec<_>>();
+    traced_rows.sort();
+    estimated_rows.sort();
+    assert_eq!(estimated_rows, traced_rows);
+    assert!(estimate
+        .components
+        .iter()
+        .any(|component| component.component == "exp2_lookup"));
+}
+
+#[test]
+fn test_estimate_without_inputs() {
+    let mut cx = Graph::new();
+    let a = cx.tensor((4, 5));
+    let w = cx.tensor((5, 3));
+    let mut out = a.matmul(w).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+    // No lookup table is needed, so none is projected
+    let estimate = cx.estimate(None).unwrap();
+
+    let op_counter = &estimate.execution_resources.op_counter;
+    assert_eq!(op_counter.inputs, 2);
+    assert_eq!(op_counter.matmul, 1);
+    let rows = |component: &str| -> usize {
+        estimate
+            .ops
+            .iter()
+            .filter(|op| op.component == component)
+            .map(|op| op.rows)
+            .sum()
+    };
+    assert_eq!(rows("inputs"), 4 * 5 + 5 * 3);
+    // One row per step of each of the 4 * 3 dot products of 5 values
+    assert_eq!(rows("matmul"), 4 * 3 * 5);
+    assert!(estimate.preprocessed_log_sizes.is_empty());
+    assert!(estimate.prover_memory > 0 && estimate.proof_size > 0);
+}
+
+#[test]
+fn test_estimate_projects_lookup_tables() {
+    let mut cx = Graph::new();
+    let a = cx.tensor((4, 5));
+    let b = cx.tensor((4, 5));
+    let w = cx.tensor((5, 3));
+    let mut out = f(a, b, w).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+    // One lookup entry per value read by the exp2 operator
+    let estimate = cx.estimate(None).unwrap();
+    let lookup = estimate
+        .components
+        .iter()
+        .find(|component| component.component == "exp2_lookup")
+        .expect("The exp2 lookup table is projected");
+    assert_eq!(lookup.log_size, calculate_log_size(4 * 5));
+    assert_eq!(estimate.preprocessed_log_sizes.len(), 2);
+    assert!(estimate
+        .components
+        .iter()
+        .all(|component| component.component != "sin_lookup"));
+}
@@ -0,0 +1,132 @@
+//! Golden proofs pinning the serialized format of proofs and settings, and the Fiat-Shamir
+//! transcript
+//!
+//! Each case has its circuit settings and proof checked in under `fixtures/golden/<case>`.
+//! Stored proofs must keep deserializing and verifying, and proving the case again must
+//! produce the exact same bytes. When a change of format or transcript is intended, regenerate
+//! the fixtures with `LUMINAIR_BLESS=1 cargo test -p luminair_graph golden` and commit them.
+
+use std::path::PathBuf;
+
+use crate::graph::LuminairGraph;
+use crate::StwoCompiler;
+use luminair_air::settings::CircuitSettings;
+use luminair_prover::{prover::prove, LuminairProof};
+use luminair_verifier::verifier::verify;
+use luminal::prelude::*;
+use stwo::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+
+/// A small graph proven by a golden fixture
+struct GoldenCase {
+    name: &'static str,
+    shape: (usize, usize),
+    graph: fn(GraphTensor, GraphTensor) -> GraphTensor,
+}
+
+const CASES: [GoldenCase; 3] = [
+    GoldenCase {
+        name: "add_mul",
+        shape: (4, 4),
+        graph: |a, b| a * b + a,
+    },
+    GoldenCase {
+        name: "exp2_sum_reduce",
+        shape: (4, 8),
+        graph: |a, b| (a.exp2() + b).sum_reduce(1),
+    },
+    GoldenCase {
+        name: "matmul_sin",
+        shape: (4, 4),
+        graph: |a, b| a.matmul(b).sin(),
+    },
+];
+
+/// Fixed inputs, independent of any random number generator
+fn input(len: usize, offset: usize) -> Vec<f32> {
+    (0..len)
+        .map(|i| ((i * 7 + offset) % 17) as f32 / 16.0 - 0.5)
+        .collect()
+}
+
+impl GoldenCase {
+    fn dir(&self) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join("golden")
+            .join(self.name)
+    }
+
+    /// Generates the settings of the case and proves it
+    fn prove(&self) -> (CircuitSettings, LuminairProof<Blake2sMerkleHasher>) {
+        let (rows, cols) = self.shape;
+        let mut cx = Graph::new();
+        let a = cx.tensor((rows, cols)).set(input(rows * cols, 0));
+        let b = cx.tensor((rows, cols)).set(input(rows * cols, 5));
+        let mut out = (self.graph)(a, b).retrieve();
+        cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+        let mut settings = cx.gen_circuit_settings();
+        out.drop();
+        let trace = cx
+            .gen_trace(&mut settings)
+            .expect("Trace generation failed");
+        let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+        (settings, proof)
+    }
+
+    /// Loads the checked-in settings and proof bytes, rewriting them first when blessing
+    ///
+    /// Missing fixtures fail the test rather than being written, so that a fixture that was
+    /// never committed can't pass silently.
+    fn load(&self) -> (Vec<u8>, Vec<u8>) {
+        let dir = self.dir();
+        let (settings_path, proof_path) = (dir.join("settings.bin"), dir.join("proof.bin"));
+        if std::env::var_os("LUMINAIR_BLESS").is_some() {
+            let (settings, proof) = self.prove();
+            std::fs::create_dir_all(&dir).unwrap();
+            settings.to_bincode_file(&settings_path).unwrap();
+            proof.to_bincode_file(&proof_path).unwrap();
+        }
+        let read = |path: &PathBuf| {
+            std::fs::read(path).unwrap_or_else(|e| {
+                panic!(
+                    "missing golden fixture {}: {}, generate it with LUMINAIR_BLESS=1 and \
+                     commit it",
+                    path.display(),
+                    e
+                )
+            })
+        };
+        (read(&settings_path), read(&proof_path))
+    }
+}
+
+// Both checks share one test so that fixtures written when blessing aren't read concurrently
+#[test]
+fn test_golden_proofs() {
+    for case in &CASES {
+        let (golden_settings, golden_proof) = case.load();
+
+        // Stored fixtures still deserialize and verify
+        let settings = CircuitSettings::from_bincode(&golden_settings)
+            .unwrap_or_else(|e| panic!("`{}` settings no longer deserialize: {}", case.name, e));
+        let proof = LuminairProof::from_bincode(&golden_proof)
+            .unwrap_or_else(|e| panic!("`{}` proof no longer deserializes: {}", case.name, e));
+        verify(proof, settings)
+            .unwrap_or_else(|e| panic!("`{}` proof no longer verifies: {}", case.name, e));
+
+        // Proving again gives the same bytes
+        let (settings, proof) = case.prove();
+        assert!(
+            settings.to_bincode().unwrap() == golden_settings,
+            "`{}` settings changed, run with LUMINAIR_BLESS=1 if intended",
+            case.name
+        );
+        assert!(
+            proof.to_bincode().unwrap() == golden_proof,
+            "`{}` proof changed (format or transcript order), run with LUMINAIR_BLESS=1 if \
+             intended",
+            case.name
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use super::random_vec_rng;
+use crate::graph::LuminairGraph;
+use crate::ir::{GraphIr, OpIr};
+use crate::StwoCompiler;
+use luminair_prover::prover::prove;
+use luminair_utils::LuminairError;
+use luminair_verifier::verifier::verify;
+use luminal::{op::Function, prelude::*};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Builds and compiles a small model with a runtime input `x` and a shipped weight,
+/// returning the graph, the input, the output and the input data
+fn compiled_model() -> (Graph, GraphTensor, GraphTensor, Vec<f32>) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let x_data = random_vec_rng(3 * 4, &mut rng, false);
+    let w_data = random_vec_rng(4 * 5, &mut rng, false);
+
+    let mut cx = Graph::new();
+    let x = cx.named_tensor("x", (3, 4));
+    let w = cx.named_tensor("w", (4, 5)).set(w_data);
+    let mut out = (x.matmul(w) * 0.5).exp2().sum_reduce(1).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+    (cx, x, out, x_data)
+}
+
+/// Sets the value of the runtime input named `name`
+fn set_input(graph: &mut Graph, name: &str, data: Vec<f32>) {
+    for node in graph.node_indices().collect::<Vec<_>>() {
+        if let Some(function) = graph
+            .graph
+            .node_weight_mut(node)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<Function>()
+        {
+            if function.0 == name {
+                let data = data.clone();
+                function.1 = Box::new(move |_| vec![Tensor::new(data.clone())]);
+            }
+        }
+    }
+}
+
+/// Proves and verifies the execution of a graph, returning its single output
+fn prove_and_verify(graph: &mut Graph) -> Vec<f32> {
+    let mut settings = graph.gen_circuit_settings();
+    let outputs = graph.to_retrieve.keys().copied().collect::<Vec<_>>();
+    graph.drop_tensors(outputs.clone());
+    let trace = graph
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+
+    graph
+        .get_tensor_ref(outputs[0], 0)
+        .unwrap()
+        .downcast_ref::<Vec<f32>>()
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn test_ir_round_trip() {
+    let (mut cx, x, out, x_data) = compiled_model();
+    let ir = GraphIr::from_graph(&cx, &[x.id]).expect("IR generation failed");
+
+    // Only `x` is left to be set at runtime, the weight ships with the graph
+    let input_name = ir
+        .nodes
+        .iter()
+        .find_map(|node| match &node.op {
+            OpIr::Input { name, value: None } => Some(name.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert!(ir.nodes.iter().any(|node| matches!(
+        &node.op,
+        OpIr::Input { value: Some(value), .. } if value.len() == 4 * 5
+    )));
+    assert!(ir.nodes.iter().any(|node| node.op == OpIr::MatMul { dim: 1 }));
+
+    let from_json = GraphIr::from_json(&ir.to_json().unwrap()).unwrap();
+    let from_bincode = GraphIr::from_bincode(&ir.to_bincode().unwrap()).unwrap();
+    assert_eq!(from_json, ir);
+    assert_eq!(from_bincode, ir);
+
+    // The original graph
+    set_input(&mut cx, &input_name, x_data.clone());
+    let expected = prove_and_verify(&mut cx);
+    assert_eq!(expected, out.data());
+
+    // The graph rebuilt from the IR computes the same output
+    let mut loaded = from_bincode.to_graph().expect("Graph loading failed");
+    set_input(&mut loaded, &input_name, x_data);
+    assert_eq!(prove_and_verify(&mut loaded), expected);
+}
+
+#[test]
+fn test_ir_rejects_uncompiled_graph() {
+    let mut cx = Graph::new();
+    let x = cx.named_tensor("x", (2, 2));
+    let _ = (x + x).retrieve();
+
+    assert!(GraphIr::from_graph(&cx, &[x.id]).is_err());
+}
+
+#[test]
+fn test_ir_rejects_unknown_version() {
+    let (cx, x, _, _) = compiled_model();
+    let mut ir = GraphIr::from_graph(&cx, &[x.id]).unwrap();
+    ir.version += 1;
+
+    assert!(ir.to_graph().is_err());
+}
+
+#[test]
+fn test_ir_rejects_unset_weight() {
+    let mut cx = Graph::new();
+    let x = cx.named_tensor("x", (2, 2));
+    let w = cx.named_tensor("w", (2, 2));
+    let mut out = (x * w).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+    // `w` is neither a runtime input nor set, so it has no value to ship
+    assert!(matches!(
+        GraphIr::from_graph(&cx, &[x.id]),
+        Err(LuminairError::SerializationError(_))
+    ));
+}
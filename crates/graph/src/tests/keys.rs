@@ -0,0 +1,126 @@
+use super::random_vec_rng;
+use crate::graph::LuminairGraph;
+use crate::StwoCompiler;
+use luminair_air::{pie::LuminairPie, settings::CircuitSettings};
+use luminair_prover::{
+    keys::{ProvingKey, VerifyingKey},
+    prover::{prove, prove_with_key},
+    LuminairProof,
+};
+use luminair_utils::LuminairError;
+use luminair_verifier::verifier::verify_with_key;
+use luminal::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+use stwo::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+
+/// Executes `f(a)` on a random tensor, returning its PIE and circuit settings
+fn pie(f: fn(GraphTensor) -> GraphTensor, seed: u64) -> (LuminairPie, CircuitSettings) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let a_data = random_vec_rng(4 * 4, &mut rng, false);
+
+    let mut cx = Graph::new();
+    let a = cx.tensor((4, 4)).set(a_data);
+    let mut out = f(a).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    (trace, settings)
+}
+
+/// Proves `f(a)` on a random tensor, returning the proof and its circuit settings
+fn proof(
+    f: fn(GraphTensor) -> GraphTensor,
+) -> (LuminairProof<Blake2sMerkleHasher>, CircuitSettings) {
+    let (trace, settings) = pie(f, 3);
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    (proof, settings)
+}
+
+#[test]
+fn test_verify_with_key() {
+    let (proof, settings) = proof(|a| (a.exp2() + a.sin()).sum_reduce(1));
+    let verifying_key = VerifyingKey::new(&settings);
+    assert_eq!(verifying_key.preprocessed_columns.len(), 4);
+    assert_eq!(proof.proof.commitments[0], verifying_key.preprocessed_root);
+
+    let bytes = verifying_key.to_bincode().unwrap();
+    let verifying_key = VerifyingKey::from_bincode(&bytes).unwrap();
+    verify_with_key(proof, &verifying_key).expect("Proof verification failed");
+}
+
+#[test]
+fn test_verify_with_key_without_lookups() {
+    let (proof, settings) = proof(|a| a * a + a);
+    let verifying_key = VerifyingKey::new(&settings);
+    verify_with_key(proof, &verifying_key).expect("Proof verification failed");
+}
+
+#[test]
+fn test_verify_with_key_rejects_wrong_root() {
+    let (proof, settings) = proof(|a| a.exp2());
+    let mut verifying_key = VerifyingKey::new(&settings);
+    verifying_key.preprocessed_root.0[0] ^= 1;
+    assert!(matches!(
+        verify_with_key(proof, &verifying_key),
+        Err(LuminairError::VerifyingKeyMismatch)
+    ));
+}
+
+#[test]
+fn test_prove_with_key() {
+    // Without lookups, the settings don't depend on the inputs
+    let f: fn(GraphTensor) -> GraphTensor = |a| (a * a + a).sum_reduce(0);
+    let (_, settings) = pie(f, 0);
+    let proving_key = ProvingKey::new(&settings, 10);
+    let verifying_key = proving_key.verifying_key();
+
+    for seed in 0..3 {
+        let (trace, _) = pie(f, seed);
+        let proof = prove_with_key(trace, &proving_key).expect("Proof generation failed");
+        verify_with_key(proof, &verifying_key).expect("Proof verification failed");
+    }
+}
+
+#[test]
+fn test_proving_key_round_trip() {
+    let f: fn(GraphTensor) -> GraphTensor = |a| (a.exp2() + a.sin()).sum_reduce(1);
+    let (proof, settings) = proof(f);
+    let proving_key = ProvingKey::new(&settings, 10);
+
+    let bytes = proving_key.to_bincode().unwrap();
+    let proving_key = ProvingKey::from_bincode(&bytes).unwrap();
+    assert!(proving_key.max_log_size() >= 10);
+
+    // Cached and recomputed setups lead to the same proof
+    let (trace, _) = pie(f, 3);
+    let cached = prove_with_key(trace, &proving_key).expect("Proof generation failed");
+    assert_eq!(cached.to_bincode().unwrap(), proof.to_bincode().unwrap());
+    verify_with_key(cached, &proving_key.verifying_key()).expect("Proof verification failed");
+}
+
+#[test]
+fn test_proving_key_rejects_wrong_root() {
+    let (_, settings) = pie(|a| a.exp2(), 0);
+    let mut bytes = ProvingKey::new(&settings, 10).to_bincode().unwrap();
+    // The root of the preprocessed commitment is serialized last
+    *bytes.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        ProvingKey::from_bincode(&bytes),
+        Err(LuminairError::SerializationError(_))
+    ));
+}
+
+#[test]
+fn test_prove_with_key_too_small() {
+    let (trace, settings) = pie(|a| a * a, 0);
+    let max_log_size = trace.metadata.execution_resources.max_log_size;
+    let proving_key = ProvingKey::new(&settings, max_log_size - 1);
+    assert!(matches!(
+        prove_with_key(trace, &proving_key),
+        Err(LuminairError::ProvingKeyTooSmall { .. })
+    ));
+}
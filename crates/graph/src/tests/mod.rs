@@ -1,8 +1,20 @@
 use luminal::{graph::Graph, op::Operator};
 use rand::Rng;
 
+mod attention;
+mod batch;
+mod bundle;
+mod compact;
+mod estimate;
 mod expansions;
+mod golden;
+mod ir;
+mod keys;
 mod ops;
+mod packed;
+mod parallel;
+mod segment;
+mod shard;
 
 #[macro_export]
 macro_rules! single_unary_test {
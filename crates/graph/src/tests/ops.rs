@@ -1,9 +1,17 @@
-use super::{assert_close, random_vec_rng};
+use super::{assert_close, assert_close_precision, random_vec_rng};
 use crate::graph::LuminairGraph;
+use crate::op::other::TraceCostCompiler;
+use crate::tensor::LuminairTensor;
 use crate::StwoCompiler;
 use crate::{binary_test, unary_test};
-use luminair_prover::prover::prove;
-use luminair_verifier::verifier::verify;
+use luminair_air::{components::fused::expr::MAX_FUSED_INPUTS, pie::TraceTable};
+use luminair_prover::{
+    keys::ProvingKey,
+    prover::{prove, prove_with_key},
+    LuminairProof,
+};
+use luminair_utils::LuminairError;
+use luminair_verifier::verifier::{verify, verify_with_key};
 use luminal::prelude::*;
 use luminal_cpu::CPUCompiler;
 use rand::{rngs::StdRng, SeedableRng};
@@ -24,6 +32,196 @@ binary_test!(|a, b| a + b, test_add, f32, false);
 binary_test!(|a, b| a * b, test_mul, f32, false);
 // binary_test!(|a, b| a % b, test_rem, f32, true); // TODO: Uncoment once LuminAIR supports dynamic fixed points.
 
+// =============== CONSTANT OPERAND ===============
+
+#[test]
+fn test_const_operand() {
+    // Graph setup
+    let mut cx = Graph::new();
+    let mut rng = StdRng::seed_from_u64(1);
+    let data = random_vec_rng(5 * 7, &mut rng, false);
+    let a = cx.tensor((5, 7));
+    a.set(data.clone());
+    // Retrieved intermediates keep the chains from being fused
+    let mut b = (a * 0.5).retrieve();
+    let mut c = (b + 1.0).retrieve();
+    let mut d = (a + 1.0).retrieve();
+    let mut e = (d * 0.5).retrieve();
+
+    // Compilation and execution using StwoCompiler
+    cx.compile(
+        <(GenericCompiler, StwoCompiler)>::default(),
+        (&mut b, &mut c, &mut d, &mut e),
+    );
+    let mut settings = cx.gen_circuit_settings();
+    b.drop();
+    c.drop();
+    d.drop();
+    e.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+
+    // Each constant should be held by its operations instead of being traced as an input,
+    // and both uses of a constant proven by the same component
+    let op_counter = &trace.metadata.execution_resources.op_counter;
+    assert_eq!(op_counter.mul_const, 2);
+    assert_eq!(op_counter.add_const, 2);
+    assert_eq!(op_counter.mul, 0);
+    assert_eq!(op_counter.add, 0);
+    let const_tables = trace
+        .trace_tables
+        .iter()
+        .filter(|table| {
+            matches!(
+                table,
+                TraceTable::MulConst { .. } | TraceTable::AddConst { .. }
+            )
+        })
+        .count();
+    assert_eq!(const_tables, 2);
+
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+
+    // CPUCompiler comparison
+    let mut cx_cpu = Graph::new();
+    let a_cpu = cx_cpu.tensor((5, 7)).set(data);
+    let mut b_cpu = (a_cpu * 0.5).retrieve();
+    let mut c_cpu = (b_cpu + 1.0).retrieve();
+    let mut d_cpu = (a_cpu + 1.0).retrieve();
+    let mut e_cpu = (d_cpu * 0.5).retrieve();
+    cx_cpu.compile(
+        <(GenericCompiler, CPUCompiler)>::default(),
+        (&mut b_cpu, &mut c_cpu, &mut d_cpu, &mut e_cpu),
+    );
+    cx_cpu.execute();
+
+    assert_close(&b.data(), &b_cpu.data());
+    assert_close(&c.data(), &c_cpu.data());
+    assert_close(&d.data(), &d_cpu.data());
+    assert_close(&e.data(), &e_cpu.data());
+}
+
+// =============== FUSED ===============
+
+#[test]
+fn test_fused_elementwise() {
+    // Graph setup
+    let mut cx = Graph::new();
+    let mut rng = StdRng::seed_from_u64(2);
+    let a_data = random_vec_rng(6 * 9, &mut rng, false);
+    let b_data = random_vec_rng(6 * 9, &mut rng, false);
+    let c_data = random_vec_rng(6 * 9, &mut rng, false);
+    let a = cx.tensor((6, 9)).set(a_data.clone());
+    let b = cx.tensor((6, 9)).set(b_data.clone());
+    let c = cx.tensor((6, 9)).set(c_data.clone());
+    // The square root reads values within [0.625, 1.375]
+    let mut out = ((a * b + c) * 0.5 + 1.0).sqrt().retrieve();
+
+    // Compilation and execution using StwoCompiler
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+
+    // The whole chain, constants included, should be evaluated by a single fused operation
+    let op_counter = &trace.metadata.execution_resources.op_counter;
+    assert_eq!(op_counter.fused, 1);
+    assert_eq!(op_counter.mul, 0);
+    assert_eq!(op_counter.add, 0);
+    assert_eq!(op_counter.sqrt, 0);
+    assert_eq!(op_counter.mul_const, 0);
+    assert_eq!(op_counter.add_const, 0);
+
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+
+    // The trace layout is derived from the claimed expression, which must be within limits
+    let mut tampered = LuminairProof::from_bincode(&proof.to_bincode().unwrap()).unwrap();
+    tampered.claim.fused[0].expr.n_inputs = MAX_FUSED_INPUTS + 1;
+    assert!(matches!(
+        verify(tampered, settings.clone()),
+        Err(LuminairError::InvalidClaim(_))
+    ));
+
+    verify(proof, settings).expect("Proof verification failed");
+
+    // CPUCompiler comparison
+    let mut cx_cpu = Graph::new();
+    let a_cpu = cx_cpu.tensor((6, 9)).set(a_data);
+    let b_cpu = cx_cpu.tensor((6, 9)).set(b_data);
+    let c_cpu = cx_cpu.tensor((6, 9)).set(c_data);
+    let mut out_cpu = ((a_cpu * b_cpu + c_cpu) * 0.5 + 1.0).sqrt().retrieve();
+    cx_cpu.compile(<(GenericCompiler, CPUCompiler)>::default(), &mut out_cpu);
+    cx_cpu.execute();
+
+    assert_close(&out.data(), &out_cpu.data());
+}
+
+// =============== TRACE COST ===============
+
+#[test]
+fn test_trace_cost_compiler() {
+    // Graph setup
+    let mut cx = Graph::new();
+    let mut rng = StdRng::seed_from_u64(3);
+    let data = random_vec_rng(4 * 5, &mut rng, false);
+    let a = cx.tensor((4, 5)).set(data.clone());
+    // The scale only reads constants, `a * 1.0 + 0.0` is `a` and both exponentials then
+    // compute the same value
+    let scale = cx.constant(4.0).sqrt().expand_to((4, 5));
+    let mut out = (((a * 1.0 + 0.0).exp2() + a.exp2()) * scale).retrieve();
+
+    // The pass alone reports the operations it removes
+    let removed = cx.compile(TraceCostCompiler::default(), &mut out);
+    assert_eq!(removed.sqrt, 1);
+    assert_eq!(removed.mul, 1);
+    assert_eq!(removed.add, 1);
+    assert_eq!(removed.exp2, 1);
+    // The folded constant and both neutral constants
+    assert_eq!(removed.inputs, 3);
+    // 1 row for the folded square root, 2 * 20 rows for the identities along with their
+    // constants and 20 rows for the duplicated exponential
+    assert_eq!(removed.rows_saved, 1 + 2 * 20 + 2 + 20);
+
+    // Compilation and execution using StwoCompiler
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    assert_eq!(trace.metadata.execution_resources.op_counter.exp2, 1);
+
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+
+    // CPUCompiler comparison
+    let mut cx_cpu = Graph::new();
+    let a_cpu = cx_cpu.tensor((4, 5)).set(data);
+    let scale_cpu = cx_cpu.constant(4.0).sqrt().expand_to((4, 5));
+    let mut out_cpu = (((a_cpu * 1.0 + 0.0).exp2() + a_cpu.exp2()) * scale_cpu).retrieve();
+    cx_cpu.compile(<(GenericCompiler, CPUCompiler)>::default(), &mut out_cpu);
+    cx_cpu.execute();
+
+    assert_close(&out.data(), &out_cpu.data());
+}
+
+#[test]
+fn test_trace_cost_reported_by_stwo_compiler() {
+    let mut cx = Graph::new();
+    let a = cx.tensor((4, 5));
+    let scale = cx.constant(4.0).sqrt().expand_to((4, 5));
+    let mut out = (a * scale).retrieve();
+
+    // The report of the pass is the first output of StwoCompiler
+    let (_, (removed, ..)) = cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+    assert_eq!(removed.sqrt, 1);
+    assert_eq!(removed.rows_saved, 1);
+}
+
 // =============== REDUCE ===============
 
 #[test]
@@ -254,3 +452,366 @@ fn test_contiguous() {
     // Assert outputs are close
     assert_close(&b.data(), &b_cpu.data());
 }
+
+// =============== MATMUL ===============
+
+#[test]
+fn test_matmul() {
+    // Graph setup
+    let mut cx = Graph::new();
+    let mut rng = StdRng::seed_from_u64(1);
+    let a_data = random_vec_rng(5 * 7, &mut rng, false);
+    let b_data = random_vec_rng(7 * 3, &mut rng, false);
+    let a = cx.tensor((5, 7));
+    let b = cx.tensor((7, 3));
+    a.set(a_data.clone());
+    b.set(b_data.clone());
+    let mut c = a.matmul(b).retrieve();
+
+    // Compilation and execution using StwoCompiler
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut c);
+    let mut settings = cx.gen_circuit_settings();
+    c.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+
+    // The broadcast Mul and the SumReduce should be fused into a single MatMul
+    let op_counter = &trace.metadata.execution_resources.op_counter;
+    assert_eq!(op_counter.matmul, 1);
+    assert_eq!(op_counter.mul, 0);
+    assert_eq!(op_counter.sum_reduce, 0);
+
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+
+    // CPUCompiler comparison
+    let mut cx_cpu = Graph::new();
+    let a_cpu = cx_cpu.tensor((5, 7)).set(a_data.clone());
+    let b_cpu = cx_cpu.tensor((7, 3)).set(b_data.clone());
+    let mut c_cpu = a_cpu.matmul(b_cpu).retrieve();
+    cx_cpu.compile(<(GenericCompiler, CPUCompiler)>::default(), &mut c_cpu);
+    cx_cpu.execute();
+
+    // Each output accumulates 7 fixed-point products
+    assert_close_precision(&c.data(), &c_cpu.data(), 1e-2);
+}
+
+// =============== CONV2D ===============
+
+/// Runs a `(ch_in, h, w)` convolution with a `(ch_out, ch_in, kh, kw)` kernel through the
+/// StwoCompiler, proves and verifies it, and compares the output with luminal's own
+/// im2col lowering (pad, pool, matmul) executed by the CPUCompiler
+fn run_conv2d_test(
+    ch_in: usize,
+    ch_out: usize,
+    (h, w): (usize, usize),
+    (kh, kw): (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let image_data = random_vec_rng(ch_in * h * w, &mut rng, false);
+    let weight_data = random_vec_rng(ch_out * ch_in * kh * kw, &mut rng, false);
+    let out_h = (h + 2 * padding.0 - kh) / stride.0 + 1;
+    let out_w = (w + 2 * padding.1 - kw) / stride.1 + 1;
+
+    // Graph setup
+    let mut cx = Graph::new();
+    let image = cx.tensor((ch_in, h, w)).set(image_data.clone());
+    let weight = cx.tensor((ch_out, ch_in, kh, kw)).set(weight_data.clone());
+    let mut out = image.conv2d(weight, stride, padding).retrieve();
+
+    // Compilation and execution using StwoCompiler
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+
+    // Patches are extracted once and multiplied by a single fused MatMul
+    let op_counter = &trace.metadata.execution_resources.op_counter;
+    assert_eq!(op_counter.im2col, 1);
+    assert_eq!(op_counter.matmul, 1);
+    assert_eq!(op_counter.sum_reduce, 0);
+
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+
+    // CPUCompiler comparison
+    let mut cx_cpu = Graph::new();
+    let image_cpu = cx_cpu.tensor((ch_in, h, w)).set(image_data);
+    let weight_cpu = cx_cpu.tensor((ch_out, ch_in, kh, kw)).set(weight_data);
+    let patches_cpu = image_cpu
+        .pad([(0, 0), (padding.0, padding.0), (padding.1, padding.1)])
+        .pool_last_dim(kw, stride.1, 1)
+        .permute((0, 2, 3, 1))
+        .pool_last_dim(kh, stride.0, 1)
+        .permute((0, 4, 2, 3, 1))
+        .contiguous()
+        .reshape((ch_in * kh * kw, out_h * out_w));
+    let mut out_cpu = weight_cpu
+        .reshape((ch_out, ch_in * kh * kw))
+        .matmul(patches_cpu)
+        .reshape((ch_out, out_h, out_w))
+        .retrieve();
+    cx_cpu.compile(<(GenericCompiler, CPUCompiler)>::default(), &mut out_cpu);
+    cx_cpu.execute();
+
+    // Each output accumulates ch_in * kh * kw fixed-point products
+    assert_close_precision(&out.data(), &out_cpu.data(), 1e-2);
+}
+
+#[test]
+fn test_conv2d_k3_s1_p0() {
+    run_conv2d_test(2, 3, (6, 6), (3, 3), (1, 1), (0, 0));
+}
+
+#[test]
+fn test_conv2d_k3_s1_p1() {
+    run_conv2d_test(2, 3, (6, 6), (3, 3), (1, 1), (1, 1));
+}
+
+#[test]
+fn test_conv2d_k3_s2_p1() {
+    run_conv2d_test(3, 2, (7, 7), (3, 3), (2, 2), (1, 1));
+}
+
+#[test]
+fn test_conv2d_k2_s2_p0() {
+    run_conv2d_test(1, 4, (8, 8), (2, 2), (2, 2), (0, 0));
+}
+
+#[test]
+fn test_conv2d_k1_s1_p0() {
+    run_conv2d_test(4, 2, (5, 5), (1, 1), (1, 1), (0, 0));
+}
+
+#[test]
+fn test_conv2d_non_square() {
+    run_conv2d_test(2, 2, (5, 8), (3, 2), (1, 2), (1, 0));
+}
+
+// =============== NORM ===============
+
+/// Normalizes the rows of `x` the way a LayerNorm (`center`) or an RMSNorm does, built
+/// from primitive ops, with an optional per-feature `(weight, bias)` affine transform
+fn norm_graph(
+    x: GraphTensor,
+    affine: Option<(GraphTensor, GraphTensor)>,
+    center: bool,
+    eps: f32,
+) -> GraphTensor {
+    let (rows, features) = (x.dims()[0], x.dims()[1]);
+    let inv_features = 1.0 / features.to_usize().unwrap() as f32;
+    let centered = if center {
+        x - (x.sum_reduce(1) * inv_features).expand(1, features)
+    } else {
+        x
+    };
+    let var = (centered * centered).sum_reduce(1) * inv_features;
+    let normed = centered * (var + eps).sqrt().recip().expand(1, features);
+    match affine {
+        Some((weight, bias)) => normed * weight.expand(0, rows) + bias.expand(0, rows),
+        None => normed,
+    }
+}
+
+/// Runs a `(rows, features)` normalization through the StwoCompiler, checks that it is
+/// fused into a single Norm, proves and verifies it, and compares with the CPUCompiler
+fn run_norm_test(rows: usize, features: usize, center: bool, with_affine: bool) {
+    let mut rng = StdRng::seed_from_u64(3);
+    let x_data = random_vec_rng(rows * features, &mut rng, false);
+    let weight_data = random_vec_rng(features, &mut rng, false);
+    let bias_data = random_vec_rng(features, &mut rng, false);
+    let eps = 1e-5;
+
+    // Graph setup
+    let mut cx = Graph::new();
+    let x = cx.tensor((rows, features)).set(x_data.clone());
+    let affine = with_affine.then(|| {
+        (
+            cx.tensor(features).set(weight_data.clone()),
+            cx.tensor(features).set(bias_data.clone()),
+        )
+    });
+    let mut out = norm_graph(x, affine, center, eps).retrieve();
+
+    // Compilation and execution using StwoCompiler
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+
+    // The whole normalization, affine included, should be fused into a single Norm
+    let op_counter = &trace.metadata.execution_resources.op_counter;
+    assert_eq!(op_counter.norm, 1);
+    assert_eq!(op_counter.mul, 0);
+    assert_eq!(op_counter.add, 0);
+    assert_eq!(op_counter.sum_reduce, 0);
+    assert_eq!(op_counter.sqrt, 0);
+    assert_eq!(op_counter.recip, 0);
+
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+
+    // The remainders are range checked
+    assert_eq!(proof.claim.range_check_lookup.len(), 1);
+
+    // The constants of the constraints are derived from the claimed parameters
+    let mut tampered = LuminairProof::from_bincode(&proof.to_bincode().unwrap()).unwrap();
+    tampered.claim.norm[0].params.eps.0 += 1;
+    assert!(verify(tampered, settings.clone()).is_err());
+
+    verify(proof, settings).expect("Proof verification failed");
+
+    // CPUCompiler comparison
+    let mut cx_cpu = Graph::new();
+    let x_cpu = cx_cpu.tensor((rows, features)).set(x_data);
+    let affine_cpu = with_affine.then(|| {
+        (
+            cx_cpu.tensor(features).set(weight_data),
+            cx_cpu.tensor(features).set(bias_data),
+        )
+    });
+    let mut out_cpu = norm_graph(x_cpu, affine_cpu, center, eps).retrieve();
+    cx_cpu.compile(<(GenericCompiler, CPUCompiler)>::default(), &mut out_cpu);
+    cx_cpu.execute();
+
+    // The fixed-point square root and reciprocal round the normalization factor
+    assert_close_precision(&out.data(), &out_cpu.data(), 5e-2);
+}
+
+#[test]
+fn test_layer_norm() {
+    run_norm_test(4, 8, true, false);
+}
+
+#[test]
+fn test_layer_norm_affine() {
+    run_norm_test(4, 8, true, true);
+}
+
+#[test]
+fn test_rms_norm() {
+    run_norm_test(3, 16, false, false);
+}
+
+#[test]
+fn test_rms_norm_affine() {
+    run_norm_test(3, 16, false, true);
+}
+
+#[test]
+fn test_layer_norm_sharded() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut cx = Graph::new();
+    let x = cx
+        .tensor((24, 24))
+        .set(random_vec_rng(24 * 24, &mut rng, false));
+    let mut out = norm_graph(x, None, true, 1e-5).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+
+    // Shards of at most 256 rows hold 10 whole normalized rows of 24 values
+    let proving_key = ProvingKey::new(&settings, 8);
+    let proof = prove_with_key(trace, &proving_key).expect("Proof generation failed");
+    let log_sizes: Vec<u32> = proof
+        .claim
+        .norm
+        .iter()
+        .map(|claim| claim.log_size)
+        .collect();
+    assert_eq!(log_sizes, vec![8, 8, 7]);
+
+    verify_with_key(proof, &proving_key.verifying_key()).expect("Proof verification failed");
+}
+
+// =============== TERNARY ===============
+
+#[test]
+fn test_select() {
+    // Graph setup
+    let mut cx = Graph::new();
+    let mut rng = StdRng::seed_from_u64(1);
+    let a_data = random_vec_rng(5 * 7, &mut rng, false);
+    let b_data = random_vec_rng(5 * 7, &mut rng, false);
+    let a = cx.tensor((5, 7));
+    let b = cx.tensor((5, 7));
+    a.set(a_data.clone());
+    b.set(b_data.clone());
+    let mask = a.less_than(b);
+    let mut c = (mask * a + (mask * -1.0 + 1.0) * b).retrieve();
+
+    // Compilation and execution using StwoCompiler
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut c);
+    let mut settings = cx.gen_circuit_settings();
+    c.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+
+    // The blend should be proven by a single Select row per element
+    let op_counter = &trace.metadata.execution_resources.op_counter;
+    assert_eq!(op_counter.select, 1);
+    assert_eq!(op_counter.mul, 0);
+    assert_eq!(op_counter.add, 0);
+
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+
+    // CPUCompiler comparison
+    let mut cx_cpu = Graph::new();
+    let a_cpu = cx_cpu.tensor((5, 7)).set(a_data.clone());
+    let b_cpu = cx_cpu.tensor((5, 7)).set(b_data.clone());
+    let mask_cpu = a_cpu.less_than(b_cpu);
+    let mut c_cpu = (mask_cpu * a_cpu + (mask_cpu * -1.0 + 1.0) * b_cpu).retrieve();
+    cx_cpu.compile(<(GenericCompiler, CPUCompiler)>::default(), &mut c_cpu);
+    cx_cpu.execute();
+
+    // Assert outputs are close
+    assert_close(&c.data(), &c_cpu.data());
+}
+
+// =============== GATHER ===============
+
+#[test]
+fn test_gather() {
+    // Graph setup
+    let mut cx = Graph::new();
+    let mut rng = StdRng::seed_from_u64(1);
+    let table_data = random_vec_rng(6 * 4, &mut rng, false);
+    let indices_data = vec![3.0, 0.0, 5.0, 3.0, 1.0];
+    let table = cx.tensor((6, 4));
+    let indices = cx.tensor(5);
+    table.set(table_data.clone());
+    indices.set(indices_data.clone());
+    let mut c = table.gather_rows(indices).retrieve();
+
+    // Compilation and execution using StwoCompiler
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut c);
+    let mut settings = cx.gen_circuit_settings();
+    c.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    assert_eq!(trace.metadata.execution_resources.op_counter.gather, 1);
+
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+
+    // Reference embedding lookup
+    let expected = indices_data
+        .iter()
+        .flat_map(|&i| table_data[i as usize * 4..(i as usize + 1) * 4].to_vec())
+        .collect::<Vec<_>>();
+
+    // Assert outputs are close
+    assert_close(&c.data(), &expected);
+}
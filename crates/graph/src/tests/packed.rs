@@ -0,0 +1,102 @@
+use luminair_air::{
+    components::lookups::sin::table::SinLookupTraceTableRow as Row,
+    utils::{circle_domain_index_to_coset_index, PackedRows},
+};
+use rayon::prelude::*;
+use stwo::{
+    core::{
+        fields::m31::M31,
+        utils::{bit_reverse_index, coset_index_to_circle_domain_index},
+    },
+    prover::backend::simd::m31::N_LANES,
+};
+
+fn row(value: u32) -> Row {
+    Row {
+        multiplicity: M31::from(value),
+    }
+}
+
+fn values(rows: &PackedRows<Row>) -> Vec<u32> {
+    rows.iter().map(|row| row.multiplicity.0).collect()
+}
+
+#[test]
+fn test_packed_rows() {
+    let expected: Vec<u32> = (0..2 * N_LANES as u32 + 5).collect();
+
+    let mut pushed = PackedRows::new();
+    expected.iter().for_each(|value| pushed.push(row(*value)));
+    assert_eq!(pushed.len(), expected.len());
+    assert_eq!(values(&pushed), expected);
+
+    // Rows extended in parallel after an incomplete SIMD vector
+    let mut extended = PackedRows::new();
+    (0..3).for_each(|value| extended.push(row(value)));
+    extended.par_extend((3..expected.len() as u32).into_par_iter().map(row));
+    assert_eq!(values(&extended), expected);
+
+    let mut appended = PackedRows::new();
+    appended.push(row(0));
+    let mut rest = PackedRows::new();
+    (1..expected.len() as u32).for_each(|value| rest.push(row(value)));
+    appended.append(rest);
+    assert_eq!(values(&appended), expected);
+
+    // Rows are serialized like a vector of rows
+    let rows: Vec<Row> = expected.iter().map(|value| row(*value)).collect();
+    let encoded = bincode::serialize(&pushed).unwrap();
+    assert_eq!(encoded, bincode::serialize(&rows).unwrap());
+    let decoded: PackedRows<Row> = bincode::deserialize(&encoded).unwrap();
+    assert_eq!(values(&decoded), expected);
+
+    let mut head = decoded.clone();
+    let tail = head.split_off(2 * N_LANES);
+    assert_eq!(values(&head), expected[..2 * N_LANES]);
+    assert_eq!(values(&tail), expected[2 * N_LANES..]);
+
+    // Padded to the next power of two
+    let packed = pushed.into_packed(row(0));
+    assert_eq!(packed.len(), 4);
+    let lanes: Vec<u32> = packed
+        .iter()
+        .flat_map(|packed| packed.multiplicity.to_array())
+        .map(|value| value.0)
+        .collect();
+    assert_eq!(lanes[..expected.len()], expected);
+    assert!(lanes[expected.len()..].iter().all(|value| *value == 0));
+}
+
+#[test]
+fn test_packed_rows_in_coset_order() {
+    for log_size in 1..10 {
+        for index in 0..1 << log_size {
+            let circle_domain_index = coset_index_to_circle_domain_index(index, log_size);
+            assert_eq!(
+                circle_domain_index_to_coset_index(circle_domain_index, log_size),
+                index
+            );
+        }
+    }
+
+    // The i-th row is evaluated at the i-th point of the coset, which the trace column holds at
+    // its bit-reversed circle domain index
+    let mut rows = PackedRows::new();
+    (1..=50).for_each(|value| rows.push(row(value)));
+    let log_size = 6;
+    let column: Vec<u32> = rows
+        .into_packed_coset_order(row(0))
+        .iter()
+        .flat_map(|packed| packed.multiplicity.to_array())
+        .map(|value| value.0)
+        .collect();
+    assert_eq!(column.len(), 1 << log_size);
+    for index in 0..1 << log_size {
+        let position = bit_reverse_index(
+            coset_index_to_circle_domain_index(index, log_size),
+            log_size,
+        );
+        let expected = if index < 50 { index as u32 + 1 } else { 0 };
+        assert_eq!(column[position], expected);
+    }
+}
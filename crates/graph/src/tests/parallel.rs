@@ -0,0 +1,75 @@
+use super::random_vec_rng;
+use crate::graph::LuminairGraph;
+use crate::StwoCompiler;
+use luminair_air::{
+    pie::{LuminairPie, TraceTable},
+    settings::CircuitSettings,
+};
+use luminair_prover::prover::prove;
+use luminair_verifier::verifier::verify;
+use luminal::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+use stwo::core::fields::m31::M31;
+
+/// Executes independent branches over tensors large enough to be split across threads
+fn pie() -> (LuminairPie, CircuitSettings, Graph) {
+    let mut rng = StdRng::seed_from_u64(11);
+    let a_data = random_vec_rng(64 * 128, &mut rng, false);
+    let b_data = random_vec_rng(64 * 128, &mut rng, false);
+
+    let mut cx = Graph::new();
+    let a = cx.tensor((64, 128)).set(a_data);
+    let b = cx.tensor((64, 128)).set(b_data);
+    let c = (a * b).sum_reduce(1);
+    let d = (a + b).max_reduce(1);
+    let e = (a.sin() * b).sum_reduce(1);
+    let mut out = (c + d + e).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    (trace, settings, cx)
+}
+
+#[test]
+fn test_parallel_trace_is_deterministic() {
+    let (trace, settings, _) = pie();
+    let (other, _, _) = pie();
+    assert_eq!(
+        bincode::serialize(&trace).unwrap(),
+        bincode::serialize(&other).unwrap()
+    );
+
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+}
+
+#[test]
+fn test_parallel_trace_follows_execution_order() {
+    let (trace, _, cx) = pie();
+    let mul_nodes: Vec<M31> = cx
+        .linearized_graph
+        .as_ref()
+        .unwrap()
+        .iter()
+        .filter(|(node, _)| format!("{:?}", cx.node_weight(*node).unwrap()) == "Mul")
+        .map(|(node, _)| M31::from(node.index() as u32))
+        .collect();
+    assert_eq!(mul_nodes.len(), 2);
+
+    let table = trace
+        .trace_tables
+        .iter()
+        .find_map(|table| match table {
+            TraceTable::Mul { table } => Some(table),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(table.table.len(), 2 * 64 * 128);
+    let mut row_nodes: Vec<M31> = table.table.iter().map(|row| row.node_id).collect();
+    row_nodes.dedup();
+    assert_eq!(row_nodes, mul_nodes);
+}
@@ -0,0 +1,130 @@
+use super::random_vec_rng;
+use crate::graph::LuminairGraph;
+use crate::StwoCompiler;
+use luminair_air::settings::CircuitSettings;
+use luminair_prover::{
+    keys::ProvingKey,
+    prover::{prove_segment, prove_segments},
+    segment::SegmentedProof,
+};
+use luminair_utils::LuminairError;
+use luminair_verifier::verifier::verify_segments;
+use luminal::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+use stwo::core::fields::m31::M31;
+
+/// Segmented proof of a graph whose intermediate tensors cross several cuts
+fn segmented_proof(nodes_per_segment: usize) -> (SegmentedProof, CircuitSettings) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let a_data = random_vec_rng(4 * 4, &mut rng, false);
+    let b_data = random_vec_rng(4 * 4, &mut rng, false);
+
+    let mut cx = Graph::new();
+    let a = cx.tensor((4, 4)).set(a_data);
+    let b = cx.tensor((4, 4)).set(b_data);
+    let c = a * b;
+    let d = c.exp2() + a;
+    let mut out = (d * c + b).sum_reduce(1).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+    let settings = cx.gen_circuit_settings();
+    out.drop();
+    let segments = cx
+        .gen_segmented_trace(&settings, nodes_per_segment)
+        .expect("Trace generation failed");
+    assert!(segments.len() > 1);
+    assert!(segments[0].boundary.inputs.is_empty());
+    assert!(segments.last().unwrap().boundary.outputs.is_empty());
+    let exp2_count: usize = segments
+        .iter()
+        .map(|segment| segment.pie.metadata.execution_resources.op_counter.exp2)
+        .sum();
+    assert_eq!(exp2_count, 1);
+
+    let proof = prove_segments(segments, settings.clone()).expect("Proof generation failed");
+    (proof, settings)
+}
+
+#[test]
+fn test_segmented_proof() {
+    for nodes_per_segment in [1, 2, 3] {
+        let (proof, settings) = segmented_proof(nodes_per_segment);
+        let decoded = SegmentedProof::from_bincode(&proof.to_bincode().unwrap()).unwrap();
+        verify_segments(decoded, settings).expect("Proof verification failed");
+    }
+}
+
+#[test]
+fn test_segmented_proof_rejects_broken_chain() {
+    let (proof, settings) = segmented_proof(2);
+    let encoded = proof.to_bincode().unwrap();
+
+    // A boundary tensor altered on one side of a cut
+    let mut tampered = SegmentedProof::from_bincode(&encoded).unwrap();
+    tampered.segments[0].boundary.outputs[0].values[0] += M31::from(1);
+    assert!(matches!(
+        verify_segments(tampered, settings.clone()),
+        Err(LuminairError::InvalidSegmentChain(_))
+    ));
+
+    // Segments out of order
+    let mut reordered = SegmentedProof::from_bincode(&encoded).unwrap();
+    reordered.segments.swap(0, 1);
+    assert!(matches!(
+        verify_segments(reordered, settings.clone()),
+        Err(LuminairError::InvalidSegmentChain(_))
+    ));
+
+    // A missing segment
+    let mut truncated = SegmentedProof::from_bincode(&encoded).unwrap();
+    truncated.segments.pop();
+    assert!(matches!(
+        verify_segments(truncated, settings.clone()),
+        Err(LuminairError::InvalidSegmentChain(_))
+    ));
+
+    // A boundary tensor altered on both sides of a cut no longer matches the proofs
+    let mut tampered = SegmentedProof::from_bincode(&encoded).unwrap();
+    tampered.segments[0].boundary.outputs[0].values[0] += M31::from(1);
+    tampered.segments[1].boundary.inputs[0].tensor.values[0] += M31::from(1);
+    assert!(verify_segments(tampered, settings).is_err());
+}
+
+#[test]
+fn test_segmented_proof_with_retrieved_boundary() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let a_data = random_vec_rng(4 * 4, &mut rng, false);
+    let b_data = random_vec_rng(4 * 4, &mut rng, false);
+
+    // `c` is retrieved, and also consumed by the operators after it
+    let mut cx = Graph::new();
+    let a = cx.tensor((4, 4)).set(a_data);
+    let b = cx.tensor((4, 4)).set(b_data);
+    let mut c = (a * b).retrieve();
+    let mut out = ((c.exp2() + a) * c).sum_reduce(1).retrieve();
+    cx.compile(
+        <(GenericCompiler, StwoCompiler)>::default(),
+        (&mut c, &mut out),
+    );
+
+    let settings = cx.gen_circuit_settings();
+    c.drop();
+    out.drop();
+    for nodes_per_segment in [1, 2] {
+        // Each segment is proven as soon as it is cut
+        let proving_key = ProvingKey::new(&settings, 0);
+        let mut segments = Vec::new();
+        cx.gen_segmented_trace_with(&settings, nodes_per_segment, &mut |segment| {
+            segments.push(prove_segment(segment, &proving_key)?);
+            Ok(())
+        })
+        .expect("Segmented proof failed");
+        assert!(segments.len() > 1);
+
+        verify_segments(SegmentedProof { segments }, settings.clone())
+            .expect("Proof verification failed");
+        assert_eq!(c.data().len(), 4 * 4);
+        c.drop();
+        out.drop();
+    }
+}
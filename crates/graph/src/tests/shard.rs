@@ -0,0 +1,121 @@
+use super::random_vec_rng;
+use crate::graph::LuminairGraph;
+use crate::StwoCompiler;
+use luminair_air::{
+    components::InteractionClaim,
+    pie::LuminairPie,
+    settings::CircuitSettings,
+    utils::{norm_shard_sizes, shard_sizes},
+};
+use luminair_prover::{
+    keys::ProvingKey,
+    prover::{prove, prove_with_key},
+    LuminairProof,
+};
+use luminair_utils::LuminairError;
+use luminair_verifier::verifier::{verify, verify_with_key};
+use luminal::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Executes `a * b + a` on random tensors of `len` values
+fn pie(len: usize) -> (LuminairPie, CircuitSettings) {
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut cx = Graph::new();
+    let a = cx.tensor(len).set(random_vec_rng(len, &mut rng, false));
+    let b = cx.tensor(len).set(random_vec_rng(len, &mut rng, false));
+    let mut out = (a * b + a).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    (trace, settings)
+}
+
+#[test]
+fn test_shard_sizes() {
+    assert_eq!(shard_sizes(0, 10), Vec::<usize>::new());
+    assert_eq!(shard_sizes(16, 10), vec![16]);
+    assert_eq!(shard_sizes(1000, 10), vec![1000]);
+    // Padding 17 rows to 32 would mostly be waste
+    assert_eq!(shard_sizes(17, 10), vec![16, 1]);
+    assert_eq!(shard_sizes(300, 8), vec![256, 32, 12]);
+    assert_eq!(shard_sizes(1 << 12, 8), vec![1 << 8; 16]);
+    // Shards are never smaller than a SIMD vector
+    assert_eq!(shard_sizes(64, 2), vec![16; 4]);
+}
+
+#[test]
+fn test_norm_shard_sizes() {
+    assert_eq!(norm_shard_sizes(0, 24, 8), Vec::<usize>::new());
+    assert_eq!(norm_shard_sizes(96, 24, 8), vec![96]);
+    // Shards are cut between normalized rows
+    assert_eq!(norm_shard_sizes(576, 24, 8), vec![240, 240, 96]);
+    // A normalized row larger than a shard is never split
+    assert_eq!(norm_shard_sizes(600, 300, 8), vec![300, 300]);
+}
+
+#[test]
+fn test_proof_of_sharded_tables() {
+    let (trace, settings) = pie(64 * 64);
+    let proving_key = ProvingKey::new(&settings, 8);
+    let proof = prove_with_key(trace, &proving_key).expect("Proof generation failed");
+
+    // The fused table of 4096 rows is proven by 16 instances of log size 8
+    assert_eq!(proof.claim.fused.len(), 16);
+    assert!(proof.claim.fused.iter().all(|claim| claim.log_size == 8));
+    assert_eq!(proof.interaction_claim.fused.len(), 16);
+
+    verify_with_key(proof, &proving_key.verifying_key()).expect("Proof verification failed");
+}
+
+#[test]
+fn test_sharding_reduces_padding() {
+    // 17 rows would otherwise be padded to 32
+    let (trace, settings) = pie(17);
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    let log_sizes: Vec<u32> = proof
+        .claim
+        .fused
+        .iter()
+        .map(|claim| claim.log_size)
+        .collect();
+    assert_eq!(log_sizes, vec![4, 4]);
+
+    verify(proof, settings).expect("Proof verification failed");
+}
+
+#[test]
+fn test_verify_rejects_missing_shard_claim() {
+    let (trace, settings) = pie(64 * 64);
+    let proving_key = ProvingKey::new(&settings, 8);
+    let proof = prove_with_key(trace, &proving_key).expect("Proof generation failed");
+
+    let mut tampered = LuminairProof::from_bincode(&proof.to_bincode().unwrap()).unwrap();
+    tampered.interaction_claim.fused.pop();
+    assert!(matches!(
+        verify_with_key(tampered, &proving_key.verifying_key()),
+        Err(LuminairError::InvalidLogUp(_))
+    ));
+}
+
+#[test]
+fn test_verify_rejects_extra_interaction_claim() {
+    let (trace, settings) = pie(64 * 64);
+    let proving_key = ProvingKey::new(&settings, 8);
+    let proof = prove_with_key(trace, &proving_key).expect("Proof generation failed");
+
+    // A claimed sum without a component shard is rejected before components are built
+    let mut tampered = LuminairProof::from_bincode(&proof.to_bincode().unwrap()).unwrap();
+    let claimed_sum = tampered.interaction_claim.fused[0].claimed_sum;
+    tampered
+        .interaction_claim
+        .fused
+        .push(InteractionClaim { claimed_sum });
+    assert!(matches!(
+        verify_with_key(tampered, &proving_key.verifying_key()),
+        Err(LuminairError::InvalidLogUp(_))
+    ));
+}
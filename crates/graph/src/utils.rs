@@ -5,6 +5,7 @@ use luminair_air::{preprocessed::Range, DEFAULT_FP_SCALE};
 use luminal::prelude::*;
 use num_traits::Zero;
 use numerair::Fixed;
+use rayon::prelude::*;
 
 /// Checks if a TypeId matches the specified type T
 /// 
@@ -20,6 +21,27 @@ pub(crate) fn get_buffer_from_tensor<'a>(tensor: &'a InputTensor) -> Option<&'a
     tensor.borrowed().downcast_ref::<StwoData>()
 }
 
+/// Minimum number of elements handled by one rayon task during trace generation
+///
+/// Tensors smaller than this are processed on the calling thread.
+pub(crate) const PARALLEL_MIN_LEN: usize = 1 << 12;
+
+/// Evaluates `f` at each index of a tensor of `len` elements, in index order
+///
+/// Large tensors are split across the rayon thread pool. `f` is given a scratch stack
+/// to evaluate shape expressions with.
+pub(crate) fn par_map_indices<R, F>(len: usize, f: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn(&mut Vec<i64>, usize) -> R + Sync + Send,
+{
+    (0..len)
+        .into_par_iter()
+        .with_min_len(PARALLEL_MIN_LEN)
+        .map_init(Vec::new, |stack, idx| f(stack, idx))
+        .collect()
+}
+
 /// Computes the value at a specific index in StwoData using shape expressions
 /// 
 /// Evaluates the index and validity expressions to determine the value at the given index.
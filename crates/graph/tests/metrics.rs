@@ -0,0 +1,127 @@
+//! Proving metrics, in their own test binary so that `PeakAllocator` is the global allocator
+//! of this binary only
+
+use luminair_air::{pie::LuminairPie, settings::CircuitSettings};
+use luminair_graph::{graph::LuminairGraph, StwoCompiler};
+use luminair_prover::{
+    keys::ProvingKey,
+    metrics::{PeakAllocator, ProvingMetrics},
+    prover::{prove_with_key_and_metrics, prove_with_metrics},
+};
+use luminair_verifier::verifier::verify;
+use luminal::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+/// Executes `exp2(a @ w) + 1` on random tensors, returning its PIE and circuit settings
+fn pie() -> (LuminairPie, CircuitSettings) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut random_vec = |n: usize| {
+        (0..n)
+            .map(|_| rng.gen_range(-0.5..0.5))
+            .collect::<Vec<f32>>()
+    };
+    let mut cx = Graph::new();
+    let a = cx.tensor((4, 5)).set(random_vec(4 * 5));
+    let w = cx.tensor((5, 3)).set(random_vec(5 * 3));
+    let mut out = (a.matmul(w).exp2() + 1.0).retrieve();
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    (trace, settings)
+}
+
+#[test]
+fn test_proving_metrics() {
+    let (trace, settings) = pie();
+    let max_log_size = trace.metadata.execution_resources.max_log_size;
+    let mut traced_rows = trace
+        .trace_tables
+        .iter()
+        .cloned()
+        .flat_map(|table| table.shard(max_log_size))
+        .map(|table| table.n_rows())
+        .collect::<Vec<_>>();
+
+    let (proof, metrics) =
+        prove_with_metrics(trace, settings.clone()).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+
+    // One component instance per shard of the traced tables, each sized by its claim
+    let mut proven_rows = metrics
+        .components
+        .iter()
+        .map(|component| component.rows)
+        .collect::<Vec<_>>();
+    traced_rows.sort();
+    proven_rows.sort();
+    assert_eq!(proven_rows, traced_rows);
+    assert!(metrics
+        .components
+        .iter()
+        .all(|component| component.rows <= 1 << component.log_size));
+    assert!(metrics
+        .components
+        .iter()
+        .any(|component| component.component == "exp2_lookup"));
+
+    let phases = metrics.preprocessed_ms
+        + metrics.main_trace_ms
+        + metrics.interaction_trace_ms
+        + metrics.prove_ms;
+    assert!(phases <= metrics.total_ms);
+    assert!(metrics.peak_allocation.unwrap() > 0);
+
+    // Timings may lose their last bit going through JSON, so they are compared with a tolerance
+    let json = metrics.to_json().unwrap();
+    let parsed: ProvingMetrics = serde_json::from_str(&json).unwrap();
+    let timings = |metrics: &ProvingMetrics| {
+        [
+            metrics.proving_key_ms,
+            metrics.preprocessed_ms,
+            metrics.main_trace_ms,
+            metrics.interaction_trace_ms,
+            metrics.prove_ms,
+            metrics.total_ms,
+        ]
+        .into_iter()
+        .chain(metrics.components.iter().map(|c| c.main_trace_ms))
+        .collect::<Vec<_>>()
+    };
+    let (parsed_timings, timings) = (timings(&parsed), timings(&metrics));
+    assert_eq!(parsed_timings.len(), timings.len());
+    for (parsed, timing) in parsed_timings.into_iter().zip(timings) {
+        assert!((parsed - timing).abs() <= 1e-9 * timing.abs().max(1.0));
+    }
+    let sizes = |metrics: &ProvingMetrics| {
+        metrics
+            .components
+            .iter()
+            .map(|c| (c.component.clone(), c.rows, c.log_size))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(sizes(&parsed), sizes(&metrics));
+    assert_eq!(parsed.peak_allocation, metrics.peak_allocation);
+}
+
+#[test]
+fn test_proving_metrics_with_key() {
+    let (trace, settings) = pie();
+    let max_log_size = trace.metadata.execution_resources.max_log_size;
+    let proving_key = ProvingKey::new(&settings, max_log_size);
+
+    let (proof, metrics) =
+        prove_with_key_and_metrics(trace, &proving_key).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+
+    // The key is built beforehand
+    assert_eq!(metrics.proving_key_ms, 0.0);
+    assert!(!metrics.components.is_empty());
+    assert!(metrics.peak_allocation.unwrap() > 0);
+}
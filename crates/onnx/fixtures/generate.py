@@ -0,0 +1,260 @@
+"""Generates the ONNX fixtures used by the importer tests.
+
+The models are written with a minimal protobuf encoder so the script has no
+dependencies, and their expected outputs are computed in plain Python.
+
+    python generate.py
+"""
+
+import json
+import math
+import os
+import struct
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+
+FLOAT, INT64 = 1, 7
+ATTR_FLOAT, ATTR_INT, ATTR_STRING, ATTR_FLOATS, ATTR_INTS = 1, 2, 3, 6, 7
+
+
+# ---------------------------------------------------------------------------
+# Protobuf encoding
+# ---------------------------------------------------------------------------
+
+
+def varint(n):
+    n &= (1 << 64) - 1
+    out = bytearray()
+    while True:
+        byte = n & 0x7F
+        n >>= 7
+        if n:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return bytes(out)
+
+
+def key(tag, wire_type):
+    return varint(tag << 3 | wire_type)
+
+
+def f_varint(tag, n):
+    return key(tag, 0) + varint(n)
+
+
+def f_bytes(tag, data):
+    return key(tag, 2) + varint(len(data)) + data
+
+
+def f_str(tag, s):
+    return f_bytes(tag, s.encode())
+
+
+def f_float(tag, x):
+    return key(tag, 5) + struct.pack("<f", x)
+
+
+def tensor(name, dims, values, data_type=FLOAT, raw=False):
+    out = b"".join(f_varint(1, d) for d in dims) + f_varint(2, data_type) + f_str(8, name)
+    if data_type == FLOAT:
+        packed = b"".join(struct.pack("<f", v) for v in values)
+        out += f_bytes(9, packed) if raw else f_bytes(4, packed)
+    else:
+        out += f_bytes(7, b"".join(varint(v) for v in values))
+    return out
+
+
+def value_info(name, dims):
+    shape = b"".join(f_bytes(1, f_varint(1, d)) for d in dims)
+    tensor_type = f_varint(1, FLOAT) + f_bytes(2, shape)
+    return f_str(1, name) + f_bytes(2, f_bytes(1, tensor_type))
+
+
+def attr(name, value):
+    out = f_str(1, name)
+    if isinstance(value, float):
+        return out + f_float(2, value) + f_varint(20, ATTR_FLOAT)
+    if isinstance(value, int):
+        return out + f_varint(3, value) + f_varint(20, ATTR_INT)
+    if isinstance(value, str):
+        return out + f_bytes(4, value.encode()) + f_varint(20, ATTR_STRING)
+    if all(isinstance(v, int) for v in value):
+        return out + b"".join(f_varint(8, v) for v in value) + f_varint(20, ATTR_INTS)
+    return out + b"".join(f_float(7, v) for v in value) + f_varint(20, ATTR_FLOATS)
+
+
+def node(op_type, inputs, outputs, name, **attrs):
+    out = b"".join(f_str(1, i) for i in inputs)
+    out += b"".join(f_str(2, o) for o in outputs)
+    out += f_str(3, name) + f_str(4, op_type)
+    out += b"".join(f_bytes(5, attr(k, v)) for k, v in attrs.items())
+    return out
+
+
+def model(name, nodes, initializers, inputs, outputs, opset=13):
+    graph = b"".join(f_bytes(1, n) for n in nodes) + f_str(2, name)
+    graph += b"".join(f_bytes(5, t) for t in initializers)
+    graph += b"".join(f_bytes(11, value_info(n, d)) for n, d in inputs)
+    graph += b"".join(f_bytes(12, value_info(n, d)) for n, d in outputs)
+    opset_import = f_str(1, "") + f_varint(2, opset)
+    return f_varint(1, 8) + f_str(2, "luminair-fixtures") + f_bytes(7, graph) + f_bytes(8, opset_import)
+
+
+# ---------------------------------------------------------------------------
+# Reference evaluation on nested lists
+# ---------------------------------------------------------------------------
+
+
+class Rng:
+    """Deterministic values in [-0.5, 0.5), rounded to two decimals"""
+
+    def __init__(self, seed):
+        self.state = seed
+
+    def values(self, n):
+        out = []
+        for _ in range(n):
+            self.state = (self.state * 1103515245 + 12345) % (1 << 31)
+            out.append(round(self.state / (1 << 31) - 0.5, 2))
+        return out
+
+
+def matrix(values, rows, cols):
+    return [values[r * cols:(r + 1) * cols] for r in range(rows)]
+
+
+def matmul(a, b):
+    return [[sum(a[i][k] * b[k][j] for k in range(len(b))) for j in range(len(b[0]))] for i in range(len(a))]
+
+
+def transpose(a):
+    return [list(row) for row in zip(*a)]
+
+
+def write(name, model_bytes, inputs, outputs):
+    with open(os.path.join(HERE, f"{name}.onnx"), "wb") as f:
+        f.write(model_bytes)
+    if inputs is not None:
+        data = {
+            "inputs": inputs,
+            "outputs": {k: [round(x, 6) for x in v] for k, v in outputs.items()},
+        }
+        with open(os.path.join(HERE, f"{name}.json"), "w") as f:
+            json.dump(data, f, indent=2)
+            f.write("\n")
+
+
+# ---------------------------------------------------------------------------
+# Fixtures
+# ---------------------------------------------------------------------------
+
+
+def mlp():
+    """Gemm -> Relu -> Gemm -> Softmax, as exported from a two-layer torch MLP"""
+    rng = Rng(1)
+    x = rng.values(4)
+    w1, b1 = rng.values(8 * 4), rng.values(8)
+    w2, b2 = rng.values(8 * 3), rng.values(3)
+
+    hidden = matmul([x], transpose(matrix(w1, 8, 4)))[0]
+    hidden = [max(h + b, 0.0) for h, b in zip(hidden, b1)]
+    logits = [l + b for l, b in zip(matmul([hidden], matrix(w2, 8, 3))[0], b2)]
+    exps = [math.exp(l - max(logits)) for l in logits]
+    y = [e / sum(exps) for e in exps]
+
+    nodes = [
+        node("Gemm", ["x", "w1", "b1"], ["h"], "fc1", transB=1),
+        node("Relu", ["h"], ["a"], "relu"),
+        node("Gemm", ["a", "w2", "b2"], ["logits"], "fc2", alpha=1.0, beta=1.0),
+        node("Softmax", ["logits"], ["y"], "softmax", axis=-1),
+    ]
+    initializers = [
+        tensor("w1", [8, 4], w1),
+        tensor("b1", [8], b1),
+        tensor("w2", [8, 3], w2),
+        tensor("b2", [3], b2),
+    ]
+    m = model("mlp", nodes, initializers, [("x", [1, 4])], [("y", [1, 3])])
+    write("mlp", m, {"x": x}, {"y": y})
+
+
+def conv():
+    """Conv (strided, padded, with bias) -> Sigmoid -> Reshape"""
+    rng = Rng(2)
+    x = rng.values(5 * 5)
+    w, b = rng.values(2 * 3 * 3), rng.values(2)
+
+    image = matrix(x, 5, 5)
+    y = []
+    for c in range(2):
+        kernel = matrix(w[c * 9:(c + 1) * 9], 3, 3)
+        for oy in range(3):
+            for ox in range(3):
+                acc = b[c]
+                for i in range(3):
+                    for j in range(3):
+                        iy, ix = oy * 2 + i - 1, ox * 2 + j - 1
+                        if 0 <= iy < 5 and 0 <= ix < 5:
+                            acc += image[iy][ix] * kernel[i][j]
+                y.append(1.0 / (1.0 + math.exp(-acc)))
+
+    nodes = [
+        node("Conv", ["x", "w", "b"], ["c"], "conv", kernel_shape=[3, 3], strides=[2, 2], pads=[1, 1, 1, 1]),
+        node("Sigmoid", ["c"], ["s"], "sigmoid"),
+        node("Reshape", ["s", "shape"], ["y"], "flatten"),
+    ]
+    initializers = [
+        tensor("w", [2, 1, 3, 3], w, raw=True),
+        tensor("b", [2], b),
+        tensor("shape", [2], [1, -1], data_type=INT64),
+    ]
+    m = model("conv", nodes, initializers, [("x", [1, 1, 5, 5])], [("y", [1, 18])])
+    write("conv", m, {"x": x}, {"y": y})
+
+
+def ops():
+    """MatMul, broadcast Add and Mul, Transpose, ReduceMax and ReduceSum"""
+    rng = Rng(3)
+    a, b, c = rng.values(2 * 3), rng.values(3 * 2), rng.values(2)
+
+    m = matmul(matrix(a, 2, 3), matrix(b, 3, 2))
+    s = [[v + c[j] for j, v in enumerate(row)] for row in m]
+    p = [[v * v for v in row] for row in s]
+    t = transpose(p)
+    r = [max(row) for row in t]
+    q = [sum(t[i][j] for i in range(2)) for j in range(2)]
+    y = [r[j] + q[j] for j in range(2)]
+
+    nodes = [
+        node("MatMul", ["a", "b"], ["m"], "matmul"),
+        node("Add", ["m", "c"], ["s"], "add"),
+        node("Mul", ["s", "s"], ["p"], "square"),
+        node("Transpose", ["p"], ["t"], "transpose", perm=[1, 0]),
+        node("ReduceMax", ["t"], ["r"], "max", axes=[1], keepdims=0),
+        node("ReduceSum", ["t", "axes"], ["q"], "sum", keepdims=1),
+        node("Add", ["r", "q"], ["y"], "combine"),
+    ]
+    initializers = [
+        tensor("c", [2], c),
+        tensor("axes", [1], [0], data_type=INT64),
+    ]
+    model_bytes = model("ops", nodes, initializers, [("a", [2, 3]), ("b", [3, 2])], [("y", [1, 2])])
+    write("ops", model_bytes, {"a": a, "b": b}, {"y": y})
+
+
+def unsupported():
+    """Operators outside of the supported subset"""
+    nodes = [
+        node("Tanh", ["x"], ["t"], "tanh"),
+        node("Erf", ["t"], ["y"], "erf"),
+    ]
+    m = model("unsupported", nodes, [], [("x", [1, 4])], [("y", [1, 4])])
+    write("unsupported", m, None, None)
+
+
+if __name__ == "__main__":
+    mlp()
+    conv()
+    ops()
+    unsupported()
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+/// Errors raised while importing an ONNX model
+#[derive(Debug, Error)]
+pub enum OnnxError {
+    #[error("Failed to read ONNX model: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to decode ONNX model: {0}")]
+    Decode(#[from] prost::DecodeError),
+
+    #[error("Unsupported ONNX operators: {}", .0.join(", "))]
+    UnsupportedOps(Vec<String>),
+
+    #[error("Unsupported use of `{op_type}` in node `{node}`: {reason}")]
+    Unsupported {
+        op_type: String,
+        node: String,
+        reason: String,
+    },
+
+    #[error("Input `{input}` has a dynamic dimension `{dim}`, only static shapes are supported")]
+    DynamicShape { input: String, dim: String },
+
+    #[error("Invalid ONNX model: {0}")]
+    InvalidModel(String),
+}
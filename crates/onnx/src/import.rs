@@ -0,0 +1,568 @@
+//! Mapping of ONNX nodes onto luminal operations
+
+use std::collections::HashMap;
+
+use luminair_graph::tensor::LuminairTensor;
+use luminal::prelude::*;
+
+use crate::{
+    proto::{AttributeProto, GraphProto, NodeProto, TensorProto, ValueInfoProto},
+    OnnxError, OnnxGraph,
+};
+
+/// ONNX operators the importer supports, from the default domain
+pub const SUPPORTED_OPS: &[&str] = &[
+    "Add",
+    "Conv",
+    "Gemm",
+    "MatMul",
+    "Mul",
+    "ReduceMax",
+    "ReduceSum",
+    "Relu",
+    "Reshape",
+    "Sigmoid",
+    "Softmax",
+    "Transpose",
+];
+
+/// Adds the nodes of `graph` to `cx`
+pub(crate) fn build(
+    graph: &GraphProto,
+    opset: i64,
+    cx: &mut Graph,
+) -> Result<OnnxGraph, OnnxError> {
+    let unsupported = graph
+        .node
+        .iter()
+        .filter(|node| !is_supported(node))
+        .map(|node| format!("`{}` (node `{}`)", node.op_type, node.name))
+        .collect::<Vec<_>>();
+    if !unsupported.is_empty() {
+        return Err(OnnxError::UnsupportedOps(unsupported));
+    }
+
+    let mut importer = Importer {
+        cx,
+        opset,
+        initializers: graph
+            .initializer
+            .iter()
+            .map(|tensor| (tensor.name.as_str(), tensor))
+            .collect(),
+        tensors: HashMap::new(),
+    };
+
+    // Older exporters also list the initializers among the graph inputs
+    let mut inputs = Vec::new();
+    for input in &graph.input {
+        if importer.initializers.contains_key(input.name.as_str()) {
+            continue;
+        }
+        let shape = input_shape(input)?;
+        let tensor = importer.cx.named_tensor(&input.name, expressions(&shape));
+        importer.tensors.insert(input.name.clone(), tensor);
+        inputs.push((input.name.clone(), tensor));
+    }
+
+    for node in &graph.node {
+        importer.node(node)?;
+    }
+
+    let outputs = graph
+        .output
+        .iter()
+        .map(|output| Ok((output.name.clone(), importer.tensor(&output.name)?)))
+        .collect::<Result<Vec<_>, OnnxError>>()?;
+
+    Ok(OnnxGraph { inputs, outputs })
+}
+
+fn is_supported(node: &NodeProto) -> bool {
+    (node.domain.is_empty() || node.domain == "ai.onnx")
+        && SUPPORTED_OPS.contains(&node.op_type.as_str())
+}
+
+/// Builds the luminal graph of an ONNX graph, node by node
+struct Importer<'a, 'g> {
+    cx: &'a mut Graph,
+    opset: i64,
+    initializers: HashMap<&'g str, &'g TensorProto>,
+    /// Tensors built so far, by ONNX name
+    tensors: HashMap<String, GraphTensor>,
+}
+
+impl Importer<'_, '_> {
+    /// Returns the tensor with the given name, loading initializers on first use
+    fn tensor(&mut self, name: &str) -> Result<GraphTensor, OnnxError> {
+        if let Some(tensor) = self.tensors.get(name) {
+            return Ok(*tensor);
+        }
+        let initializer = self.initializers.get(name).ok_or_else(|| {
+            OnnxError::InvalidModel(format!(
+                "tensor `{}` is neither an input, an initializer nor a node output",
+                name
+            ))
+        })?;
+        let dims = initializer
+            .dims
+            .iter()
+            .map(|&d| d as usize)
+            .collect::<Vec<_>>();
+        let tensor = self
+            .cx
+            .named_tensor(name, expressions(&dims))
+            .set(floats(initializer)?);
+        self.tensors.insert(name.to_string(), tensor);
+        Ok(tensor)
+    }
+
+    /// Returns the `index`-th input of a node
+    fn input(&mut self, node: &NodeProto, index: usize) -> Result<GraphTensor, OnnxError> {
+        self.optional_input(node, index)?.ok_or_else(|| {
+            OnnxError::InvalidModel(format!("node `{}` is missing input {}", node.name, index))
+        })
+    }
+
+    /// Returns the `index`-th input of a node, if it is given
+    fn optional_input(
+        &mut self,
+        node: &NodeProto,
+        index: usize,
+    ) -> Result<Option<GraphTensor>, OnnxError> {
+        match node.input.get(index).filter(|name| !name.is_empty()) {
+            Some(name) => self.tensor(name).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the values of an integer input of a node, which must be an initializer
+    fn ints(&self, node: &NodeProto, index: usize) -> Result<Vec<i64>, OnnxError> {
+        let name = node.input.get(index).ok_or_else(|| {
+            OnnxError::InvalidModel(format!("node `{}` is missing input {}", node.name, index))
+        })?;
+        match self.initializers.get(name.as_str()) {
+            Some(tensor) if tensor.data_type == TensorProto::INT64 => Ok(int64s(tensor)),
+            _ => Err(unsupported(
+                node,
+                format!("input `{}` must be a constant int64 initializer", name),
+            )),
+        }
+    }
+
+    fn node(&mut self, node: &NodeProto) -> Result<(), OnnxError> {
+        let output = match node.op_type.as_str() {
+            "Add" => {
+                let (a, b) = (self.input(node, 0)?, self.input(node, 1)?);
+                let (a, b) = broadcast(a, b)?;
+                a + b
+            }
+            "Mul" => {
+                let (a, b) = (self.input(node, 0)?, self.input(node, 1)?);
+                let (a, b) = broadcast(a, b)?;
+                a * b
+            }
+            "Relu" => self.input(node, 0)?.relu(),
+            "Sigmoid" => self.input(node, 0)?.sigmoid(),
+            "Softmax" => self.softmax(node)?,
+            "Gemm" => self.gemm(node)?,
+            "MatMul" => self.matmul(node)?,
+            "Reshape" => self.reshape(node)?,
+            "Transpose" => self.transpose(node)?,
+            "Conv" => self.conv(node)?,
+            "ReduceSum" | "ReduceMax" => self.reduce(node)?,
+            _ => unreachable!("unsupported operators are reported before building"),
+        };
+
+        let name = node.output.first().ok_or_else(|| {
+            OnnxError::InvalidModel(format!("node `{}` has no output", node.name))
+        })?;
+        self.tensors.insert(name.clone(), output);
+        Ok(())
+    }
+
+    /// `Y = alpha * A' * B' + beta * C`, where `'` is an optional transposition
+    fn gemm(&mut self, node: &NodeProto) -> Result<GraphTensor, OnnxError> {
+        let (mut a, mut b) = (self.input(node, 0)?, self.input(node, 1)?);
+        if shape(a)?.len() != 2 || shape(b)?.len() != 2 {
+            return Err(OnnxError::InvalidModel(format!(
+                "Gemm node `{}` expects 2D inputs",
+                node.name
+            )));
+        }
+        if attr_int(node, "transA", 0) != 0 {
+            a = a.permute((1, 0));
+        }
+        if attr_int(node, "transB", 0) != 0 {
+            b = b.permute((1, 0));
+        }
+
+        let mut y = a.matmul(b);
+        let alpha = attr_float(node, "alpha", 1.0);
+        if alpha != 1.0 {
+            y = y * alpha;
+        }
+        if let Some(mut c) = self.optional_input(node, 2)? {
+            let beta = attr_float(node, "beta", 1.0);
+            if beta != 1.0 {
+                c = c * beta;
+            }
+            let (y_full, c) = broadcast(y, c)?;
+            y = y_full + c;
+        }
+        Ok(y)
+    }
+
+    fn matmul(&mut self, node: &NodeProto) -> Result<GraphTensor, OnnxError> {
+        let (a, b) = (self.input(node, 0)?, self.input(node, 1)?);
+        let (rank_a, rank_b) = (shape(a)?.len(), shape(b)?.len());
+        if rank_a < 2 || (rank_b != 2 && rank_b != rank_a) {
+            return Err(unsupported(
+                node,
+                format!("matmul of a rank {} by a rank {} tensor", rank_a, rank_b),
+            ));
+        }
+        Ok(a.matmul(b))
+    }
+
+    fn reshape(&mut self, node: &NodeProto) -> Result<GraphTensor, OnnxError> {
+        let x = self.input(node, 0)?;
+        let spec = self.ints(node, 1)?;
+        let x_shape = shape(x)?;
+        let total = x_shape.iter().product::<usize>();
+
+        // `0` copies the input dimension, `-1` is inferred from the others
+        let mut dims = Vec::with_capacity(spec.len());
+        let mut inferred = None;
+        for (i, &d) in spec.iter().enumerate() {
+            match d {
+                0 => dims.push(x_shape.get(i).copied().ok_or_else(|| {
+                    OnnxError::InvalidModel(format!(
+                        "Reshape node `{}` copies missing dimension {}",
+                        node.name, i
+                    ))
+                })?),
+                -1 if inferred.is_none() => {
+                    inferred = Some(i);
+                    dims.push(1);
+                }
+                d if d > 0 => dims.push(d as usize),
+                _ => {
+                    return Err(OnnxError::InvalidModel(format!(
+                        "Reshape node `{}` has an invalid shape {:?}",
+                        node.name, spec
+                    )))
+                }
+            }
+        }
+        if let Some(i) = inferred {
+            dims[i] = total / dims.iter().product::<usize>().max(1);
+        }
+        if dims.iter().product::<usize>() != total {
+            return Err(OnnxError::InvalidModel(format!(
+                "Reshape node `{}` can't reshape {:?} into {:?}",
+                node.name, x_shape, spec
+            )));
+        }
+        Ok(reshape(x, &dims))
+    }
+
+    fn transpose(&mut self, node: &NodeProto) -> Result<GraphTensor, OnnxError> {
+        let mut x = self.input(node, 0)?;
+        let rank = shape(x)?.len();
+        let perm = match attr_ints(node, "perm") {
+            Some(perm) => perm.iter().map(|&a| a as usize).collect::<Vec<_>>(),
+            None => (0..rank).rev().collect(),
+        };
+        x.shape.permute(&perm);
+        Ok(x)
+    }
+
+    fn softmax(&mut self, node: &NodeProto) -> Result<GraphTensor, OnnxError> {
+        let x = self.input(node, 0)?;
+        let rank = shape(x)?.len();
+        let default_axis = if self.opset >= 13 { -1 } else { 1 };
+        let axis = normalize_axis(node, attr_int(node, "axis", default_axis), rank)?;
+        // Before opset 13, the input is flattened to 2D around the axis
+        if self.opset < 13 && axis != rank - 1 {
+            return Err(unsupported(
+                node,
+                "softmax over a flattened input, only the last axis is supported before opset 13",
+            ));
+        }
+        Ok(x.softmax(axis))
+    }
+
+    fn reduce(&mut self, node: &NodeProto) -> Result<GraphTensor, OnnxError> {
+        let x = self.input(node, 0)?;
+        let x_shape = shape(x)?;
+
+        // Axes moved from an attribute to an input in recent opsets
+        let axes = match attr_ints(node, "axes") {
+            Some(axes) => axes,
+            None if node.input.get(1).is_some_and(|name| !name.is_empty()) => self.ints(node, 1)?,
+            None => vec![],
+        };
+        if axes.is_empty() && attr_int(node, "noop_with_empty_axes", 0) != 0 {
+            return Ok(x);
+        }
+        let mut axes = if axes.is_empty() {
+            (0..x_shape.len()).collect::<Vec<_>>()
+        } else {
+            axes.iter()
+                .map(|&a| normalize_axis(node, a, x_shape.len()))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        axes.sort_unstable();
+        axes.dedup();
+
+        let mut y = x;
+        for &axis in axes.iter().rev() {
+            y = match node.op_type.as_str() {
+                "ReduceSum" => y.sum_reduce(axis),
+                _ => y.max_reduce(axis),
+            };
+        }
+
+        if attr_int(node, "keepdims", 1) != 0 {
+            let dims = x_shape
+                .iter()
+                .enumerate()
+                .map(|(i, &d)| if axes.contains(&i) { 1 } else { d })
+                .collect::<Vec<_>>();
+            y = reshape(y, &dims);
+        }
+        Ok(y)
+    }
+
+    /// 2D convolution of a single `(1, C, H, W)` image, through the proven im2col view
+    fn conv(&mut self, node: &NodeProto) -> Result<GraphTensor, OnnxError> {
+        let (x, w) = (self.input(node, 0)?, self.input(node, 1)?);
+        let bias = self.optional_input(node, 2)?;
+        let (x_shape, w_shape) = (shape(x)?, shape(w)?);
+
+        if x_shape.len() != 4 || w_shape.len() != 4 {
+            return Err(unsupported(node, "only 2D convolutions are supported"));
+        }
+        if x_shape[0] != 1 {
+            return Err(unsupported(node, "only a batch size of 1 is supported"));
+        }
+        if attr_int(node, "group", 1) != 1 {
+            return Err(unsupported(node, "grouped convolutions are not supported"));
+        }
+        if attr_ints(node, "dilations").is_some_and(|d| d.iter().any(|&d| d != 1)) {
+            return Err(unsupported(node, "dilated convolutions are not supported"));
+        }
+        if attr_string(node, "auto_pad").is_some_and(|pad| pad != "NOTSET") {
+            return Err(unsupported(node, "only explicit `pads` are supported"));
+        }
+
+        let strides = attr_ints(node, "strides").unwrap_or_else(|| vec![1, 1]);
+        let pads = attr_ints(node, "pads").unwrap_or_else(|| vec![0; 4]);
+        if strides.len() != 2 || pads.len() != 4 {
+            return Err(OnnxError::InvalidModel(format!(
+                "Conv node `{}` has invalid strides or pads",
+                node.name
+            )));
+        }
+        if pads[0] != pads[2] || pads[1] != pads[3] {
+            return Err(unsupported(node, "asymmetric padding is not supported"));
+        }
+
+        let y = reshape(x, &x_shape[1..]).conv2d(
+            w,
+            (strides[0] as usize, strides[1] as usize),
+            (pads[0] as usize, pads[1] as usize),
+        );
+        let y_shape = shape(y)?;
+        let y = match bias {
+            Some(bias) => y + bias.expand(1, y_shape[1]).expand(2, y_shape[2]),
+            None => y,
+        };
+        Ok(reshape(y, &[1, y_shape[0], y_shape[1], y_shape[2]]))
+    }
+}
+
+/// Broadcasts two tensors to a common shape, following numpy rules
+fn broadcast(a: GraphTensor, b: GraphTensor) -> Result<(GraphTensor, GraphTensor), OnnxError> {
+    let (a_shape, b_shape) = (shape(a)?, shape(b)?);
+    let rank = a_shape.len().max(b_shape.len());
+    let dim = |s: &[usize], i: usize| {
+        if i + s.len() < rank {
+            1
+        } else {
+            s[i + s.len() - rank]
+        }
+    };
+
+    let mut target = Vec::with_capacity(rank);
+    for i in 0..rank {
+        let (da, db) = (dim(&a_shape, i), dim(&b_shape, i));
+        if da != db && da != 1 && db != 1 {
+            return Err(OnnxError::InvalidModel(format!(
+                "shapes {:?} and {:?} can't be broadcast together",
+                a_shape, b_shape
+            )));
+        }
+        target.push(da.max(db));
+    }
+    Ok((broadcast_to(a, &target)?, broadcast_to(b, &target)?))
+}
+
+/// Expands a tensor to a broadcast-compatible `target` shape
+fn broadcast_to(t: GraphTensor, target: &[usize]) -> Result<GraphTensor, OnnxError> {
+    let t_shape = shape(t)?;
+    let offset = target.len() - t_shape.len();
+    let is_broadcast = |axis: usize| axis < offset || t_shape[axis - offset] != target[axis];
+
+    // Size-one dimensions being broadcast are dropped, then every broadcast dimension is
+    // expanded in place
+    let kept = (offset..target.len())
+        .filter(|&axis| !is_broadcast(axis))
+        .map(|axis| target[axis])
+        .collect::<Vec<_>>();
+    let mut t = if kept.len() != t_shape.len() {
+        reshape(t, &kept)
+    } else {
+        t
+    };
+    for axis in (0..target.len()).filter(|&axis| is_broadcast(axis)) {
+        t = t.expand(axis, target[axis]);
+    }
+    Ok(t)
+}
+
+fn reshape(t: GraphTensor, dims: &[usize]) -> GraphTensor {
+    t.contiguous().reshape(expressions(dims))
+}
+
+/// Static shape of a tensor built from the model
+fn shape(t: GraphTensor) -> Result<Vec<usize>, OnnxError> {
+    t.dims()
+        .iter()
+        .map(|d| {
+            d.to_usize().ok_or_else(|| {
+                OnnxError::InvalidModel(format!(
+                    "a tensor has a dynamic dimension {:?}, only static shapes are supported",
+                    d
+                ))
+            })
+        })
+        .collect()
+}
+
+fn expressions(dims: &[usize]) -> Vec<Expression> {
+    dims.iter().map(|&d| d.into()).collect()
+}
+
+/// Resolves a possibly negative axis of a node, which must be in `-rank..rank`
+fn normalize_axis(node: &NodeProto, axis: i64, rank: usize) -> Result<usize, OnnxError> {
+    let rank = rank as i64;
+    if !(-rank..rank).contains(&axis) {
+        return Err(unsupported(
+            node,
+            format!("axis {} is out of range for a rank {} tensor", axis, rank),
+        ));
+    }
+    Ok(axis.rem_euclid(rank) as usize)
+}
+
+/// Static shape of a float graph input
+fn input_shape(input: &ValueInfoProto) -> Result<Vec<usize>, OnnxError> {
+    let tensor_type = input
+        .r#type
+        .as_ref()
+        .and_then(|t| t.tensor_type.as_ref())
+        .filter(|t| t.elem_type == TensorProto::FLOAT)
+        .ok_or_else(|| {
+            OnnxError::InvalidModel(format!("input `{}` must be a float tensor", input.name))
+        })?;
+    let dims = tensor_type
+        .shape
+        .as_ref()
+        .map(|shape| shape.dim.as_slice())
+        .unwrap_or_default();
+
+    dims.iter()
+        .map(|dim| match (dim.dim_value, &dim.dim_param) {
+            (Some(value), _) if value > 0 => Ok(value as usize),
+            (_, Some(param)) => Err(OnnxError::DynamicShape {
+                input: input.name.clone(),
+                dim: param.clone(),
+            }),
+            _ => Err(OnnxError::InvalidModel(format!(
+                "input `{}` has an unknown dimension",
+                input.name
+            ))),
+        })
+        .collect()
+}
+
+fn floats(tensor: &TensorProto) -> Result<Vec<f32>, OnnxError> {
+    if tensor.data_type != TensorProto::FLOAT {
+        return Err(OnnxError::InvalidModel(format!(
+            "initializer `{}` must be a float tensor, got data type {}",
+            tensor.name, tensor.data_type
+        )));
+    }
+    let values = if tensor.raw_data.is_empty() {
+        tensor.float_data.clone()
+    } else {
+        tensor
+            .raw_data
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect()
+    };
+
+    let len = tensor.dims.iter().product::<i64>() as usize;
+    if values.len() != len {
+        return Err(OnnxError::InvalidModel(format!(
+            "initializer `{}` holds {} values for {} elements",
+            tensor.name,
+            values.len(),
+            len
+        )));
+    }
+    Ok(values)
+}
+
+fn int64s(tensor: &TensorProto) -> Vec<i64> {
+    if tensor.raw_data.is_empty() {
+        tensor.int64_data.clone()
+    } else {
+        tensor
+            .raw_data
+            .chunks_exact(8)
+            .map(|b| i64::from_le_bytes(b.try_into().unwrap()))
+            .collect()
+    }
+}
+
+fn unsupported(node: &NodeProto, reason: impl Into<String>) -> OnnxError {
+    OnnxError::Unsupported {
+        op_type: node.op_type.clone(),
+        node: node.name.clone(),
+        reason: reason.into(),
+    }
+}
+
+fn attribute<'n>(node: &'n NodeProto, name: &str) -> Option<&'n AttributeProto> {
+    node.attribute.iter().find(|attr| attr.name == name)
+}
+
+fn attr_int(node: &NodeProto, name: &str, default: i64) -> i64 {
+    attribute(node, name).map_or(default, |attr| attr.i)
+}
+
+fn attr_float(node: &NodeProto, name: &str, default: f32) -> f32 {
+    attribute(node, name).map_or(default, |attr| attr.f)
+}
+
+fn attr_ints(node: &NodeProto, name: &str) -> Option<Vec<i64>> {
+    attribute(node, name).map(|attr| attr.ints.clone())
+}
+
+fn attr_string(node: &NodeProto, name: &str) -> Option<String> {
+    attribute(node, name).map(|attr| String::from_utf8_lossy(&attr.s).into_owned())
+}
@@ -0,0 +1,101 @@
+//! ONNX model import for LuminAIR
+//!
+//! Maps a supported subset of ONNX operators onto luminal `GraphTensor` operations, so that
+//! models exported to ONNX can be compiled with the `StwoCompiler` and proven without being
+//! translated by hand.
+//!
+//! ```ignore
+//! let model = OnnxModel::from_file("model.onnx")?;
+//! let mut cx = Graph::new();
+//! let onnx = model.build(&mut cx)?;
+//! onnx.input("x").unwrap().set(data);
+//! let mut y = onnx.output("y").unwrap().retrieve();
+//! cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut y);
+//! ```
+
+use std::path::Path;
+
+use luminal::prelude::*;
+use prost::Message;
+
+mod error;
+mod import;
+mod proto;
+
+#[cfg(test)]
+mod tests;
+
+pub use error::OnnxError;
+pub use import::SUPPORTED_OPS;
+
+/// A decoded ONNX model
+#[derive(Clone, Debug)]
+pub struct OnnxModel {
+    model: proto::ModelProto,
+}
+
+/// Tensors of a model built into a luminal graph
+#[derive(Clone)]
+pub struct OnnxGraph {
+    /// Runtime inputs of the model, to be set before execution
+    pub inputs: Vec<(String, GraphTensor)>,
+    /// Outputs of the model
+    pub outputs: Vec<(String, GraphTensor)>,
+}
+
+impl OnnxModel {
+    /// Decodes a model from the bytes of an `.onnx` file
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, OnnxError> {
+        Ok(Self {
+            model: proto::ModelProto::decode(bytes)?,
+        })
+    }
+
+    /// Reads and decodes an `.onnx` file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, OnnxError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Version of the default ONNX operator set the model was exported with
+    pub fn opset(&self) -> i64 {
+        self.model
+            .opset_import
+            .iter()
+            .find(|opset| opset.domain.is_empty() || opset.domain == "ai.onnx")
+            .map(|opset| opset.version)
+            .unwrap_or(1)
+    }
+
+    /// Adds the model to `cx`
+    ///
+    /// Initializers become named tensors holding their values, graph inputs become named
+    /// tensors to be set at runtime. Every unsupported operator of the model is reported at
+    /// once, before anything is added to the graph.
+    pub fn build(&self, cx: &mut Graph) -> Result<OnnxGraph, OnnxError> {
+        let graph = self
+            .model
+            .graph
+            .as_ref()
+            .ok_or_else(|| OnnxError::InvalidModel("the model has no graph".to_string()))?;
+        import::build(graph, self.opset(), cx)
+    }
+}
+
+impl OnnxGraph {
+    /// Returns the runtime input with the given name
+    pub fn input(&self, name: &str) -> Option<GraphTensor> {
+        find(&self.inputs, name)
+    }
+
+    /// Returns the output with the given name
+    pub fn output(&self, name: &str) -> Option<GraphTensor> {
+        find(&self.outputs, name)
+    }
+}
+
+fn find(tensors: &[(String, GraphTensor)], name: &str) -> Option<GraphTensor> {
+    tensors
+        .iter()
+        .find(|(tensor_name, _)| tensor_name == name)
+        .map(|(_, tensor)| *tensor)
+}
@@ -0,0 +1,178 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use luminair_graph::{graph::LuminairGraph, StwoCompiler};
+use luminair_prover::prover::prove;
+use luminair_verifier::verifier::verify;
+use luminal::prelude::*;
+use luminal_cpu::CPUCompiler;
+use serde::Deserialize;
+
+use crate::{
+    proto::{
+        AttributeProto, Dimension, GraphProto, ModelProto, NodeProto, TensorProto,
+        TensorShapeProto, TensorTypeProto, TypeProto, ValueInfoProto,
+    },
+    OnnxError, OnnxModel,
+};
+
+/// Inputs of a fixture model and its expected outputs, computed by `fixtures/generate.py`
+#[derive(Deserialize)]
+struct Expected {
+    inputs: HashMap<String, Vec<f32>>,
+    outputs: HashMap<String, Vec<f32>>,
+}
+
+fn fixture(file: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(file)
+}
+
+fn load_fixture(name: &str) -> (OnnxModel, Expected) {
+    let model = OnnxModel::from_file(fixture(&format!("{name}.onnx"))).expect("Decoding failed");
+    let json = std::fs::read_to_string(fixture(&format!("{name}.json"))).unwrap();
+    (model, serde_json::from_str(&json).unwrap())
+}
+
+/// Imports a model with the fixture inputs set, returning the graph, its retrieved output and
+/// the expected value of that output
+fn import(model: &OnnxModel, expected: &Expected) -> (Graph, GraphTensor, Vec<f32>) {
+    let mut cx = Graph::new();
+    let onnx = model.build(&mut cx).expect("Import failed");
+    for (name, tensor) in &onnx.inputs {
+        tensor.set(expected.inputs[name].clone());
+    }
+    let (name, output) = &onnx.outputs[0];
+    (cx, output.retrieve(), expected.outputs[name].clone())
+}
+
+fn assert_close(actual: &[f32], expected: &[f32], threshold: f32) {
+    assert_eq!(
+        actual.len(),
+        expected.len(),
+        "Number of elements doesn't match"
+    );
+    for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            (a - e).abs() <= threshold,
+            "Mismatch at index {i}: {a} vs expected {e} (threshold {threshold})"
+        );
+    }
+}
+
+/// Checks the import of a fixture model on the CPUCompiler, then proves and verifies it
+/// with the StwoCompiler
+fn run_fixture(name: &str, threshold: f32) {
+    let (model, expected) = load_fixture(name);
+
+    // CPUCompiler, checking the import itself
+    let (mut cx_cpu, mut out_cpu, expected_output) = import(&model, &expected);
+    cx_cpu.compile(<(GenericCompiler, CPUCompiler)>::default(), &mut out_cpu);
+    cx_cpu.execute();
+    assert_close(&out_cpu.data(), &expected_output, 1e-4);
+
+    // Compilation and execution using StwoCompiler
+    let (mut cx, mut out, expected_output) = import(&model, &expected);
+    cx.compile(<(GenericCompiler, StwoCompiler)>::default(), &mut out);
+    let mut settings = cx.gen_circuit_settings();
+    out.drop();
+    let trace = cx
+        .gen_trace(&mut settings)
+        .expect("Trace generation failed");
+    let proof = prove(trace, settings.clone()).expect("Proof generation failed");
+    verify(proof, settings).expect("Proof verification failed");
+
+    assert_close(&out.data(), &expected_output, threshold);
+}
+
+#[test]
+fn test_mlp() {
+    run_fixture("mlp", 2e-2);
+}
+
+#[test]
+fn test_conv() {
+    run_fixture("conv", 2e-2);
+}
+
+#[test]
+fn test_ops() {
+    run_fixture("ops", 1e-2);
+}
+
+#[test]
+fn test_unsupported_ops() {
+    let model = OnnxModel::from_file(fixture("unsupported.onnx")).expect("Decoding failed");
+    let mut cx = Graph::new();
+
+    match model.build(&mut cx) {
+        Err(OnnxError::UnsupportedOps(ops)) => {
+            assert_eq!(ops, vec!["`Tanh` (node `tanh`)", "`Erf` (node `erf`)"]);
+        }
+        Err(e) => panic!("Unexpected error: {e}"),
+        Ok(_) => panic!("Unsupported operators were imported"),
+    }
+    // Nothing is added to the graph when the model can't be imported
+    assert_eq!(cx.graph.node_count(), 0);
+}
+
+#[test]
+fn test_invalid_bytes() {
+    assert!(matches!(
+        OnnxModel::from_bytes(&[0xff, 0xff, 0xff]),
+        Err(OnnxError::Decode(_))
+    ));
+}
+
+#[test]
+fn test_axis_out_of_range() {
+    // Softmax of a (2, 3) input over axis 2
+    let x = ValueInfoProto {
+        name: "x".to_string(),
+        r#type: Some(TypeProto {
+            tensor_type: Some(TensorTypeProto {
+                elem_type: TensorProto::FLOAT,
+                shape: Some(TensorShapeProto {
+                    dim: [2, 3]
+                        .map(|value| Dimension {
+                            dim_value: Some(value),
+                            dim_param: None,
+                        })
+                        .to_vec(),
+                }),
+            }),
+        }),
+    };
+    let softmax = NodeProto {
+        input: vec!["x".to_string()],
+        output: vec!["y".to_string()],
+        name: "softmax".to_string(),
+        op_type: "Softmax".to_string(),
+        attribute: vec![AttributeProto {
+            name: "axis".to_string(),
+            i: 2,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let model = OnnxModel {
+        model: ModelProto {
+            graph: Some(GraphProto {
+                node: vec![softmax],
+                input: vec![x],
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    };
+
+    let mut cx = Graph::new();
+    match model.build(&mut cx) {
+        Err(OnnxError::Unsupported { node, reason, .. }) => {
+            assert_eq!(node, "softmax");
+            assert_eq!(reason, "axis 2 is out of range for a rank 2 tensor");
+        }
+        Err(e) => panic!("Unexpected error: {e}"),
+        Ok(_) => panic!("An out of range axis was imported"),
+    }
+}
@@ -4,3 +4,7 @@
 //! into a single prelude module for easier usage.
 
 pub mod prelude;
+pub mod weights;
+
+#[cfg(test)]
+mod tests;
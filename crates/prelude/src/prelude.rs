@@ -12,15 +12,36 @@ pub use luminal_nn::*;
 // --- luminair_graph ---
 /// Re-exports the LuminAIR graph trait for computational graph operations
 pub use luminair_graph::graph::LuminairGraph;
+/// Re-exports the inputs and trace of batch proving
+pub use luminair_graph::graph::{BatchInputs, BatchTrace};
+/// Re-exports LuminAIR-specific tensor operations such as gather
+pub use luminair_graph::tensor::LuminairTensor;
 /// Re-exports the STWO compiler configuration
 pub use luminair_graph::StwoCompiler;
+/// Re-exports the portable graph IR used to ship compiled models as files
+pub use luminair_graph::ir::GraphIr;
 
 // --- luminair_prover ---
 /// Re-exports the main proving function
-pub use luminair_prover::{prover::prove, LuminairProof};
+pub use luminair_prover::{
+    prover::{prove, prove_with_io, prove_with_key},
+    LuminairProof,
+};
+/// Re-exports the single-file proof bundle format
+pub use luminair_prover::bundle::{BundleMetadata, ProofBundle, PublicIo};
+/// Re-exports the proving and verifying keys
+pub use luminair_prover::keys::{ProvingKey, VerifyingKey};
+/// Re-exports segmented proving of graphs too large for a single trace
+pub use luminair_prover::{prover::prove_segments, segment::SegmentedProof};
 
 // --- luminair_verifier ---
 /// Re-exports core AIR components and circuit settings
 pub use luminair_air::{pie::LuminairPie, settings::CircuitSettings};
 /// Re-exports the verification function
-pub use luminair_verifier::verifier::verify;
+pub use luminair_verifier::verifier::{
+    verify, verify_bundle, verify_segments, verify_with_io, verify_with_key,
+};
+
+// --- weights ---
+/// Re-exports the safetensors / npy weight loader for luminal_nn modules
+pub use crate::weights::{WeightError, Weights};
@@ -0,0 +1,163 @@
+use std::path::PathBuf;
+
+use luminal::prelude::*;
+use luminal_nn::Linear;
+
+use crate::weights::{WeightError, Weights};
+
+/// Creates an empty directory unique to the calling test
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("luminair-weights-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Encodes a little-endian `f32` array in the `.npy` format
+fn npy(shape: &[usize], data: &[f32]) -> Vec<u8> {
+    let shape = shape
+        .iter()
+        .map(|dim| format!("{dim},"))
+        .collect::<String>();
+    let mut header = format!("{{'descr': '<f4', 'fortran_order': False, 'shape': ({shape}), }}");
+    while (header.len() + 11) % 64 != 0 {
+        header.push(' ');
+    }
+    header.push('\n');
+
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend((header.len() as u16).to_le_bytes());
+    bytes.extend(header.as_bytes());
+    bytes.extend(data.iter().flat_map(|x| x.to_le_bytes()));
+    bytes
+}
+
+/// Encodes `f32` tensors in the safetensors format
+fn safetensors(tensors: &[(&str, &[usize], &[f32])]) -> Vec<u8> {
+    let mut entries = Vec::new();
+    let mut data = Vec::new();
+    for (name, shape, values) in tensors {
+        let start = data.len();
+        data.extend(values.iter().flat_map(|x| x.to_le_bytes()));
+        entries.push(format!(
+            "\"{name}\":{{\"dtype\":\"F32\",\"shape\":{shape:?},\"data_offsets\":[{start},{}]}}",
+            data.len()
+        ));
+    }
+    let header = format!("{{{}}}", entries.join(","));
+
+    let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+    bytes.extend(header.as_bytes());
+    bytes.extend(data);
+    bytes
+}
+
+const WEIGHT: [f32; 6] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+const BIAS: [f32; 3] = [0.5, -0.5, 1.0];
+
+/// Runs a `Linear` layer of 2 inputs and 3 outputs on `[1, 1]` with weights loaded under `fc`
+fn run_linear(weights: &Weights) -> Result<Vec<f32>, WeightError> {
+    let mut cx = Graph::new();
+    let model = Linear::new(2, 3, true, &mut cx);
+    let input = cx.tensor((1, 2)).set(vec![1.0, 1.0]);
+    let output = model.forward(input).retrieve();
+
+    weights.load_module(&model, "fc", &mut cx)?;
+    cx.execute();
+    Ok(output.data())
+}
+
+#[test]
+fn test_load_npy_dir() {
+    let dir = test_dir("npy");
+    std::fs::write(dir.join("fc.weight.npy"), npy(&[2, 3], &WEIGHT)).unwrap();
+    std::fs::write(dir.join("fc.bias.npy"), npy(&[3], &BIAS)).unwrap();
+    std::fs::write(dir.join("README.md"), "not a tensor").unwrap();
+
+    let weights = Weights::from_npy_dir(&dir).unwrap();
+    let mut names = weights.names().collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, vec!["fc.bias", "fc.weight"]);
+    assert_eq!(weights.get("fc.weight").unwrap().shape, vec![2, 3]);
+
+    assert_eq!(run_linear(&weights).unwrap(), vec![5.5, 6.5, 10.0]);
+}
+
+#[test]
+fn test_load_safetensors() {
+    let dir = test_dir("safetensors");
+    let path = dir.join("model.safetensors");
+    let bytes = safetensors(&[("fc.weight", &[2, 3], &WEIGHT), ("fc.bias", &[3], &BIAS)]);
+    std::fs::write(&path, bytes).unwrap();
+
+    let weights = Weights::from_safetensors(&path).unwrap();
+    assert_eq!(run_linear(&weights).unwrap(), vec![5.5, 6.5, 10.0]);
+}
+
+#[test]
+fn test_load_tensor() {
+    let dir = test_dir("tensor");
+    let path = dir.join("model.safetensors");
+    std::fs::write(&path, safetensors(&[("x", &[2, 3], &WEIGHT)])).unwrap();
+    let weights = Weights::from_safetensors(&path).unwrap();
+
+    let mut cx = Graph::new();
+    let x = cx.named_tensor("x", (2, 3)).retrieve();
+    weights.load_tensor("x", x).unwrap();
+    cx.execute();
+    assert_eq!(x.data(), WEIGHT.to_vec());
+
+    let y = cx.named_tensor("y", (3, 2));
+    assert!(matches!(
+        weights.load_tensor("x", y),
+        Err(WeightError::ShapeMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_missing_tensor() {
+    let dir = test_dir("missing");
+    std::fs::write(dir.join("fc.weight.npy"), npy(&[2, 3], &WEIGHT)).unwrap();
+
+    let weights = Weights::from_npy_dir(&dir).unwrap();
+    match run_linear(&weights) {
+        Err(WeightError::MissingTensor(name)) => assert_eq!(name, "fc.bias"),
+        other => panic!("Unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn test_shape_mismatch() {
+    let dir = test_dir("mismatch");
+    std::fs::write(dir.join("fc.weight.npy"), npy(&[3, 3], &[0.0; 9])).unwrap();
+    std::fs::write(dir.join("fc.bias.npy"), npy(&[3], &BIAS)).unwrap();
+
+    let weights = Weights::from_npy_dir(&dir).unwrap();
+    match run_linear(&weights) {
+        Err(WeightError::ShapeMismatch {
+            name,
+            expected,
+            found,
+        }) => {
+            assert_eq!(name, "fc.weight");
+            assert_eq!(expected, vec![2, 3]);
+            assert_eq!(found, vec![3, 3]);
+        }
+        other => panic!("Unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn test_invalid_file() {
+    let dir = test_dir("invalid");
+    std::fs::write(dir.join("fc.weight.npy"), b"not a npy file").unwrap();
+
+    assert!(matches!(
+        Weights::from_npy_dir(&dir),
+        Err(WeightError::Format { .. })
+    ));
+    assert!(matches!(
+        Weights::from_safetensors(dir.join("missing.safetensors")),
+        Err(WeightError::Io { .. })
+    ));
+}
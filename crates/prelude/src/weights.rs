@@ -0,0 +1,311 @@
+//! Loading of model weights from safetensors files or directories of `.npy` files
+//!
+//! Tensors are bound to the parameters of `luminal_nn` modules (or of any module implementing
+//! `SerializeModule`) by name. Parameter paths are joined with dots, as in a PyTorch
+//! `state_dict`, so the `weight` of a `Linear` serialized as `layer1` is read from the tensor
+//! named `layer1.weight`.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use luminal::{
+    module::param_dict,
+    op::Function,
+    prelude::{petgraph::Direction, *},
+};
+use safetensors::{Dtype, SafeTensors};
+use thiserror::Error;
+
+/// Errors raised while loading weights
+#[derive(Debug, Error)]
+pub enum WeightError {
+    #[error("Failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to parse {}: {reason}", path.display())]
+    Format { path: PathBuf, reason: String },
+
+    #[error("Tensor `{name}` has unsupported data type {dtype}, expected a float tensor")]
+    UnsupportedDtype { name: String, dtype: String },
+
+    #[error("Tensor `{0}` is missing from the weights")]
+    MissingTensor(String),
+
+    #[error("Tensor `{name}` has shape {found:?} but {expected:?} is expected")]
+    ShapeMismatch {
+        name: String,
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+}
+
+/// A float tensor read from a weights file
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightTensor {
+    pub shape: Vec<usize>,
+    pub data: Vec<f32>,
+}
+
+/// Named tensors read from a safetensors file or a directory of `.npy` files
+#[derive(Clone, Debug, Default)]
+pub struct Weights {
+    tensors: HashMap<String, WeightTensor>,
+}
+
+impl Weights {
+    /// Reads every tensor of a safetensors file, converting them to `f32`
+    pub fn from_safetensors<P: AsRef<Path>>(path: P) -> Result<Self, WeightError> {
+        let path = path.as_ref();
+        let bytes = read(path)?;
+        let file = SafeTensors::deserialize(&bytes).map_err(|e| WeightError::Format {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+
+        let mut tensors = HashMap::new();
+        for (name, view) in file.tensors() {
+            let data = match view.dtype() {
+                Dtype::F32 => decode(view.data(), f32::from_le_bytes),
+                Dtype::F64 => decode(view.data(), |b| f64::from_le_bytes(b) as f32),
+                Dtype::F16 => decode(view.data(), |b| f16_to_f32(u16::from_le_bytes(b))),
+                Dtype::BF16 => decode(view.data(), |b| {
+                    f32::from_bits((u16::from_le_bytes(b) as u32) << 16)
+                }),
+                dtype => {
+                    return Err(WeightError::UnsupportedDtype {
+                        name,
+                        dtype: format!("{:?}", dtype),
+                    })
+                }
+            };
+            let shape = view.shape().to_vec();
+            tensors.insert(name, WeightTensor { shape, data });
+        }
+        Ok(Self { tensors })
+    }
+
+    /// Reads every `.npy` file of a directory, naming each tensor after its file stem
+    pub fn from_npy_dir<P: AsRef<Path>>(dir: P) -> Result<Self, WeightError> {
+        let dir = dir.as_ref();
+        let entries = fs::read_dir(dir).map_err(|source| WeightError::Io {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+
+        let mut tensors = HashMap::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|source| WeightError::Io {
+                    path: dir.to_path_buf(),
+                    source,
+                })?
+                .path();
+            if path.extension().is_some_and(|ext| ext == "npy") {
+                let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+                tensors.insert(name, read_npy(&path)?);
+            }
+        }
+        Ok(Self { tensors })
+    }
+
+    /// Returns the tensor with the given name
+    pub fn get(&self, name: &str) -> Option<&WeightTensor> {
+        self.tensors.get(name)
+    }
+
+    /// Names of the tensors, in no particular order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.tensors.keys().map(String::as_str)
+    }
+
+    /// Sets the value of a single tensor from the weight named `name`
+    pub fn load_tensor(&self, name: &str, tensor: GraphTensor) -> Result<(), WeightError> {
+        let weight = self.tensor(name)?;
+        let expected = tensor
+            .dims()
+            .iter()
+            .map(|d| d.to_usize().unwrap_or_default())
+            .collect::<Vec<_>>();
+        if weight.shape != expected {
+            return Err(WeightError::ShapeMismatch {
+                name: name.to_string(),
+                expected,
+                found: weight.shape.clone(),
+            });
+        }
+        tensor.set(weight.data.clone());
+        Ok(())
+    }
+
+    /// Sets every parameter of `module` from the weights, reading the parameter `path` of
+    /// the module from the tensor `{prefix}.{path}`
+    ///
+    /// Each weight must have the shape its parameter is used with in the graph, or at least
+    /// its number of elements when the parameter is only used through a reshape. Shapes are
+    /// only checked for parameters already consumed, so modules should be loaded after their
+    /// forward pass is built and before the graph is compiled.
+    pub fn load_module<M: SerializeModule>(
+        &self,
+        module: &M,
+        prefix: &str,
+        graph: &mut Graph,
+    ) -> Result<(), WeightError> {
+        for (path, node) in param_dict(module) {
+            let path = path.replace('/', ".");
+            let name = if prefix.is_empty() {
+                path
+            } else {
+                format!("{prefix}.{path}")
+            };
+            let weight = self.tensor(&name)?;
+
+            if let Some(expected) = param_shape(graph, node) {
+                let numel = |shape: &[usize]| shape.iter().product::<usize>();
+                if weight.shape != expected && numel(&weight.shape) != numel(&expected) {
+                    return Err(WeightError::ShapeMismatch {
+                        name,
+                        expected,
+                        found: weight.shape.clone(),
+                    });
+                }
+            }
+
+            let function = graph
+                .graph
+                .node_weight_mut(node)
+                .and_then(|op| op.as_any_mut().downcast_mut::<Function>())
+                .expect("Module parameters are loaded tensors");
+            let data = weight.data.clone();
+            function.1 = Box::new(move |_| vec![Tensor::new(data.clone())]);
+        }
+        Ok(())
+    }
+
+    fn tensor(&self, name: &str) -> Result<&WeightTensor, WeightError> {
+        self.tensors
+            .get(name)
+            .ok_or_else(|| WeightError::MissingTensor(name.to_string()))
+    }
+}
+
+/// Shape of a parameter as read by its first consumer, with expanded dimensions removed
+fn param_shape(graph: &Graph, node: NodeIndex) -> Option<Vec<usize>> {
+    let (_, _, shape) = graph
+        .graph
+        .edges_directed(node, Direction::Outgoing)
+        .find_map(|edge| edge.weight().as_data())?;
+    Some(
+        shape
+            .dims
+            .iter()
+            .zip(shape.fake.iter())
+            .filter(|(_, fake)| !**fake)
+            .map(|(dim, _)| dim.to_usize().unwrap_or_default())
+            .collect(),
+    )
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, WeightError> {
+    fs::read(path).map_err(|source| WeightError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Decodes little-endian values of `N` bytes
+fn decode<const N: usize>(bytes: &[u8], f: impl Fn([u8; N]) -> f32) -> Vec<f32> {
+    bytes
+        .chunks_exact(N)
+        .map(|chunk| f(chunk.try_into().unwrap()))
+        .collect()
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits >> 15 == 1 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+/// Reads a little-endian, C-ordered float `.npy` file
+fn read_npy(path: &Path) -> Result<WeightTensor, WeightError> {
+    let bytes = read(path)?;
+    let format_error = |reason: &str| WeightError::Format {
+        path: path.to_path_buf(),
+        reason: reason.to_string(),
+    };
+
+    if !bytes.starts_with(b"\x93NUMPY") || bytes.len() < 10 {
+        return Err(format_error("not a .npy file"));
+    }
+    let (header_len, header_start) = match bytes[6] {
+        1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+        2 | 3 if bytes.len() >= 12 => (
+            u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize,
+            12,
+        ),
+        _ => return Err(format_error("unsupported .npy version")),
+    };
+    let header = bytes
+        .get(header_start..header_start + header_len)
+        .and_then(|header| std::str::from_utf8(header).ok())
+        .ok_or_else(|| format_error("invalid header"))?;
+
+    let descr = header_value(header, "descr")
+        .map(|v| v.trim_matches('\''))
+        .ok_or_else(|| format_error("missing `descr`"))?;
+    if header_value(header, "fortran_order") != Some("False") {
+        return Err(format_error("Fortran-ordered arrays are not supported"));
+    }
+    let shape = header_value(header, "shape")
+        .ok_or_else(|| format_error("missing `shape`"))?
+        .trim_matches(|c| c == '(' || c == ')')
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| dim.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| format_error("invalid `shape`"))?;
+
+    let body = &bytes[header_start + header_len..];
+    let data = match descr {
+        "<f4" => decode(body, f32::from_le_bytes),
+        "<f8" => decode(body, |b| f64::from_le_bytes(b) as f32),
+        "<f2" => decode(body, |b| f16_to_f32(u16::from_le_bytes(b))),
+        dtype => {
+            return Err(WeightError::UnsupportedDtype {
+                name: path.display().to_string(),
+                dtype: dtype.to_string(),
+            })
+        }
+    };
+    if data.len() != shape.iter().product::<usize>() {
+        return Err(format_error("data doesn't match `shape`"));
+    }
+    Ok(WeightTensor { shape, data })
+}
+
+/// Returns the raw value of `key` in a `.npy` header dictionary
+fn header_value<'h>(header: &'h str, key: &str) -> Option<&'h str> {
+    let start = header.find(&format!("'{key}':"))? + key.len() + 3;
+    let rest = header[start..].trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')')? + 1
+    } else if let Some(quoted) = rest.strip_prefix('\'') {
+        quoted.find('\'')? + 2
+    } else {
+        rest.find([',', '}'])?
+    };
+    Some(rest[..end].trim())
+}
@@ -0,0 +1,332 @@
+//! Self-describing proof bundle file format
+//!
+//! A bundle packs a proof together with everything needed to check it into a single file:
+//!
+//! ```text
+//! magic (8 bytes) | format version (u32 LE) | hasher tag (u8) | checksum (32 bytes) | payload
+//! ```
+//!
+//! The payload is the bincode encoding of the proof, the circuit settings (or only their
+//! digest, when they are distributed separately), the public inputs and outputs of each
+//! proven execution of the graph and some metadata. The checksum is the Blake2s hash of the
+//! payload.
+//!
+//! The public inputs and outputs are bound to the proof, which only verifies against the
+//! values it was generated with.
+
+use std::path::Path;
+
+use luminair_air::{
+    pie::{LuminairPie, OpCounter},
+    segment::{BoundaryTensor, SegmentBoundary},
+    settings::CircuitSettings,
+};
+use luminair_utils::LuminairError;
+use serde::{Deserialize, Serialize};
+use stwo::core::vcs::{blake2_hash::Blake2sHasher, blake2_merkle::Blake2sMerkleHasher};
+
+use crate::LuminairProof;
+
+/// Magic bytes starting every bundle
+pub const BUNDLE_MAGIC: [u8; 8] = *b"LUMINAIR";
+
+/// Version of the bundle format written by this build, the only one it reads
+pub const BUNDLE_VERSION: u32 = 3;
+
+const HEADER_LEN: usize = BUNDLE_MAGIC.len() + 4 + 1 + 32;
+
+/// Merkle hasher a bundled proof was generated with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum HasherTag {
+    Blake2s = 1,
+}
+
+impl TryFrom<u8> for HasherTag {
+    type Error = LuminairError;
+
+    fn try_from(tag: u8) -> Result<Self, Self::Error> {
+        match tag {
+            1 => Ok(Self::Blake2s),
+            _ => Err(LuminairError::InvalidBundle(format!(
+                "unknown hasher tag {}",
+                tag
+            ))),
+        }
+    }
+}
+
+/// Circuit settings of a bundled proof
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum BundleSettings {
+    /// The settings themselves, making the bundle self-contained
+    Embedded(CircuitSettings),
+    /// Blake2s digest of the bincode-encoded settings, which are provided at verification
+    Digest([u8; 32]),
+}
+
+/// A named tensor of the computation, flattened
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IoTensor {
+    pub name: String,
+    pub data: Vec<f32>,
+}
+
+/// Public inputs and outputs of one proven execution of the graph
+///
+/// The boundary holds their fixed-point values, bound to the proof: it is mixed into the
+/// proof transcript and balances the LogUp sum of the execution. The names follow the order
+/// of the boundary tensors.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublicIo {
+    pub input_names: Vec<String>,
+    pub output_names: Vec<String>,
+    pub boundary: SegmentBoundary,
+}
+
+impl PublicIo {
+    /// Names the inputs and outputs of an execution, as returned by `gen_trace_with_io`
+    pub fn new(
+        input_names: Vec<String>,
+        output_names: Vec<String>,
+        boundary: SegmentBoundary,
+    ) -> Result<Self, LuminairError> {
+        let io = Self {
+            input_names,
+            output_names,
+            boundary,
+        };
+        io.check()?;
+        Ok(io)
+    }
+
+    /// Checks that every input and output has a name
+    pub fn check(&self) -> Result<(), LuminairError> {
+        if self.input_names.len() != self.boundary.inputs.len()
+            || self.output_names.len() != self.boundary.outputs.len()
+        {
+            return Err(LuminairError::InvalidPublicIo(format!(
+                "{} inputs and {} outputs are named for {} inputs and {} outputs",
+                self.input_names.len(),
+                self.output_names.len(),
+                self.boundary.inputs.len(),
+                self.boundary.outputs.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Proven inputs, decoded from their fixed-point values
+    pub fn inputs(&self) -> Vec<IoTensor> {
+        let tensors = self.boundary.inputs.iter().map(|input| &input.tensor);
+        named_tensors(&self.input_names, tensors)
+    }
+
+    /// Proven outputs, decoded from their fixed-point values
+    pub fn outputs(&self) -> Vec<IoTensor> {
+        named_tensors(&self.output_names, self.boundary.outputs.iter())
+    }
+}
+
+fn named_tensors<'a>(
+    names: &[String],
+    tensors: impl Iterator<Item = &'a BoundaryTensor>,
+) -> Vec<IoTensor> {
+    names
+        .iter()
+        .zip(tensors)
+        .map(|(name, tensor)| IoTensor {
+            name: name.clone(),
+            data: tensor.to_f32(),
+        })
+        .collect()
+}
+
+/// Information about how a bundled proof was produced
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BundleMetadata {
+    /// Version of LuminAIR the proof was generated with
+    pub luminair_version: String,
+    /// Number of operators of each type in the proven graph
+    pub op_counter: Option<OpCounter>,
+    /// Maximum log size across all trace tables
+    pub max_log_size: Option<u32>,
+}
+
+impl Default for BundleMetadata {
+    fn default() -> Self {
+        Self {
+            luminair_version: env!("CARGO_PKG_VERSION").to_string(),
+            op_counter: None,
+            max_log_size: None,
+        }
+    }
+}
+
+impl BundleMetadata {
+    /// Collects the metadata of a PIE, to be called before it is consumed by `prove`
+    pub fn from_pie(pie: &LuminairPie) -> Self {
+        let resources = &pie.metadata.execution_resources;
+        Self {
+            op_counter: Some(resources.op_counter.clone()),
+            max_log_size: Some(resources.max_log_size),
+            ..Default::default()
+        }
+    }
+}
+
+/// A proof with its circuit settings, public inputs and outputs, and metadata
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProofBundle {
+    pub proof: LuminairProof<Blake2sMerkleHasher>,
+    pub settings: BundleSettings,
+    /// Inputs and outputs of each execution, a batch proof covering several, empty when the
+    /// proof isn't bound to any
+    pub public_io: Vec<PublicIo>,
+    pub metadata: BundleMetadata,
+}
+
+impl ProofBundle {
+    /// Bundles a proof with its circuit settings
+    pub fn new(proof: LuminairProof<Blake2sMerkleHasher>, settings: CircuitSettings) -> Self {
+        Self {
+            proof,
+            settings: BundleSettings::Embedded(settings),
+            public_io: Vec::new(),
+            metadata: BundleMetadata::default(),
+        }
+    }
+
+    /// Bundles a proof with the digest of its circuit settings only, the settings having to
+    /// be provided at verification
+    pub fn with_settings_digest(
+        proof: LuminairProof<Blake2sMerkleHasher>,
+        settings: &CircuitSettings,
+    ) -> Result<Self, LuminairError> {
+        Ok(Self {
+            proof,
+            settings: BundleSettings::Digest(settings_digest(settings)?),
+            public_io: Vec::new(),
+            metadata: BundleMetadata::default(),
+        })
+    }
+
+    /// Sets the inputs and outputs the proof of a single execution is bound to
+    pub fn with_public_io(mut self, public_io: PublicIo) -> Self {
+        self.public_io = vec![public_io];
+        self
+    }
+
+    /// Sets the inputs and outputs of each execution a batch proof is bound to, in batch
+    /// order
+    pub fn with_public_batch_io(mut self, public_io: Vec<PublicIo>) -> Self {
+        self.public_io = public_io;
+        self
+    }
+
+    /// Sets the metadata of the bundle
+    pub fn with_metadata(mut self, metadata: BundleMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Returns the circuit settings to verify the proof with
+    ///
+    /// Embedded settings are used unless `provided` is given, in which case those must match
+    /// the bundle. Settings must be provided when the bundle only holds their digest.
+    pub fn resolve_settings(
+        &self,
+        provided: Option<CircuitSettings>,
+    ) -> Result<CircuitSettings, LuminairError> {
+        let (expected, provided) = match (&self.settings, provided) {
+            (BundleSettings::Embedded(settings), None) => return Ok(settings.clone()),
+            (BundleSettings::Embedded(settings), Some(provided)) => {
+                (settings_digest(settings)?, provided)
+            }
+            (BundleSettings::Digest(digest), Some(provided)) => (*digest, provided),
+            (BundleSettings::Digest(_), None) => {
+                return Err(LuminairError::InvalidBundle(
+                    "the bundle only holds the digest of its circuit settings, \
+                     the settings must be provided"
+                        .to_string(),
+                ))
+            }
+        };
+        if settings_digest(&provided)? != expected {
+            return Err(LuminairError::SettingsMismatch);
+        }
+        Ok(provided)
+    }
+
+    /// Returns true if `data` starts with the bundle magic bytes
+    pub fn is_bundle(data: &[u8]) -> bool {
+        data.starts_with(&BUNDLE_MAGIC)
+    }
+
+    /// Serializes the bundle, header included
+    pub fn to_bytes(&self) -> Result<Vec<u8>, LuminairError> {
+        let payload = bincode::serialize(self).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to serialize proof bundle: {}", e))
+        })?;
+
+        let mut data = Vec::with_capacity(HEADER_LEN + payload.len());
+        data.extend_from_slice(&BUNDLE_MAGIC);
+        data.extend_from_slice(&BUNDLE_VERSION.to_le_bytes());
+        data.push(HasherTag::Blake2s as u8);
+        data.extend_from_slice(&Blake2sHasher::hash(&payload).0);
+        data.extend_from_slice(&payload);
+        Ok(data)
+    }
+
+    /// Deserializes a bundle, rejecting unknown versions and corrupted payloads
+    pub fn from_bytes(data: &[u8]) -> Result<Self, LuminairError> {
+        if !Self::is_bundle(data) {
+            return Err(LuminairError::InvalidBundle(
+                "missing magic bytes, not a proof bundle".to_string(),
+            ));
+        }
+        if data.len() < HEADER_LEN {
+            return Err(LuminairError::InvalidBundle("truncated header".to_string()));
+        }
+
+        let (header, payload) = data.split_at(HEADER_LEN);
+        let version = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        if version != BUNDLE_VERSION {
+            return Err(LuminairError::UnsupportedBundleVersion {
+                found: version,
+                supported: BUNDLE_VERSION,
+            });
+        }
+        HasherTag::try_from(header[12])?;
+        if Blake2sHasher::hash(payload).0 != header[13..HEADER_LEN] {
+            return Err(LuminairError::InvalidBundle(
+                "checksum mismatch, the bundle is corrupted".to_string(),
+            ));
+        }
+
+        bincode::deserialize(payload).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to deserialize proof bundle: {}", e))
+        })
+    }
+
+    /// Writes the bundle to a file
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LuminairError> {
+        let data = self.to_bytes()?;
+        std::fs::write(path, data).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to write bundle file: {}", e))
+        })
+    }
+
+    /// Reads a bundle from a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, LuminairError> {
+        let data = std::fs::read(path).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to read bundle file: {}", e))
+        })?;
+        Self::from_bytes(&data)
+    }
+}
+
+/// Blake2s digest of the bincode encoding of `settings`
+pub fn settings_digest(settings: &CircuitSettings) -> Result<[u8; 32], LuminairError> {
+    Ok(Blake2sHasher::hash(&settings.to_bincode()?).0)
+}
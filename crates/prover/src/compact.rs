@@ -0,0 +1,795 @@
+//! Compact binary encoding of proofs
+//!
+//! The encoding follows the serde data model like bincode, with a few changes that matter for
+//! proofs:
+//! - values are written to a bit stream, so `M31` elements (and the `CM31` / `QM31` built from
+//!   them) take 31 bits instead of 32,
+//! - integers and lengths are LEB128 varints instead of fixed-width,
+//! - hashes (newtypes named `*Hash`) are deduplicated: each one is written once, then
+//!   referenced by its index,
+//! - the result can optionally be compressed with zstd (`zstd` feature).
+//!
+//! The encoding isn't self-describing: it can only be decoded into the type it was encoded
+//! from.
+
+use std::{collections::HashMap, fmt};
+
+use luminair_utils::LuminairError;
+use serde::{
+    de::{self, DeserializeOwned, IntoDeserializer},
+    ser, Serialize,
+};
+
+/// Version of the compact encoding, stored in the first byte of encoded data
+pub const COMPACT_VERSION: u8 = 1;
+
+/// Name of the serde newtype wrapping base field elements
+const M31_NAME: &str = "M31";
+
+/// Compression applied to compact-encoded data
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    /// zstd at the given level, requires the `zstd` feature
+    Zstd(i32),
+}
+
+impl Compression {
+    fn tag(self) -> u8 {
+        match self {
+            Compression::None => 0,
+            Compression::Zstd(_) => 1,
+        }
+    }
+}
+
+/// Encodes `value` in the compact format
+pub fn encode<T: Serialize>(value: &T, compression: Compression) -> Result<Vec<u8>, LuminairError> {
+    let mut encoder = Encoder::default();
+    value
+        .serialize(&mut encoder)
+        .map_err(|e| LuminairError::SerializationError(format!("Failed to encode: {}", e)))?;
+    let body = encoder.out.bytes;
+
+    let mut data = vec![COMPACT_VERSION, compression.tag()];
+    match compression {
+        Compression::None => data.extend(body),
+        Compression::Zstd(level) => data.extend(compress(&body, level)?),
+    }
+    Ok(data)
+}
+
+/// Decodes a value from the compact format
+pub fn decode<T: DeserializeOwned>(data: &[u8]) -> Result<T, LuminairError> {
+    let error =
+        |reason: String| LuminairError::SerializationError(format!("Failed to decode: {}", reason));
+
+    let [version, tag, body @ ..] = data else {
+        return Err(error("truncated header".to_string()));
+    };
+    if *version != COMPACT_VERSION {
+        return Err(error(format!(
+            "unsupported compact encoding version {}, expected {}",
+            version, COMPACT_VERSION
+        )));
+    }
+    let body = match tag {
+        0 => body.to_vec(),
+        1 => decompress(body)?,
+        _ => return Err(error(format!("unknown compression {}", tag))),
+    };
+
+    let mut decoder = Decoder::new(&body, 0);
+    let value = T::deserialize(&mut decoder).map_err(|e| error(e.to_string()))?;
+    if body.len() * 8 - decoder.input.pos >= 8 {
+        return Err(error("trailing bytes".to_string()));
+    }
+    Ok(value)
+}
+
+#[cfg(feature = "zstd")]
+fn compress(data: &[u8], level: i32) -> Result<Vec<u8>, LuminairError> {
+    zstd::encode_all(data, level)
+        .map_err(|e| LuminairError::SerializationError(format!("Failed to compress: {}", e)))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn compress(_: &[u8], _: i32) -> Result<Vec<u8>, LuminairError> {
+    Err(LuminairError::SerializationError(
+        "zstd compression requires the `zstd` feature".to_string(),
+    ))
+}
+
+#[cfg(feature = "zstd")]
+fn decompress(data: &[u8]) -> Result<Vec<u8>, LuminairError> {
+    zstd::decode_all(data)
+        .map_err(|e| LuminairError::SerializationError(format!("Failed to decompress: {}", e)))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decompress(_: &[u8]) -> Result<Vec<u8>, LuminairError> {
+    Err(LuminairError::SerializationError(
+        "zstd decompression requires the `zstd` feature".to_string(),
+    ))
+}
+
+// --- Errors ---
+
+#[derive(Debug)]
+struct CodecError(String);
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+impl ser::Error for CodecError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        CodecError(msg.to_string())
+    }
+}
+
+impl de::Error for CodecError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        CodecError(msg.to_string())
+    }
+}
+
+type Result<T, E = CodecError> = std::result::Result<T, E>;
+
+// --- Bit streams ---
+
+/// Bits written least significant first
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    fn write_bits(&mut self, value: u64, count: u32) {
+        for i in 0..count {
+            if self.len % 8 == 0 {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                *self.bytes.last_mut().unwrap() |= 1 << (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+
+    fn write_varint(&mut self, mut value: u64) {
+        loop {
+            let byte = value & 0x7f;
+            value >>= 7;
+            if value == 0 {
+                self.write_bits(byte, 8);
+                return;
+            }
+            self.write_bits(byte | 0x80, 8);
+        }
+    }
+
+    fn append(&mut self, other: &BitWriter) {
+        for i in 0..other.len {
+            self.write_bits(((other.bytes[i / 8] >> (i % 8)) & 1) as u64, 1);
+        }
+    }
+}
+
+struct BitReader<'de> {
+    data: &'de [u8],
+    pos: usize,
+}
+
+impl BitReader<'_> {
+    fn read_bits(&mut self, count: u32) -> Result<u64> {
+        if self.pos + count as usize > self.data.len() * 8 {
+            return Err(CodecError("unexpected end of data".to_string()));
+        }
+        let mut value = 0;
+        for i in 0..count {
+            let bit = (self.data[self.pos / 8] >> (self.pos % 8)) & 1;
+            value |= (bit as u64) << i;
+            self.pos += 1;
+        }
+        Ok(value)
+    }
+
+    fn read_varint(&mut self) -> Result<u64> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_bits(8)?;
+            value |= (byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(CodecError("varint overflow".to_string()))
+    }
+
+    fn read_len(&mut self) -> Result<usize> {
+        let len = self.read_varint()? as usize;
+        // Every element takes at least one bit
+        if len > self.data.len() * 8 - self.pos {
+            return Err(CodecError(format!("invalid length {}", len)));
+        }
+        Ok(len)
+    }
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+fn is_hash(name: &str) -> bool {
+    name.ends_with("Hash")
+}
+
+// --- Encoding ---
+
+#[derive(Default)]
+struct Encoder {
+    out: BitWriter,
+    /// Index of every hash written so far, keyed by its encoding
+    hashes: HashMap<(Vec<u8>, usize), u64>,
+    /// Set while serializing the content of an `M31`
+    pack_m31: bool,
+}
+
+impl Encoder {
+    fn write_len(&mut self, len: Option<usize>) -> Result<()> {
+        let len =
+            len.ok_or_else(|| CodecError("sequences must have a known length".to_string()))?;
+        self.out.write_varint(len as u64);
+        Ok(())
+    }
+}
+
+impl ser::Serializer for &mut Encoder {
+    type Ok = ();
+    type Error = CodecError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.out.write_bits(v as u64, 1);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.out.write_bits(v as u8 as u64, 8);
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.out.write_varint(zigzag(v));
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.out.write_bits(v as u64, 8);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        if std::mem::take(&mut self.pack_m31) {
+            if v >= 1 << 31 {
+                return Err(CodecError(format!("{} is not a reduced M31 element", v)));
+            }
+            self.out.write_bits(v as u64, 31);
+            return Ok(());
+        }
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.out.write_varint(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.out.write_bits(v.to_bits() as u64, 32);
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.out.write_bits(v.to_bits(), 64);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.out.write_varint(v.len() as u64);
+        for byte in v {
+            self.out.write_bits(*byte as u64, 8);
+        }
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.out.write_bits(0, 1);
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<()> {
+        self.out.write_bits(1, 1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, index: u32, _: &'static str) -> Result<()> {
+        self.serialize_u64(index as u64)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        if name == M31_NAME {
+            self.pack_m31 = true;
+            let result = value.serialize(&mut *self);
+            self.pack_m31 = false;
+            return result;
+        }
+        if is_hash(name) {
+            let mut hash = Encoder::default();
+            value.serialize(&mut hash)?;
+            let key = (hash.out.bytes.clone(), hash.out.len);
+            match self.hashes.get(&key) {
+                Some(&index) => self.out.write_varint(index),
+                None => {
+                    let index = self.hashes.len() as u64;
+                    self.out.write_varint(index);
+                    self.out.append(&hash.out);
+                    self.hashes.insert(key, index);
+                }
+            }
+            return Ok(());
+        }
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.out.write_varint(index as u64);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self> {
+        self.out.write_varint(index as u64);
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self> {
+        self.out.write_varint(index as u64);
+        Ok(self)
+    }
+}
+
+/// Compound values are written as the concatenation of their elements
+macro_rules! impl_compound {
+    ($($trait:ident :: $method:ident $(($key:ident))?),*) => {
+        $(
+            impl ser::$trait for &mut Encoder {
+                type Ok = ();
+                type Error = CodecError;
+
+                fn $method<T: ?Sized + Serialize>(
+                    &mut self,
+                    $($key: &'static str,)?
+                    value: &T,
+                ) -> Result<()> {
+                    $(let _ = $key;)?
+                    value.serialize(&mut **self)
+                }
+
+                fn end(self) -> Result<()> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_compound!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field,
+    SerializeStruct::serialize_field(key),
+    SerializeStructVariant::serialize_field(key)
+);
+
+impl ser::SerializeMap for &mut Encoder {
+    type Ok = ();
+    type Error = CodecError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<()> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+// --- Decoding ---
+
+struct Decoder<'de> {
+    input: BitReader<'de>,
+    /// Bit position of every hash read so far
+    hashes: Vec<usize>,
+    /// Set while deserializing the content of an `M31`
+    unpack_m31: bool,
+}
+
+impl<'de> Decoder<'de> {
+    fn new(data: &'de [u8], pos: usize) -> Self {
+        Self {
+            input: BitReader { data, pos },
+            hashes: Vec::new(),
+            unpack_m31: false,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Decoder<'de> {
+    type Error = CodecError;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, _: V) -> Result<V::Value> {
+        Err(CodecError(
+            "the compact encoding is not self-describing".to_string(),
+        ))
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_bool(self.input.read_bits(1)? == 1)
+    }
+
+    fn deserialize_i8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i8(self.input.read_bits(8)? as u8 as i8)
+    }
+
+    fn deserialize_i16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(unzigzag(self.input.read_varint()?))
+    }
+
+    fn deserialize_u8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u8(self.input.read_bits(8)? as u8)
+    }
+
+    fn deserialize_u16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if std::mem::take(&mut self.unpack_m31) {
+            return visitor.visit_u32(self.input.read_bits(31)? as u32);
+        }
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.input.read_varint()?)
+    }
+
+    fn deserialize_f32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f32(f32::from_bits(self.input.read_bits(32)? as u32))
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f64(f64::from_bits(self.input.read_bits(64)?))
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let value = self.input.read_varint()?;
+        let char = u32::try_from(value)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| CodecError(format!("invalid char {}", value)))?;
+        visitor.visit_char(char)
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = read_bytes(self)?;
+        let string = String::from_utf8(bytes).map_err(|e| CodecError(e.to_string()))?;
+        visitor.visit_string(string)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_byte_buf(read_bytes(self)?)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.input.read_bits(1)? {
+            0 => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        if name == M31_NAME {
+            self.unpack_m31 = true;
+            let result = visitor.visit_newtype_struct(&mut *self);
+            self.unpack_m31 = false;
+            return result;
+        }
+        if is_hash(name) {
+            // Hashes are decoded on their own, like they were encoded
+            let index = self.input.read_varint()? as usize;
+            if index == self.hashes.len() {
+                self.hashes.push(self.input.pos);
+                let mut hash = Decoder::new(self.input.data, self.input.pos);
+                let value = visitor.visit_newtype_struct(&mut hash)?;
+                self.input.pos = hash.input.pos;
+                return Ok(value);
+            }
+            let pos = *self
+                .hashes
+                .get(index)
+                .ok_or_else(|| CodecError(format!("invalid hash reference {}", index)))?;
+            return visitor.visit_newtype_struct(&mut Decoder::new(self.input.data, pos));
+        }
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.input.read_len()?;
+        visitor.visit_seq(Elements { de: self, len })
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Elements { de: self, len })
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.input.read_len()?;
+        visitor.visit_map(Elements { de: self, len })
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_any(visitor)
+    }
+}
+
+fn read_bytes(de: &mut Decoder<'_>) -> Result<Vec<u8>> {
+    let len = de.input.read_len()?;
+    (0..len).map(|_| Ok(de.input.read_bits(8)? as u8)).collect()
+}
+
+/// Elements of sequences, tuples, structs and maps, `len` being the number of entries left
+struct Elements<'a, 'de> {
+    de: &'a mut Decoder<'de>,
+    len: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Elements<'_, 'de> {
+    type Error = CodecError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Elements<'_, 'de> {
+    type Error = CodecError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Decoder<'de> {
+    type Error = CodecError;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let index = u32::try_from(self.input.read_varint()?)
+            .map_err(|_| CodecError("invalid variant index".to_string()))?;
+        let value = seed.deserialize(index.into_deserializer())?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Decoder<'de> {
+    type Error = CodecError;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
@@ -0,0 +1,274 @@
+//! Keys derived once from circuit settings
+//!
+//! The proving key caches what `prove` would otherwise recompute for each proof of a circuit:
+//! the twiddles, the values of the preprocessed lookup tables and the root of their
+//! commitment.
+//! The verifying key holds what the verifier needs to know about the preprocessed trace (the
+//! root of its commitment and the id and log size of each column), so verifying doesn't
+//! depend on the size of the lookup tables.
+
+use std::path::Path;
+
+use luminair_air::{
+    components::TraceEval,
+    preprocessed::{lookups_to_preprocessed_column, PreProcessedTrace},
+    settings::CircuitSettings,
+};
+use luminair_utils::LuminairError;
+use serde::{Deserialize, Serialize};
+use stwo::core::{
+    channel::Blake2sChannel,
+    pcs::PcsConfig,
+    poly::circle::CanonicCoset,
+    vcs::{blake2_hash::Blake2sHash, blake2_merkle::Blake2sMerkleChannel},
+};
+use stwo::prover::{
+    backend::simd::SimdBackend,
+    poly::{circle::PolyOps, twiddles::TwiddleTree},
+    CommitmentSchemeProver,
+};
+use stwo_constraint_framework::preprocessed_columns::PreProcessedColumnId;
+
+/// Id and log size of a preprocessed column
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PreProcessedColumnInfo {
+    pub id: String,
+    pub log_size: u32,
+}
+
+/// Everything the verifier needs to check proofs of a circuit
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VerifyingKey {
+    /// Settings the circuit was generated with
+    pub settings: CircuitSettings,
+    /// Root of the commitment to the preprocessed trace
+    pub preprocessed_root: Blake2sHash,
+    /// Preprocessed columns, in commitment order
+    pub preprocessed_columns: Vec<PreProcessedColumnInfo>,
+}
+
+impl VerifyingKey {
+    /// Derives the verifying key of a circuit, generating and committing its lookup tables
+    pub fn new(settings: &CircuitSettings) -> Self {
+        ProvingKey::new(settings, 0).verifying_key()
+    }
+
+    /// Ids of the preprocessed columns
+    pub fn column_ids(&self) -> Vec<PreProcessedColumnId> {
+        self.preprocessed_columns
+            .iter()
+            .map(|c| PreProcessedColumnId { id: c.id.clone() })
+            .collect()
+    }
+
+    /// Log sizes of the preprocessed columns
+    pub fn column_log_sizes(&self) -> Vec<u32> {
+        self.preprocessed_columns
+            .iter()
+            .map(|c| c.log_size)
+            .collect()
+    }
+
+    // --- Serde Binary ---
+    /// Serializes the verifying key to bincode format
+    pub fn to_bincode(&self) -> Result<Vec<u8>, LuminairError> {
+        bincode::serialize(self).map_err(|e| {
+            LuminairError::SerializationError(format!(
+                "Failed to serialize verifying key to bincode: {}",
+                e
+            ))
+        })
+    }
+
+    /// Deserializes a verifying key from bincode format
+    pub fn from_bincode(data: &[u8]) -> Result<Self, LuminairError> {
+        bincode::deserialize(data).map_err(|e| {
+            LuminairError::SerializationError(format!(
+                "Failed to deserialize verifying key from bincode: {}",
+                e
+            ))
+        })
+    }
+
+    /// Writes the verifying key to a bincode file
+    pub fn to_bincode_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LuminairError> {
+        let data = self.to_bincode()?;
+        std::fs::write(path, data).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to write bincode file: {}", e))
+        })
+    }
+
+    /// Reads a verifying key from a bincode file
+    pub fn from_bincode_file<P: AsRef<Path>>(path: P) -> Result<Self, LuminairError> {
+        let data = std::fs::read(path).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to read bincode file: {}", e))
+        })?;
+        Self::from_bincode(&data)
+    }
+}
+
+/// Cached setup to prove many computations of a circuit
+///
+/// Build it once with the largest trace log size the proven computations will have, then
+/// pass it to `prove_with_key`. As the commitment scheme takes ownership of the columns it
+/// commits, each proof commits freshly generated preprocessed columns rather than copies of
+/// the cached ones, which are only read by the lookups.
+pub struct ProvingKey {
+    pub(crate) settings: CircuitSettings,
+    pub(crate) max_log_size: u32,
+    pub(crate) twiddles: TwiddleTree<SimdBackend>,
+    pub(crate) preprocessed_trace: PreProcessedTrace,
+    pub(crate) preprocessed_evals: TraceEval,
+    preprocessed_root: Blake2sHash,
+}
+
+/// Serialized form of a proving key
+///
+/// The twiddles and preprocessed columns are derived from the settings when the key is read
+/// back, and the commitment of the columns is checked against the stored root.
+#[derive(Serialize, Deserialize)]
+struct ProvingKeyData {
+    settings: CircuitSettings,
+    max_log_size: u32,
+    preprocessed_root: Blake2sHash,
+}
+
+impl ProvingKey {
+    /// Builds the proving key of a circuit for traces of log size up to `max_log_size`,
+    /// generating and committing its lookup tables
+    pub fn new(settings: &CircuitSettings, max_log_size: u32) -> Self {
+        let preprocessed_trace =
+            PreProcessedTrace::new(lookups_to_preprocessed_column(&settings.lookups));
+        let max_log_size = max_log_size.max(max_preprocessed_log_size(&preprocessed_trace));
+        let twiddles = precompute_twiddles(max_log_size);
+
+        let preprocessed_evals = preprocessed_trace.gen_trace();
+        let preprocessed_root = commit_preprocessed_trace(&preprocessed_trace, &twiddles);
+
+        Self {
+            settings: settings.clone(),
+            max_log_size,
+            twiddles,
+            preprocessed_trace,
+            preprocessed_evals,
+            preprocessed_root,
+        }
+    }
+
+    /// Settings of the circuit
+    pub fn settings(&self) -> &CircuitSettings {
+        &self.settings
+    }
+
+    /// Largest trace log size the key can prove
+    pub fn max_log_size(&self) -> u32 {
+        self.max_log_size
+    }
+
+    /// Derives the verifying key of the circuit
+    pub fn verifying_key(&self) -> VerifyingKey {
+        let preprocessed_columns = self
+            .preprocessed_trace
+            .ids()
+            .into_iter()
+            .zip(self.preprocessed_trace.log_sizes())
+            .map(|(id, log_size)| PreProcessedColumnInfo {
+                id: id.id,
+                log_size,
+            })
+            .collect();
+
+        VerifyingKey {
+            settings: self.settings.clone(),
+            preprocessed_root: self.preprocessed_root,
+            preprocessed_columns,
+        }
+    }
+
+    // --- Serde Binary ---
+    /// Serializes the proving key to bincode format
+    pub fn to_bincode(&self) -> Result<Vec<u8>, LuminairError> {
+        let data = ProvingKeyData {
+            settings: self.settings.clone(),
+            max_log_size: self.max_log_size,
+            preprocessed_root: self.preprocessed_root,
+        };
+        bincode::serialize(&data).map_err(|e| {
+            LuminairError::SerializationError(format!(
+                "Failed to serialize proving key to bincode: {}",
+                e
+            ))
+        })
+    }
+
+    /// Deserializes a proving key from bincode format, regenerating its twiddles and
+    /// preprocessed columns and checking their commitment against the stored root
+    pub fn from_bincode(data: &[u8]) -> Result<Self, LuminairError> {
+        let data: ProvingKeyData = bincode::deserialize(data).map_err(|e| {
+            LuminairError::SerializationError(format!(
+                "Failed to deserialize proving key from bincode: {}",
+                e
+            ))
+        })?;
+
+        let proving_key = Self::new(&data.settings, data.max_log_size);
+        if proving_key.max_log_size != data.max_log_size
+            || proving_key.preprocessed_root != data.preprocessed_root
+        {
+            return Err(LuminairError::SerializationError(
+                "Proving key doesn't match the preprocessed columns of its settings".to_string(),
+            ));
+        }
+        Ok(proving_key)
+    }
+
+    /// Writes the proving key to a bincode file
+    pub fn to_bincode_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LuminairError> {
+        let data = self.to_bincode()?;
+        std::fs::write(path, data).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to write bincode file: {}", e))
+        })
+    }
+
+    /// Reads a proving key from a bincode file
+    pub fn from_bincode_file<P: AsRef<Path>>(path: P) -> Result<Self, LuminairError> {
+        let data = std::fs::read(path).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to read bincode file: {}", e))
+        })?;
+        Self::from_bincode(&data)
+    }
+}
+
+fn max_preprocessed_log_size(preprocessed_trace: &PreProcessedTrace) -> u32 {
+    preprocessed_trace
+        .log_sizes()
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+}
+
+/// Twiddles for traces of log size up to `max_log_size`, as `prove` has always computed them
+fn precompute_twiddles(max_log_size: u32) -> TwiddleTree<SimdBackend> {
+    let config = PcsConfig::default();
+    SimdBackend::precompute_twiddles(
+        CanonicCoset::new(max_log_size + config.fri_config.log_blowup_factor + 2)
+            .circle_domain()
+            .half_coset,
+    )
+}
+
+/// Commits the preprocessed columns the way `prove` does, returning the root of the
+/// commitment
+fn commit_preprocessed_trace(
+    preprocessed_trace: &PreProcessedTrace,
+    twiddles: &TwiddleTree<SimdBackend>,
+) -> Blake2sHash {
+    let mut commitment_scheme =
+        CommitmentSchemeProver::<_, Blake2sMerkleChannel>::new(PcsConfig::default(), twiddles);
+    let mut tree_builder = commitment_scheme.tree_builder();
+    tree_builder.extend_evals(preprocessed_trace.gen_trace());
+    tree_builder.commit(&mut Blake2sChannel::default());
+
+    // The preprocessed trace is the first committed tree
+    commitment_scheme.roots()[0]
+}
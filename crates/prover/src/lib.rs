@@ -9,7 +9,14 @@ use std::{
 use stwo::core::vcs::blake2_merkle::Blake2sMerkleHasher;
 use stwo::core::{proof::StarkProof, vcs::MerkleHasher};
 
+use crate::compact::Compression;
+
+pub mod bundle;
+pub mod compact;
+pub mod keys;
+pub mod metrics;
 pub mod prover;
+pub mod segment;
 
 /// Complete LuminAIR proof containing claim, interaction claim, and STARK proof
 #[derive(Serialize, Deserialize, Debug)]
@@ -57,6 +64,38 @@ impl LuminairProof<Blake2sMerkleHasher> {
         Self::from_bincode(&data)
     }
 
+    // --- Compact Binary ---
+    /// Serializes the proof to the compact format, packing field elements and deduplicating
+    /// hashes
+    pub fn to_compact(&self, compression: Compression) -> Result<Vec<u8>, LuminairError> {
+        compact::encode(self, compression)
+    }
+
+    /// Deserializes a proof from the compact format
+    pub fn from_compact(data: &[u8]) -> Result<Self, LuminairError> {
+        compact::decode(data)
+    }
+
+    /// Writes the proof to a compact file
+    pub fn to_compact_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        compression: Compression,
+    ) -> Result<(), LuminairError> {
+        let data = self.to_compact(compression)?;
+        std::fs::write(path, data).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to write compact file: {}", e))
+        })
+    }
+
+    /// Reads a proof from a compact file
+    pub fn from_compact_file<P: AsRef<Path>>(path: P) -> Result<Self, LuminairError> {
+        let data = std::fs::read(path).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to read compact file: {}", e))
+        })?;
+        Self::from_compact(&data)
+    }
+
     // --- Serde JSON ---
     /// Serializes the proof to JSON format
     pub fn to_json(&self) -> Result<String, LuminairError> {
@@ -0,0 +1,165 @@
+//! Wall time, trace sizes and memory of the phases of a proof
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use luminair_utils::LuminairError;
+use serde::{Deserialize, Serialize};
+
+/// Metrics of a proof, returned by `prove_with_metrics` and its variants taking a proving key
+///
+/// Times are wall times in milliseconds.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ProvingMetrics {
+    /// Building the proving key: twiddles and preprocessed lookup columns. Zero when the key
+    /// is given
+    pub proving_key_ms: f64,
+    /// Committing the preprocessed trace
+    pub preprocessed_ms: f64,
+    /// Writing and committing the main trace of every component
+    pub main_trace_ms: f64,
+    /// Writing and committing the interaction (LogUp) trace
+    pub interaction_trace_ms: f64,
+    /// Composition polynomial, out-of-domain sampling, FRI and decommitments
+    pub prove_ms: f64,
+    /// Whole proof, proving key included
+    pub total_ms: f64,
+    /// Main trace of each component instance, in proving order
+    pub components: Vec<ComponentMetrics>,
+    /// Peak number of heap bytes in use while proving, when `PeakAllocator` is the global
+    /// allocator
+    pub peak_allocation: Option<usize>,
+}
+
+/// Main trace of a component instance
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ComponentMetrics {
+    /// Name of the component, e.g. `add` or `sin_lookup`
+    pub component: String,
+    /// Number of rows of the instance, before padding
+    pub rows: usize,
+    /// Log size of the instance, as bound by its claim
+    pub log_size: u32,
+    /// Writing the main trace of the instance
+    pub main_trace_ms: f64,
+}
+
+impl ProvingMetrics {
+    /// Serializes the metrics to JSON format
+    pub fn to_json(&self) -> Result<String, LuminairError> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to serialize metrics to JSON: {}", e))
+        })
+    }
+
+    /// Writes the metrics to a JSON file
+    pub fn to_json_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LuminairError> {
+        let file = File::create(path).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to create file: {}", e))
+        })?;
+        let mut writer = BufWriter::new(file);
+
+        serde_json::to_writer_pretty(&mut writer, self).map_err(|e| {
+            LuminairError::SerializationError(format!(
+                "Failed to write metrics to JSON file: {}",
+                e
+            ))
+        })?;
+
+        writer.flush().map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to flush writer: {}", e))
+        })?;
+
+        Ok(())
+    }
+}
+
+/// Milliseconds elapsed since `start`
+pub(crate) fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1e3
+}
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Global allocator tracking the peak number of heap bytes in use, reported in
+/// `ProvingMetrics::peak_allocation`
+///
+/// Install it in the binary running the prover:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: PeakAllocator = PeakAllocator;
+/// ```
+///
+/// The counters are global to the process, so proofs generated concurrently report the peak
+/// of all of them.
+pub struct PeakAllocator;
+
+impl PeakAllocator {
+    fn record_alloc(size: usize) {
+        if !INSTALLED.load(Ordering::Relaxed) {
+            INSTALLED.store(true, Ordering::Relaxed);
+        }
+        let allocated = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(allocated, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        ALLOCATED.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                Self::record_alloc(new_size - layout.size());
+            } else {
+                Self::record_dealloc(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Restarts the peak allocation from the number of bytes currently in use
+pub(crate) fn reset_peak_allocation() {
+    PEAK.store(ALLOCATED.load(Ordering::Relaxed), Ordering::Relaxed);
+}
+
+/// Peak number of heap bytes in use since the last reset, if `PeakAllocator` is installed
+pub(crate) fn peak_allocation() -> Option<usize> {
+    INSTALLED
+        .load(Ordering::Relaxed)
+        .then(|| PEAK.load(Ordering::Relaxed))
+}
@@ -1,319 +1,623 @@
 use luminair_air::{
     components::{
-        add, contiguous, exp2, inputs, less_than, log2, lookups, max_reduce, mul, recip, rem, sin,
-        sqrt, sum_reduce, LuminairComponents, LuminairInteractionElements,
+        add, add_const, contiguous, exp2, fused, gather, im2col, inputs, less_than, log2, lookups,
+        matmul, max_reduce, mul, mul_const, norm, recip, rem, select, sin, sqrt, sum_reduce,
+        LuminairComponents, LuminairInteractionElements,
     },
     pie::{LuminairPie, TraceTable},
-    preprocessed::{
-        lookups_to_preprocessed_column, Exp2PreProcessed, Log2PreProcessed, PreProcessedTrace,
-        RangeCheckPreProcessed, SinPreProcessed,
-    },
+    preprocessed::{Exp2PreProcessed, Log2PreProcessed, RangeCheckPreProcessed, SinPreProcessed},
+    segment::{LuminairSegment, SegmentBoundary},
     settings::CircuitSettings,
+    utils::calculate_log_size,
     LuminairClaim, LuminairInteractionClaim, LuminairInteractionClaimGenerator,
 };
 use luminair_utils::LuminairError;
+use std::time::Instant;
 use stwo::core::{
     channel::Blake2sChannel,
     pcs::PcsConfig,
-    poly::circle::CanonicCoset,
     vcs::blake2_merkle::{Blake2sMerkleChannel, Blake2sMerkleHasher},
 };
-use stwo::prover::{
-    self, backend::simd::SimdBackend, poly::circle::PolyOps, CommitmentSchemeProver,
+use stwo::prover::{self, backend::simd::SimdBackend, CommitmentSchemeProver};
+
+use crate::{
+    keys::ProvingKey,
+    metrics::{
+        elapsed_ms, peak_allocation, reset_peak_allocation, ComponentMetrics, ProvingMetrics,
+    },
+    segment::{SegmentProof, SegmentedProof},
+    LuminairProof,
 };
 
-use crate::LuminairProof;
+/// Largest log size of a component instance when the proving key is sized by `prove`
+///
+/// Larger tables are sharded into several instances, so that a single large table doesn't set
+/// the log size of the whole proof.
+pub const MAX_COMPONENT_LOG_SIZE: u32 = 20;
 
 /// Generates a LuminAIR proof from the given PIE and circuit settings
 pub fn prove(
     pie: LuminairPie,
     settings: CircuitSettings,
 ) -> Result<LuminairProof<Blake2sMerkleHasher>, LuminairError> {
+    prove_with_metrics(pie, settings).map(|(proof, _)| proof)
+}
+
+/// Generates a LuminAIR proof from the given PIE and circuit settings, along with the wall
+/// time of each phase, the size of each component and the peak memory of the proof
+pub fn prove_with_metrics(
+    pie: LuminairPie,
+    settings: CircuitSettings,
+) -> Result<(LuminairProof<Blake2sMerkleHasher>, ProvingMetrics), LuminairError> {
+    prove_with_io_and_metrics(pie, settings, &[])
+}
+
+/// Generates a LuminAIR proof bound to the inputs and outputs of each execution the PIE
+/// covers, in the order they were traced
+///
+/// The proof only verifies against the same inputs and outputs, see `verify_with_io`.
+pub fn prove_with_io(
+    pie: LuminairPie,
+    settings: CircuitSettings,
+    io: &[SegmentBoundary],
+) -> Result<LuminairProof<Blake2sMerkleHasher>, LuminairError> {
+    prove_with_io_and_metrics(pie, settings, io).map(|(proof, _)| proof)
+}
+
+/// Generates a LuminAIR proof bound to the inputs and outputs of each execution, along with
+/// the metrics of the proof
+pub fn prove_with_io_and_metrics(
+    pie: LuminairPie,
+    settings: CircuitSettings,
+    io: &[SegmentBoundary],
+) -> Result<(LuminairProof<Blake2sMerkleHasher>, ProvingMetrics), LuminairError> {
+    let start = Instant::now();
+    tracing::info!("Proving Key");
+    let max_log_size = pie.metadata.execution_resources.max_log_size;
+    let proving_key = ProvingKey::new(&settings, max_log_size.min(MAX_COMPONENT_LOG_SIZE));
+    let proving_key_ms = elapsed_ms(start);
+
+    let (proof, mut metrics) = prove_pie(pie, &proving_key, io)?;
+    metrics.proving_key_ms = proving_key_ms;
+    metrics.total_ms = elapsed_ms(start);
+    Ok((proof, metrics))
+}
+
+/// Generates a LuminAIR proof from the given PIE, reusing the setup cached in `proving_key`
+///
+/// The PIE must come from a graph compiled with the settings of the key. Tables larger than
+/// the maximum log size of the key are sharded into several component instances.
+pub fn prove_with_key(
+    pie: LuminairPie,
+    proving_key: &ProvingKey,
+) -> Result<LuminairProof<Blake2sMerkleHasher>, LuminairError> {
+    prove_with_key_and_metrics(pie, proving_key).map(|(proof, _)| proof)
+}
+
+/// Generates a LuminAIR proof from the given PIE with a cached proving key, along with the
+/// metrics of the proof
+///
+/// The key is built beforehand, so `proving_key_ms` is left at zero.
+pub fn prove_with_key_and_metrics(
+    pie: LuminairPie,
+    proving_key: &ProvingKey,
+) -> Result<(LuminairProof<Blake2sMerkleHasher>, ProvingMetrics), LuminairError> {
+    prove_pie(pie, proving_key, &[])
+}
+
+/// Proves every segment of a graph, sharing one proving key sized for the largest segment
+pub fn prove_segments(
+    segments: Vec<LuminairSegment>,
+    settings: CircuitSettings,
+) -> Result<SegmentedProof, LuminairError> {
+    let max_log_size = segments
+        .iter()
+        .map(|segment| segment.pie.metadata.execution_resources.max_log_size)
+        .max()
+        .unwrap_or(0);
+    let proving_key = ProvingKey::new(&settings, max_log_size.min(MAX_COMPONENT_LOG_SIZE));
+    let segments = segments
+        .into_iter()
+        .map(|segment| prove_segment(segment, &proving_key))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(SegmentedProof { segments })
+}
+
+/// Proves one segment of a graph, binding the proof to the boundary of the segment
+pub fn prove_segment(
+    segment: LuminairSegment,
+    proving_key: &ProvingKey,
+) -> Result<SegmentProof, LuminairError> {
+    prove_segment_with_metrics(segment, proving_key).map(|(proof, _)| proof)
+}
+
+/// Proves one segment of a graph, along with the metrics of its proof
+pub fn prove_segment_with_metrics(
+    segment: LuminairSegment,
+    proving_key: &ProvingKey,
+) -> Result<(SegmentProof, ProvingMetrics), LuminairError> {
+    let (proof, metrics) = prove_pie(
+        segment.pie,
+        proving_key,
+        std::slice::from_ref(&segment.boundary),
+    )?;
+    let proof = SegmentProof {
+        proof,
+        boundary: segment.boundary,
+    };
+    Ok((proof, metrics))
+}
+
+/// Proves a PIE, mixing into the channel the boundary of its segment when it is one, or the
+/// inputs and outputs of its executions
+///
+/// The metrics cover the proof of the PIE, the proving key aside.
+fn prove_pie(
+    pie: LuminairPie,
+    proving_key: &ProvingKey,
+    boundaries: &[SegmentBoundary],
+) -> Result<(LuminairProof<Blake2sMerkleHasher>, ProvingMetrics), LuminairError> {
     // ┌──────────────────────────┐
     // │     Protocol Setup       │
     // └──────────────────────────┘
     tracing::info!("Protocol Setup");
+    let start = Instant::now();
+    reset_peak_allocation();
+    let mut metrics = ProvingMetrics::default();
     let config: PcsConfig = PcsConfig::default();
-    let max_log_size = pie.metadata.execution_resources.max_log_size;
-    let twiddles = SimdBackend::precompute_twiddles(
-        CanonicCoset::new(max_log_size + config.fri_config.log_blowup_factor + 2)
-            .circle_domain()
-            .half_coset,
-    );
+    // Tables too large for the key, or mostly padding once padded, are split into shards
+    // proven by separate component instances. The PIE is consumed, so each table is moved into
+    // its witness rather than copied.
+    let trace_tables = pie
+        .trace_tables
+        .into_iter()
+        .flat_map(|table| table.shard(proving_key.max_log_size))
+        .collect::<Vec<_>>();
+    let max_log_size = trace_tables
+        .iter()
+        .map(|table| calculate_log_size(table.n_rows()))
+        .max()
+        .unwrap_or(0);
+    if max_log_size > proving_key.max_log_size {
+        return Err(LuminairError::ProvingKeyTooSmall {
+            required: max_log_size,
+            supported: proving_key.max_log_size,
+        });
+    }
+    let settings = &proving_key.settings;
     // Setup protocol.
     let channel = &mut Blake2sChannel::default();
     let mut commitment_scheme =
-        CommitmentSchemeProver::<_, Blake2sMerkleChannel>::new(config, &twiddles);
+        CommitmentSchemeProver::<_, Blake2sMerkleChannel>::new(config, &proving_key.twiddles);
 
     // ┌───────────────────────────────────────────────┐
     // │   Interaction Phase 0 - Preprocessed Trace    │
     // └───────────────────────────────────────────────┘
 
     tracing::info!("Preprocessed Trace");
-    // Convert lookups in circuit settings to preprocessed column.
-    let lut_cols = lookups_to_preprocessed_column(&settings.lookups);
-    let preprocessed_trace = PreProcessedTrace::new(lut_cols);
+    let phase = Instant::now();
+    let preprocessed_trace = &proving_key.preprocessed_trace;
+    let preprocessed_evals = &proving_key.preprocessed_evals;
     let mut tree_builder = commitment_scheme.tree_builder();
+    // The tree takes ownership of its columns, so they are generated again rather than copied
+    // from the key
     tree_builder.extend_evals(preprocessed_trace.gen_trace());
     // Commit the preprocessed trace
     tree_builder.commit(channel);
+    metrics.preprocessed_ms = elapsed_ms(phase);
 
     // ┌───────────────────────────────────────┐
     // │    Interaction Phase 1 - Main Trace   │
     // └───────────────────────────────────────┘
 
     tracing::info!("Main Trace");
+    let phase = Instant::now();
     let mut main_claim = LuminairClaim::default();
     let mut interaction_claim_gen = LuminairInteractionClaimGenerator::default();
     let mut tree_builder = commitment_scheme.tree_builder();
 
-    for table in pie.trace_tables.clone() {
+    for table in trace_tables {
+        let (component, rows) = (table.name(), table.n_rows());
+        let component_start = Instant::now();
+        // Log size of the component instance, as bound by its claim
+        let log_size;
         match table {
             TraceTable::Add { table } => {
                 let claim_gen = add::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.add = Some(cl.clone());
-                interaction_claim_gen.add = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.add.push(cl);
+                interaction_claim_gen.add.push(in_cl_gen);
             }
             TraceTable::Mul { table } => {
                 let claim_gen = mul::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.mul = Some(cl.clone());
-                interaction_claim_gen.mul = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.mul.push(cl);
+                interaction_claim_gen.mul.push(in_cl_gen);
             }
             TraceTable::Recip { table } => {
                 let claim_gen = recip::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.recip = Some(cl.clone());
-                interaction_claim_gen.recip = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.recip.push(cl);
+                interaction_claim_gen.recip.push(in_cl_gen);
             }
             TraceTable::Sin { table } => {
                 let claim_gen = sin::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.sin = Some(cl.clone());
-                interaction_claim_gen.sin = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.sin.push(cl);
+                interaction_claim_gen.sin.push(in_cl_gen);
             }
             TraceTable::SinLookup { table } => {
                 let claim_gen = lookups::sin::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.sin_lookup = Some(cl.clone());
-                interaction_claim_gen.sin_lookup = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.sin_lookup.push(cl);
+                interaction_claim_gen.sin_lookup.push(in_cl_gen);
             }
             TraceTable::SumReduce { table } => {
                 let claim_gen = sum_reduce::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.sum_reduce = Some(cl.clone());
-                interaction_claim_gen.sum_reduce = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.sum_reduce.push(cl);
+                interaction_claim_gen.sum_reduce.push(in_cl_gen);
             }
             TraceTable::MaxReduce { table } => {
                 let claim_gen = max_reduce::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.max_reduce = Some(cl.clone());
-                interaction_claim_gen.max_reduce = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.max_reduce.push(cl);
+                interaction_claim_gen.max_reduce.push(in_cl_gen);
             }
             TraceTable::Sqrt { table } => {
                 let claim_gen = sqrt::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.sqrt = Some(cl.clone());
-                interaction_claim_gen.sqrt = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.sqrt.push(cl);
+                interaction_claim_gen.sqrt.push(in_cl_gen);
             }
             TraceTable::Rem { table } => {
                 let claim_gen = rem::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.rem = Some(cl.clone());
-                interaction_claim_gen.rem = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.rem.push(cl);
+                interaction_claim_gen.rem.push(in_cl_gen);
             }
             TraceTable::Exp2 { table } => {
                 let claim_gen = exp2::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.exp2 = Some(cl.clone());
-                interaction_claim_gen.exp2 = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.exp2.push(cl);
+                interaction_claim_gen.exp2.push(in_cl_gen);
             }
             TraceTable::Exp2Lookup { table } => {
                 let claim_gen = lookups::exp2::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.exp2_lookup = Some(cl.clone());
-                interaction_claim_gen.exp2_lookup = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.exp2_lookup.push(cl);
+                interaction_claim_gen.exp2_lookup.push(in_cl_gen);
             }
             TraceTable::Log2 { table } => {
                 let claim_gen = log2::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.log2 = Some(cl.clone());
-                interaction_claim_gen.log2 = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.log2.push(cl);
+                interaction_claim_gen.log2.push(in_cl_gen);
             }
             TraceTable::Log2Lookup { table } => {
                 let claim_gen = lookups::log2::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.log2_lookup = Some(cl.clone());
-                interaction_claim_gen.log2_lookup = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.log2_lookup.push(cl);
+                interaction_claim_gen.log2_lookup.push(in_cl_gen);
             }
             TraceTable::LessThan { table } => {
                 let claim_gen = less_than::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.less_than = Some(cl.clone());
-                interaction_claim_gen.less_than = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.less_than.push(cl);
+                interaction_claim_gen.less_than.push(in_cl_gen);
             }
             TraceTable::RangeCheckLookup { table } => {
                 let claim_gen = lookups::range_check::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.range_check_lookup = Some(cl.clone());
-                interaction_claim_gen.range_check_lookup = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.range_check_lookup.push(cl);
+                interaction_claim_gen.range_check_lookup.push(in_cl_gen);
             }
             TraceTable::Inputs { table } => {
                 let claim_gen = inputs::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.inputs = Some(cl.clone());
-                interaction_claim_gen.inputs = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.inputs.push(cl);
+                interaction_claim_gen.inputs.push(in_cl_gen);
             }
             TraceTable::Contiguous { table } => {
                 let claim_gen = contiguous::witness::ClaimGenerator::new(table);
                 let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
-                main_claim.contiguous = Some(cl.clone());
-                interaction_claim_gen.contiguous = Some(in_cl_gen);
+                log_size = cl.log_size;
+                main_claim.contiguous.push(cl);
+                interaction_claim_gen.contiguous.push(in_cl_gen);
+            }
+            TraceTable::Select { table } => {
+                let claim_gen = select::witness::ClaimGenerator::new(table);
+                let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
+                log_size = cl.log_size;
+                main_claim.select.push(cl);
+                interaction_claim_gen.select.push(in_cl_gen);
+            }
+            TraceTable::Gather { table } => {
+                let claim_gen = gather::witness::ClaimGenerator::new(table);
+                let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
+                log_size = cl.log_size;
+                main_claim.gather.push(cl);
+                interaction_claim_gen.gather.push(in_cl_gen);
+            }
+            TraceTable::GatherLookup { table } => {
+                let claim_gen = lookups::gather::witness::ClaimGenerator::new(table);
+                let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
+                log_size = cl.log_size;
+                main_claim.gather_lookup.push(cl);
+                interaction_claim_gen.gather_lookup.push(in_cl_gen);
+            }
+            TraceTable::Im2Col { table } => {
+                let claim_gen = im2col::witness::ClaimGenerator::new(table);
+                let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
+                log_size = cl.log_size;
+                main_claim.im2col.push(cl);
+                interaction_claim_gen.im2col.push(in_cl_gen);
+            }
+            TraceTable::MatMul { table } => {
+                let claim_gen = matmul::witness::ClaimGenerator::new(table);
+                let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
+                log_size = cl.log_size;
+                main_claim.matmul.push(cl);
+                interaction_claim_gen.matmul.push(in_cl_gen);
+            }
+            TraceTable::Norm { table } => {
+                let claim_gen = norm::witness::ClaimGenerator::new(table);
+                let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
+                log_size = cl.log_size;
+                main_claim.norm.push(cl);
+                interaction_claim_gen.norm.push(in_cl_gen);
+            }
+            TraceTable::MulConst { table } => {
+                let claim_gen = mul_const::witness::ClaimGenerator::new(table);
+                let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
+                log_size = cl.log_size;
+                main_claim.mul_const.push(cl);
+                interaction_claim_gen.mul_const.push(in_cl_gen);
+            }
+            TraceTable::AddConst { table } => {
+                let claim_gen = add_const::witness::ClaimGenerator::new(table);
+                let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
+                log_size = cl.log_size;
+                main_claim.add_const.push(cl);
+                interaction_claim_gen.add_const.push(in_cl_gen);
+            }
+            TraceTable::Fused { table } => {
+                let claim_gen = fused::witness::ClaimGenerator::new(table);
+                let (cl, in_cl_gen) = claim_gen.write_trace(&mut tree_builder)?;
+                log_size = cl.log_size;
+                main_claim.fused.push(cl);
+                interaction_claim_gen.fused.push(in_cl_gen);
             }
         }
+        metrics.components.push(ComponentMetrics {
+            component: component.to_string(),
+            rows,
+            log_size,
+            main_trace_ms: elapsed_ms(component_start),
+        });
     }
     // Mix the claim into the Fiat-Shamir channel.
     main_claim.mix_into(channel);
+    for boundary in boundaries {
+        boundary.mix_into(channel);
+    }
     // Commit the main trace.
     tree_builder.commit(channel);
+    metrics.main_trace_ms = elapsed_ms(phase);
 
     // ┌───────────────────────────────────────────────┐
     // │    Interaction Phase 2 - Interaction Trace    │
     // └───────────────────────────────────────────────┘
 
     tracing::info!("Interaction Trace");
+    let phase = Instant::now();
     let interaction_elements = LuminairInteractionElements::draw(channel);
     let mut interaction_claim = LuminairInteractionClaim::default();
     let mut tree_builder = commitment_scheme.tree_builder();
     let node_elements = &interaction_elements.node_elements;
     let lookup_elements = &interaction_elements.lookup_elements;
-    if let Some(claim_gen) = interaction_claim_gen.add {
+    for claim_gen in interaction_claim_gen.add {
         let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
-        interaction_claim.add = Some(claim)
+        interaction_claim.add.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.mul {
+    for claim_gen in interaction_claim_gen.mul {
         let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
-        interaction_claim.mul = Some(claim)
+        interaction_claim.mul.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.recip {
+    for claim_gen in interaction_claim_gen.recip {
         let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
-        interaction_claim.recip = Some(claim)
+        interaction_claim.recip.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.sin {
+    for claim_gen in interaction_claim_gen.sin {
         let claim = claim_gen.write_interaction_trace(
             &mut tree_builder,
             node_elements,
             &lookup_elements.sin,
         );
-        interaction_claim.sin = Some(claim)
+        interaction_claim.sin.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.sin_lookup {
-        let mut sin_luts = preprocessed_trace.columns_of::<SinPreProcessed>();
-        sin_luts.sort_by_key(|c| c.col_index);
+    for claim_gen in interaction_claim_gen.sin_lookup {
+        let sin_luts =
+            preprocessed_trace.values_of(preprocessed_evals, |c: &SinPreProcessed| c.col_index);
 
         let claim =
             claim_gen.write_interaction_trace(&mut tree_builder, &lookup_elements.sin, &sin_luts);
-        interaction_claim.sin_lookup = Some(claim)
+        interaction_claim.sin_lookup.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.sum_reduce {
+    for claim_gen in interaction_claim_gen.sum_reduce {
         let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
-        interaction_claim.sum_reduce = Some(claim)
+        interaction_claim.sum_reduce.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.max_reduce {
+    for claim_gen in interaction_claim_gen.max_reduce {
         let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
-        interaction_claim.max_reduce = Some(claim)
+        interaction_claim.max_reduce.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.sqrt {
+    for claim_gen in interaction_claim_gen.sqrt {
         let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
-        interaction_claim.sqrt = Some(claim)
+        interaction_claim.sqrt.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.rem {
+    for claim_gen in interaction_claim_gen.rem {
         let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
-        interaction_claim.rem = Some(claim)
+        interaction_claim.rem.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.exp2 {
+    for claim_gen in interaction_claim_gen.exp2 {
         let claim = claim_gen.write_interaction_trace(
             &mut tree_builder,
             node_elements,
             &lookup_elements.exp2,
         );
-        interaction_claim.exp2 = Some(claim)
+        interaction_claim.exp2.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.exp2_lookup {
-        let mut exp2_luts = preprocessed_trace.columns_of::<Exp2PreProcessed>();
-        exp2_luts.sort_by_key(|c| c.col_index);
+    for claim_gen in interaction_claim_gen.exp2_lookup {
+        let exp2_luts =
+            preprocessed_trace.values_of(preprocessed_evals, |c: &Exp2PreProcessed| c.col_index);
 
         let claim =
             claim_gen.write_interaction_trace(&mut tree_builder, &lookup_elements.exp2, &exp2_luts);
-        interaction_claim.exp2_lookup = Some(claim)
+        interaction_claim.exp2_lookup.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.log2 {
+    for claim_gen in interaction_claim_gen.log2 {
         let claim = claim_gen.write_interaction_trace(
             &mut tree_builder,
             node_elements,
             &lookup_elements.log2,
         );
-        interaction_claim.log2 = Some(claim)
+        interaction_claim.log2.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.log2_lookup {
-        let mut log2_luts = preprocessed_trace.columns_of::<Log2PreProcessed>();
-        log2_luts.sort_by_key(|c| c.col_index);
+    for claim_gen in interaction_claim_gen.log2_lookup {
+        let log2_luts =
+            preprocessed_trace.values_of(preprocessed_evals, |c: &Log2PreProcessed| c.col_index);
 
         let claim =
             claim_gen.write_interaction_trace(&mut tree_builder, &lookup_elements.log2, &log2_luts);
-        interaction_claim.log2_lookup = Some(claim)
+        interaction_claim.log2_lookup.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.less_than {
+    for claim_gen in interaction_claim_gen.less_than {
         let claim = claim_gen.write_interaction_trace(
             &mut tree_builder,
             node_elements,
             &lookup_elements.range_check,
         );
-        interaction_claim.less_than = Some(claim)
+        interaction_claim.less_than.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.range_check_lookup {
-        let mut range_check_lut = preprocessed_trace.columns_of::<RangeCheckPreProcessed<1>>();
-        range_check_lut.sort_by_key(|c| c.col_index);
+    for claim_gen in interaction_claim_gen.range_check_lookup {
+        let range_check_lut = preprocessed_trace
+            .values_of(preprocessed_evals, |c: &RangeCheckPreProcessed<1>| {
+                c.col_index
+            });
 
         let claim = claim_gen.write_interaction_trace(
             &mut tree_builder,
             &lookup_elements.range_check,
             &range_check_lut,
         );
-        interaction_claim.range_check_lookup = Some(claim)
+        interaction_claim.range_check_lookup.push(claim);
+    }
+    for claim_gen in interaction_claim_gen.inputs {
+        let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
+        interaction_claim.inputs.push(claim);
+    }
+    for claim_gen in interaction_claim_gen.contiguous {
+        let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
+        interaction_claim.contiguous.push(claim);
+    }
+    for claim_gen in interaction_claim_gen.select {
+        let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
+        interaction_claim.select.push(claim);
+    }
+    for claim_gen in interaction_claim_gen.gather {
+        let claim = claim_gen.write_interaction_trace(
+            &mut tree_builder,
+            node_elements,
+            &lookup_elements.gather,
+        );
+        interaction_claim.gather.push(claim);
+    }
+    for claim_gen in interaction_claim_gen.gather_lookup {
+        let claim = claim_gen.write_interaction_trace(
+            &mut tree_builder,
+            node_elements,
+            &lookup_elements.gather,
+        );
+        interaction_claim.gather_lookup.push(claim);
+    }
+    for claim_gen in interaction_claim_gen.im2col {
+        let claim = claim_gen.write_interaction_trace(
+            &mut tree_builder,
+            node_elements,
+            &lookup_elements.gather,
+        );
+        interaction_claim.im2col.push(claim);
+    }
+    for claim_gen in interaction_claim_gen.matmul {
+        let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
+        interaction_claim.matmul.push(claim);
+    }
+    for claim_gen in interaction_claim_gen.norm {
+        let claim = claim_gen.write_interaction_trace(
+            &mut tree_builder,
+            node_elements,
+            &lookup_elements.range_check,
+        );
+        interaction_claim.norm.push(claim);
+    }
+    for claim_gen in interaction_claim_gen.mul_const {
+        let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
+        interaction_claim.mul_const.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.inputs {
+    for claim_gen in interaction_claim_gen.add_const {
         let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
-        interaction_claim.inputs = Some(claim)
+        interaction_claim.add_const.push(claim);
     }
-    if let Some(claim_gen) = interaction_claim_gen.contiguous {
+    for claim_gen in interaction_claim_gen.fused {
         let claim = claim_gen.write_interaction_trace(&mut tree_builder, node_elements);
-        interaction_claim.contiguous = Some(claim)
+        interaction_claim.fused.push(claim);
     }
 
     // Mix the interaction claim into the Fiat-Shamir channel.
     interaction_claim.mix_into(channel);
     // Commit the interaction trace.
     tree_builder.commit(channel);
+    metrics.interaction_trace_ms = elapsed_ms(phase);
 
     // ┌──────────────────────────┐
     // │     Proof Generation     │
     // └──────────────────────────┘
     tracing::info!("Proof Generation");
+    let phase = Instant::now();
     let component_builder = LuminairComponents::new(
         &main_claim,
         &interaction_elements,
         &interaction_claim,
-        &preprocessed_trace,
+        &preprocessed_trace.ids(),
         &settings.lookups,
     );
     let components = component_builder.provers();
     let proof = prover::prove::<SimdBackend, _>(&components, channel, commitment_scheme)?;
+    metrics.prove_ms = elapsed_ms(phase);
+    metrics.total_ms = elapsed_ms(start);
+    metrics.peak_allocation = peak_allocation();
 
-    Ok(LuminairProof {
+    let proof = LuminairProof {
         claim: main_claim,
         interaction_claim,
         proof,
-    })
+    };
+    Ok((proof, metrics))
 }
@@ -0,0 +1,65 @@
+//! Proofs of a graph split into segments
+//!
+//! Each segment is proven on its own, with the tensors crossing its cuts as public
+//! boundary values. A `SegmentedProof` holds the chain of segment proofs in execution order.
+
+use std::path::Path;
+
+use luminair_air::segment::SegmentBoundary;
+use luminair_utils::LuminairError;
+use serde::{Deserialize, Serialize};
+use stwo::core::vcs::blake2_merkle::Blake2sMerkleHasher;
+
+use crate::LuminairProof;
+
+/// Proof of one segment with its public boundary
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SegmentProof {
+    pub proof: LuminairProof<Blake2sMerkleHasher>,
+    pub boundary: SegmentBoundary,
+}
+
+/// Chain of segment proofs covering a whole graph
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SegmentedProof {
+    pub segments: Vec<SegmentProof>,
+}
+
+impl SegmentedProof {
+    // --- Serde Binary ---
+    /// Serializes the segmented proof to bincode format
+    pub fn to_bincode(&self) -> Result<Vec<u8>, LuminairError> {
+        bincode::serialize(self).map_err(|e| {
+            LuminairError::SerializationError(format!(
+                "Failed to serialize segmented proof to bincode: {}",
+                e
+            ))
+        })
+    }
+
+    /// Deserializes a segmented proof from bincode format
+    pub fn from_bincode(data: &[u8]) -> Result<Self, LuminairError> {
+        bincode::deserialize(data).map_err(|e| {
+            LuminairError::SerializationError(format!(
+                "Failed to deserialize segmented proof from bincode: {}",
+                e
+            ))
+        })
+    }
+
+    /// Writes the segmented proof to a bincode file
+    pub fn to_bincode_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LuminairError> {
+        let data = self.to_bincode()?;
+        std::fs::write(path, data).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to write bincode file: {}", e))
+        })
+    }
+
+    /// Reads a segmented proof from a bincode file
+    pub fn from_bincode_file<P: AsRef<Path>>(path: P) -> Result<Self, LuminairError> {
+        let data = std::fs::read(path).map_err(|e| {
+            LuminairError::SerializationError(format!("Failed to read bincode file: {}", e))
+        })?;
+        Self::from_bincode(&data)
+    }
+}
@@ -24,6 +24,36 @@ pub enum LuminairError {
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    #[error("Invalid proof bundle: {0}")]
+    InvalidBundle(String),
+
+    #[error("Unsupported proof bundle version {found}, expected version {supported}")]
+    UnsupportedBundleVersion { found: u32, supported: u32 },
+
+    #[error("Circuit settings do not match the ones the proof was generated with.")]
+    SettingsMismatch,
+
+    #[error("Preprocessed trace commitment does not match the verifying key.")]
+    VerifyingKeyMismatch,
+
+    #[error("Trace log size {required} exceeds the proving key maximum of {supported}.")]
+    ProvingKeyTooSmall { required: u32, supported: u32 },
+
+    #[error("Invalid batch: {0}")]
+    InvalidBatch(String),
+
+    #[error("Invalid segment chain: {0}")]
+    InvalidSegmentChain(String),
+
+    #[error("Invalid public IO: {0}")]
+    InvalidPublicIo(String),
+
+    #[error("Invalid claim: {0}")]
+    InvalidClaim(String),
+
+    #[error("Cannot estimate the graph: {0}")]
+    EstimateError(String),
 }
 
 /// Errors related to trace generation and processing
@@ -1,29 +1,34 @@
 use luminair_air::{
-    components::{LuminairComponents, LuminairInteractionElements},
+    components::{lookups::Lookups, LuminairComponents, LuminairInteractionElements},
     preprocessed::{lookups_to_preprocessed_column, PreProcessedTrace},
+    segment::SegmentBoundary,
     settings::CircuitSettings,
-    utils::log_sum_valid,
+    utils::log_sum,
+};
+use luminair_prover::{
+    bundle::ProofBundle, keys::VerifyingKey, segment::SegmentedProof, LuminairProof,
 };
-use luminair_prover::LuminairProof;
 use luminair_utils::LuminairError;
 use tracing::{info, span, Level};
 
+use num_traits::Zero;
 use stwo::core::{
     channel::Blake2sChannel,
+    fields::qm31::SecureField,
     pcs::{CommitmentSchemeVerifier, PcsConfig},
-    vcs::blake2_merkle::{Blake2sMerkleChannel, Blake2sMerkleHasher},
+    vcs::{
+        blake2_hash::Blake2sHash,
+        blake2_merkle::{Blake2sMerkleChannel, Blake2sMerkleHasher},
+    },
 };
 use stwo_constraint_framework::{
-    INTERACTION_TRACE_IDX, ORIGINAL_TRACE_IDX, PREPROCESSED_TRACE_IDX,
+    preprocessed_columns::PreProcessedColumnId, INTERACTION_TRACE_IDX, ORIGINAL_TRACE_IDX,
+    PREPROCESSED_TRACE_IDX,
 };
 
 /// Verifies a LuminAIR proof using the given circuit settings
 pub fn verify(
-    LuminairProof {
-        claim,
-        interaction_claim,
-        proof,
-    }: LuminairProof<Blake2sMerkleHasher>,
+    proof: LuminairProof<Blake2sMerkleHasher>,
     settings: CircuitSettings,
 ) -> Result<(), LuminairError> {
     let _span = span!(Level::INFO, "luminair_verification").entered();
@@ -33,6 +38,129 @@ pub fn verify(
     let lut_cols = lookups_to_preprocessed_column(&settings.lookups);
     let preprocessed_trace = PreProcessedTrace::new(lut_cols);
 
+    verify_proof(
+        proof,
+        &settings.lookups,
+        &preprocessed_trace.ids(),
+        preprocessed_trace.log_sizes(),
+        None,
+        &[],
+    )
+}
+
+/// Verifies a LuminAIR proof bound to the inputs and outputs of each execution it covers, in
+/// the order they were traced
+///
+/// Verification fails unless `io` holds the tensors the proof was generated with.
+pub fn verify_with_io(
+    proof: LuminairProof<Blake2sMerkleHasher>,
+    settings: CircuitSettings,
+    io: &[SegmentBoundary],
+) -> Result<(), LuminairError> {
+    let _span = span!(Level::INFO, "luminair_verification").entered();
+    info!("🚀 Starting LuminAIR proof verification with public IO");
+
+    let lut_cols = lookups_to_preprocessed_column(&settings.lookups);
+    let preprocessed_trace = PreProcessedTrace::new(lut_cols);
+
+    verify_proof(
+        proof,
+        &settings.lookups,
+        &preprocessed_trace.ids(),
+        preprocessed_trace.log_sizes(),
+        None,
+        io,
+    )
+}
+
+/// Verifies a LuminAIR proof using the verifying key of its circuit
+///
+/// Unlike `verify`, this doesn't depend on the size of the lookup tables, and checks the
+/// preprocessed trace committed by the proof against the key.
+pub fn verify_with_key(
+    proof: LuminairProof<Blake2sMerkleHasher>,
+    verifying_key: &VerifyingKey,
+) -> Result<(), LuminairError> {
+    let _span = span!(Level::INFO, "luminair_verification").entered();
+    info!("🚀 Starting LuminAIR proof verification with verifying key");
+
+    verify_proof(
+        proof,
+        &verifying_key.settings.lookups,
+        &verifying_key.column_ids(),
+        verifying_key.column_log_sizes(),
+        Some(verifying_key.preprocessed_root),
+        &[],
+    )
+}
+
+/// Verifies the chain of segment proofs of a graph
+///
+/// The first segment must take no boundary tensor, the last one must pass none, and the
+/// outputs of each segment must be the inputs of the next one.
+pub fn verify_segments(
+    proof: SegmentedProof,
+    settings: CircuitSettings,
+) -> Result<(), LuminairError> {
+    let _span = span!(Level::INFO, "luminair_verification").entered();
+    info!("🚀 Starting LuminAIR segmented proof verification");
+
+    let segments = proof.segments;
+    let (Some(first), Some(last)) = (segments.first(), segments.last()) else {
+        return Err(LuminairError::InvalidSegmentChain(
+            "the proof holds no segment".to_string(),
+        ));
+    };
+    if !first.boundary.inputs.is_empty() {
+        return Err(LuminairError::InvalidSegmentChain(
+            "the first segment takes boundary tensors".to_string(),
+        ));
+    }
+    if !last.boundary.outputs.is_empty() {
+        return Err(LuminairError::InvalidSegmentChain(
+            "the last segment passes boundary tensors".to_string(),
+        ));
+    }
+    for (index, pair) in segments.windows(2).enumerate() {
+        if pair[0].boundary.outputs_digest() != pair[1].boundary.inputs_digest() {
+            return Err(LuminairError::InvalidSegmentChain(format!(
+                "the outputs of segment {} are not the inputs of segment {}",
+                index,
+                index + 1
+            )));
+        }
+    }
+
+    let lut_cols = lookups_to_preprocessed_column(&settings.lookups);
+    let preprocessed_trace = PreProcessedTrace::new(lut_cols);
+    for segment in segments {
+        verify_proof(
+            segment.proof,
+            &settings.lookups,
+            &preprocessed_trace.ids(),
+            preprocessed_trace.log_sizes(),
+            None,
+            std::slice::from_ref(&segment.boundary),
+        )?;
+    }
+    Ok(())
+}
+
+/// Verifies a proof given the preprocessed columns of its circuit, optionally the expected
+/// root of their commitment, and the boundary of the segment it proves or the inputs and
+/// outputs of its executions
+fn verify_proof(
+    LuminairProof {
+        claim,
+        interaction_claim,
+        proof,
+    }: LuminairProof<Blake2sMerkleHasher>,
+    lookups: &Lookups,
+    preprocessed_column_ids: &[PreProcessedColumnId],
+    preprocessed_log_sizes: Vec<u32>,
+    preprocessed_root: Option<Blake2sHash>,
+    boundaries: &[SegmentBoundary],
+) -> Result<(), LuminairError> {
     // ┌──────────────────────────┐
     // │     Protocol Setup       │
     // └──────────────────────────┘
@@ -45,9 +173,25 @@ pub fn verify(
         let commitment_scheme_verifier =
             &mut CommitmentSchemeVerifier::<Blake2sMerkleChannel>::new(config);
 
+        // The trace layout of fused components is derived from their claimed expression, and
+        // the constraints of norm components from their claimed parameters
+        if !claim.is_well_formed() {
+            return Err(LuminairError::InvalidClaim(
+                "Fused expression or norm parameters out of the component limits".to_string(),
+            ));
+        }
+
+        // Every shard of every component must have exactly one claimed sum, before components
+        // are built from both claims
+        if !interaction_claim.matches(&claim) {
+            return Err(LuminairError::InvalidLogUp(
+                "Interaction claims and component shards".to_string(),
+            ));
+        }
+
         // Prepare log sizes for each phase
         let mut log_sizes = claim.log_sizes();
-        log_sizes[PREPROCESSED_TRACE_IDX] = preprocessed_trace.log_sizes();
+        log_sizes[PREPROCESSED_TRACE_IDX] = preprocessed_log_sizes;
 
         info!("✅ Protocol Setup: Configuration complete");
 
@@ -58,6 +202,11 @@ pub fn verify(
             let _span = span!(Level::INFO, "interaction_phase_0").entered();
             info!("🔄 Interaction Phase 0: Processing preprocessed trace");
 
+            if let Some(root) = preprocessed_root {
+                if proof.commitments[PREPROCESSED_TRACE_IDX] != root {
+                    return Err(LuminairError::VerifyingKeyMismatch);
+                }
+            }
             commitment_scheme_verifier.commit(
                 proof.commitments[PREPROCESSED_TRACE_IDX],
                 &log_sizes[PREPROCESSED_TRACE_IDX],
@@ -75,6 +224,9 @@ pub fn verify(
             info!("🔄 Interaction Phase 1: Processing main trace");
 
             claim.mix_into(channel);
+            for boundary in boundaries {
+                boundary.mix_into(channel);
+            }
             commitment_scheme_verifier.commit(
                 proof.commitments[ORIGINAL_TRACE_IDX],
                 &log_sizes[ORIGINAL_TRACE_IDX],
@@ -93,8 +245,13 @@ pub fn verify(
 
             let interaction_elements = LuminairInteractionElements::draw(channel);
 
-            // Validate LogUp sum
-            if !log_sum_valid(&interaction_claim) {
+            // Validate LogUp sum, balanced by the boundary tensors of a segment or the inputs
+            // and outputs of the executions
+            let boundary_sum = boundaries
+                .iter()
+                .map(|boundary| boundary.logup_sum(&interaction_elements.node_elements))
+                .fold(SecureField::zero(), |sum, boundary_sum| sum + boundary_sum);
+            if !(log_sum(&interaction_claim) + boundary_sum).is_zero() {
                 return Err(LuminairError::InvalidLogUp("Invalid LogUp sum".to_string()));
             }
 
@@ -118,8 +275,8 @@ pub fn verify(
                     &claim,
                     &interaction_elements,
                     &interaction_claim,
-                    &preprocessed_trace,
-                    &settings.lookups,
+                    preprocessed_column_ids,
+                    lookups,
                 );
                 let components = component_builder.components();
 
@@ -141,3 +298,21 @@ pub fn verify(
         }
     }
 }
+
+/// Verifies a proof bundle against the public inputs and outputs it holds
+///
+/// The circuit settings embedded in the bundle are used unless `settings` is given, in which
+/// case they must match the ones the proof was generated with. They are required when the
+/// bundle only holds their digest.
+pub fn verify_bundle(
+    bundle: ProofBundle,
+    settings: Option<CircuitSettings>,
+) -> Result<(), LuminairError> {
+    let settings = bundle.resolve_settings(settings)?;
+    let io = bundle
+        .public_io
+        .into_iter()
+        .map(|io| io.check().map(|()| io.boundary))
+        .collect::<Result<Vec<_>, _>>()?;
+    verify_with_io(bundle.proof, settings, &io)
+}
@@ -29,6 +29,35 @@ export function verify(
   settingsBytes: Uint8Array
 ): VerificationResult;
 
+/**
+ * Verifies a self-contained LuminAIR proof bundle
+ * @param bundleBytes - Proof bundle as Uint8Array
+ * @returns Verification result
+ */
+export function verify_bundle(bundleBytes: Uint8Array): VerificationResult;
+
+/**
+ * Verifies a LuminAIR proof bundle against separately distributed circuit settings
+ * @param bundleBytes - Proof bundle as Uint8Array
+ * @param settingsBytes - Binary settings data as Uint8Array
+ * @returns Verification result
+ */
+export function verify_bundle_with_settings(
+  bundleBytes: Uint8Array,
+  settingsBytes: Uint8Array
+): VerificationResult;
+
+/**
+ * Verifies a LuminAIR proof against the verifying key of its circuit, without regenerating lookup tables
+ * @param proofBytes - Binary proof data as Uint8Array
+ * @param verifyingKeyBytes - Binary verifying key data as Uint8Array
+ * @returns Verification result
+ */
+export function verify_with_key(
+  proofBytes: Uint8Array,
+  verifyingKeyBytes: Uint8Array
+): VerificationResult;
+
 /**
  * Tests if the WASM module is working correctly
  * @returns Success message
@@ -1,6 +1,9 @@
 use luminair_air::settings::CircuitSettings;
-use luminair_prover::LuminairProof;
-use luminair_verifier::verifier::verify as verify_rust;
+use luminair_prover::{bundle::ProofBundle, keys::VerifyingKey, LuminairProof};
+use luminair_verifier::verifier::{
+    verify as verify_rust, verify_bundle as verify_bundle_rust,
+    verify_with_key as verify_with_key_rust,
+};
 use stwo::core::vcs::blake2_merkle::Blake2sMerkleHasher;
 use wasm_bindgen::prelude::*;
 use tracing::{info, span, Level};
@@ -98,6 +101,110 @@ pub fn verify(proof_bytes: &[u8], settings_bytes: &[u8]) -> VerificationResult {
     }
 }
 
+/// Verifies a LuminAIR proof against the verifying key of its circuit using WASM bindings
+///
+/// Verification time doesn't depend on the size of the lookup tables.
+#[wasm_bindgen]
+pub fn verify_with_key(proof_bytes: &[u8], verifying_key_bytes: &[u8]) -> VerificationResult {
+    let _span = span!(Level::INFO, "wasm_key_verification_wrapper").entered();
+    console_info("🌟 Starting WASM proof verification with verifying key...");
+    info!("🌟 LuminAIR WASM Verifier: Beginning verification process with verifying key");
+
+    let failure = |error_msg: String| {
+        console_error(&error_msg);
+        info!("💥 LuminAIR WASM Verifier: {}", error_msg);
+        VerificationResult {
+            success: false,
+            error_message: Some(error_msg),
+        }
+    };
+
+    let proof = match LuminairProof::from_bincode(proof_bytes) {
+        Ok(proof) => proof,
+        Err(e) => return failure(format!("Failed to parse proof binary: {}", e)),
+    };
+    let verifying_key = match VerifyingKey::from_bincode(verifying_key_bytes) {
+        Ok(verifying_key) => verifying_key,
+        Err(e) => return failure(format!("Failed to parse verifying key binary: {}", e)),
+    };
+
+    console_info("🔍 Delegating to Rust verifier with detailed tracing...");
+    info!("🔍 Delegating to Rust verifier core");
+
+    match verify_with_key_rust(proof, &verifying_key) {
+        Ok(()) => {
+            console_info("🎉 Proof verification successful! ✅");
+            info!("🎉 LuminAIR WASM Verifier: Verification completed successfully");
+            VerificationResult {
+                success: true,
+                error_message: None,
+            }
+        }
+        Err(e) => failure(format!("Proof verification failed: {}", e)),
+    }
+}
+
+/// Verifies a self-contained LuminAIR proof bundle using WASM bindings
+#[wasm_bindgen]
+pub fn verify_bundle(bundle_bytes: &[u8]) -> VerificationResult {
+    verify_bundle_bytes(bundle_bytes, None)
+}
+
+/// Verifies a LuminAIR proof bundle against circuit settings distributed separately
+#[wasm_bindgen]
+pub fn verify_bundle_with_settings(
+    bundle_bytes: &[u8],
+    settings_bytes: &[u8],
+) -> VerificationResult {
+    verify_bundle_bytes(bundle_bytes, Some(settings_bytes))
+}
+
+fn verify_bundle_bytes(bundle_bytes: &[u8], settings_bytes: Option<&[u8]>) -> VerificationResult {
+    let _span = span!(Level::INFO, "wasm_bundle_verification_wrapper").entered();
+    console_info("🌟 Starting WASM proof bundle verification...");
+    info!("🌟 LuminAIR WASM Verifier: Beginning bundle verification process");
+
+    let failure = |error_msg: String| {
+        console_error(&error_msg);
+        info!("💥 LuminAIR WASM Verifier: {}", error_msg);
+        VerificationResult {
+            success: false,
+            error_message: Some(error_msg),
+        }
+    };
+
+    // Parse the bundle, checking its header and checksum
+    let bundle = match ProofBundle::from_bytes(bundle_bytes) {
+        Ok(bundle) => {
+            console_info("✅ Successfully parsed proof bundle");
+            info!("📦 Bundle parsing: Success");
+            bundle
+        }
+        Err(e) => return failure(format!("Failed to parse proof bundle: {}", e)),
+    };
+
+    // Parse the settings from bincode, when provided
+    let settings = match settings_bytes.map(CircuitSettings::from_bincode).transpose() {
+        Ok(settings) => settings,
+        Err(e) => return failure(format!("Failed to parse settings binary: {}", e)),
+    };
+
+    console_info("🔍 Delegating to Rust verifier with detailed tracing...");
+    info!("🔍 Delegating to Rust verifier core");
+
+    match verify_bundle_rust(bundle, settings) {
+        Ok(()) => {
+            console_info("🎉 Proof verification successful! ✅");
+            info!("🎉 LuminAIR WASM Verifier: Verification completed successfully");
+            VerificationResult {
+                success: true,
+                error_message: None,
+            }
+        }
+        Err(e) => failure(format!("Proof verification failed: {}", e)),
+    }
+}
+
 /// Tests if the WASM module is working correctly
 #[wasm_bindgen]
 pub fn test_wasm_module() -> String {
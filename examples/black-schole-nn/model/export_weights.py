@@ -20,17 +20,17 @@ def export_weights_to_numpy(model_path="model.pth", output_dir="weights/"):
     model.load_state_dict(state)
     model.eval()
 
-    # Collect weights (transposed) and biases
+    # Collect weights (transposed) and biases, named after their path in the Rust `Network`
     weights = {
         # PyTorch Linear weight is (out, in); we save as (in, out)
-        "layer1_weight": _to_numpy_T(model.net[0].weight),
-        "layer1_bias":   _to_numpy(model.net[0].bias),
+        "layer1.weight": _to_numpy_T(model.net[0].weight),
+        "layer1.bias":   _to_numpy(model.net[0].bias),
 
-        "layer2_weight": _to_numpy_T(model.net[2].weight),
-        "layer2_bias":   _to_numpy(model.net[2].bias),
+        "layer2.weight": _to_numpy_T(model.net[2].weight),
+        "layer2.bias":   _to_numpy(model.net[2].bias),
 
-        "layer3_weight": _to_numpy_T(model.net[4].weight),
-        "layer3_bias":   _to_numpy(model.net[4].bias),
+        "layer3.weight": _to_numpy_T(model.net[4].weight),
+        "layer3.bias":   _to_numpy(model.net[4].bias),
     }
 
     for name, arr in weights.items():
@@ -41,18 +41,18 @@ def export_weights_to_numpy(model_path="model.pth", output_dir="weights/"):
     shapes_info = {
         "layer1": {
             "pytorch_weight": [model.net[0].weight.shape[0], model.net[0].weight.shape[1]],  # (out, in)
-            "saved_weight":   [weights["layer1_weight"].shape[0], weights["layer1_weight"].shape[1]],  # (in, out)
-            "bias":           list(weights["layer1_bias"].shape)
+            "saved_weight":   [weights["layer1.weight"].shape[0], weights["layer1.weight"].shape[1]],  # (in, out)
+            "bias":           list(weights["layer1.bias"].shape)
         },
         "layer2": {
             "pytorch_weight": [model.net[2].weight.shape[0], model.net[2].weight.shape[1]],
-            "saved_weight":   [weights["layer2_weight"].shape[0], weights["layer2_weight"].shape[1]],
-            "bias":           list(weights["layer2_bias"].shape)
+            "saved_weight":   [weights["layer2.weight"].shape[0], weights["layer2.weight"].shape[1]],
+            "bias":           list(weights["layer2.bias"].shape)
         },
         "layer3": {
             "pytorch_weight": [model.net[4].weight.shape[0], model.net[4].weight.shape[1]],
-            "saved_weight":   [weights["layer3_weight"].shape[0], weights["layer3_weight"].shape[1]],
-            "bias":           list(weights["layer3_bias"].shape)
+            "saved_weight":   [weights["layer3.weight"].shape[0], weights["layer3.weight"].shape[1]],
+            "bias":           list(weights["layer3.bias"].shape)
         }
     }
 
@@ -69,12 +69,12 @@ def test_exported_weights(model_path="model.pth", weights_dir="weights/"):
     model.eval()
 
     # Load exported NP arrays (weights already transposed to (in, out))
-    w1 = np.load(os.path.join(weights_dir, "layer1_weight.npy"))  # (2, 64)
-    b1 = np.load(os.path.join(weights_dir, "layer1_bias.npy"))    # (64,)
-    w2 = np.load(os.path.join(weights_dir, "layer2_weight.npy"))  # (64, 64)
-    b2 = np.load(os.path.join(weights_dir, "layer2_bias.npy"))    # (64,)
-    w3 = np.load(os.path.join(weights_dir, "layer3_weight.npy"))  # (64, 1)
-    b3 = np.load(os.path.join(weights_dir, "layer3_bias.npy"))    # (1,)
+    w1 = np.load(os.path.join(weights_dir, "layer1.weight.npy"))  # (2, 64)
+    b1 = np.load(os.path.join(weights_dir, "layer1.bias.npy"))    # (64,)
+    w2 = np.load(os.path.join(weights_dir, "layer2.weight.npy"))  # (64, 64)
+    b2 = np.load(os.path.join(weights_dir, "layer2.bias.npy"))    # (64,)
+    w3 = np.load(os.path.join(weights_dir, "layer3.weight.npy"))  # (64, 1)
+    b3 = np.load(os.path.join(weights_dir, "layer3.bias.npy"))    # (1,)
 
     # Test input
     test_S = torch.tensor([[15.0]], dtype=torch.float32)
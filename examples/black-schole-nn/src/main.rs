@@ -1,5 +1,4 @@
 use luminair::prelude::*;
-use ndarray::*;
 use std::time::Instant;
 
 /// Physics-Informed Neural Network for Black-Scholes Option Pricing
@@ -8,16 +7,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize computational graph
     let mut graph = Graph::new();
 
-    // Load pre-trained PINN weights (3-layer network: 2 -> 64 -> 64 -> 1)
-    let weights = load_pinn_weights()?;
-
-    // Build neural network layers
-    let network = build_network(&mut graph, weights);
+    // Build neural network layers (3-layer network: 2 -> 64 -> 64 -> 1)
+    let network = build_network(&mut graph);
 
     // Define forward pass computation
     let input = graph.tensor((1, 2));
     let mut output = forward_pass(&network, input);
 
+    // Load pre-trained PINN weights, checked against the shapes used by the forward pass
+    let weights = load_pinn_weights()?;
+    weights.load_module(&network, "", &mut graph)?;
+
     // Compile the computational graph
     compile_graph(&mut graph, &mut output);
 
@@ -32,64 +32,33 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-struct PinnWeights {
-    layer1_w: Array2<f32>,
-    layer1_b: Array1<f32>,
-    layer2_w: Array2<f32>,
-    layer2_b: Array1<f32>,
-    layer3_w: Array2<f32>,
-    layer3_b: Array1<f32>,
-}
-
 struct Network {
     layer1: Linear,
     layer2: Linear,
     layer3: Linear,
 }
 
-fn load_pinn_weights() -> Result<PinnWeights, Box<dyn std::error::Error>> {
-    Ok(PinnWeights {
-        layer1_w: ndarray_npy::read_npy("model/weights/layer1_weight.npy")?,
-        layer1_b: ndarray_npy::read_npy("model/weights/layer1_bias.npy")?,
-        layer2_w: ndarray_npy::read_npy("model/weights/layer2_weight.npy")?,
-        layer2_b: ndarray_npy::read_npy("model/weights/layer2_bias.npy")?,
-        layer3_w: ndarray_npy::read_npy("model/weights/layer3_weight.npy")?,
-        layer3_b: ndarray_npy::read_npy("model/weights/layer3_bias.npy")?,
-    })
-}
-
-fn build_network(graph: &mut Graph, weights: PinnWeights) -> Network {
-    // Layer 1: Input (2 features: S, t) -> Hidden (64 neurons)
-    let layer1 = Linear::new(2, 64, true, graph);
-    layer1
-        .weight
-        .set(weights.layer1_w.as_slice().unwrap().to_vec());
-    if let Some(bias) = &layer1.bias {
-        bias.set(weights.layer1_b.as_slice().unwrap().to_vec());
-    }
-
-    // Layer 2: Hidden (64 neurons) -> Hidden (64 neurons)
-    let layer2 = Linear::new(64, 64, true, graph);
-    layer2
-        .weight
-        .set(weights.layer2_w.as_slice().unwrap().to_vec());
-    if let Some(bias) = &layer2.bias {
-        bias.set(weights.layer2_b.as_slice().unwrap().to_vec());
+impl SerializeModule for Network {
+    fn serialize(&self, s: &mut Serializer) {
+        s.module("layer1", &self.layer1);
+        s.module("layer2", &self.layer2);
+        s.module("layer3", &self.layer3);
     }
+}
 
-    // Layer 3: Hidden (64 neurons) -> Output (1 neuron: option price)
-    let layer3 = Linear::new(64, 1, true, graph);
-    layer3
-        .weight
-        .set(weights.layer3_w.as_slice().unwrap().to_vec());
-    if let Some(bias) = &layer3.bias {
-        bias.set(weights.layer3_b.as_slice().unwrap().to_vec());
-    }
+fn load_pinn_weights() -> Result<Weights, WeightError> {
+    // One `.npy` file per parameter, named after its path in the network (e.g. `layer1.weight`)
+    Weights::from_npy_dir("model/weights")
+}
 
+fn build_network(graph: &mut Graph) -> Network {
     Network {
-        layer1,
-        layer2,
-        layer3,
+        // Layer 1: Input (2 features: S, t) -> Hidden (64 neurons)
+        layer1: Linear::new(2, 64, true, graph),
+        // Layer 2: Hidden (64 neurons) -> Hidden (64 neurons)
+        layer2: Linear::new(64, 64, true, graph),
+        // Layer 3: Hidden (64 neurons) -> Output (1 neuron: option price)
+        layer3: Linear::new(64, 1, true, graph),
     }
 }
 
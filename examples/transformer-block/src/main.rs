@@ -0,0 +1,201 @@
+use luminair::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::time::Instant;
+
+const SEQ_LEN: usize = 4;
+const HIDDEN: usize = 8;
+const HEADS: usize = 2;
+const FF_HIDDEN: usize = 16;
+
+/// Pre-norm transformer block with causal multi-head self-attention
+/// Demonstrates attention inference (Q/K/V projections, masked softmax, residual adds
+/// and layer norms) with zero-knowledge proof generation
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize computational graph
+    let mut graph = Graph::new();
+
+    // Build the block with deterministic random weights
+    let mut rng = StdRng::seed_from_u64(0);
+    let block = TransformerBlock::new(&mut graph, &mut rng);
+
+    // Define forward pass computation
+    let input = graph.tensor((SEQ_LEN, HIDDEN));
+    let mut output = block.forward(input).retrieve();
+
+    // Compile the computational graph
+    graph.compile(
+        (GenericCompiler::default(), StwoCompiler::default()),
+        &mut output,
+    );
+
+    // Set a sequence of token embeddings
+    input.set(random_vec(SEQ_LEN * HIDDEN, &mut rng));
+
+    // Generate and verify ZK proof
+    let result = generate_and_verify_proof(&mut graph, &mut output)?;
+
+    print_results(&result);
+
+    Ok(())
+}
+
+/// Multi-head self-attention with learned Q/K/V and output projections
+struct MultiHeadAttention {
+    q_proj: Linear,
+    k_proj: Linear,
+    v_proj: Linear,
+    o_proj: Linear,
+}
+
+impl MultiHeadAttention {
+    fn new(graph: &mut Graph, rng: &mut StdRng) -> Self {
+        Self {
+            q_proj: linear(HIDDEN, HIDDEN, graph, rng),
+            k_proj: linear(HIDDEN, HIDDEN, graph, rng),
+            v_proj: linear(HIDDEN, HIDDEN, graph, rng),
+            o_proj: linear(HIDDEN, HIDDEN, graph, rng),
+        }
+    }
+
+    /// Attends over a `(seq, hidden)` input, where `mask` zeroes the attention weights of
+    /// future positions
+    fn forward(&self, input: GraphTensor, mask: GraphTensor) -> GraphTensor {
+        let head_dim = HIDDEN / HEADS;
+
+        // (seq, hidden) -> (heads, seq, head_dim)
+        let split_heads =
+            |x: GraphTensor| x.reshape((SEQ_LEN, HEADS, head_dim)).permute((1, 0, 2));
+        let q = split_heads(self.q_proj.forward(input));
+        let k = split_heads(self.k_proj.forward(input));
+        let v = split_heads(self.v_proj.forward(input));
+
+        // Scaled dot-product scores, softmax over the unmasked keys
+        let scores = q.matmul(k.permute((0, 2, 1))) * (1.0 / (head_dim as f32).sqrt());
+        let exp = (scores - scores.max_reduce(2).expand(2, SEQ_LEN)).exp()
+            * mask.expand(0, HEADS);
+        let weights = exp / exp.sum_reduce(2).expand(2, SEQ_LEN);
+
+        // (heads, seq, head_dim) -> (seq, hidden)
+        let attended = weights
+            .matmul(v)
+            .permute((1, 0, 2))
+            .contiguous()
+            .reshape((SEQ_LEN, HIDDEN));
+        self.o_proj.forward(attended)
+    }
+}
+
+struct TransformerBlock {
+    norm1: LayerNorm,
+    attention: MultiHeadAttention,
+    norm2: LayerNorm,
+    ff1: Linear,
+    ff2: Linear,
+    causal_mask: GraphTensor,
+}
+
+impl TransformerBlock {
+    fn new(graph: &mut Graph, rng: &mut StdRng) -> Self {
+        // Position i attends to positions 0..=i
+        let causal_mask = graph.tensor((SEQ_LEN, SEQ_LEN)).set(
+            (0..SEQ_LEN * SEQ_LEN)
+                .map(|i| if i % SEQ_LEN <= i / SEQ_LEN { 1.0 } else { 0.0 })
+                .collect::<Vec<f32>>(),
+        );
+
+        Self {
+            norm1: layer_norm(HIDDEN, graph, rng),
+            attention: MultiHeadAttention::new(graph, rng),
+            norm2: layer_norm(HIDDEN, graph, rng),
+            ff1: linear(HIDDEN, FF_HIDDEN, graph, rng),
+            ff2: linear(FF_HIDDEN, HIDDEN, graph, rng),
+            causal_mask,
+        }
+    }
+}
+
+impl Module<GraphTensor> for TransformerBlock {
+    type Output = GraphTensor;
+
+    fn forward(&self, input: GraphTensor) -> GraphTensor {
+        // Attention sub-layer with residual connection
+        let x = input
+            + self
+                .attention
+                .forward(self.norm1.forward(input), self.causal_mask);
+
+        // Feed-forward sub-layer with residual connection
+        x + self
+            .ff2
+            .forward(self.ff1.forward(self.norm2.forward(x)).relu())
+    }
+}
+
+fn linear(inp: usize, out: usize, graph: &mut Graph, rng: &mut StdRng) -> Linear {
+    let layer = Linear::new(inp, out, true, graph);
+    layer.weight.set(random_vec(inp * out, rng));
+    if let Some(bias) = &layer.bias {
+        bias.set(random_vec(out, rng));
+    }
+    layer
+}
+
+fn layer_norm(dim: usize, graph: &mut Graph, rng: &mut StdRng) -> LayerNorm {
+    let norm = LayerNorm::new(dim, true, true, true, 1e-5, graph);
+    if let Some(weight) = &norm.weight {
+        weight.set(random_vec(dim, rng).iter().map(|w| w + 1.0).collect::<Vec<_>>());
+    }
+    if let Some(bias) = &norm.bias {
+        bias.set(random_vec(dim, rng));
+    }
+    norm
+}
+
+fn random_vec(n: usize, rng: &mut StdRng) -> Vec<f32> {
+    (0..n).map(|_| rng.gen_range(-0.5..0.5)).collect()
+}
+
+fn generate_and_verify_proof(
+    graph: &mut Graph,
+    output: &mut GraphTensor,
+) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
+    // Generate circuit settings for ZK proof
+    // Note: In real-world applications, circuit settings should be generated once and reused for multiple inferences.
+    println!("Generating Circuit Settings...");
+    let mut settings = graph.gen_circuit_settings();
+    println!("✅ Circuit Settings generated");
+
+    // Generate execution trace
+    let timing_start = Instant::now();
+    println!("Generating Trace...");
+    let trace = graph.gen_trace(&mut settings)?;
+    println!("✅ Trace generated in {:?}", timing_start.elapsed());
+
+    let op_counter = &trace.metadata.execution_resources.op_counter;
+    println!(
+        "Fused operators: {} MatMul, {} Norm",
+        op_counter.matmul, op_counter.norm
+    );
+
+    // Generate ZK proof
+    let timing_start = Instant::now();
+    println!("Generating Proof...");
+    let proof = prove(trace, settings.clone())?;
+    println!("✅ Proof generated in {:?}", timing_start.elapsed());
+
+    // Verify ZK proof
+    // Note: In real-world applications, proof verification should be performed by another party.
+    let timing_start = Instant::now();
+    println!("Verifying Proof...");
+    verify(proof, settings)?;
+    println!("✅ Proof verified in {:?}", timing_start.elapsed());
+
+    Ok(output.data())
+}
+
+fn print_results(result: &[f32]) {
+    println!("Transformer Block Results:");
+    for (position, hidden) in result.chunks(HIDDEN).enumerate() {
+        println!("Position {position}: {hidden:.4?}");
+    }
+}